@@ -0,0 +1,825 @@
+use anyhow::Context as _;
+use twilight_interactions::command::{
+    CommandModel, CommandOption, CreateCommand, CreateOption, DescLocalizations,
+};
+use twilight_model::{
+    application::interaction::Interaction,
+    id::{Id, marker::ChannelMarker},
+};
+
+use crate::{
+    context::Context,
+    dbs::mongo::models::{
+        ai_persona::AiPersona,
+        guild_settings::{AiChannelMode, AiSafetyThreshold},
+    },
+    services::{
+        ai::{
+            AiService, catalogue,
+            persona::{self, SaveOutcome},
+            usage,
+        },
+        guild_settings::GuildSettingsService,
+    },
+    utils::{embed, interaction::require_guild_ref},
+};
+use std::sync::Arc;
+
+#[derive(CommandModel, CreateCommand, Debug)]
+#[command(name = "ai", desc_localizations = "admin_ai_desc")]
+pub enum AdminAiCommand {
+    #[command(name = "quota")]
+    Quota(AdminAiQuotaCommand),
+    #[command(name = "safety")]
+    Safety(AdminAiSafetyCommand),
+    #[command(name = "persona-set")]
+    PersonaSet(AdminAiPersonaSetCommand),
+    #[command(name = "persona-remove")]
+    PersonaRemove(AdminAiPersonaRemoveCommand),
+    #[command(name = "base-prompt")]
+    BasePrompt(AdminAiBasePromptCommand),
+    #[command(name = "access")]
+    Access(AdminAiAccessCommand),
+    #[command(name = "channels")]
+    Channels(AdminAiChannelsCommand),
+    #[command(name = "usage")]
+    Usage(AdminAiUsageCommand),
+}
+
+#[derive(CommandModel, CreateCommand, Debug)]
+#[command(name = "quota", desc_localizations = "admin_ai_quota_desc")]
+pub struct AdminAiQuotaCommand {
+    #[command(
+        min_value = 0,
+        desc_localizations = "admin_ai_quota_user_requests_desc"
+    )]
+    pub user_requests: Option<i64>,
+    #[command(
+        min_value = 0,
+        desc_localizations = "admin_ai_quota_user_tokens_desc"
+    )]
+    pub user_tokens: Option<i64>,
+    #[command(
+        min_value = 0,
+        desc_localizations = "admin_ai_quota_guild_requests_desc"
+    )]
+    pub guild_requests: Option<i64>,
+    #[command(
+        min_value = 0,
+        desc_localizations = "admin_ai_quota_guild_tokens_desc"
+    )]
+    pub guild_tokens: Option<i64>,
+}
+
+#[derive(CommandModel, CreateCommand, Debug)]
+#[command(name = "safety", desc_localizations = "admin_ai_safety_desc")]
+pub struct AdminAiSafetyCommand {
+    #[command(desc_localizations = "admin_ai_safety_category_desc")]
+    pub category: AdminAiSafetyCategory,
+    #[command(desc_localizations = "admin_ai_safety_threshold_desc")]
+    pub threshold: AdminAiSafetyThreshold,
+}
+
+#[derive(CommandModel, CreateCommand, Debug)]
+#[command(
+    name = "persona-set",
+    desc_localizations = "admin_ai_persona_set_desc"
+)]
+pub struct AdminAiPersonaSetCommand {
+    #[command(
+        max_length = 32,
+        desc_localizations = "admin_ai_persona_name_desc"
+    )]
+    pub name: String,
+    #[command(
+        max_length = 4000,
+        desc_localizations = "admin_ai_persona_prompt_desc"
+    )]
+    pub prompt: String,
+    #[command(desc_localizations = "admin_ai_persona_models_desc")]
+    pub models: Option<String>,
+    #[command(
+        min_value = 0.0,
+        max_value = 2.0,
+        desc_localizations = "admin_ai_persona_temperature_desc"
+    )]
+    pub temperature: Option<f64>,
+}
+
+#[derive(CommandModel, CreateCommand, Debug)]
+#[command(
+    name = "persona-remove",
+    desc_localizations = "admin_ai_persona_remove_desc"
+)]
+pub struct AdminAiPersonaRemoveCommand {
+    #[command(
+        autocomplete = true,
+        desc_localizations = "admin_ai_persona_remove_persona_desc"
+    )]
+    pub persona: String,
+}
+
+#[derive(CommandModel, CreateCommand, Debug)]
+#[command(
+    name = "base-prompt",
+    desc_localizations = "admin_ai_base_prompt_desc"
+)]
+pub struct AdminAiBasePromptCommand {
+    #[command(
+        max_length = 4000,
+        desc_localizations = "admin_ai_base_prompt_prompt_desc"
+    )]
+    pub prompt: Option<String>,
+}
+
+#[derive(CommandModel, CreateCommand, Debug)]
+#[command(name = "access", desc_localizations = "admin_ai_access_desc")]
+pub struct AdminAiAccessCommand {
+    #[command(desc_localizations = "admin_ai_access_enabled_desc")]
+    pub enabled: Option<bool>,
+    #[command(desc_localizations = "admin_ai_access_channel_mode_desc")]
+    pub channel_mode: Option<AdminAiChannelMode>,
+    #[command(desc_localizations = "admin_ai_access_reply_mentions_desc")]
+    pub reply_mentions: Option<bool>,
+    #[command(desc_localizations = "admin_ai_access_dms_desc")]
+    pub dms: Option<bool>,
+}
+
+#[derive(CommandModel, CreateCommand, Debug)]
+#[command(
+    name = "channels",
+    desc_localizations = "admin_ai_channels_desc"
+)]
+pub struct AdminAiChannelsCommand {
+    #[command(desc_localizations = "admin_ai_channels_action_desc")]
+    pub action: AdminAiChannelAction,
+    #[command(desc_localizations = "admin_ai_channels_channel_desc")]
+    pub channel: Option<Id<ChannelMarker>>,
+}
+
+#[derive(CommandModel, CreateCommand, Debug)]
+#[command(name = "usage", desc_localizations = "admin_ai_usage_desc")]
+pub struct AdminAiUsageCommand {
+    #[command(
+        desc_localizations = "admin_ai_usage_days_desc",
+        min_value = 1,
+        max_value = 30
+    )]
+    pub days: Option<i64>,
+}
+
+#[derive(CreateOption, CommandOption, Debug, Clone, Copy)]
+pub enum AdminAiChannelMode {
+    #[option(name = "All channels", value = "all")]
+    All,
+    #[option(name = "Only listed channels", value = "allow")]
+    Allow,
+    #[option(name = "All but listed channels", value = "deny")]
+    Deny,
+}
+
+impl AdminAiChannelMode {
+    fn mode(self) -> AiChannelMode {
+        match self {
+            Self::All => AiChannelMode::All,
+            Self::Allow => AiChannelMode::Allow,
+            Self::Deny => AiChannelMode::Deny,
+        }
+    }
+}
+
+#[derive(CreateOption, CommandOption, Debug, Clone, Copy)]
+pub enum AdminAiChannelAction {
+    #[option(name = "Add", value = "add")]
+    Add,
+    #[option(name = "Remove", value = "remove")]
+    Remove,
+    #[option(name = "Clear", value = "clear")]
+    Clear,
+}
+
+#[derive(CreateOption, CommandOption, Debug, Clone, Copy)]
+pub enum AdminAiSafetyCategory {
+    #[option(name = "Harassment", value = "harassment")]
+    Harassment,
+    #[option(name = "Hate speech", value = "hate_speech")]
+    HateSpeech,
+    #[option(name = "Sexually explicit", value = "sexually_explicit")]
+    SexuallyExplicit,
+    #[option(name = "Dangerous content", value = "dangerous_content")]
+    DangerousContent,
+}
+
+#[derive(CreateOption, CommandOption, Debug, Clone, Copy)]
+pub enum AdminAiSafetyThreshold {
+    #[option(name = "Model default", value = "default")]
+    Default,
+    #[option(name = "Off", value = "off")]
+    Off,
+    #[option(name = "Block none", value = "block_none")]
+    BlockNone,
+    #[option(name = "Block only high", value = "block_only_high")]
+    BlockOnlyHigh,
+    #[option(
+        name = "Block medium and above",
+        value = "block_medium_and_above"
+    )]
+    BlockMediumAndAbove,
+    #[option(name = "Block low and above", value = "block_low_and_above")]
+    BlockLowAndAbove,
+}
+
+impl AdminAiSafetyThreshold {
+    fn threshold(self) -> Option<AiSafetyThreshold> {
+        match self {
+            Self::Default => None,
+            Self::Off => Some(AiSafetyThreshold::Off),
+            Self::BlockNone => Some(AiSafetyThreshold::BlockNone),
+            Self::BlockOnlyHigh => Some(AiSafetyThreshold::BlockOnlyHigh),
+            Self::BlockMediumAndAbove => Some(AiSafetyThreshold::BlockMediumAndAbove),
+            Self::BlockLowAndAbove => Some(AiSafetyThreshold::BlockLowAndAbove),
+        }
+    }
+}
+
+fn admin_ai_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Configure the AI assistant",
+        [("th", "ตั้งค่าผู้ช่วย AI")],
+    )
+}
+
+fn admin_ai_quota_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Set daily AI budgets (0 = unlimited)",
+        [("th", "ตั้งค่าโควตา AI รายวัน (0 = ไม่จำกัด)")],
+    )
+}
+
+fn admin_ai_quota_user_requests_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Requests per user per day",
+        [("th", "จำนวนคำขอต่อผู้ใช้ต่อวัน")],
+    )
+}
+
+fn admin_ai_quota_user_tokens_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Tokens per user per day",
+        [("th", "จำนวนโทเคนต่อผู้ใช้ต่อวัน")],
+    )
+}
+
+fn admin_ai_quota_guild_requests_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Requests for the whole server per day",
+        [("th", "จำนวนคำขอของทั้งเซิร์ฟเวอร์ต่อวัน")],
+    )
+}
+
+fn admin_ai_quota_guild_tokens_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Tokens for the whole server per day",
+        [("th", "จำนวนโทเคนของทั้งเซิร์ฟเวอร์ต่อวัน")],
+    )
+}
+
+fn admin_ai_safety_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Set how strictly AI replies are filtered",
+        [("th", "ตั้งค่าความเข้มงวดของตัวกรองคำตอบ AI")],
+    )
+}
+
+fn admin_ai_safety_category_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Harm category to configure",
+        [("th", "หมวดเนื้อหาที่ต้องการตั้งค่า")],
+    )
+}
+
+fn admin_ai_safety_threshold_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Blocking threshold for this category",
+        [("th", "ระดับการบล็อกของหมวดนี้")],
+    )
+}
+
+fn admin_ai_persona_set_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Create or update an AI persona",
+        [("th", "สร้างหรือแก้ไขเพอร์โซนา AI")],
+    )
+}
+
+fn admin_ai_persona_name_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Persona name (letters, digits, - and _)",
+        [("th", "ชื่อเพอร์โซนา (ตัวอักษร ตัวเลข - และ _)")],
+    )
+}
+
+fn admin_ai_persona_prompt_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "System prompt for this persona",
+        [("th", "พรอมพ์ระบบของเพอร์โซนานี้")],
+    )
+}
+
+fn admin_ai_persona_models_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Allowed chat models in order, comma-separated (default: all)",
+        [(
+            "th",
+            "โมเดลที่อนุญาตตามลำดับ คั่นด้วยจุลภาค (ค่าเริ่มต้น: ทั้งหมด)",
+        )],
+    )
+}
+
+fn admin_ai_persona_temperature_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Sampling temperature from 0 to 2",
+        [("th", "ค่า temperature ตั้งแต่ 0 ถึง 2")],
+    )
+}
+
+fn admin_ai_persona_remove_desc() -> DescLocalizations {
+    DescLocalizations::new("Remove an AI persona", [("th", "ลบเพอร์โซนา AI")])
+}
+
+fn admin_ai_persona_remove_persona_desc() -> DescLocalizations {
+    DescLocalizations::new("Persona to remove", [("th", "เพอร์โซนาที่ต้องการลบ")])
+}
+
+fn admin_ai_base_prompt_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Override the base prompt for this server",
+        [("th", "กำหนดพรอมพ์หลักของเซิร์ฟเวอร์นี้")],
+    )
+}
+
+fn admin_ai_base_prompt_prompt_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "New base prompt (leave empty to reset)",
+        [("th", "พรอมพ์หลักใหม่ (เว้นว่างเพื่อใช้ค่าเริ่มต้น)")],
+    )
+}
+
+fn admin_ai_access_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Choose where and how members can reach the AI",
+        [("th", "กำหนดว่าสมาชิกใช้ AI ได้ที่ไหนและอย่างไร")],
+    )
+}
+
+fn admin_ai_access_enabled_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Turn the AI on or off in this server",
+        [("th", "เปิดหรือปิด AI ในเซิร์ฟเวอร์นี้")],
+    )
+}
+
+fn admin_ai_access_channel_mode_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "How the channel list from /admin ai channels is used",
+        [("th", "วิธีใช้รายการช่องจาก /admin ai channels")],
+    )
+}
+
+fn admin_ai_access_reply_mentions_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Whether replying to the bot's AI answers counts as mentioning it",
+        [(
+            "th",
+            "การตอบกลับคำตอบ AI ของบอทนับเป็นการเรียกบอทหรือไม่",
+        )],
+    )
+}
+
+fn admin_ai_access_dms_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Whether members may use the AI in direct messages",
+        [("th", "อนุญาตให้สมาชิกใช้ AI ในข้อความส่วนตัวหรือไม่")],
+    )
+}
+
+fn admin_ai_channels_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Edit the AI channel list",
+        [("th", "แก้ไขรายการช่องของ AI")],
+    )
+}
+
+fn admin_ai_channels_action_desc() -> DescLocalizations {
+    DescLocalizations::new("What to do", [("th", "สิ่งที่ต้องการทำ")])
+}
+
+fn admin_ai_channels_channel_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Channel to add or remove (default: this channel)",
+        [("th", "ช่องที่จะเพิ่มหรือลบ (ค่าเริ่มต้น: ช่องนี้)")],
+    )
+}
+
+fn admin_ai_usage_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Show AI usage, model fallbacks and errors in this server",
+        [(
+            "th",
+            "ดูการใช้งาน AI การสลับโมเดล และข้อผิดพลาดในเซิร์ฟเวอร์นี้",
+        )],
+    )
+}
+
+fn admin_ai_usage_days_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "How many days back to include (default 7)",
+        [("th", "จำนวนวันย้อนหลัง (ค่าเริ่มต้น 7)")],
+    )
+}
+
+impl AdminAiCommand {
+    pub async fn run(&self, ctx: Arc<Context>, interaction: Interaction) -> anyhow::Result<()> {
+        match self {
+            AdminAiCommand::Quota(command) => command.run(ctx, interaction).await,
+            AdminAiCommand::Safety(command) => command.run(ctx, interaction).await,
+            AdminAiCommand::PersonaSet(command) => command.run(ctx, interaction).await,
+            AdminAiCommand::PersonaRemove(command) => command.run(ctx, interaction).await,
+            AdminAiCommand::BasePrompt(command) => command.run(ctx, interaction).await,
+            AdminAiCommand::Access(command) => command.run(ctx, interaction).await,
+            AdminAiCommand::Channels(command) => command.run(ctx, interaction).await,
+            AdminAiCommand::Usage(command) => command.run(ctx, interaction).await,
+        }
+    }
+}
+
+async fn reply_invalid(
+    ctx: &Context,
+    interaction: &Interaction,
+    description: &str,
+) -> anyhow::Result<()> {
+    let embed = embed::invalid_option_embed(description)?;
+    ctx.http
+        .interaction(interaction.application_id)
+        .update_response(&interaction.token)
+        .embeds(Some(&[embed]))
+        .await?;
+    Ok(())
+}
+
+impl AdminAiQuotaCommand {
+    pub async fn run(&self, ctx: Arc<Context>, interaction: Interaction) -> anyhow::Result<()> {
+        let guild_id = interaction
+            .guild_id
+            .context("failed to parse guild_id")?;
+        let author = interaction
+            .author()
+            .context("failed to parse author")?;
+
+        let mut quota = GuildSettingsService::get(&ctx, guild_id.get())
+            .await
+            .ai_quota;
+        let updates = [
+            (&mut quota.user_requests, self.user_requests),
+            (&mut quota.user_tokens, self.user_tokens),
+            (&mut quota.guild_requests, self.guild_requests),
+            (&mut quota.guild_tokens, self.guild_tokens),
+        ];
+        for (field, value) in updates {
+            if let Some(value) = value {
+                *field = Some(value.max(0) as u64);
+            }
+        }
+
+        GuildSettingsService::set_ai_quota(&ctx, guild_id.get(), quota).await?;
+
+        if let Some(guild_ref) =
+            require_guild_ref(&ctx, &interaction, guild_id, "admin ai quota").await
+        {
+            let embed = embed::set_ai_quota_embed(&guild_ref, &quota, &author.name)?;
+            ctx.http
+                .interaction(interaction.application_id)
+                .update_response(&interaction.token)
+                .embeds(Some(&[embed]))
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl AdminAiSafetyCommand {
+    pub async fn run(&self, ctx: Arc<Context>, interaction: Interaction) -> anyhow::Result<()> {
+        let guild_id = interaction
+            .guild_id
+            .context("failed to parse guild_id")?;
+        let author = interaction
+            .author()
+            .context("failed to parse author")?;
+
+        let mut safety = GuildSettingsService::get(&ctx, guild_id.get())
+            .await
+            .ai_safety;
+        let field = match self.category {
+            AdminAiSafetyCategory::Harassment => &mut safety.harassment,
+            AdminAiSafetyCategory::HateSpeech => &mut safety.hate_speech,
+            AdminAiSafetyCategory::SexuallyExplicit => &mut safety.sexually_explicit,
+            AdminAiSafetyCategory::DangerousContent => &mut safety.dangerous_content,
+        };
+        *field = self.threshold.threshold();
+
+        GuildSettingsService::set_ai_safety(&ctx, guild_id.get(), safety).await?;
+
+        if let Some(guild_ref) =
+            require_guild_ref(&ctx, &interaction, guild_id, "admin ai safety").await
+        {
+            let embed = embed::set_ai_safety_embed(&guild_ref, &safety, &author.name)?;
+            ctx.http
+                .interaction(interaction.application_id)
+                .update_response(&interaction.token)
+                .embeds(Some(&[embed]))
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl AdminAiPersonaSetCommand {
+    pub async fn run(&self, ctx: Arc<Context>, interaction: Interaction) -> anyhow::Result<()> {
+        let guild_id = interaction
+            .guild_id
+            .context("failed to parse guild_id")?;
+        let author = interaction
+            .author()
+            .context("failed to parse author")?;
+
+        let Some(name) = persona::normalize_name(&self.name) else {
+            return reply_invalid(
+                &ctx,
+                &interaction,
+                "ชื่อเพอร์โซนาใช้ได้เฉพาะตัวอักษร ตัวเลข - และ _ (ไม่เกิน 32 ตัว)",
+            )
+            .await;
+        };
+        let prompt = self.prompt.trim();
+        if prompt.is_empty() {
+            return reply_invalid(&ctx, &interaction, "พรอมพ์ต้องไม่ว่าง").await;
+        }
+        let models = match self.models.as_deref() {
+            Some(raw) => match persona::parse_models(raw, &catalogue::current().chat) {
+                Ok(models) => models,
+                Err(unknown) => {
+                    return reply_invalid(
+                        &ctx,
+                        &interaction,
+                        &format!("ไม่พบโมเดล `{unknown}` ในรายการโมเดลแชต"),
+                    )
+                    .await;
+                }
+            },
+            None => Vec::new(),
+        };
+
+        let persona = AiPersona {
+            id: None,
+            guild_id: guild_id.get(),
+            name,
+            system_prompt: prompt.to_string(),
+            models,
+            temperature: self.temperature.map(|t| t as f32),
+        };
+        let created = match persona::save(&ctx, persona.clone()).await? {
+            SaveOutcome::Created => true,
+            SaveOutcome::Updated => false,
+            SaveOutcome::LimitReached => {
+                return reply_invalid(
+                    &ctx,
+                    &interaction,
+                    &format!(
+                        "เซิร์ฟเวอร์นี้มีเพอร์โซนาครบ {} รายการแล้ว",
+                        persona::MAX_PERSONAS
+                    ),
+                )
+                .await;
+            }
+        };
+
+        if let Some(guild_ref) = require_guild_ref(
+            &ctx,
+            &interaction,
+            guild_id,
+            "admin ai persona-set",
+        )
+        .await
+        {
+            let embed = embed::set_ai_persona_embed(&guild_ref, &persona, created, &author.name)?;
+            ctx.http
+                .interaction(interaction.application_id)
+                .update_response(&interaction.token)
+                .embeds(Some(&[embed]))
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl AdminAiPersonaRemoveCommand {
+    pub async fn run(&self, ctx: Arc<Context>, interaction: Interaction) -> anyhow::Result<()> {
+        let guild_id = interaction
+            .guild_id
+            .context("failed to parse guild_id")?;
+        let author = interaction
+            .author()
+            .context("failed to parse author")?;
+
+        let name = persona::normalize_name(&self.persona).unwrap_or_default();
+        if !persona::remove(&ctx, guild_id.get(), &name).await? {
+            return reply_invalid(
+                &ctx,
+                &interaction,
+                &format!("ไม่พบเพอร์โซนา `{}`", self.persona),
+            )
+            .await;
+        }
+
+        if let Some(guild_ref) = require_guild_ref(
+            &ctx,
+            &interaction,
+            guild_id,
+            "admin ai persona-remove",
+        )
+        .await
+        {
+            let embed = embed::remove_ai_persona_embed(&guild_ref, &name, &author.name)?;
+            ctx.http
+                .interaction(interaction.application_id)
+                .update_response(&interaction.token)
+                .embeds(Some(&[embed]))
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl AdminAiBasePromptCommand {
+    pub async fn run(&self, ctx: Arc<Context>, interaction: Interaction) -> anyhow::Result<()> {
+        let guild_id = interaction
+            .guild_id
+            .context("failed to parse guild_id")?;
+        let author = interaction
+            .author()
+            .context("failed to parse author")?;
+
+        let prompt = self
+            .prompt
+            .as_deref()
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .map(str::to_string);
+        GuildSettingsService::set_ai_base_prompt(&ctx, guild_id.get(), prompt.clone()).await?;
+
+        if let Some(guild_ref) = require_guild_ref(
+            &ctx,
+            &interaction,
+            guild_id,
+            "admin ai base-prompt",
+        )
+        .await
+        {
+            let embed =
+                embed::set_ai_base_prompt_embed(&guild_ref, prompt.as_deref(), &author.name)?;
+            ctx.http
+                .interaction(interaction.application_id)
+                .update_response(&interaction.token)
+                .embeds(Some(&[embed]))
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The channel list is shown in one embed field, so keep it readable.
+const MAX_AI_CHANNELS: usize = 25;
+
+impl AdminAiAccessCommand {
+    pub async fn run(&self, ctx: Arc<Context>, interaction: Interaction) -> anyhow::Result<()> {
+        let guild_id = interaction
+            .guild_id
+            .context("failed to parse guild_id")?;
+        let author = interaction
+            .author()
+            .context("failed to parse author")?;
+
+        let mut access = GuildSettingsService::get(&ctx, guild_id.get())
+            .await
+            .ai_access;
+        if let Some(enabled) = self.enabled {
+            access.enabled = enabled;
+        }
+        if let Some(mode) = self.channel_mode {
+            access.channel_mode = mode.mode();
+        }
+        if let Some(reply_mentions) = self.reply_mentions {
+            access.reply_mentions = reply_mentions;
+        }
+        if let Some(dms) = self.dms {
+            access.allow_dms = dms;
+        }
+
+        GuildSettingsService::set_ai_access(&ctx, guild_id.get(), &access).await?;
+
+        if let Some(guild_ref) =
+            require_guild_ref(&ctx, &interaction, guild_id, "admin ai access").await
+        {
+            let embed = embed::set_ai_access_embed(&guild_ref, &access, &author.name)?;
+            ctx.http
+                .interaction(interaction.application_id)
+                .update_response(&interaction.token)
+                .embeds(Some(&[embed]))
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl AdminAiChannelsCommand {
+    pub async fn run(&self, ctx: Arc<Context>, interaction: Interaction) -> anyhow::Result<()> {
+        let guild_id = interaction
+            .guild_id
+            .context("failed to parse guild_id")?;
+        let author = interaction
+            .author()
+            .context("failed to parse author")?;
+        let channel_id = self
+            .channel
+            .or_else(|| {
+                interaction
+                    .channel
+                    .as_ref()
+                    .map(|c| c.id)
+            })
+            .context("failed to parse channel")?
+            .get();
+
+        let mut access = GuildSettingsService::get(&ctx, guild_id.get())
+            .await
+            .ai_access;
+        match self.action {
+            AdminAiChannelAction::Add => {
+                if !access.channels.contains(&channel_id) {
+                    if access.channels.len() >= MAX_AI_CHANNELS {
+                        return reply_invalid(
+                            &ctx,
+                            &interaction,
+                            &format!("รายการช่องมีครบ {MAX_AI_CHANNELS} ช่องแล้ว"),
+                        )
+                        .await;
+                    }
+                    access.channels.push(channel_id);
+                }
+            }
+            AdminAiChannelAction::Remove => access
+                .channels
+                .retain(|id| *id != channel_id),
+            AdminAiChannelAction::Clear => access.channels.clear(),
+        }
+
+        GuildSettingsService::set_ai_access(&ctx, guild_id.get(), &access).await?;
+
+        if let Some(guild_ref) =
+            require_guild_ref(&ctx, &interaction, guild_id, "admin ai channels").await
+        {
+            let embed = embed::set_ai_access_embed(&guild_ref, &access, &author.name)?;
+            ctx.http
+                .interaction(interaction.application_id)
+                .update_response(&interaction.token)
+                .embeds(Some(&[embed]))
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl AdminAiUsageCommand {
+    pub async fn run(&self, ctx: Arc<Context>, interaction: Interaction) -> anyhow::Result<()> {
+        let guild_id = interaction
+            .guild_id
+            .context("failed to parse guild_id")?;
+
+        let report = usage::report(&ctx, guild_id.get(), self.days.unwrap_or(7)).await?;
+        let embed = AiService::usage_embed(&report)?;
+        ctx.http
+            .interaction(interaction.application_id)
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+
+        Ok(())
+    }
+}
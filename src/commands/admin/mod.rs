@@ -13,14 +13,19 @@ use twilight_model::{
 
 use crate::{
     commands::admin::{
-        channel::AdminChannelCommand, role::AdminRoleCommand, scam_detect::AdminScamDetectCommand,
+        ai::AdminAiCommand,
+        channel::AdminChannelCommand,
+        role::AdminRoleCommand,
+        scam_detect::{AdminScamDetectCommand, AdminScamTextCommand},
     },
     context::Context,
     handle_ephemeral,
+    services::ai::persona::persona_choices,
     utils::ascii::ascii_starts_with_icase,
 };
 use std::sync::Arc;
 
+pub mod ai;
 pub mod channel;
 pub mod role;
 pub mod scam_detect;
@@ -34,6 +39,10 @@ pub enum AdminCommand {
     Role(AdminRoleCommand),
     #[command(name = "scam-detect")]
     ScamDetect(AdminScamDetectCommand),
+    #[command(name = "scam-text")]
+    ScamText(AdminScamTextCommand),
+    #[command(name = "ai")]
+    Ai(AdminAiCommand),
 }
 
 fn admin_desc() -> DescLocalizations {
@@ -80,6 +89,8 @@ impl AdminCommand {
                 AdminCommand::Channel(command) => command.run(ctx, interaction).await,
                 AdminCommand::Role(command) => command.run(ctx, interaction).await,
                 AdminCommand::ScamDetect(command) => command.run(ctx, interaction).await,
+                AdminCommand::ScamText(command) => command.run(ctx, interaction).await,
+                AdminCommand::Ai(command) => command.run(ctx, interaction).await,
             }?;
         });
     }
@@ -120,6 +131,10 @@ impl AdminCommand {
                 );
             }
 
+            if focused.0 == "persona" {
+                choices.extend(persona_choices(&ctx, guild_id.get(), focused.1).await);
+            }
+
             let response = InteractionResponse {
                 kind: InteractionResponseType::ApplicationCommandAutocompleteResult,
                 data: Some(InteractionResponseData {
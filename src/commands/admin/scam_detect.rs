@@ -6,6 +6,7 @@ use twilight_model::application::interaction::Interaction;
 
 use crate::{
     context::Context,
+    dbs::mongo::models::guild_settings::ScamTextMode,
     services::guild_settings::GuildSettingsService,
     utils::{embed, interaction::require_guild_ref},
 };
@@ -29,6 +30,26 @@ pub enum AdminScamDetectChoice {
     Disable,
 }
 
+#[derive(CommandModel, CreateCommand, Debug)]
+#[command(
+    name = "scam-text",
+    desc_localizations = "admin_scam_text_desc"
+)]
+pub struct AdminScamTextCommand {
+    #[command(desc_localizations = "admin_scam_text_mode_desc")]
+    pub mode: AdminScamTextMode,
+}
+
+#[derive(CreateOption, CommandOption, Debug, Clone, Copy)]
+pub enum AdminScamTextMode {
+    #[option(name = "Off", value = "off")]
+    Off,
+    #[option(name = "Shadow (log only)", value = "shadow")]
+    Shadow,
+    #[option(name = "Enforce (quarantine)", value = "enforce")]
+    Enforce,
+}
+
 fn admin_scam_detect_desc() -> DescLocalizations {
     DescLocalizations::new(
         "Configure OCR scam image detection",
@@ -43,6 +64,23 @@ fn admin_scam_detect_choice_desc() -> DescLocalizations {
     )
 }
 
+fn admin_scam_text_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Configure AI scam text detection",
+        [("th", "ตั้งค่าการตรวจจับข้อความ scam ด้วย AI")],
+    )
+}
+
+fn admin_scam_text_mode_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Check links from new members: off, log only, or quarantine",
+        [(
+            "th",
+            "ตรวจลิงก์จากสมาชิกใหม่: ปิด, บันทึกอย่างเดียว หรือกักตัว",
+        )],
+    )
+}
+
 impl AdminScamDetectCommand {
     pub async fn run(&self, ctx: Arc<Context>, interaction: Interaction) -> anyhow::Result<()> {
         let enabled = match self.choice {
@@ -77,3 +115,34 @@ impl AdminScamDetectCommand {
         Ok(())
     }
 }
+
+impl AdminScamTextCommand {
+    pub async fn run(&self, ctx: Arc<Context>, interaction: Interaction) -> anyhow::Result<()> {
+        let mode = match self.mode {
+            AdminScamTextMode::Off => ScamTextMode::Off,
+            AdminScamTextMode::Shadow => ScamTextMode::Shadow,
+            AdminScamTextMode::Enforce => ScamTextMode::Enforce,
+        };
+        let guild_id = interaction
+            .guild_id
+            .context("failed to parse guild_id")?;
+        let author = interaction
+            .author()
+            .context("failed to parse author")?;
+
+        GuildSettingsService::set_scam_text_mode(&ctx, guild_id.get(), mode).await?;
+
+        if let Some(guild_ref) =
+            require_guild_ref(&ctx, &interaction, guild_id, "admin scam-text").await
+        {
+            let embed = embed::set_scam_text_embed(&guild_ref, mode, &author.name)?;
+            ctx.http
+                .interaction(interaction.application_id)
+                .update_response(&interaction.token)
+                .embeds(Some(&[embed]))
+                .await?;
+        }
+
+        Ok(())
+    }
+}
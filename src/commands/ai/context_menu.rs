@@ -0,0 +1,108 @@
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use twilight_model::{
+    application::{
+        command::{Command, CommandType},
+        interaction::{Interaction, application_command::CommandData},
+    },
+    channel::message::MessageFlags,
+};
+use twilight_util::builder::command::CommandBuilder;
+
+use super::within_limits;
+use crate::{
+    context::Context,
+    defer_interaction,
+    services::ai::{AiInteraction, AiService, client, message_action::MessageAction},
+};
+
+/// Discord's cap on attachments, shared with bot mentions.
+const MAX_ATTACHMENTS: usize = 5;
+
+/// The message-type commands shown under "Apps" when right-clicking a message.
+pub fn commands() -> Vec<Command> {
+    MessageAction::ALL
+        .into_iter()
+        .map(|action| {
+            CommandBuilder::new(action.command_name(), "", CommandType::Message)
+                .name_localizations([("th", action.thai_name())])
+                .build()
+        })
+        .collect()
+}
+
+pub async fn handle(ctx: Arc<Context>, interaction: Interaction, data: CommandData) {
+    if let Err(e) = async {
+        defer_interaction!(ctx.http, &interaction, true).await?;
+        let action =
+            MessageAction::from_command_name(&data.name).context("unknown message command")?;
+        let user = interaction
+            .author()
+            .context("no author")?;
+        let target = data
+            .target_id
+            .and_then(|id| {
+                data.resolved
+                    .as_ref()?
+                    .messages
+                    .get(&id.cast())
+            })
+            .context("no target message")?;
+
+        if target.content.trim().is_empty() && target.attachments.is_empty() {
+            let embeds = AiService::ai_embeds("ข้อความนี้ไม่มีเนื้อหาให้ AI อ่าน")?;
+            ctx.http
+                .interaction(interaction.application_id)
+                .update_response(&interaction.token)
+                .embeds(Some(&embeds))
+                .await?;
+            return Ok(());
+        }
+        if !within_limits(&ctx, &interaction, user.id).await? {
+            return Ok(());
+        }
+
+        let client = Arc::new(client::client().await?.clone());
+        let input = action.input(&target.author.name, &target.content);
+        let mut ref_attachments = target.attachments.clone();
+        ref_attachments.truncate(MAX_ATTACHMENTS);
+        let reply = AiService::handle_interaction(
+            &ctx,
+            &client,
+            AiInteraction {
+                guild_id: interaction.guild_id,
+                user_id: user.id,
+                user_name: &user.name,
+                message: &input,
+                attachments: Vec::new(),
+                ref_text: Some(&target.content),
+                ref_attachments,
+                ref_author: Some(&target.author.name),
+                remember: false,
+            },
+        )
+        .await?;
+        for embed in AiService::reply_embeds(&reply)? {
+            ctx.http
+                .interaction(interaction.application_id)
+                .create_followup(&interaction.token)
+                .embeds(&[embed])
+                .flags(MessageFlags::EPHEMERAL)
+                .await?;
+        }
+        Ok::<_, anyhow::Error>(())
+    }
+    .await
+    {
+        tracing::error!(error = %e, command = %data.name, "error handling ai message command");
+        if let Ok(embed) = AiService::unavailable_embed() {
+            let _ = ctx
+                .http
+                .interaction(interaction.application_id)
+                .update_response(&interaction.token)
+                .embeds(Some(&[embed]))
+                .await;
+        }
+    }
+}
@@ -1,17 +1,44 @@
 use anyhow::Context as _;
+use chrono::Utc;
 use twilight_interactions::command::{CommandModel, CreateCommand, DescLocalizations};
 use twilight_model::{
-    application::interaction::{Interaction, application_command::CommandData},
-    channel::message::MessageFlags,
+    application::interaction::{
+        Interaction,
+        application_command::{CommandData, CommandDataOption, CommandOptionValue},
+        modal::ModalInteractionData,
+    },
+    channel::{
+        Attachment,
+        message::{
+            Component, MessageFlags,
+            component::{Label, TextInput, TextInputStyle},
+        },
+    },
+    guild::Permissions,
+    http::interaction::{InteractionResponse, InteractionResponseData, InteractionResponseType},
+    id::{Id, marker::UserMarker},
 };
 
 use crate::{
     context::Context,
     defer_interaction,
-    services::ai::{AiInteraction, AiService, client},
+    services::ai::{
+        AiInteraction, AiService, access,
+        attachments::AttachmentHttp,
+        channel_summary::{self, ChannelSummaryRequest},
+        client,
+        knowledge::{self, AddOutcome},
+        memory, persona,
+    },
+    utils::modal::modal_value_of,
 };
+
 use std::sync::Arc;
 
+pub mod context_menu;
+
+pub const KB_MODAL_ID: &str = "ai_kb_modal";
+
 #[derive(CommandModel, CreateCommand, Debug)]
 #[command(name = "ai", desc_localizations = "ai_desc")]
 pub enum AiCommand {
@@ -21,6 +48,16 @@ pub enum AiCommand {
     Talk(Box<AiTalkCommand>),
     #[command(name = "clear")]
     Clear(AiClearCommand),
+    #[command(name = "quota")]
+    Quota(AiQuotaCommand),
+    #[command(name = "summarize")]
+    Summarize(AiSummarizeCommand),
+    #[command(name = "persona")]
+    Persona(AiPersonaCommand),
+    #[command(name = "kb")]
+    Kb(AiKbCommand),
+    #[command(name = "memory")]
+    Memory(AiMemoryCommand),
 }
 
 #[derive(CommandModel, CreateCommand, Debug)]
@@ -43,6 +80,90 @@ pub struct AiTalkCommand {
 #[command(name = "clear", desc_localizations = "clear_desc")]
 pub struct AiClearCommand {}
 
+#[derive(CommandModel, CreateCommand, Debug)]
+#[command(name = "quota", desc_localizations = "quota_desc")]
+pub struct AiQuotaCommand {}
+
+#[derive(CommandModel, CreateCommand, Debug)]
+#[command(name = "summarize", desc_localizations = "summarize_desc")]
+pub struct AiSummarizeCommand {
+    #[command(
+        min_value = 1,
+        max_value = 500,
+        desc_localizations = "summarize_messages_desc"
+    )]
+    pub messages: Option<i64>,
+    #[command(desc_localizations = "summarize_since_desc")]
+    pub since: Option<String>,
+}
+
+#[derive(CommandModel, CreateCommand, Debug)]
+#[command(name = "persona", desc_localizations = "persona_desc")]
+pub struct AiPersonaCommand {
+    #[command(
+        autocomplete = true,
+        desc_localizations = "persona_name_desc"
+    )]
+    pub name: Option<String>,
+}
+
+#[derive(CommandModel, CreateCommand, Debug)]
+#[command(name = "kb", desc_localizations = "kb_desc")]
+pub enum AiKbCommand {
+    #[command(name = "add")]
+    Add(Box<AiKbAddCommand>),
+    #[command(name = "remove")]
+    Remove(AiKbRemoveCommand),
+    #[command(name = "list")]
+    List(AiKbListCommand),
+}
+
+#[derive(CommandModel, CreateCommand, Debug)]
+#[command(name = "add", desc_localizations = "kb_add_desc")]
+pub struct AiKbAddCommand {
+    #[command(desc_localizations = "kb_add_file_desc")]
+    pub file: Option<twilight_model::channel::Attachment>,
+    #[command(max_length = 100, desc_localizations = "kb_add_title_desc")]
+    pub title: Option<String>,
+}
+
+#[derive(CommandModel, CreateCommand, Debug)]
+#[command(name = "remove", desc_localizations = "kb_remove_desc")]
+pub struct AiKbRemoveCommand {
+    #[command(
+        autocomplete = true,
+        desc_localizations = "kb_remove_document_desc"
+    )]
+    pub document: String,
+}
+
+#[derive(CommandModel, CreateCommand, Debug)]
+#[command(name = "list", desc_localizations = "kb_list_desc")]
+pub struct AiKbListCommand {}
+
+#[derive(CommandModel, CreateCommand, Debug)]
+#[command(name = "memory", desc_localizations = "memory_desc")]
+pub enum AiMemoryCommand {
+    #[command(name = "show")]
+    Show(AiMemoryShowCommand),
+    #[command(name = "forget")]
+    Forget(AiMemoryForgetCommand),
+}
+
+#[derive(CommandModel, CreateCommand, Debug)]
+#[command(name = "show", desc_localizations = "memory_show_desc")]
+pub struct AiMemoryShowCommand {}
+
+#[derive(CommandModel, CreateCommand, Debug)]
+#[command(name = "forget", desc_localizations = "memory_forget_desc")]
+pub struct AiMemoryForgetCommand {
+    #[command(
+        autocomplete = true,
+        desc_localizations = "memory_forget_item_desc"
+    )]
+    pub item: String,
+}
+
 fn ai_desc() -> DescLocalizations {
     DescLocalizations::new("AI utilities", [("th", "ผู้ช่วย AI")])
 }
@@ -77,12 +198,338 @@ fn clear_desc() -> DescLocalizations {
     )
 }
 
+fn quota_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Show your remaining AI usage for today",
+        [("th", "ดูโควตา AI ที่เหลือของวันนี้")],
+    )
+}
+
+fn summarize_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Summarize recent messages in this channel",
+        [("th", "สรุปข้อความล่าสุดในช่องนี้")],
+    )
+}
+
+fn summarize_messages_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "How many messages to read (default 100)",
+        [("th", "จำนวนข้อความที่จะอ่าน (ค่าเริ่มต้น 100)")],
+    )
+}
+
+fn summarize_since_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Only messages newer than this, e.g. 2h, 90m, 1d",
+        [("th", "เฉพาะข้อความใหม่กว่านี้ เช่น 2h, 90m, 1d")],
+    )
+}
+
+fn persona_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Pick one of this server's AI personas",
+        [("th", "เลือกเพอร์โซนา AI ของเซิร์ฟเวอร์นี้")],
+    )
+}
+
+fn persona_name_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Persona to use (leave empty to go back to the default)",
+        [("th", "เพอร์โซนาที่จะใช้ (เว้นว่างเพื่อกลับไปใช้ค่าเริ่มต้น)")],
+    )
+}
+
+fn kb_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Manage this server's AI knowledge base",
+        [("th", "จัดการคลังความรู้ AI ของเซิร์ฟเวอร์")],
+    )
+}
+
+fn kb_add_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Add or replace a document (leave the file empty to type it in)",
+        [("th", "เพิ่มหรือแทนที่เอกสาร (ไม่แนบไฟล์เพื่อพิมพ์เอง)")],
+    )
+}
+
+fn kb_add_file_desc() -> DescLocalizations {
+    DescLocalizations::new("A .txt or .md file", [("th", "ไฟล์ .txt หรือ .md")])
+}
+
+fn kb_add_title_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Document title (defaults to the file name)",
+        [("th", "ชื่อเอกสาร (ค่าเริ่มต้นคือชื่อไฟล์)")],
+    )
+}
+
+fn kb_remove_desc() -> DescLocalizations {
+    DescLocalizations::new("Remove a document", [("th", "ลบเอกสาร")])
+}
+
+fn kb_remove_document_desc() -> DescLocalizations {
+    DescLocalizations::new("Document title", [("th", "ชื่อเอกสาร")])
+}
+
+fn kb_list_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "List the documents in the knowledge base",
+        [("th", "แสดงรายการเอกสารในคลังความรู้")],
+    )
+}
+
+fn memory_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "What the AI remembers about you",
+        [("th", "สิ่งที่ AI จำเกี่ยวกับคุณ")],
+    )
+}
+
+fn memory_show_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Show what the AI remembers about you",
+        [("th", "ดูสิ่งที่ AI จำเกี่ยวกับคุณ")],
+    )
+}
+
+fn memory_forget_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Make the AI forget one item",
+        [("th", "ให้ AI ลืมข้อมูลหนึ่งรายการ")],
+    )
+}
+
+fn memory_forget_item_desc() -> DescLocalizations {
+    DescLocalizations::new("Item to forget", [("th", "ข้อมูลที่จะให้ลืม")])
+}
+
+fn extract_focused(cmd: &CommandData) -> Option<(&str, &str)> {
+    fn focused_in(options: &[CommandDataOption]) -> Option<(&str, &str)> {
+        options
+            .iter()
+            .find_map(|opt| match &opt.value {
+                CommandOptionValue::SubCommand(nested)
+                | CommandOptionValue::SubCommandGroup(nested) => focused_in(nested),
+                CommandOptionValue::Focused(user_input, _) => {
+                    Some((opt.name.as_str(), user_input.as_str()))
+                }
+                _ => None,
+            })
+    }
+    focused_in(&cmd.options)
+}
+
+/// The knowledge base is edited by members who can manage the server.
+fn can_manage_knowledge(interaction: &Interaction) -> bool {
+    interaction
+        .member
+        .as_ref()
+        .and_then(|member| member.permissions)
+        .is_some_and(|permissions| permissions.contains(Permissions::MANAGE_GUILD))
+}
+
+#[allow(deprecated)]
+fn kb_modal() -> InteractionResponse {
+    let text_input = |custom_id: &str, style, max_length, placeholder: &str| {
+        Box::new(Component::TextInput(TextInput {
+            id: None,
+            custom_id: custom_id.into(),
+            label: None,
+            max_length: Some(max_length),
+            min_length: None,
+            placeholder: Some(placeholder.into()),
+            required: Some(true),
+            style,
+            value: None,
+        }))
+    };
+    let components = vec![
+        Component::Label(Label {
+            id: None,
+            label: "Title".into(),
+            description: None,
+            component: text_input(
+                "title",
+                TextInputStyle::Short,
+                knowledge::MAX_TITLE_CHARS as u16,
+                "ชื่อเอกสาร",
+            ),
+        }),
+        Component::Label(Label {
+            id: None,
+            label: "Content".into(),
+            description: None,
+            component: text_input(
+                "content",
+                TextInputStyle::Paragraph,
+                4000,
+                "เนื้อหาที่ AI ใช้ตอบคำถาม",
+            ),
+        }),
+    ];
+    InteractionResponse {
+        kind: InteractionResponseType::Modal,
+        data: Some(InteractionResponseData {
+            components: Some(components),
+            custom_id: Some(KB_MODAL_ID.into()),
+            title: Some("Knowledge base document".into()),
+            ..Default::default()
+        }),
+    }
+}
+
+fn kb_add_text(title: &str, outcome: AddOutcome) -> String {
+    match outcome {
+        AddOutcome::Created { chunks } => {
+            format!("เพิ่มเอกสาร **{title}** แล้ว ({chunks} ส่วน)")
+        }
+        AddOutcome::Replaced { chunks } => {
+            format!("แทนที่เอกสาร **{title}** แล้ว ({chunks} ส่วน)")
+        }
+        AddOutcome::Empty => "เอกสารว่างเปล่า".to_string(),
+        AddOutcome::TooLong => format!(
+            "เอกสารยาวเกินไป (สูงสุด {} ตัวอักษร)",
+            knowledge::MAX_DOCUMENT_CHARS
+        ),
+        AddOutcome::LimitReached => format!(
+            "คลังความรู้มีเอกสารครบ {} รายการแล้ว ลบเอกสารเก่าก่อน",
+            knowledge::MAX_DOCUMENTS
+        ),
+    }
+}
+
+/// Downloads a `/ai kb add` upload, or explains why it was refused.
+async fn read_kb_file(ctx: &Context, file: &Attachment) -> anyhow::Result<Result<String, String>> {
+    if !knowledge::is_text_attachment(&file.filename, file.content_type.as_deref()) {
+        return Ok(Err("รองรับเฉพาะไฟล์ .txt หรือ .md".to_string()));
+    }
+    if file.size > knowledge::MAX_ATTACHMENT_BYTES {
+        return Ok(Err(format!(
+            "ไฟล์ใหญ่เกินไป (สูงสุด {} ตัวอักษร)",
+            knowledge::MAX_DOCUMENT_CHARS
+        )));
+    }
+    let bytes = AttachmentHttp::get(&ctx.reqwest, &file.url)
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?;
+    Ok(String::from_utf8(bytes.to_vec()).map_err(|_| "ไฟล์ต้องเป็นข้อความ UTF-8".to_string()))
+}
+
+async fn handle_kb(
+    ctx: &Context,
+    interaction: &Interaction,
+    guild_id: u64,
+    command: AiKbCommand,
+) -> anyhow::Result<String> {
+    Ok(match command {
+        AiKbCommand::Add(c) => {
+            let file = c
+                .file
+                .context("kb add without a file is answered with the modal")?;
+            let raw_title = c
+                .title
+                .unwrap_or_else(|| file.filename.clone());
+            let Some(title) = knowledge::normalize_title(&raw_title) else {
+                return Ok("ชื่อเอกสารไม่ถูกต้อง".to_string());
+            };
+            let content = match read_kb_file(ctx, &file).await? {
+                Ok(content) => content,
+                Err(reason) => return Ok(reason),
+            };
+            let user = interaction
+                .author()
+                .context("no author")?;
+            let outcome = knowledge::add(ctx, guild_id, &title, &content, user.id.get()).await?;
+            kb_add_text(&title, outcome)
+        }
+        AiKbCommand::Remove(c) => {
+            if knowledge::remove(ctx, guild_id, &c.document).await? {
+                format!("ลบเอกสาร **{}** แล้ว", c.document)
+            } else {
+                format!("ไม่พบเอกสาร `{}`", c.document)
+            }
+        }
+        AiKbCommand::List(_) => {
+            let documents = knowledge::list(ctx, guild_id).await;
+            if documents.is_empty() {
+                "คลังความรู้ยังว่างอยู่".to_string()
+            } else {
+                let lines: Vec<String> = documents
+                    .iter()
+                    .map(|d| {
+                        format!(
+                            "• **{}** ({} ส่วน) โดย <@{}>",
+                            d.title,
+                            d.chunks.len(),
+                            d.added_by
+                        )
+                    })
+                    .collect();
+                format!(
+                    "**คลังความรู้ ({}/{})**\n{}",
+                    documents.len(),
+                    knowledge::MAX_DOCUMENTS,
+                    lines.join("\n")
+                )
+            }
+        }
+    })
+}
+
+/// Sends the access, rate-limit or quota embed and returns `false` when the
+/// user may not make another AI request here right now.
+async fn within_limits(
+    ctx: &Arc<Context>,
+    interaction: &Interaction,
+    user: Id<UserMarker>,
+) -> anyhow::Result<bool> {
+    let channel_id = interaction
+        .channel
+        .as_ref()
+        .map(|channel| channel.id)
+        .context("no channel")?;
+    let embed = if let Err(denied) =
+        access::check(ctx, interaction.guild_id, channel_id, user).await
+    {
+        AiService::access_denied_embed(denied)
+    } else if let Some(wait) = AiService::check_rate_limit(ctx, user).await {
+        AiService::rate_limit_embed(wait)
+    } else if let Some(exceeded) = AiService::check_quota(ctx, interaction.guild_id, user).await {
+        AiService::quota_exceeded_embed(&exceeded)
+    } else {
+        return Ok(true);
+    };
+    if let Ok(embed) = embed {
+        ctx.http
+            .interaction(interaction.application_id)
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+    }
+    Ok(false)
+}
+
 impl AiCommand {
     pub async fn handle(ctx: Arc<Context>, interaction: Interaction, data: CommandData) {
         if let Err(e) = async {
-            defer_interaction!(ctx.http, &interaction, true).await?;
             let command =
                 AiCommand::from_interaction(data.into()).context("parse ai command data")?;
+            if let AiCommand::Kb(AiKbCommand::Add(c)) = &command
+                && c.file.is_none()
+                && interaction.guild_id.is_some()
+                && can_manage_knowledge(&interaction)
+            {
+                ctx.http
+                    .interaction(interaction.application_id)
+                    .create_response(interaction.id, &interaction.token, &kb_modal())
+                    .await?;
+                return Ok(());
+            }
+            defer_interaction!(ctx.http, &interaction, true).await?;
             match command {
                 AiCommand::Prompt(c) => {
                     if let Some(user) = interaction.author() {
@@ -99,14 +546,7 @@ impl AiCommand {
                     let user = interaction
                         .author()
                         .context("no author")?;
-                    if let Some(wait) = AiService::check_rate_limit(&ctx, user.id).await {
-                        if let Ok(embed) = AiService::rate_limit_embed(wait) {
-                            ctx.http
-                                .interaction(interaction.application_id)
-                                .update_response(&interaction.token)
-                                .embeds(Some(&[embed]))
-                                .await?;
-                        }
+                    if !within_limits(&ctx, &interaction, user.id).await? {
                         return Ok::<_, anyhow::Error>(());
                     }
                     let attachments = c.attachment.into_iter().collect();
@@ -128,6 +568,7 @@ impl AiCommand {
                         &ctx,
                         &client,
                         AiInteraction {
+                            guild_id: interaction.guild_id,
                             user_id: user.id,
                             user_name: &user.name,
                             message: &c.message,
@@ -135,10 +576,83 @@ impl AiCommand {
                             ref_text: None,
                             ref_attachments: Vec::new(),
                             ref_author: None,
+                            remember: true,
+                        },
+                    )
+                    .await?;
+                    for embed in AiService::reply_embeds(&reply)? {
+                        ctx.http
+                            .interaction(interaction.application_id)
+                            .create_followup(&interaction.token)
+                            .embeds(&[embed])
+                            .flags(MessageFlags::EPHEMERAL)
+                            .await?;
+                    }
+                }
+                AiCommand::Summarize(c) => {
+                    let user = interaction
+                        .author()
+                        .context("no author")?;
+                    let channel_id = interaction
+                        .channel
+                        .as_ref()
+                        .map(|channel| channel.id)
+                        .context("no channel")?;
+                    let since = match c.since.as_deref() {
+                        Some(raw) => match channel_summary::parse_since(raw) {
+                            Some(duration) => Some(Utc::now() - duration),
+                            None => {
+                                let embeds = AiService::ai_embeds(
+                                    "รูปแบบเวลาไม่ถูกต้อง ใช้เช่น 2h, 90m หรือ 1d (สูงสุด 7 วัน)",
+                                )?;
+                                ctx.http
+                                    .interaction(interaction.application_id)
+                                    .update_response(&interaction.token)
+                                    .embeds(Some(&embeds))
+                                    .await?;
+                                return Ok::<_, anyhow::Error>(());
+                            }
+                        },
+                        None => None,
+                    };
+                    if !within_limits(&ctx, &interaction, user.id).await? {
+                        return Ok::<_, anyhow::Error>(());
+                    }
+                    let client = Arc::new(client::client().await?.clone());
+                    let summary = AiService::summarize_channel(
+                        &ctx,
+                        &client,
+                        user.id,
+                        ChannelSummaryRequest {
+                            guild_id: interaction.guild_id,
+                            channel_id,
+                            limit: c
+                                .messages
+                                .map_or(channel_summary::DEFAULT_MESSAGES, |n| {
+                                    n.max(1) as usize
+                                }),
+                            since,
                         },
                     )
                     .await?;
-                    for embed in AiService::ai_embeds(&reply)? {
+                    let text = match summary {
+                        Some(summary) => format!(
+                            "**สรุป {} ข้อความล่าสุด**\n\n{}",
+                            summary.messages, summary.text
+                        ),
+                        None => "ไม่มีข้อความที่จะสรุปในช่วงนี้".to_string(),
+                    };
+                    // One embed per message keeps a long summary under Discord's
+                    // 6000 character total for the embeds of a message.
+                    let mut embeds = AiService::ai_embeds(&text)?.into_iter();
+                    if let Some(first) = embeds.next() {
+                        ctx.http
+                            .interaction(interaction.application_id)
+                            .update_response(&interaction.token)
+                            .embeds(Some(&[first]))
+                            .await?;
+                    }
+                    for embed in embeds {
                         ctx.http
                             .interaction(interaction.application_id)
                             .create_followup(&interaction.token)
@@ -147,6 +661,104 @@ impl AiCommand {
                             .await?;
                     }
                 }
+                AiCommand::Persona(c) => {
+                    let user = interaction
+                        .author()
+                        .context("no author")?;
+                    let text = match interaction.guild_id {
+                        None => "เพอร์โซนาใช้ได้เฉพาะในเซิร์ฟเวอร์".to_string(),
+                        Some(guild_id) => match c.name.as_deref() {
+                            None => {
+                                persona::select(&ctx, guild_id.get(), user.id.get(), None).await?;
+                                "กลับไปใช้พรอมพ์เริ่มต้นของเซิร์ฟเวอร์แล้ว".to_string()
+                            }
+                            Some(raw) => {
+                                let found = match persona::normalize_name(raw) {
+                                    Some(name) => persona::find(&ctx, guild_id.get(), &name).await,
+                                    None => None,
+                                };
+                                match found {
+                                    Some(found) => {
+                                        persona::select(
+                                            &ctx,
+                                            guild_id.get(),
+                                            user.id.get(),
+                                            Some(&found.name),
+                                        )
+                                        .await?;
+                                        format!("ตอนนี้ใช้เพอร์โซนา **{}** แล้ว", found.name)
+                                    }
+                                    None => format!("ไม่พบเพอร์โซนา `{raw}` ในเซิร์ฟเวอร์นี้"),
+                                }
+                            }
+                        },
+                    };
+                    let embeds = AiService::ai_embeds(&text)?;
+                    ctx.http
+                        .interaction(interaction.application_id)
+                        .update_response(&interaction.token)
+                        .embeds(Some(&embeds))
+                        .await?;
+                }
+                AiCommand::Kb(c) => {
+                    let text = match interaction.guild_id {
+                        None => "คลังความรู้ใช้ได้เฉพาะในเซิร์ฟเวอร์".to_string(),
+                        Some(_) if !can_manage_knowledge(&interaction) => {
+                            "ต้องมีสิทธิ์จัดการเซิร์ฟเวอร์เพื่อแก้ไขคลังความรู้".to_string()
+                        }
+                        Some(guild_id) => handle_kb(&ctx, &interaction, guild_id.get(), c).await?,
+                    };
+                    let embeds = AiService::ai_embeds(&text)?;
+                    ctx.http
+                        .interaction(interaction.application_id)
+                        .update_response(&interaction.token)
+                        .embeds(Some(&embeds))
+                        .await?;
+                }
+                AiCommand::Memory(c) => {
+                    let user = interaction
+                        .author()
+                        .context("no author")?;
+                    let text = match c {
+                        AiMemoryCommand::Show(_) => {
+                            let facts = memory::load(&ctx, user.id.get()).await;
+                            if facts.is_empty() {
+                                "AI ยังไม่ได้จำอะไรเกี่ยวกับคุณ".to_string()
+                            } else {
+                                let lines: Vec<String> = facts
+                                    .iter()
+                                    .map(|fact| format!("• **{}**: {}", fact.key, fact.value))
+                                    .collect();
+                                format!("**สิ่งที่ AI จำเกี่ยวกับคุณ**\n{}", lines.join("\n"))
+                            }
+                        }
+                        AiMemoryCommand::Forget(c) => {
+                            if memory::forget(&ctx, user.id.get(), &c.item).await? {
+                                format!("ลืม **{}** แล้ว", c.item)
+                            } else {
+                                format!("ไม่พบ `{}` ในความจำของ AI", c.item)
+                            }
+                        }
+                    };
+                    let embeds = AiService::ai_embeds(&text)?;
+                    ctx.http
+                        .interaction(interaction.application_id)
+                        .update_response(&interaction.token)
+                        .embeds(Some(&embeds))
+                        .await?;
+                }
+                AiCommand::Quota(_) => {
+                    let user = interaction
+                        .author()
+                        .context("no author")?;
+                    let status = AiService::quota_status(&ctx, interaction.guild_id, user.id).await;
+                    let embed = AiService::quota_embed(&status)?;
+                    ctx.http
+                        .interaction(interaction.application_id)
+                        .update_response(&interaction.token)
+                        .embeds(Some(&[embed]))
+                        .await?;
+                }
                 AiCommand::Clear(_) => {
                     if let Some(user) = interaction.author() {
                         AiService::clear_history(&ctx.redis, user.id).await;
@@ -174,4 +786,98 @@ impl AiCommand {
             }
         }
     }
+
+    pub async fn autocomplete(ctx: Arc<Context>, interaction: Interaction, data: CommandData) {
+        if let Err(e) = async {
+            let focused = extract_focused(&data).context("parse focused field failed")?;
+            let choices = match interaction.guild_id {
+                Some(guild_id) if focused.0 == "name" => {
+                    persona::persona_choices(&ctx, guild_id.get(), focused.1).await
+                }
+                Some(guild_id) if focused.0 == "document" => {
+                    knowledge::document_choices(&ctx, guild_id.get(), focused.1).await
+                }
+                _ if focused.0 == "item" => match interaction.author() {
+                    Some(user) => memory::fact_choices(&ctx, user.id.get(), focused.1).await,
+                    None => Vec::new(),
+                },
+                _ => Vec::new(),
+            };
+
+            let response = InteractionResponse {
+                kind: InteractionResponseType::ApplicationCommandAutocompleteResult,
+                data: Some(InteractionResponseData {
+                    choices: Some(choices),
+                    ..InteractionResponseData::default()
+                }),
+            };
+
+            ctx.http
+                .interaction(interaction.application_id)
+                .create_response(interaction.id, &interaction.token, &response)
+                .await?;
+
+            Ok::<_, anyhow::Error>(())
+        }
+        .await
+        {
+            tracing::error!(error = %e, "ai autocomplete handler failed");
+        }
+    }
+
+    pub async fn handle_kb_modal(
+        ctx: Arc<Context>,
+        interaction: Interaction,
+        data: ModalInteractionData,
+    ) {
+        if let Err(e) = async {
+            defer_interaction!(ctx.http, &interaction, true).await?;
+            let text = match interaction.guild_id {
+                None => "คลังความรู้ใช้ได้เฉพาะในเซิร์ฟเวอร์".to_string(),
+                Some(_) if !can_manage_knowledge(&interaction) => {
+                    "ต้องมีสิทธิ์จัดการเซิร์ฟเวอร์เพื่อแก้ไขคลังความรู้".to_string()
+                }
+                Some(guild_id) => {
+                    let user = interaction
+                        .author()
+                        .context("no author")?;
+                    let title = modal_value_of(&data, "title").and_then(knowledge::normalize_title);
+                    let content = modal_value_of(&data, "content").unwrap_or_default();
+                    match title {
+                        Some(title) => {
+                            let outcome = knowledge::add(
+                                &ctx,
+                                guild_id.get(),
+                                &title,
+                                content,
+                                user.id.get(),
+                            )
+                            .await?;
+                            kb_add_text(&title, outcome)
+                        }
+                        None => "ชื่อเอกสารไม่ถูกต้อง".to_string(),
+                    }
+                }
+            };
+            let embeds = AiService::ai_embeds(&text)?;
+            ctx.http
+                .interaction(interaction.application_id)
+                .update_response(&interaction.token)
+                .embeds(Some(&embeds))
+                .await?;
+            Ok::<_, anyhow::Error>(())
+        }
+        .await
+        {
+            tracing::error!(error = %e, "error handling ai kb modal");
+            if let Ok(embed) = AiService::unavailable_embed() {
+                let _ = ctx
+                    .http
+                    .interaction(interaction.application_id)
+                    .update_response(&interaction.token)
+                    .embeds(Some(&[embed]))
+                    .await;
+            }
+        }
+    }
 }
@@ -0,0 +1,183 @@
+use anyhow::Context as _;
+use twilight_interactions::command::{CommandModel, CreateCommand, DescLocalizations};
+use twilight_model::{
+    application::interaction::Interaction,
+    guild::Permissions,
+    id::{Id, marker::RoleMarker},
+};
+
+use crate::{
+    context::Context,
+    services::worldstate_alert::{
+        AddOutcome, MAX_ALERTS_PER_USER, WorldstateAlertService, filter::AlertFilter,
+    },
+};
+use std::sync::Arc;
+
+#[derive(CommandModel, CreateCommand, Debug)]
+#[command(name = "alert", desc_localizations = "alert_desc")]
+pub enum WarframeAlertCommand {
+    #[command(name = "add")]
+    Add(WarframeAlertAddCommand),
+    #[command(name = "list")]
+    List(WarframeAlertListCommand),
+    #[command(name = "remove")]
+    Remove(WarframeAlertRemoveCommand),
+}
+
+#[derive(CommandModel, CreateCommand, Debug)]
+#[command(name = "add", desc_localizations = "alert_add_desc")]
+pub struct WarframeAlertAddCommand {
+    #[command(
+        max_length = 200,
+        desc_localizations = "alert_add_filter_desc"
+    )]
+    pub filter: String,
+    #[command(desc_localizations = "alert_add_role_desc")]
+    pub role: Option<Id<RoleMarker>>,
+}
+
+#[derive(CommandModel, CreateCommand, Debug)]
+#[command(name = "list", desc_localizations = "alert_list_desc")]
+pub struct WarframeAlertListCommand {}
+
+#[derive(CommandModel, CreateCommand, Debug)]
+#[command(name = "remove", desc_localizations = "alert_remove_desc")]
+pub struct WarframeAlertRemoveCommand {
+    #[command(
+        min_value = 1,
+        max_value = 10,
+        desc_localizations = "alert_remove_number_desc"
+    )]
+    pub number: i64,
+}
+
+fn alert_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Get notified about worldstate events",
+        [("th", "รับการแจ้งเตือนเหตุการณ์ในเกม")],
+    )
+}
+
+fn alert_add_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Subscribe to entries matching a filter",
+        [("th", "สมัครรับแจ้งเตือนเมื่อมีรายการที่ตรงกับตัวกรอง")],
+    )
+}
+
+fn alert_add_filter_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "e.g. fissure tier:axi mode:storm, invasion reward:catalyst, cycle place:cetus state:night",
+        [(
+            "th",
+            "เช่น fissure tier:axi mode:storm, invasion reward:catalyst, cycle place:cetus state:night",
+        )],
+    )
+}
+
+fn alert_add_role_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Ping this role in the notification channel instead of a DM (server managers)",
+        [(
+            "th",
+            "แท็ก role นี้ในช่องแจ้งเตือนแทนการส่ง DM (ผู้จัดการเซิร์ฟเวอร์)",
+        )],
+    )
+}
+
+fn alert_list_desc() -> DescLocalizations {
+    DescLocalizations::new("Show your alerts", [("th", "ดูการแจ้งเตือนของคุณ")])
+}
+
+fn alert_remove_desc() -> DescLocalizations {
+    DescLocalizations::new("Remove an alert", [("th", "ลบการแจ้งเตือน")])
+}
+
+fn alert_remove_number_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Number shown by /warframe alert list",
+        [("th", "หมายเลขจาก /warframe alert list")],
+    )
+}
+
+fn can_ping_roles(interaction: &Interaction) -> bool {
+    interaction
+        .member
+        .as_ref()
+        .and_then(|member| member.permissions)
+        .is_some_and(|permissions| permissions.contains(Permissions::MANAGE_GUILD))
+}
+
+impl WarframeAlertCommand {
+    pub async fn run(&self, ctx: Arc<Context>, interaction: Interaction) -> anyhow::Result<()> {
+        let guild_id = interaction
+            .guild_id
+            .context("parse guild_id failed")?
+            .get();
+        let user_id = interaction
+            .author_id()
+            .context("parse author failed")?
+            .get();
+
+        let text = match self {
+            Self::Add(c) => match AlertFilter::parse(&c.filter) {
+                Err(e) => e.to_string(),
+                Ok(_) if c.role.is_some() && !can_ping_roles(&interaction) => {
+                    "ต้องมีสิทธิ์จัดการเซิร์ฟเวอร์เพื่อแจ้งเตือนผ่าน role".to_string()
+                }
+                Ok(filter) => {
+                    let role_id = c.role.map(|role| role.get());
+                    match WorldstateAlertService::add(&ctx, guild_id, user_id, &filter, role_id)
+                        .await?
+                    {
+                        AddOutcome::Added => match role_id {
+                            Some(role_id) => {
+                                format!("จะแท็ก <@&{role_id}> เมื่อพบ `{filter}`")
+                            }
+                            None => format!("จะส่ง DM เมื่อพบ `{filter}`"),
+                        },
+                        AddOutcome::Duplicate => format!("มีการแจ้งเตือน `{filter}` อยู่แล้ว"),
+                        AddOutcome::LimitReached => {
+                            format!("มีการแจ้งเตือนครบ {MAX_ALERTS_PER_USER} รายการแล้ว ลบรายการเก่าก่อน")
+                        }
+                    }
+                }
+            },
+            Self::List(_) => {
+                let alerts = WorldstateAlertService::list(&ctx, guild_id, user_id).await;
+                if alerts.is_empty() {
+                    "ยังไม่มีการแจ้งเตือน ใช้ /warframe alert add".to_string()
+                } else {
+                    alerts
+                        .iter()
+                        .enumerate()
+                        .map(|(i, alert)| match alert.role_id {
+                            Some(role_id) => {
+                                format!("{}. `{}` → <@&{role_id}>", i + 1, alert.filter)
+                            }
+                            None => format!("{}. `{}` → DM", i + 1, alert.filter),
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                }
+            }
+            Self::Remove(c) => {
+                match WorldstateAlertService::remove(&ctx, guild_id, user_id, c.number as usize)
+                    .await?
+                {
+                    Some(alert) => format!("ลบ `{}` แล้ว", alert.filter),
+                    None => format!("ไม่พบการแจ้งเตือนหมายเลข {}", c.number),
+                }
+            }
+        };
+
+        let embed = WorldstateAlertService::reply_embed(&text)?;
+        ctx.http
+            .interaction(interaction.application_id)
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+use anyhow::Context as _;
+use twilight_interactions::command::{CommandModel, CreateCommand, DescLocalizations};
+use twilight_model::application::interaction::Interaction;
+
+use crate::{
+    context::Context,
+    services::farm::FarmService,
+    utils::{interaction::require_guild_ref, locale::Lang},
+};
+use std::sync::Arc;
+
+#[derive(CommandModel, CreateCommand, Debug)]
+#[command(name = "farm", desc_localizations = "farm_desc")]
+pub struct WarframeFarmCommand {
+    #[command(desc_localizations = "farm_item_desc", autocomplete = true)]
+    pub item: String,
+}
+
+fn farm_item_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Item or prime part",
+        [("th", "ชื่อไอเทมหรือชิ้นส่วน prime")],
+    )
+}
+
+fn farm_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Find the relics and missions that drop an item",
+        [("th", "ค้นหา relic และภารกิจที่ดรอปไอเทม")],
+    )
+}
+
+impl WarframeFarmCommand {
+    pub async fn run(&self, ctx: Arc<Context>, interaction: Interaction) -> anyhow::Result<()> {
+        let guild_id = interaction
+            .guild_id
+            .context("parse guild_id failed")?;
+        if let Some(guild_ref) =
+            require_guild_ref(&ctx, &interaction, guild_id, "warframe farm").await
+        {
+            let lang = Lang::of(&interaction);
+            let embed = FarmService::farm_embed(&ctx, &guild_ref, &self.item, lang).await?;
+            ctx.http
+                .interaction(interaction.application_id)
+                .update_response(&interaction.token)
+                .embeds(Some(&[embed]))
+                .await?;
+        }
+        Ok(())
+    }
+}
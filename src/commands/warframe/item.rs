@@ -0,0 +1,54 @@
+use anyhow::Context as _;
+use twilight_interactions::command::{CommandModel, CreateCommand, DescLocalizations};
+use twilight_model::application::interaction::Interaction;
+
+use crate::{
+    context::Context,
+    services::build::BuildService,
+    utils::{interaction::require_guild_ref, locale::Lang},
+};
+use std::sync::Arc;
+
+#[derive(CommandModel, CreateCommand, Debug)]
+#[command(name = "item", desc_localizations = "item_desc")]
+pub struct WarframeItemCommand {
+    #[command(desc_localizations = "item_name_desc", autocomplete = true)]
+    pub name: String,
+}
+
+fn item_name_desc() -> DescLocalizations {
+    DescLocalizations::new("Item name", [("th", "ชื่อไอเทม")])
+}
+
+fn item_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Show an item's stats, drops and components",
+        [("th", "ดูค่าพื้นฐาน แหล่งดรอป และส่วนประกอบของไอเทม")],
+    )
+}
+
+impl WarframeItemCommand {
+    pub async fn run(&self, ctx: Arc<Context>, interaction: Interaction) -> anyhow::Result<()> {
+        let guild_id = interaction
+            .guild_id
+            .context("parse guild_id failed")?;
+        if let Some(guild_ref) =
+            require_guild_ref(&ctx, &interaction, guild_id, "warframe item").await
+        {
+            let lang = Lang::of(&interaction);
+            let embed =
+                match BuildService::find_record_with_update(&ctx.reqwest, &ctx.redis, &self.name)
+                    .await
+                {
+                    Some(record) => BuildService::item_embed(&guild_ref, &record, lang)?,
+                    None => BuildService::item_not_found_embed(&guild_ref, lang)?,
+                };
+            ctx.http
+                .interaction(interaction.application_id)
+                .update_response(&interaction.token)
+                .embeds(Some(&[embed]))
+                .await?;
+        }
+        Ok(())
+    }
+}
@@ -7,7 +7,7 @@ use twilight_model::application::interaction::Interaction;
 use crate::{
     context::Context,
     services::market::{MarketKind, MarketService},
-    utils::interaction::require_guild_ref,
+    utils::{interaction::require_guild_ref, locale::Lang},
 };
 use std::sync::Arc;
 
@@ -64,8 +64,9 @@ impl WarframeMarketCommand {
             if let Some(session) =
                 MarketService::create_session(&ctx, &self.item, self.kind.into()).await?
             {
-                let embed = MarketService::embed_for_session(&guild_ref, &session)?;
-                let components = MarketService::components(&session);
+                let lang = Lang::of(&interaction);
+                let embed = MarketService::embed_for_session(&guild_ref, &session, lang)?;
+                let components = MarketService::components(&session, lang);
                 let message = ctx
                     .http
                     .interaction(interaction.application_id)
@@ -75,7 +76,7 @@ impl WarframeMarketCommand {
                     .await?
                     .model()
                     .await?;
-                MarketService::insert_session(message.id, session).await;
+                MarketService::insert_session(&ctx, message.id, &session).await;
             } else {
                 let embed = MarketService::not_found_embed(&guild_ref)?;
                 ctx.http
@@ -5,7 +5,7 @@ use twilight_model::{
         command::{CommandOptionChoice, CommandOptionChoiceValue},
         interaction::{
             Interaction,
-            application_command::{CommandData, CommandOptionValue},
+            application_command::{CommandData, CommandDataOption, CommandOptionValue},
             message_component::MessageComponentInteractionData,
         },
     },
@@ -15,14 +15,30 @@ use twilight_model::{
 use crate::{
     context::Context,
     handle_ephemeral,
-    services::{build::BuildService, market::MarketService},
+    services::{
+        build::BuildService, farm::FarmService, market::MarketService, riven::RivenService,
+    },
 };
 use std::sync::Arc;
 
+mod alert;
 mod build;
+mod farm;
+mod item;
 mod market;
+mod price;
+mod riven;
+mod watch;
+mod worldstate;
+use alert::WarframeAlertCommand;
 use build::WarframeBuildCommand;
+use farm::WarframeFarmCommand;
+use item::WarframeItemCommand;
 use market::WarframeMarketCommand;
+use price::WarframePriceCommand;
+use riven::WarframeRivenCommand;
+use watch::WarframeWatchCommand;
+use worldstate::WarframeWorldstateCommand;
 
 #[derive(CommandModel, CreateCommand, Debug)]
 #[command(name = "warframe", desc_localizations = "warframe_desc")]
@@ -31,24 +47,60 @@ pub enum WarframeCommand {
     Build(WarframeBuildCommand),
     #[command(name = "market")]
     Market(WarframeMarketCommand),
+    #[command(name = "price")]
+    Price(WarframePriceCommand),
+    #[command(name = "watch")]
+    Watch(WarframeWatchCommand),
+    #[command(name = "riven")]
+    Riven(WarframeRivenCommand),
+    #[command(name = "item")]
+    Item(WarframeItemCommand),
+    #[command(name = "farm")]
+    Farm(WarframeFarmCommand),
+    #[command(name = "worldstate")]
+    Worldstate(WarframeWorldstateCommand),
+    #[command(name = "alert")]
+    Alert(WarframeAlertCommand),
 }
 
 fn warframe_desc() -> DescLocalizations {
     DescLocalizations::new("Warframe utilities", [("th", "ตัวช่วย Warframe")])
 }
 
+fn focused_in<'a>(
+    sub: &'a str,
+    options: &'a [CommandDataOption],
+) -> Option<(&'a str, &'a str, &'a str)> {
+    options
+        .iter()
+        .find_map(|nested| match &nested.value {
+            CommandOptionValue::Focused(user_input, _) => {
+                Some((sub, nested.name.as_str(), user_input.as_str()))
+            }
+            _ => None,
+        })
+}
+
+/// `(subcommand, option, input)`; inside a group such as `watch add` the
+/// group's name stands in for the subcommand.
 fn extract_focused(cmd: &CommandData) -> Option<(&str, &str, &str)> {
     for opt in &cmd.options {
-        if let CommandOptionValue::SubCommand(sub_opts) = &opt.value {
-            for nested in sub_opts {
-                if let CommandOptionValue::Focused(user_input, _) = &nested.value {
-                    return Some((
-                        opt.name.as_str(),
-                        nested.name.as_str(),
-                        user_input.as_str(),
-                    ));
+        match &opt.value {
+            CommandOptionValue::SubCommand(sub_opts) => {
+                if let Some(found) = focused_in(&opt.name, sub_opts) {
+                    return Some(found);
+                }
+            }
+            CommandOptionValue::SubCommandGroup(subs) => {
+                for sub in subs {
+                    if let CommandOptionValue::SubCommand(sub_opts) = &sub.value
+                        && let Some(found) = focused_in(&opt.name, sub_opts)
+                    {
+                        return Some(found);
+                    }
                 }
             }
+            _ => {}
         }
     }
     None
@@ -62,6 +114,13 @@ impl WarframeCommand {
             match command {
                 WarframeCommand::Build(cmd) => cmd.run(ctx, interaction).await?,
                 WarframeCommand::Market(cmd) => cmd.run(ctx, interaction).await?,
+                WarframeCommand::Price(cmd) => cmd.run(ctx, interaction).await?,
+                WarframeCommand::Watch(cmd) => cmd.run(ctx, interaction).await?,
+                WarframeCommand::Riven(cmd) => cmd.run(ctx, interaction).await?,
+                WarframeCommand::Item(cmd) => cmd.run(ctx, interaction).await?,
+                WarframeCommand::Farm(cmd) => cmd.run(ctx, interaction).await?,
+                WarframeCommand::Worldstate(cmd) => cmd.run(ctx, interaction).await?,
+                WarframeCommand::Alert(cmd) => cmd.run(ctx, interaction).await?,
             }
         });
     }
@@ -71,15 +130,26 @@ impl WarframeCommand {
         interaction: Interaction,
         data: MessageComponentInteractionData,
     ) {
-        MarketService::handle_component(ctx, interaction, data).await;
+        if data.custom_id.starts_with("riven_") {
+            RivenService::handle_component(ctx, interaction, data).await;
+        } else {
+            MarketService::handle_component(ctx, interaction, data).await;
+        }
     }
 
     pub async fn autocomplete(ctx: Arc<Context>, interaction: Interaction, data: CommandData) {
         if let Some((sub, name, user_input)) = extract_focused(&data) {
             let mut choices = Vec::with_capacity(25);
-            if name == "item" {
+            if name == "item" || name == "weapon" || name == "name" {
                 let results = if sub == "build" {
                     BuildService::search_with_update(&ctx.reqwest, &ctx.redis, user_input).await
+                } else if sub == "item" {
+                    BuildService::search_records_with_update(&ctx.reqwest, &ctx.redis, user_input)
+                        .await
+                } else if sub == "farm" {
+                    FarmService::search(user_input).await
+                } else if sub == "riven" {
+                    RivenService::search_with_update(&ctx, user_input).await
                 } else {
                     MarketService::search_with_update(&ctx, user_input).await
                 };
@@ -92,6 +162,17 @@ impl WarframeCommand {
                             name_localizations: None,
                         }),
                 );
+            } else if sub == "riven" && (name == "positive" || name == "negative") {
+                choices.extend(
+                    RivenService::search_stats_with_update(&ctx, user_input)
+                        .await
+                        .into_iter()
+                        .map(|stat| CommandOptionChoice {
+                            name: stat.clone(),
+                            value: CommandOptionChoiceValue::String(stat),
+                            name_localizations: None,
+                        }),
+                );
             }
 
             let response = InteractionResponse {
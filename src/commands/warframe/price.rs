@@ -0,0 +1,59 @@
+use anyhow::Context as _;
+use twilight_interactions::command::{CommandModel, CreateCommand, DescLocalizations};
+use twilight_model::application::interaction::Interaction;
+
+use crate::{
+    context::Context, services::market::MarketService, utils::interaction::require_guild_ref,
+};
+use std::sync::Arc;
+
+#[derive(CommandModel, CreateCommand, Debug)]
+#[command(name = "price", desc_localizations = "price_desc")]
+pub struct WarframePriceCommand {
+    #[command(desc_localizations = "price_item_desc", autocomplete = true)]
+    pub item: String,
+    #[command(
+        min_value = 0,
+        max_value = 10,
+        desc_localizations = "price_rank_desc"
+    )]
+    pub rank: Option<i64>,
+}
+
+fn price_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Recent closed-trade prices on warframe.market",
+        [("th", "ราคาซื้อขายจริงล่าสุดจาก warframe.market")],
+    )
+}
+
+fn price_item_desc() -> DescLocalizations {
+    DescLocalizations::new("Item name", [("th", "ชื่อไอเทม")])
+}
+
+fn price_rank_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Only this rank, for mods and arcanes",
+        [("th", "เฉพาะ Rank นี้ สำหรับ mod และ arcane")],
+    )
+}
+
+impl WarframePriceCommand {
+    pub async fn run(&self, ctx: Arc<Context>, interaction: Interaction) -> anyhow::Result<()> {
+        let guild_id = interaction
+            .guild_id
+            .context("parse guild_id failed")?;
+        if let Some(guild_ref) =
+            require_guild_ref(&ctx, &interaction, guild_id, "warframe price").await
+        {
+            let rank = self.rank.map(|r| r as u8);
+            let embed = MarketService::price_embed(&ctx, &guild_ref, &self.item, rank).await?;
+            ctx.http
+                .interaction(interaction.application_id)
+                .update_response(&interaction.token)
+                .embeds(Some(&[embed]))
+                .await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,139 @@
+use anyhow::Context as _;
+use twilight_interactions::command::{CommandModel, CreateCommand, DescLocalizations};
+use twilight_model::application::interaction::Interaction;
+
+use crate::{
+    context::Context,
+    services::riven::{RivenSearch, RivenService},
+    utils::{interaction::require_guild_ref, locale::Lang},
+};
+use std::sync::Arc;
+
+#[derive(CommandModel, CreateCommand, Debug)]
+#[command(name = "riven", desc_localizations = "riven_desc")]
+pub struct WarframeRivenCommand {
+    #[command(
+        desc_localizations = "riven_weapon_desc",
+        autocomplete = true
+    )]
+    pub weapon: String,
+    #[command(
+        max_length = 100,
+        desc_localizations = "riven_positive_desc",
+        autocomplete = true
+    )]
+    pub positive: Option<String>,
+    #[command(
+        max_length = 50,
+        desc_localizations = "riven_negative_desc",
+        autocomplete = true
+    )]
+    pub negative: Option<String>,
+    #[command(
+        min_value = 1,
+        max_value = 100000,
+        desc_localizations = "riven_max_price_desc"
+    )]
+    pub max_price: Option<i64>,
+}
+
+fn riven_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Search riven auctions on warframe.market",
+        [("th", "ค้นหา riven ที่ประมูลบน warframe.market")],
+    )
+}
+
+fn riven_weapon_desc() -> DescLocalizations {
+    DescLocalizations::new("Weapon name", [("th", "ชื่ออาวุธ")])
+}
+
+fn riven_positive_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Up to 3 positive stats, comma separated, e.g. critical_chance, multishot",
+        [(
+            "th",
+            "ค่าบวกไม่เกิน 3 ค่า คั่นด้วยจุลภาค เช่น critical_chance, multishot",
+        )],
+    )
+}
+
+fn riven_negative_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Negative stat, e.g. recoil",
+        [("th", "ค่าลบ เช่น recoil")],
+    )
+}
+
+fn riven_max_price_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Highest price in platinum",
+        [("th", "ราคาสูงสุดเป็น platinum")],
+    )
+}
+
+impl WarframeRivenCommand {
+    pub async fn run(&self, ctx: Arc<Context>, interaction: Interaction) -> anyhow::Result<()> {
+        let guild_id = interaction
+            .guild_id
+            .context("parse guild_id failed")?;
+        let Some(guild_ref) =
+            require_guild_ref(&ctx, &interaction, guild_id, "warframe riven").await
+        else {
+            return Ok(());
+        };
+        let lang = Lang::of(&interaction);
+        let session = RivenService::create_session(
+            &ctx,
+            &self.weapon,
+            self.positive.as_deref(),
+            self.negative.as_deref(),
+            self.max_price.map(|p| p as u32),
+        )
+        .await;
+        let session = match session {
+            Ok(RivenSearch::Found(session)) => session,
+            Ok(RivenSearch::UnknownWeapon) => {
+                let embed = RivenService::not_found_embed(&guild_ref, lang)?;
+                ctx.http
+                    .interaction(interaction.application_id)
+                    .update_response(&interaction.token)
+                    .embeds(Some(&[embed]))
+                    .await?;
+                return Ok(());
+            }
+            Ok(RivenSearch::UnknownStat(stat)) => {
+                let embed = RivenService::unknown_stat_embed(&guild_ref, &stat, lang)?;
+                ctx.http
+                    .interaction(interaction.application_id)
+                    .update_response(&interaction.token)
+                    .embeds(Some(&[embed]))
+                    .await?;
+                return Ok(());
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "failed to search riven auctions");
+                let embed = RivenService::error_embed(&guild_ref, lang)?;
+                ctx.http
+                    .interaction(interaction.application_id)
+                    .update_response(&interaction.token)
+                    .embeds(Some(&[embed]))
+                    .await?;
+                return Ok(());
+            }
+        };
+        let embed = RivenService::build_embed(&guild_ref, &session, lang)?;
+        let components = RivenService::components(&session, lang);
+        let message = ctx
+            .http
+            .interaction(interaction.application_id)
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .components(Some(&components))
+            .await?
+            .model()
+            .await?;
+        RivenService::insert_session(&ctx, message.id, &session).await;
+        Ok(())
+    }
+}
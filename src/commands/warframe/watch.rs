@@ -0,0 +1,190 @@
+use anyhow::Context as _;
+use twilight_interactions::command::{CommandModel, CreateCommand, DescLocalizations};
+use twilight_model::application::interaction::Interaction;
+
+use super::market::MarketType;
+use crate::{
+    context::Context,
+    services::market::watch::{AddOutcome, MAX_WATCHES_PER_USER, PriceWatchService},
+};
+use std::sync::Arc;
+
+#[derive(CommandModel, CreateCommand, Debug)]
+#[command(name = "watch", desc_localizations = "watch_desc")]
+pub enum WarframeWatchCommand {
+    #[command(name = "add")]
+    Add(WarframeWatchAddCommand),
+    #[command(name = "list")]
+    List(WarframeWatchListCommand),
+    #[command(name = "remove")]
+    Remove(WarframeWatchRemoveCommand),
+}
+
+#[derive(CommandModel, CreateCommand, Debug)]
+#[command(name = "add", desc_localizations = "watch_add_desc")]
+pub struct WarframeWatchAddCommand {
+    #[command(desc_localizations = "watch_item_desc", autocomplete = true)]
+    pub item: String,
+    #[command(desc_localizations = "watch_kind_desc")]
+    pub kind: MarketType,
+    #[command(
+        min_value = 1,
+        max_value = 100000,
+        desc_localizations = "watch_price_desc"
+    )]
+    pub price: i64,
+    #[command(
+        min_value = 0,
+        max_value = 10,
+        desc_localizations = "watch_rank_desc"
+    )]
+    pub rank: Option<i64>,
+}
+
+#[derive(CommandModel, CreateCommand, Debug)]
+#[command(name = "list", desc_localizations = "watch_list_desc")]
+pub struct WarframeWatchListCommand {}
+
+#[derive(CommandModel, CreateCommand, Debug)]
+#[command(name = "remove", desc_localizations = "watch_remove_desc")]
+pub struct WarframeWatchRemoveCommand {
+    #[command(
+        min_value = 1,
+        max_value = 10,
+        desc_localizations = "watch_remove_number_desc"
+    )]
+    pub number: i64,
+}
+
+fn watch_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Get a DM when warframe.market reaches your price",
+        [("th", "รับ DM เมื่อ warframe.market มีราคาที่ต้องการ")],
+    )
+}
+
+fn watch_add_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Watch an item's price",
+        [("th", "ติดตามราคาไอเทม")],
+    )
+}
+
+fn watch_item_desc() -> DescLocalizations {
+    DescLocalizations::new("Item name", [("th", "ชื่อไอเทม")])
+}
+
+fn watch_kind_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Buy: sellers at or below the price, Sell: buyers at or above it",
+        [("th", "ซื้อ: ผู้ขายที่ราคาไม่เกินนี้ ขาย: ผู้ซื้อที่ราคาตั้งแต่นี้")],
+    )
+}
+
+fn watch_price_desc() -> DescLocalizations {
+    DescLocalizations::new("Price in platinum", [("th", "ราคาเป็น platinum")])
+}
+
+fn watch_rank_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Only this rank, for mods and arcanes",
+        [("th", "เฉพาะ Rank นี้ สำหรับ mod และ arcane")],
+    )
+}
+
+fn watch_list_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Show your price watches",
+        [("th", "ดูรายการติดตามราคาของคุณ")],
+    )
+}
+
+fn watch_remove_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Remove a price watch",
+        [("th", "ลบรายการติดตามราคา")],
+    )
+}
+
+fn watch_remove_number_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Number shown by /warframe watch list",
+        [("th", "หมายเลขจาก /warframe watch list")],
+    )
+}
+
+impl WarframeWatchCommand {
+    pub async fn run(&self, ctx: Arc<Context>, interaction: Interaction) -> anyhow::Result<()> {
+        let guild_id = interaction
+            .guild_id
+            .context("parse guild_id failed")?
+            .get();
+        let user_id = interaction
+            .author_id()
+            .context("parse author failed")?
+            .get();
+
+        let text = match self {
+            Self::Add(c) => {
+                match PriceWatchService::add(
+                    &ctx,
+                    guild_id,
+                    user_id,
+                    &c.item,
+                    c.kind.into(),
+                    c.price as u32,
+                    c.rank.map(|r| r as u8),
+                )
+                .await?
+                {
+                    AddOutcome::Added(watch) => {
+                        format!(
+                            "จะส่ง DM เมื่อพบ `{}`",
+                            PriceWatchService::describe(&watch)
+                        )
+                    }
+                    AddOutcome::Duplicate => {
+                        format!("ติดตาม `{}` อยู่แล้ว ลบรายการเดิมก่อนเปลี่ยนราคา", c.item)
+                    }
+                    AddOutcome::LimitReached => {
+                        format!("ติดตามครบ {MAX_WATCHES_PER_USER} รายการแล้ว ลบรายการเก่าก่อน")
+                    }
+                    AddOutcome::NotFound => "ไม่พบไอเทมนี้ กรุณาตรวจสอบชื่อ item อีกครั้ง".to_string(),
+                }
+            }
+            Self::List(_) => {
+                let watches = PriceWatchService::list(&ctx, guild_id, user_id).await;
+                if watches.is_empty() {
+                    "ยังไม่มีรายการติดตาม ใช้ /warframe watch add".to_string()
+                } else {
+                    watches
+                        .iter()
+                        .enumerate()
+                        .map(|(i, watch)| {
+                            format!(
+                                "{}. `{}`",
+                                i + 1,
+                                PriceWatchService::describe(watch)
+                            )
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                }
+            }
+            Self::Remove(c) => {
+                match PriceWatchService::remove(&ctx, guild_id, user_id, c.number as usize).await? {
+                    Some(watch) => format!("ลบ `{}` แล้ว", PriceWatchService::describe(&watch)),
+                    None => format!("ไม่พบรายการติดตามหมายเลข {}", c.number),
+                }
+            }
+        };
+
+        let embed = PriceWatchService::reply_embed(&text)?;
+        ctx.http
+            .interaction(interaction.application_id)
+            .update_response(&interaction.token)
+            .embeds(Some(&[embed]))
+            .await?;
+        Ok(())
+    }
+}
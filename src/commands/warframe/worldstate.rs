@@ -0,0 +1,81 @@
+use anyhow::Context as _;
+use twilight_interactions::command::{
+    CommandModel, CommandOption, CreateCommand, CreateOption, DescLocalizations,
+};
+use twilight_model::application::interaction::Interaction;
+
+use crate::{
+    context::Context,
+    utils::interaction::require_guild_ref,
+    warframe::worldstate::{self, WorldstateSection},
+};
+use std::sync::Arc;
+
+#[derive(CreateOption, CommandOption, Clone, Copy, Debug)]
+pub enum WorldstateChoice {
+    #[option(name = "Void Fissures", value = "fissures")]
+    Fissures,
+    #[option(name = "Sortie", value = "sortie")]
+    Sortie,
+    #[option(name = "Archon Hunt", value = "archon")]
+    ArchonHunt,
+    #[option(name = "Invasions", value = "invasions")]
+    Invasions,
+    #[option(name = "Baro Ki'Teer", value = "baro")]
+    VoidTrader,
+}
+
+impl WorldstateChoice {
+    fn section(self) -> WorldstateSection {
+        match self {
+            Self::Fissures => WorldstateSection::Fissures,
+            Self::Sortie => WorldstateSection::Sortie,
+            Self::ArchonHunt => WorldstateSection::ArchonHunt,
+            Self::Invasions => WorldstateSection::Invasions,
+            Self::VoidTrader => WorldstateSection::VoidTrader,
+        }
+    }
+}
+
+#[derive(CommandModel, CreateCommand, Debug)]
+#[command(name = "worldstate", desc_localizations = "worldstate_desc")]
+pub struct WarframeWorldstateCommand {
+    #[command(desc_localizations = "worldstate_section_desc")]
+    pub section: WorldstateChoice,
+}
+
+fn worldstate_desc() -> DescLocalizations {
+    DescLocalizations::new(
+        "Show the current Warframe worldstate",
+        [("th", "ดูสถานะโลก Warframe ปัจจุบัน")],
+    )
+}
+
+fn worldstate_section_desc() -> DescLocalizations {
+    DescLocalizations::new("Section to show", [("th", "หัวข้อที่ต้องการดู")])
+}
+
+impl WarframeWorldstateCommand {
+    pub async fn run(&self, ctx: Arc<Context>, interaction: Interaction) -> anyhow::Result<()> {
+        let guild_id = interaction
+            .guild_id
+            .context("parse guild_id failed")?;
+        if let Some(guild_ref) = require_guild_ref(
+            &ctx,
+            &interaction,
+            guild_id,
+            "warframe worldstate",
+        )
+        .await
+        {
+            let embed =
+                worldstate::worldstate_embed(&ctx, &guild_ref, self.section.section()).await?;
+            ctx.http
+                .interaction(interaction.application_id)
+                .update_response(&interaction.token)
+                .embeds(Some(&[embed]))
+                .await?;
+        }
+        Ok(())
+    }
+}
@@ -1,13 +1,35 @@
 use std::sync::LazyLock;
 
-use crate::utils::env::{parse_env, secret_or_default};
+use crate::utils::env::{parse_env, parse_env_opt, secret_or_default};
 
 pub struct GoogleConfigs {
     pub api_key: String,
     pub base_prompt: String,
+    /// Per-user daily request budget used when a guild has not set its own;
+    /// `0` means unlimited.
+    pub daily_user_requests: u64,
+    /// Per-user daily token budget used when a guild has not set its own;
+    /// `0` means unlimited.
+    pub daily_user_tokens: u64,
+    /// JSON model catalogue polled for changes; the built-in chains are used
+    /// when unset.
+    pub models_file: Option<String>,
+    pub models_reload_secs: u64,
+    /// Share each model's request window and cooldown through Redis so that
+    /// several bot instances respect one RPM budget.
+    pub distributed_scheduler: bool,
+    /// How long answers to context-free questions are reused; `0` disables
+    /// the response cache.
+    pub response_cache_ttl_secs: u64,
 }
 
 pub static GOOGLE_CONFIGS: LazyLock<GoogleConfigs> = LazyLock::new(|| GoogleConfigs {
     api_key: secret_or_default("GOOGLE_API_KEY", ""),
     base_prompt: parse_env("AI_BASE_PROMPT", ""),
+    daily_user_requests: parse_env("AI_DAILY_USER_REQUESTS", "0"),
+    daily_user_tokens: parse_env("AI_DAILY_USER_TOKENS", "0"),
+    models_file: parse_env_opt("AI_MODELS_FILE"),
+    models_reload_secs: parse_env("AI_MODELS_RELOAD_SECS", "60"),
+    distributed_scheduler: parse_env("AI_SCHEDULER_DISTRIBUTED", "false"),
+    response_cache_ttl_secs: parse_env("AI_RESPONSE_CACHE_TTL_SECS", "0"),
 });
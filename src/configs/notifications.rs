@@ -1,11 +1,21 @@
+use std::sync::LazyLock;
+
+use crate::utils::env::parse_env;
+
 pub struct Notifications {
     pub helminth: &'static str,
     pub riven_sliver: &'static str,
     pub umbra_forma: &'static str,
+    pub baro_leaving: &'static str,
 }
 
 pub const NOTIFICATIONS: Notifications = Notifications {
     helminth: "อย่าลืมไปดูบัพ Helminth หล่ะ",
     riven_sliver: "อย่าลืมไปแลก Riven Sliver กับ Palladino ที่ Iron Wake หล่ะ",
     umbra_forma: "มี Umbra Forma Blueprint อย่าลืมไปแลกกันหล่ะ",
+    baro_leaving: "Baro Ki'Teer ใกล้จะกลับแล้ว อย่าลืมไปซื้อของกันหล่ะ",
 };
+
+/// Hours before Baro Ki'Teer leaves to post a reminder; `0` disables it.
+pub static BARO_REMINDER_HOURS: LazyLock<u64> =
+    LazyLock::new(|| parse_env("BARO_REMINDER_HOURS", "6"));
@@ -116,14 +116,8 @@ impl ContextBuilder {
             .scam_detect
             .unwrap_or_else(ScamDetectQueue::from_env);
 
-        Ok(Context {
-            http,
-            cache,
-            redis,
-            mongo,
-            reqwest,
-            ai_scheduler: AiService::scheduler(),
-            scam_detect,
-        })
+        let ai_scheduler = AiService::scheduler(&redis);
+
+        Ok(Context { http, cache, redis, mongo, reqwest, ai_scheduler, scam_detect })
     }
 }
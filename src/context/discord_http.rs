@@ -436,6 +436,33 @@ impl Client {
         .await
     }
 
+    /// One page of channel history, newest first, optionally ending just
+    /// before `before`.
+    pub async fn channel_messages_before(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        before: Option<Id<MessageMarker>>,
+        limit: u16,
+    ) -> anyhow::Result<Response<ListBody<Message>>> {
+        self.execute(
+            DiscordOpKind::ListChannelMessages.default_priority(),
+            DiscordOpKind::ListChannelMessages,
+            move |client| async move {
+                let request = client.channel_messages(channel_id);
+                match before {
+                    Some(before) => {
+                        request
+                            .before(before)
+                            .limit(limit)
+                            .await
+                    }
+                    None => request.limit(limit).await,
+                }
+            },
+        )
+        .await
+    }
+
     pub async fn delete_message(
         &self,
         channel_id: Id<ChannelMarker>,
@@ -99,14 +99,8 @@ impl ContextBuilder {
         let reqwest = self.reqwest.unwrap_or_default();
         let scam_detect = self.scam_detect.unwrap_or_default();
 
-        Ok(Context {
-            http,
-            cache,
-            redis,
-            mongo,
-            reqwest,
-            ai_scheduler: AiService::scheduler(),
-            scam_detect,
-        })
+        let ai_scheduler = AiService::scheduler(&redis);
+
+        Ok(Context { http, cache, redis, mongo, reqwest, ai_scheduler, scam_detect })
     }
 }
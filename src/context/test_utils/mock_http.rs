@@ -101,6 +101,16 @@ impl<'a> MockCreateMessage<'a> {
         self
     }
     async fn exec(self) -> anyhow::Result<MockResponse<Message>> {
+        let countdown = self
+            .client
+            .fail_create_message_in
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| {
+                Some(n.saturating_sub(1))
+            })
+            .unwrap_or_default();
+        if countdown == 1 {
+            return Err(anyhow::anyhow!("mock create_message failure"));
+        }
         let id = self
             .client
             .next_id
@@ -280,6 +290,8 @@ pub struct MockClient {
     pub channels: Mutex<HashMap<Id<ChannelMarker>, Vec<Message>>>,
     member_roles: Mutex<MemberRoles>,
     role_calls: Mutex<Vec<RoleCall>>,
+    /// Counts down one per `create_message`; the call that reaches zero fails.
+    fail_create_message_in: AtomicU64,
     fail_next_add_guild_member_role: AtomicBool,
     fail_next_update_guild_member_roles: AtomicBool,
     add_role_failures: Mutex<HashMap<Id<RoleMarker>, MockHttpError>>,
@@ -300,6 +312,7 @@ impl MockClient {
             channels: Mutex::new(HashMap::new()),
             member_roles: Mutex::new(HashMap::new()),
             role_calls: Mutex::new(Vec::new()),
+            fail_create_message_in: AtomicU64::new(0),
             fail_next_add_guild_member_role: AtomicBool::new(false),
             fail_next_update_guild_member_roles: AtomicBool::new(false),
             add_role_failures: Mutex::new(HashMap::new()),
@@ -343,6 +356,16 @@ impl MockClient {
         self.role_calls.lock().unwrap().clone()
     }
 
+    pub fn fail_next_create_message(&self) {
+        self.fail_nth_create_message(1);
+    }
+
+    /// Fails the `n`th `create_message` from now, counting from one.
+    pub fn fail_nth_create_message(&self, n: u64) {
+        self.fail_create_message_in
+            .store(n, Ordering::SeqCst);
+    }
+
     pub fn fail_next_add_guild_member_role(&self) {
         self.fail_next_add_guild_member_role
             .store(true, Ordering::SeqCst);
@@ -391,6 +414,25 @@ impl MockClient {
         Ok(MockResponse::new(data))
     }
 
+    pub async fn channel_messages_before(
+        &self,
+        channel_id: Id<ChannelMarker>,
+        before: Option<Id<MessageMarker>>,
+        limit: u16,
+    ) -> anyhow::Result<MockResponse<Vec<Message>>> {
+        let map = self.channels.lock().unwrap();
+        let mut data: Vec<Message> = map
+            .get(&channel_id)
+            .cloned()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|message| before.is_none_or(|before| message.id < before))
+            .collect();
+        data.sort_by_key(|message| std::cmp::Reverse(message.id));
+        data.truncate(limit as usize);
+        Ok(MockResponse::new(data))
+    }
+
     pub async fn delete_message(
         &self,
         channel_id: Id<ChannelMarker>,
@@ -540,9 +582,10 @@ impl MockClient {
 
     pub async fn create_private_channel(
         &self,
-        _user_id: Id<UserMarker>,
+        user_id: Id<UserMarker>,
     ) -> anyhow::Result<MockResponse<Channel>> {
-        let channel = fake_channel(Id::new(0));
+        // Reuse the user's id so tests can tell which DM a message went to.
+        let channel = fake_channel(user_id.cast());
         Ok(MockResponse::new(channel))
     }
 
@@ -48,8 +48,15 @@ impl HttpProvider for MockReqwest {
 
 #[async_trait]
 impl AttachmentHttp for MockReqwest {
-    async fn get(&self, _url: &str) -> reqwest::Result<Response> {
-        unimplemented!("MockReqwest::get")
+    async fn get(&self, url: &str) -> reqwest::Result<Response> {
+        let body = self
+            .responses
+            .lock()
+            .unwrap()
+            .get(url)
+            .cloned()
+            .unwrap_or_else(|| unimplemented!("MockReqwest::get {url}"));
+        Ok(Response::from(axum::http::Response::new(body)))
     }
 
     async fn post(&self, _url: Url, _headers: HeaderMap, _body: Body) -> reqwest::Result<Response> {
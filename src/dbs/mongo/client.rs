@@ -12,8 +12,18 @@ use crate::{
     configs::mongo::MONGO_CONFIGS,
     dbs::mongo::{
         models::{
-            ai_prompt::AiPrompt, channel::Channel, guild_settings::GuildSettings, message::Message,
-            quarantine::Quarantine, role::Role,
+            ai_knowledge::AiKnowledgeDocument,
+            ai_memory::AiMemory,
+            ai_persona::{AiPersona, AiPersonaChoice},
+            ai_prompt::AiPrompt,
+            ai_usage::AiUsageDaily,
+            channel::Channel,
+            guild_settings::GuildSettings,
+            message::Message,
+            price_watch::PriceWatch,
+            quarantine::Quarantine,
+            role::Role,
+            worldstate_alert::WorldstateAlert,
         },
         monitor, watchers,
     },
@@ -29,6 +39,13 @@ pub struct MongoDB {
     pub messages: Collection<Message>,
     pub ai_prompts: Collection<AiPrompt>,
     pub guild_settings: Collection<GuildSettings>,
+    pub ai_personas: Collection<AiPersona>,
+    pub ai_persona_choices: Collection<AiPersonaChoice>,
+    pub ai_knowledge: Collection<AiKnowledgeDocument>,
+    pub ai_memories: Collection<AiMemory>,
+    pub ai_usage: Collection<AiUsageDaily>,
+    pub worldstate_alerts: Collection<WorldstateAlert>,
+    pub price_watches: Collection<PriceWatch>,
 }
 
 impl MongoDB {
@@ -61,8 +78,18 @@ impl MongoDB {
         let client = Client::with_options(opts)?;
         let database = client.database(&MONGO_CONFIGS.database);
 
-        const COLLECTIONS: [&str; 6] =
-            ["channels", "roles", "quarantines", "messages", "ai_prompts", "guild_settings"];
+        const COLLECTIONS: [&str; 10] = [
+            "channels",
+            "roles",
+            "quarantines",
+            "messages",
+            "ai_prompts",
+            "guild_settings",
+            "ai_personas",
+            "ai_persona_choices",
+            "ai_knowledge",
+            "ai_memories",
+        ];
 
         for coll in COLLECTIONS {
             if let Err(e) = database.create_collection(coll).await {
@@ -97,6 +124,16 @@ impl MongoDB {
         let messages = database.collection::<Message>("messages");
         let ai_prompts = database.collection::<AiPrompt>("ai_prompts");
         let guild_settings = database.collection::<GuildSettings>("guild_settings");
+        let ai_personas = database.collection::<AiPersona>("ai_personas");
+        let ai_persona_choices = database.collection::<AiPersonaChoice>("ai_persona_choices");
+        let ai_knowledge = database.collection::<AiKnowledgeDocument>("ai_knowledge");
+        let ai_memories = database.collection::<AiMemory>("ai_memories");
+        // Not cached or watched, so it stays out of `COLLECTIONS`; the first
+        // rollup write creates it.
+        let ai_usage = database.collection::<AiUsageDaily>("ai_usage");
+        // Only the alert poller reads these, so they are not cached either.
+        let worldstate_alerts = database.collection::<WorldstateAlert>("worldstate_alerts");
+        let price_watches = database.collection::<PriceWatch>("price_watches");
 
         let idx1 = IndexModel::builder()
             .keys(doc! { "guild_id": 1, "channel_type": 1 })
@@ -184,8 +221,118 @@ impl MongoDB {
             tracing::warn!(collection = "guild_settings", error = %e, "failed to create index");
         }
 
-        let repo =
-            Self { client, channels, roles, quarantines, messages, ai_prompts, guild_settings };
+        let idx = IndexModel::builder()
+            .keys(doc! { "guild_id": 1, "name": 1 })
+            .options(
+                IndexOptions::builder()
+                    .unique(true)
+                    .build(),
+            )
+            .build();
+        if let Err(e) = ai_personas.create_index(idx).await {
+            tracing::warn!(collection = "ai_personas", error = %e, "failed to create index");
+        }
+
+        let idx = IndexModel::builder()
+            .keys(doc! { "guild_id": 1, "user_id": 1 })
+            .options(
+                IndexOptions::builder()
+                    .unique(true)
+                    .build(),
+            )
+            .build();
+        if let Err(e) = ai_persona_choices
+            .create_index(idx)
+            .await
+        {
+            tracing::warn!(collection = "ai_persona_choices", error = %e, "failed to create index");
+        }
+
+        let idx = IndexModel::builder()
+            .keys(doc! { "guild_id": 1, "title": 1 })
+            .options(
+                IndexOptions::builder()
+                    .unique(true)
+                    .build(),
+            )
+            .build();
+        if let Err(e) = ai_knowledge.create_index(idx).await {
+            tracing::warn!(collection = "ai_knowledge", error = %e, "failed to create index");
+        }
+
+        let idx = IndexModel::builder()
+            .keys(doc! { "user_id": 1 })
+            .options(
+                IndexOptions::builder()
+                    .unique(true)
+                    .build(),
+            )
+            .build();
+        if let Err(e) = ai_memories.create_index(idx).await {
+            tracing::warn!(collection = "ai_memories", error = %e, "failed to create index");
+        }
+
+        let idx1 = IndexModel::builder()
+            .keys(doc! { "day": 1, "guild_id": 1, "user_id": 1, "model": 1, "operation": 1 })
+            .options(
+                IndexOptions::builder()
+                    .unique(true)
+                    .build(),
+            )
+            .build();
+        let idx2 = IndexModel::builder()
+            .keys(doc! { "guild_id": 1, "day": 1 })
+            .build();
+        if let Err(e) = ai_usage
+            .create_indexes([idx1, idx2])
+            .await
+        {
+            tracing::warn!(collection = "ai_usage", error = %e, "failed to create indexes");
+        }
+
+        let idx = IndexModel::builder()
+            .keys(doc! { "guild_id": 1, "user_id": 1, "filter": 1 })
+            .options(
+                IndexOptions::builder()
+                    .unique(true)
+                    .build(),
+            )
+            .build();
+        if let Err(e) = worldstate_alerts
+            .create_index(idx)
+            .await
+        {
+            tracing::warn!(collection = "worldstate_alerts", error = %e, "failed to create index");
+        }
+
+        let idx = IndexModel::builder()
+            .keys(doc! { "guild_id": 1, "user_id": 1, "item_id": 1, "kind": 1, "rank": 1 })
+            .options(
+                IndexOptions::builder()
+                    .unique(true)
+                    .build(),
+            )
+            .build();
+        if let Err(e) = price_watches.create_index(idx).await {
+            tracing::warn!(collection = "price_watches", error = %e, "failed to create index");
+        }
+
+        let repo = Self {
+            client,
+            channels,
+            roles,
+            quarantines,
+            messages,
+            ai_prompts,
+            guild_settings,
+            ai_personas,
+            ai_persona_choices,
+            ai_knowledge,
+            ai_memories,
+            ai_usage,
+            worldstate_alerts,
+            price_watches,
+        };
 
         if watchers {
             let options = ChangeStreamOptions::builder()
@@ -231,6 +378,34 @@ impl MongoDB {
             .await?;
             watchers::spawn_guild_settings_watcher(
                 repo.guild_settings.clone(),
+                options.clone(),
+                redis.clone(),
+                token.clone(),
+            )
+            .await?;
+            watchers::spawn_ai_persona_watcher(
+                repo.ai_personas.clone(),
+                options.clone(),
+                redis.clone(),
+                token.clone(),
+            )
+            .await?;
+            watchers::spawn_ai_persona_choice_watcher(
+                repo.ai_persona_choices.clone(),
+                options.clone(),
+                redis.clone(),
+                token.clone(),
+            )
+            .await?;
+            watchers::spawn_ai_knowledge_watcher(
+                repo.ai_knowledge.clone(),
+                options.clone(),
+                redis.clone(),
+                token.clone(),
+            )
+            .await?;
+            watchers::spawn_ai_memory_watcher(
+                repo.ai_memories.clone(),
                 options,
                 redis.clone(),
                 token.clone(),
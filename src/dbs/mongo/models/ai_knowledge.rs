@@ -0,0 +1,16 @@
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+/// A guild knowledge-base document, stored already split into the chunks
+/// that retrieval scores and cites.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct AiKnowledgeDocument {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    pub guild_id: u64,
+    pub title: String,
+    pub chunks: Vec<String>,
+    pub added_by: u64,
+    pub added_at: i64,
+}
@@ -0,0 +1,22 @@
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+/// One durable fact about a user, such as `ign` or `timezone`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct MemoryFact {
+    pub key: String,
+    pub value: String,
+}
+
+/// Facts the summary model pulled out of a user's conversations. They are
+/// kept across history resets and added to every prompt.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct AiMemory {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    pub user_id: u64,
+    #[serde(default)]
+    pub facts: Vec<MemoryFact>,
+    pub updated_at: i64,
+}
@@ -0,0 +1,29 @@
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+/// A named system prompt defined by guild admins. An empty `models` list
+/// allows the whole chat chain from the catalogue.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct AiPersona {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    pub guild_id: u64,
+    pub name: String,
+    pub system_prompt: String,
+    #[serde(default)]
+    pub models: Vec<String>,
+    #[serde(default)]
+    pub temperature: Option<f32>,
+}
+
+/// The persona a user picked with `/ai persona` in one guild.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct AiPersonaChoice {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    pub guild_id: u64,
+    pub user_id: u64,
+    pub persona: String,
+}
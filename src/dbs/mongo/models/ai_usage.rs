@@ -0,0 +1,38 @@
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+/// Daily AI usage for one user, model and operation in one guild. Counters
+/// are only ever `$inc`remented, so concurrent requests never lose updates.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct AiUsageDaily {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    /// UTC date as `YYYY-MM-DD`, which sorts and compares as text.
+    pub day: String,
+    /// `None` for direct messages.
+    pub guild_id: Option<u64>,
+    /// `None` for work not started by a member, such as channel digests.
+    pub user_id: Option<u64>,
+    pub model: String,
+    pub operation: String,
+    #[serde(default)]
+    pub requests: i64,
+    #[serde(default)]
+    pub prompt_tokens: i64,
+    #[serde(default)]
+    pub output_tokens: i64,
+    /// Requests answered by a model other than the first in its chain.
+    #[serde(default)]
+    pub fallbacks: i64,
+    #[serde(default)]
+    pub errors_rate_limited: i64,
+    #[serde(default)]
+    pub errors_timeout: i64,
+    #[serde(default)]
+    pub errors_unavailable: i64,
+    #[serde(default)]
+    pub errors_transport: i64,
+    #[serde(default)]
+    pub errors_fatal: i64,
+}
@@ -7,5 +7,111 @@ pub struct GuildSettings {
     pub id: Option<ObjectId>,
 
     pub guild_id: u64,
+    #[serde(default)]
     pub scam_detect_enabled: bool,
+    #[serde(default)]
+    pub scam_text_mode: ScamTextMode,
+    #[serde(default)]
+    pub ai_quota: AiQuotaSettings,
+    #[serde(default)]
+    pub ai_safety: AiSafetySettings,
+    /// Replaces the global base prompt for every AI request in this guild.
+    #[serde(default)]
+    pub ai_base_prompt: Option<String>,
+    #[serde(default)]
+    pub ai_access: AiAccessSettings,
+}
+
+/// How the AI text scam classifier treats links from recently joined members.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ScamTextMode {
+    #[default]
+    Off,
+    /// Classify and log verdicts without touching the message.
+    Shadow,
+    /// Quarantine the author when a verdict blocks.
+    Enforce,
+}
+
+/// Where and how members may reach the AI assistant in a guild.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct AiAccessSettings {
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub channel_mode: AiChannelMode,
+    /// Allowed or denied channels, depending on `channel_mode`. Threads
+    /// follow their parent channel.
+    #[serde(default)]
+    pub channels: Vec<u64>,
+    /// Whether replying to one of the bot's AI answers addresses the bot
+    /// even when the reply does not mention it.
+    #[serde(default)]
+    pub reply_mentions: bool,
+    /// Whether members may use the AI in direct messages with the bot.
+    #[serde(default)]
+    pub allow_dms: bool,
+}
+
+impl Default for AiAccessSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            channel_mode: AiChannelMode::default(),
+            channels: Vec::new(),
+            reply_mentions: false,
+            allow_dms: false,
+        }
+    }
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AiChannelMode {
+    #[default]
+    All,
+    Allow,
+    Deny,
+}
+
+/// Daily AI budgets set by guild admins. `None` falls back to the global
+/// default (user limits) or no limit (guild limits); `Some(0)` is unlimited.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AiQuotaSettings {
+    #[serde(default)]
+    pub user_requests: Option<u64>,
+    #[serde(default)]
+    pub user_tokens: Option<u64>,
+    #[serde(default)]
+    pub guild_requests: Option<u64>,
+    #[serde(default)]
+    pub guild_tokens: Option<u64>,
+}
+
+/// Per-category Gemini blocking thresholds. `None` leaves the model default.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AiSafetySettings {
+    #[serde(default)]
+    pub harassment: Option<AiSafetyThreshold>,
+    #[serde(default)]
+    pub hate_speech: Option<AiSafetyThreshold>,
+    #[serde(default)]
+    pub sexually_explicit: Option<AiSafetyThreshold>,
+    #[serde(default)]
+    pub dangerous_content: Option<AiSafetyThreshold>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AiSafetyThreshold {
+    Off,
+    BlockNone,
+    BlockOnlyHigh,
+    BlockMediumAndAbove,
+    BlockLowAndAbove,
 }
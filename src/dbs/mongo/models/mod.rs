@@ -1,6 +1,12 @@
+pub mod ai_knowledge;
+pub mod ai_memory;
+pub mod ai_persona;
 pub mod ai_prompt;
+pub mod ai_usage;
 pub mod channel;
 pub mod guild_settings;
 pub mod message;
+pub mod price_watch;
 pub mod quarantine;
 pub mod role;
+pub mod worldstate_alert;
@@ -0,0 +1,23 @@
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+/// A member waiting for a warframe.market order at or past `price`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct PriceWatch {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    pub guild_id: u64,
+    pub user_id: u64,
+    pub item: String,
+    pub item_id: String,
+    pub slug: String,
+    /// What the member wants to do, `"buy"` or `"sell"`; buyers wait for
+    /// sell orders at or below `price`, sellers for buy orders at or above.
+    pub kind: String,
+    pub price: u32,
+    /// Only orders for this rank; any rank when unset.
+    #[serde(default)]
+    pub rank: Option<u8>,
+    pub created_at: i64,
+}
@@ -0,0 +1,20 @@
+use mongodb::bson::oid::ObjectId;
+use serde::{Deserialize, Serialize};
+
+/// A member's standing request to hear about worldstate entries matching
+/// `filter`, such as `fissure tier:axi mode:storm`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct WorldstateAlert {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+
+    pub guild_id: u64,
+    pub user_id: u64,
+    /// Canonical filter text as produced by `AlertFilter`'s `Display`.
+    pub filter: String,
+    /// Ping this opt-in role in the notification channel instead of sending
+    /// a DM to `user_id`.
+    #[serde(default)]
+    pub role_id: Option<u64>,
+    pub created_at: i64,
+}
@@ -1,7 +1,7 @@
 use anyhow::{Result, anyhow};
 use deadpool_redis::Pool;
 use futures::Stream;
-use mongodb::bson::{Document, to_document};
+use mongodb::bson::{Bson, Document, to_document};
 use serde::{Serialize, de::DeserializeOwned};
 use std::pin::Pin;
 use std::sync::Arc;
@@ -10,8 +10,18 @@ use std::task::{Context, Poll};
 use tokio::sync::RwLock;
 
 use super::models::{
-    ai_prompt::AiPrompt, channel::Channel, guild_settings::GuildSettings, message::Message,
-    quarantine::Quarantine, role::Role,
+    ai_knowledge::AiKnowledgeDocument,
+    ai_memory::AiMemory,
+    ai_persona::{AiPersona, AiPersonaChoice},
+    ai_prompt::AiPrompt,
+    ai_usage::AiUsageDaily,
+    channel::Channel,
+    guild_settings::GuildSettings,
+    message::Message,
+    price_watch::PriceWatch,
+    quarantine::Quarantine,
+    role::Role,
+    worldstate_alert::WorldstateAlert,
 };
 
 #[derive(Clone)]
@@ -126,18 +136,28 @@ where
                     for (k, v) in set.iter() {
                         doc.insert(k, v.clone());
                     }
-                    *item = mongodb::bson::from_document(doc)?;
                 }
+                apply_inc(&mut doc, &update);
+                *item = mongodb::bson::from_document(doc)?;
                 return Ok(());
             }
         }
-        if upsert && let Ok(set) = update.get_document("$set") {
+        if !upsert {
+            return Ok(());
+        }
+        if let Ok(set) = update.get_document("$set") {
             let mut doc = Document::new();
             for (k, v) in set.iter() {
                 doc.insert(k, v.clone());
             }
             let item: T = mongodb::bson::from_document(doc)?;
             data.push(item);
+        } else if update.contains_key("$inc") {
+            // Like Mongo, an `$inc` upsert starts from the filter's equality fields.
+            let mut doc = filter.clone();
+            apply_inc(&mut doc, &update);
+            let item: T = mongodb::bson::from_document(doc)?;
+            data.push(item);
         }
         Ok(())
     }
@@ -187,10 +207,45 @@ where
     }
 }
 
+/// Adds each top-level `$inc` amount, treating missing fields as zero.
+fn apply_inc(doc: &mut Document, update: &Document) {
+    let Ok(inc) = update.get_document("$inc") else {
+        return;
+    };
+    for (k, v) in inc.iter() {
+        let current = doc
+            .get(k)
+            .and_then(bson_i64)
+            .unwrap_or(0);
+        doc.insert(k, current + bson_i64(v).unwrap_or(0));
+    }
+}
+
+fn bson_i64(value: &Bson) -> Option<i64> {
+    match value {
+        Bson::Int32(v) => Some(i64::from(*v)),
+        Bson::Int64(v) => Some(*v),
+        _ => None,
+    }
+}
+
+/// Equality on every key, plus `{ "$gte": value }` for strings and integers.
 fn matches(doc: &Document, filter: &Document) -> bool {
-    filter
-        .iter()
-        .all(|(k, v)| doc.get(k) == Some(v))
+    filter.iter().all(|(k, v)| {
+        if let Bson::Document(op) = v
+            && let Some(bound) = op.get("$gte")
+        {
+            return match (doc.get(k), bound) {
+                (Some(Bson::String(a)), Bson::String(b)) => a >= b,
+                (Some(a), b) => match (bson_i64(a), bson_i64(b)) {
+                    (Some(a), Some(b)) => a >= b,
+                    _ => false,
+                },
+                (None, _) => false,
+            };
+        }
+        doc.get(k) == Some(v)
+    })
 }
 
 #[derive(Clone, Default)]
@@ -201,6 +256,13 @@ pub struct MongoDB {
     pub messages: MockCollection<Message>,
     pub ai_prompts: MockCollection<AiPrompt>,
     pub guild_settings: MockCollection<GuildSettings>,
+    pub ai_personas: MockCollection<AiPersona>,
+    pub ai_persona_choices: MockCollection<AiPersonaChoice>,
+    pub ai_knowledge: MockCollection<AiKnowledgeDocument>,
+    pub ai_memories: MockCollection<AiMemory>,
+    pub ai_usage: MockCollection<AiUsageDaily>,
+    pub worldstate_alerts: MockCollection<WorldstateAlert>,
+    pub price_watches: MockCollection<PriceWatch>,
 }
 
 impl MongoDB {
@@ -85,8 +85,17 @@ async fn delete_without_preimage_sweeps_each_collections_cache_families() {
     let quarantine_keys =
         ["spam:quarantine:93:93001", "spam:log:93:93001", "spam:campaign:93:93001:hash"];
     let message_keys = ["discord-bot:role-message:94", "discord-bot:status-message:94"];
-    let ai_keys = ["discord-bot:ai:prompt:95001", "discord-bot:ai:history:95001"];
+    let ai_keys = [
+        "discord-bot:ai:prompt:95001",
+        "discord-bot:ai:history:95001",
+        "discord-bot:ai:memory:95001",
+    ];
     let guild_settings_keys = ["discord-bot:guild-settings:96"];
+    let ai_guild_keys = [
+        "discord-bot:ai:personas:97",
+        "discord-bot:ai:persona-choice:97:97001",
+        "discord-bot:ai:kb:97",
+    ];
     let preserved_keys =
         ["discord-bot:wf:news", "discord-bot:ai:rate:95001", "changestream:resume:test-fallback"];
 
@@ -97,6 +106,7 @@ async fn delete_without_preimage_sweeps_each_collections_cache_families() {
         message_keys.as_slice(),
         ai_keys.as_slice(),
         guild_settings_keys.as_slice(),
+        ai_guild_keys.as_slice(),
         preserved_keys.as_slice(),
     ] {
         seed(&pool, keys).await;
@@ -108,6 +118,10 @@ async fn delete_without_preimage_sweeps_each_collections_cache_families() {
     handle_message_event(&pool, event("delete", None, None)).await;
     handle_ai_prompt_event(&pool, event("delete", None, None)).await;
     handle_guild_settings_event(&pool, event("delete", None, None)).await;
+    handle_ai_persona_event(&pool, event("delete", None, None)).await;
+    handle_ai_persona_choice_event(&pool, event("delete", None, None)).await;
+    handle_ai_knowledge_event(&pool, event("delete", None, None)).await;
+    handle_ai_memory_event(&pool, event("delete", None, None)).await;
 
     for keys in [
         channel_keys.as_slice(),
@@ -116,6 +130,7 @@ async fn delete_without_preimage_sweeps_each_collections_cache_families() {
         message_keys.as_slice(),
         ai_keys.as_slice(),
         guild_settings_keys.as_slice(),
+        ai_guild_keys.as_slice(),
     ] {
         assert_missing(&pool, keys).await;
     }
@@ -144,7 +159,11 @@ async fn continuity_recovery_sweeps_each_collections_cache_families() {
         "discord-bot:status-message:914",
         "discord-bot:ai:prompt:91501",
         "discord-bot:ai:history:91501",
+        "discord-bot:ai:memory:91501",
         "discord-bot:guild-settings:916",
+        "discord-bot:ai:personas:917",
+        "discord-bot:ai:persona-choice:917:91701",
+        "discord-bot:ai:kb:917",
     ];
     let preserved_keys = [
         "discord-bot:wf:news",
@@ -162,6 +181,10 @@ async fn continuity_recovery_sweeps_each_collections_cache_families() {
         message_cache_prefixes(),
         ai_prompt_cache_prefixes(),
         guild_settings_cache_prefixes(),
+        ai_persona_cache_prefixes(),
+        ai_persona_choice_cache_prefixes(),
+        ai_knowledge_cache_prefixes(),
+        ai_memory_cache_prefixes(),
     ] {
         deleted += redis_delete_prefixes_checked(&pool, &prefixes)
             .await
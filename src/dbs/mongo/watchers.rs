@@ -15,6 +15,9 @@ use crate::{
     dbs::{
         mongo::{
             models::{
+                ai_knowledge::AiKnowledgeDocument,
+                ai_memory::AiMemory,
+                ai_persona::{AiPersona, AiPersonaChoice},
                 ai_prompt::AiPrompt,
                 channel::Channel,
                 guild_settings::GuildSettings,
@@ -64,6 +67,22 @@ fn guild_settings_cache_prefixes() -> Vec<String> {
     vec![format!("{CACHE_PREFIX}:guild-settings:")]
 }
 
+fn ai_persona_cache_prefixes() -> Vec<String> {
+    vec![format!("{CACHE_PREFIX}:ai:personas:")]
+}
+
+fn ai_persona_choice_cache_prefixes() -> Vec<String> {
+    vec![format!("{CACHE_PREFIX}:ai:persona-choice:")]
+}
+
+fn ai_knowledge_cache_prefixes() -> Vec<String> {
+    vec![format!("{CACHE_PREFIX}:ai:kb:")]
+}
+
+fn ai_memory_cache_prefixes() -> Vec<String> {
+    vec![format!("{CACHE_PREFIX}:ai:memory:")]
+}
+
 fn invalidation_for<T>(evt: ChangeStreamEvent<T>) -> Invalidation<T> {
     match evt.operation_type {
         OperationType::Insert => evt
@@ -238,6 +257,87 @@ async fn handle_guild_settings_event(pool: &Pool, evt: ChangeStreamEvent<GuildSe
     }
 }
 
+async fn handle_ai_persona_event(pool: &Pool, evt: ChangeStreamEvent<AiPersona>) {
+    match invalidation_for(evt) {
+        Invalidation::Documents(documents) => {
+            for document in documents {
+                AiService::purge_persona_cache(pool, document.guild_id).await;
+            }
+        }
+        Invalidation::Sweep(operation) => {
+            sweep_cache(
+                pool,
+                "ai_personas",
+                operation,
+                &ai_persona_cache_prefixes(),
+            )
+            .await;
+        }
+        Invalidation::Ignore => {}
+    }
+}
+
+async fn handle_ai_persona_choice_event(pool: &Pool, evt: ChangeStreamEvent<AiPersonaChoice>) {
+    match invalidation_for(evt) {
+        Invalidation::Documents(documents) => {
+            for document in documents {
+                AiService::purge_persona_choice_cache(pool, document.guild_id, document.user_id)
+                    .await;
+            }
+        }
+        Invalidation::Sweep(operation) => {
+            sweep_cache(
+                pool,
+                "ai_persona_choices",
+                operation,
+                &ai_persona_choice_cache_prefixes(),
+            )
+            .await;
+        }
+        Invalidation::Ignore => {}
+    }
+}
+
+async fn handle_ai_knowledge_event(pool: &Pool, evt: ChangeStreamEvent<AiKnowledgeDocument>) {
+    match invalidation_for(evt) {
+        Invalidation::Documents(documents) => {
+            for document in documents {
+                AiService::purge_knowledge_cache(pool, document.guild_id).await;
+            }
+        }
+        Invalidation::Sweep(operation) => {
+            sweep_cache(
+                pool,
+                "ai_knowledge",
+                operation,
+                &ai_knowledge_cache_prefixes(),
+            )
+            .await;
+        }
+        Invalidation::Ignore => {}
+    }
+}
+
+async fn handle_ai_memory_event(pool: &Pool, evt: ChangeStreamEvent<AiMemory>) {
+    match invalidation_for(evt) {
+        Invalidation::Documents(documents) => {
+            for document in documents {
+                AiService::purge_memory_cache(pool, document.user_id).await;
+            }
+        }
+        Invalidation::Sweep(operation) => {
+            sweep_cache(
+                pool,
+                "ai_memories",
+                operation,
+                &ai_memory_cache_prefixes(),
+            )
+            .await;
+        }
+        Invalidation::Ignore => {}
+    }
+}
+
 pub async fn spawn_channel_watcher(
     coll: Collection<Channel>,
     options: ChangeStreamOptions,
@@ -390,6 +490,108 @@ pub async fn spawn_guild_settings_watcher(
     .await
 }
 
+pub async fn spawn_ai_persona_watcher(
+    coll: Collection<AiPersona>,
+    options: ChangeStreamOptions,
+    pool: Pool,
+    token: CancellationToken,
+) -> anyhow::Result<()> {
+    let handler_pool = pool.clone();
+    let recovery_pool = pool.clone();
+    spawn_watcher(
+        coll,
+        options,
+        pool,
+        move |evt| {
+            let pool = handler_pool.clone();
+            async move { handle_ai_persona_event(&pool, evt).await }
+        },
+        move || {
+            let pool = recovery_pool.clone();
+            async move { redis_delete_prefixes_checked(&pool, &ai_persona_cache_prefixes()).await }
+        },
+        token,
+    )
+    .await
+}
+
+pub async fn spawn_ai_persona_choice_watcher(
+    coll: Collection<AiPersonaChoice>,
+    options: ChangeStreamOptions,
+    pool: Pool,
+    token: CancellationToken,
+) -> anyhow::Result<()> {
+    let handler_pool = pool.clone();
+    let recovery_pool = pool.clone();
+    spawn_watcher(
+        coll,
+        options,
+        pool,
+        move |evt| {
+            let pool = handler_pool.clone();
+            async move { handle_ai_persona_choice_event(&pool, evt).await }
+        },
+        move || {
+            let pool = recovery_pool.clone();
+            async move {
+                redis_delete_prefixes_checked(&pool, &ai_persona_choice_cache_prefixes()).await
+            }
+        },
+        token,
+    )
+    .await
+}
+
+pub async fn spawn_ai_knowledge_watcher(
+    coll: Collection<AiKnowledgeDocument>,
+    options: ChangeStreamOptions,
+    pool: Pool,
+    token: CancellationToken,
+) -> anyhow::Result<()> {
+    let handler_pool = pool.clone();
+    let recovery_pool = pool.clone();
+    spawn_watcher(
+        coll,
+        options,
+        pool,
+        move |evt| {
+            let pool = handler_pool.clone();
+            async move { handle_ai_knowledge_event(&pool, evt).await }
+        },
+        move || {
+            let pool = recovery_pool.clone();
+            async move { redis_delete_prefixes_checked(&pool, &ai_knowledge_cache_prefixes()).await }
+        },
+        token,
+    )
+    .await
+}
+
+pub async fn spawn_ai_memory_watcher(
+    coll: Collection<AiMemory>,
+    options: ChangeStreamOptions,
+    pool: Pool,
+    token: CancellationToken,
+) -> anyhow::Result<()> {
+    let handler_pool = pool.clone();
+    let recovery_pool = pool.clone();
+    spawn_watcher(
+        coll,
+        options,
+        pool,
+        move |evt| {
+            let pool = handler_pool.clone();
+            async move { handle_ai_memory_event(&pool, evt).await }
+        },
+        move || {
+            let pool = recovery_pool.clone();
+            async move { redis_delete_prefixes_checked(&pool, &ai_memory_cache_prefixes()).await }
+        },
+        token,
+    )
+    .await
+}
+
 #[cfg(test)]
 #[path = "tests/watchers.rs"]
 mod tests;
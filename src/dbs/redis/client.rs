@@ -1,5 +1,10 @@
+use std::time::Duration;
+
 use anyhow::Context as _;
-use deadpool_redis::{Config, Pool, Runtime, redis::cmd};
+use deadpool_redis::{
+    Config, Pool, Runtime,
+    redis::{cmd, pipe},
+};
 use serde::{Serialize, de::DeserializeOwned};
 
 use crate::configs::redis::REDIS_CONFIGS;
@@ -131,6 +136,111 @@ where
     })
 }
 
+pub async fn redis_incr_by(pool: &Pool, key: &str, delta: u64, ttl: usize) -> Option<u64> {
+    async {
+        let mut conn = pool
+            .get()
+            .await
+            .context("get redis connection")?;
+        let (value,): (u64,) = pipe()
+            .atomic()
+            .cmd("INCRBY")
+            .arg(key)
+            .arg(delta)
+            .cmd("EXPIRE")
+            .arg(key)
+            .arg(ttl)
+            .ignore()
+            .query_async(&mut conn)
+            .await
+            .context("execute INCRBY in redis")?;
+        Ok::<u64, anyhow::Error>(value)
+    }
+    .await
+    .inspect_err(|e| tracing::error!(key, error = %e, "Redis INCRBY failed"))
+    .ok()
+}
+
+/// Sliding-window admission: prunes entries older than the window, then
+/// admits only when the cooldown key is absent and the window has room.
+/// Returns the wait in milliseconds, or `0` when admitted.
+const WINDOW_ADMIT_SCRIPT: &str = r"
+local t = redis.call('TIME')
+local now = tonumber(t[1]) * 1000 + math.floor(tonumber(t[2]) / 1000)
+local window = tonumber(ARGV[1])
+local limit = tonumber(ARGV[2])
+local wait = redis.call('PTTL', KEYS[2])
+if wait < 0 then wait = 0 end
+redis.call('ZREMRANGEBYSCORE', KEYS[1], '-inf', now - window)
+if limit > 0 and redis.call('ZCARD', KEYS[1]) >= limit then
+  local oldest = redis.call('ZRANGE', KEYS[1], 0, 0, 'WITHSCORES')
+  local rpm_wait = tonumber(oldest[2]) + window - now
+  if rpm_wait > wait then wait = rpm_wait end
+end
+if wait > 0 then return wait end
+redis.call('ZADD', KEYS[1], now, ARGV[3])
+redis.call('PEXPIRE', KEYS[1], window)
+return 0
+";
+
+/// Sets the cooldown key unless an existing one already lasts longer.
+const EXTEND_COOLDOWN_SCRIPT: &str = r"
+local current = redis.call('PTTL', KEYS[1])
+if current < tonumber(ARGV[1]) then
+  redis.call('SET', KEYS[1], '1', 'PX', ARGV[1])
+end
+return 0
+";
+
+/// Atomically tries to admit one request into a shared sliding window.
+/// Returns `None` when admitted, or how long to wait before trying again.
+pub async fn redis_window_admit(
+    pool: &Pool,
+    window_key: &str,
+    cooldown_key: &str,
+    window: Duration,
+    limit: usize,
+) -> anyhow::Result<Option<Duration>> {
+    let mut conn = pool
+        .get()
+        .await
+        .context("get redis connection")?;
+    let member = format!("{:016x}", fastrand::u64(..));
+    let wait_ms: u64 = cmd("EVAL")
+        .arg(WINDOW_ADMIT_SCRIPT)
+        .arg(2)
+        .arg(window_key)
+        .arg(cooldown_key)
+        .arg(window.as_millis().max(1) as u64)
+        .arg(limit)
+        .arg(member)
+        .query_async(&mut conn)
+        .await
+        .context("execute window admission script in redis")?;
+    Ok((wait_ms > 0).then(|| Duration::from_millis(wait_ms)))
+}
+
+/// Extends a shared cooldown so it lasts at least `duration` from now.
+pub async fn redis_extend_cooldown(
+    pool: &Pool,
+    key: &str,
+    duration: Duration,
+) -> anyhow::Result<()> {
+    let mut conn = pool
+        .get()
+        .await
+        .context("get redis connection")?;
+    cmd("EVAL")
+        .arg(EXTEND_COOLDOWN_SCRIPT)
+        .arg(1)
+        .arg(key)
+        .arg(duration.as_millis().max(1) as u64)
+        .query_async::<()>(&mut conn)
+        .await
+        .context("execute cooldown script in redis")?;
+    Ok(())
+}
+
 pub async fn redis_exists(pool: &Pool, key: &str) -> bool {
     async {
         let mut conn = pool
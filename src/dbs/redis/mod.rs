@@ -4,8 +4,8 @@ pub mod client;
 pub(crate) use client::redis_delete_prefixes_checked;
 #[cfg(not(any(test, feature = "test-utils")))]
 pub use client::{
-    new_pool, redis_delete, redis_delete_prefixes, redis_exists, redis_get, redis_set,
-    redis_set_ex, redis_set_nx, redis_set_nx_ex,
+    new_pool, redis_delete, redis_delete_prefixes, redis_exists, redis_extend_cooldown, redis_get,
+    redis_incr_by, redis_set, redis_set_ex, redis_set_nx, redis_set_nx_ex, redis_window_admit,
 };
 
 #[cfg(any(test, feature = "test-utils"))]
@@ -1,11 +1,16 @@
 use deadpool_redis::{Config, Pool, Runtime};
 use once_cell::sync::Lazy;
 use serde::{Serialize, de::DeserializeOwned};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
 
 static REDIS_STORE: Lazy<Mutex<HashMap<String, String>>> = Lazy::new(|| Mutex::new(HashMap::new()));
 static REDIS_TTLS: Lazy<Mutex<HashMap<String, usize>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+static REDIS_WINDOWS: Lazy<Mutex<HashMap<String, VecDeque<Instant>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+static REDIS_COOLDOWNS: Lazy<Mutex<HashMap<String, Instant>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
 
 pub fn new_pool() -> Pool {
     let cfg = Config::default();
@@ -71,6 +76,75 @@ where
     was_set
 }
 
+pub async fn redis_incr_by(_pool: &Pool, key: &str, delta: u64, ttl: usize) -> Option<u64> {
+    tokio::task::yield_now().await;
+    let mut store = REDIS_STORE.lock().await;
+    let current = store
+        .get(key)
+        .and_then(|json| serde_json::from_str::<u64>(json).ok())
+        .unwrap_or(0);
+    let value = current.saturating_add(delta);
+    store.insert(key.to_string(), value.to_string());
+    let mut ttls = REDIS_TTLS.lock().await;
+    ttls.insert(key.to_string(), ttl);
+    Some(value)
+}
+
+pub async fn redis_window_admit(
+    _pool: &Pool,
+    window_key: &str,
+    cooldown_key: &str,
+    window: Duration,
+    limit: usize,
+) -> anyhow::Result<Option<Duration>> {
+    tokio::task::yield_now().await;
+    let now = Instant::now();
+    let cooldown_wait = REDIS_COOLDOWNS
+        .lock()
+        .await
+        .get(cooldown_key)
+        .and_then(|until| until.checked_duration_since(now));
+
+    let mut windows = REDIS_WINDOWS.lock().await;
+    let recent = windows
+        .entry(window_key.to_string())
+        .or_default();
+    while recent
+        .front()
+        .is_some_and(|seen| now.duration_since(*seen) >= window)
+    {
+        recent.pop_front();
+    }
+    let window_wait = if limit > 0 && recent.len() >= limit {
+        recent
+            .front()
+            .and_then(|seen| (*seen + window).checked_duration_since(now))
+    } else {
+        None
+    };
+
+    let wait = cooldown_wait.max(window_wait);
+    if wait.is_none() {
+        recent.push_back(now);
+    }
+    Ok(wait)
+}
+
+pub async fn redis_extend_cooldown(
+    _pool: &Pool,
+    key: &str,
+    duration: Duration,
+) -> anyhow::Result<()> {
+    tokio::task::yield_now().await;
+    let until = Instant::now() + duration;
+    let mut cooldowns = REDIS_COOLDOWNS.lock().await;
+    let current = cooldowns
+        .entry(key.to_string())
+        .or_insert(until);
+    *current = (*current).max(until);
+    Ok(())
+}
+
 pub async fn redis_exists(_pool: &Pool, key: &str) -> bool {
     tokio::task::yield_now().await;
     let store = REDIS_STORE.lock().await;
@@ -5,7 +5,10 @@ use twilight_model::{
 
 use crate::{
     context::Context,
-    services::ai::{self, AiInteraction, AiService},
+    services::{
+        ai::{self, AiInteraction, AiService, access},
+        guild_settings::GuildSettingsService,
+    },
 };
 use std::{borrow::Cow, sync::Arc};
 
@@ -95,12 +98,63 @@ pub fn strip_mention<'a>(raw: &'a str, id: Id<UserMarker>) -> Cow<'a, str> {
     }
 }
 
+/// Whether the bot should answer `message`, applying the guild's AI access
+/// settings. Direct messages must mention the bot like guild messages do; a
+/// refused one gets an explanation, while refusals in guilds stay silent.
+async fn addressed(ctx: &Arc<Context>, message: &Message, bot_id: Id<UserMarker>) -> bool {
+    let explicit_mention = matches!(
+        strip_mention(&message.content, bot_id),
+        Cow::Owned(_)
+    );
+    let Some(guild_id) = message.guild_id else {
+        if !explicit_mention {
+            return false;
+        }
+        let Err(denied) = access::check(ctx, None, message.channel_id, message.author.id).await
+        else {
+            return true;
+        };
+        if let Ok(embed) = AiService::access_denied_embed(denied)
+            && let Err(e) = ctx
+                .http
+                .create_message(message.channel_id)
+                .embeds(&[embed])
+                .await
+        {
+            tracing::warn!(
+                channel_id = message.channel_id.get(),
+                error = %e,
+                "failed to send AI access denied message",
+            );
+        }
+        return false;
+    };
+
+    let reply_to_bot = message
+        .referenced_message
+        .as_ref()
+        .is_some_and(|m| m.author.id == bot_id && AiService::is_answer(&m.embeds));
+    if !explicit_mention && !reply_to_bot {
+        return false;
+    }
+    let settings = GuildSettingsService::get(ctx, guild_id.get()).await;
+    access::addresses_bot(
+        &settings.ai_access,
+        explicit_mention,
+        reply_to_bot,
+    ) && access::check(
+        ctx,
+        Some(guild_id),
+        message.channel_id,
+        message.author.id,
+    )
+    .await
+    .is_ok()
+}
+
 pub async fn handle_ai(ctx: &Arc<Context>, message: &Message) {
     if let Some(user) = ctx.cache.current_user()
-        && message
-            .mentions
-            .iter()
-            .any(|m| m.id == user.id)
+        && addressed(ctx, message, user.id).await
     {
         if let Err(e) = ctx
             .http
@@ -126,6 +180,25 @@ pub async fn handle_ai(ctx: &Arc<Context>, message: &Message) {
             }
             return;
         }
+        if let Some(exceeded) =
+            AiService::check_quota(ctx, message.guild_id, message.author.id).await
+        {
+            if let Ok(embed) = AiService::quota_exceeded_embed(&exceeded)
+                && let Err(e) = ctx
+                    .http
+                    .create_message(message.channel_id)
+                    .embeds(&[embed])
+                    .await
+            {
+                tracing::warn!(
+                    channel_id = message.channel_id.get(),
+                    user_id = message.author.id.get(),
+                    error = %e,
+                    "failed to send quota exceeded message",
+                );
+            }
+            return;
+        }
         let content = strip_mention(&message.content, user.id);
         let ref_text_opt = message
             .referenced_message
@@ -161,6 +234,7 @@ pub async fn handle_ai(ctx: &Arc<Context>, message: &Message) {
             ctx,
             &client,
             AiInteraction {
+                guild_id: message.guild_id,
                 user_id: message.author.id,
                 user_name: &message.author.name,
                 message: input.as_ref(),
@@ -168,12 +242,13 @@ pub async fn handle_ai(ctx: &Arc<Context>, message: &Message) {
                 ref_text: ref_text_opt,
                 ref_attachments,
                 ref_author,
+                remember: true,
             },
         )
         .await
         {
             Ok(reply) => {
-                if let Ok(embeds) = AiService::ai_embeds(&reply) {
+                if let Ok(embeds) = AiService::reply_embeds(&reply) {
                     for embed in embeds {
                         if let Err(e) = ctx
                             .http
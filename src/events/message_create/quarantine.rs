@@ -101,10 +101,17 @@ pub async fn handle_quarantine(ctx: &Arc<Context>, message: &Message) -> bool {
                     return true;
                 }
             }
-            if GuildSettingsService::scam_detect_enabled(ctx, guild_id.get()).await {
+            let settings = GuildSettingsService::get(ctx, guild_id.get()).await;
+            if settings.scam_detect_enabled {
                 ctx.scam_detect
                     .try_enqueue(ctx.clone(), message, channel.channel_id);
             }
+            ctx.scam_detect.try_enqueue_text(
+                ctx.clone(),
+                message,
+                channel.channel_id,
+                settings.scam_text_mode,
+            );
         }
     }
 
@@ -3,8 +3,15 @@ use twilight_model::gateway::payload::incoming::Ready;
 use crate::{
     context::Context,
     services::{
-        build::BuildService, health::HealthService, market::MarketService,
-        notification::NotificationService, status::StatusService,
+        ai::catalogue,
+        build::BuildService,
+        farm::FarmService,
+        health::HealthService,
+        market::{MarketService, watch::PriceWatchService},
+        notification::NotificationService,
+        riven::RivenService,
+        status::StatusService,
+        worldstate_alert::WorldstateAlertService,
     },
 };
 use once_cell::sync::Lazy;
@@ -19,6 +26,10 @@ pub async fn handle(ctx: Arc<Context>, event: Ready) {
 
     if !INIT.swap(true, Ordering::Relaxed) {
         StatusService::spawn(&ctx);
+        catalogue::spawn(&ctx);
+        WorldstateAlertService::spawn(&ctx);
+        PriceWatchService::spawn(&ctx);
+        FarmService::spawn(&ctx);
 
         let build_ctx = ctx.clone();
         tokio::spawn(async move {
@@ -30,6 +41,11 @@ pub async fn handle(ctx: Arc<Context>, event: Ready) {
             MarketService::init(market_ctx).await;
         });
 
+        let riven_ctx = ctx.clone();
+        tokio::spawn(async move {
+            RivenService::init(riven_ctx).await;
+        });
+
         NotificationService::spawn(ctx);
     }
 
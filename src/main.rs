@@ -31,6 +31,7 @@ async fn main() -> anyhow::Result<()> {
             | Intents::GUILD_MESSAGES
             | Intents::GUILD_MESSAGE_REACTIONS
             | Intents::GUILD_VOICE_STATES
+            | Intents::DIRECT_MESSAGES
             | Intents::MESSAGE_CONTENT,
     );
 
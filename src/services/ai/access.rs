@@ -0,0 +1,128 @@
+use twilight_model::id::{
+    Id,
+    marker::{ChannelMarker, GuildMarker, UserMarker},
+};
+
+use crate::{
+    context::Context,
+    dbs::mongo::models::guild_settings::{AiAccessSettings, AiChannelMode},
+    services::guild_settings::GuildSettingsService,
+};
+
+/// Why a guild's AI settings refuse a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessDenied {
+    Disabled,
+    Channel,
+    DirectMessages,
+}
+
+impl AccessDenied {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Disabled => "disabled",
+            Self::Channel => "channel",
+            Self::DirectMessages => "direct_messages",
+        }
+    }
+}
+
+/// Checks `channel_id` against the allow or deny list. A thread is listed
+/// when its parent channel is.
+pub(crate) fn channel_allowed(
+    access: &AiAccessSettings,
+    channel_id: u64,
+    parent_id: Option<u64>,
+) -> bool {
+    let listed = access.channels.contains(&channel_id)
+        || parent_id.is_some_and(|parent| access.channels.contains(&parent));
+    match access.channel_mode {
+        AiChannelMode::All => true,
+        AiChannelMode::Allow => listed,
+        AiChannelMode::Deny => !listed,
+    }
+}
+
+pub(crate) fn guild_access(
+    access: &AiAccessSettings,
+    channel_id: u64,
+    parent_id: Option<u64>,
+) -> Result<(), AccessDenied> {
+    if !access.enabled {
+        return Err(AccessDenied::Disabled);
+    }
+    if !channel_allowed(access, channel_id, parent_id) {
+        return Err(AccessDenied::Channel);
+    }
+    Ok(())
+}
+
+/// Direct messages are allowed when any guild the user shares with the bot
+/// allows them. With no shared guild in the cache nothing can vouch for the
+/// user, so they are denied.
+pub(crate) fn dm_access<'a>(
+    shared: impl IntoIterator<Item = &'a AiAccessSettings>,
+) -> Result<(), AccessDenied> {
+    if shared
+        .into_iter()
+        .any(|access| access.enabled && access.allow_dms)
+    {
+        Ok(())
+    } else {
+        Err(AccessDenied::DirectMessages)
+    }
+}
+
+/// Whether a guild message is meant for the bot: an explicit mention, or a
+/// reply to the bot when the guild counts those.
+pub(crate) fn addresses_bot(
+    access: &AiAccessSettings,
+    explicit_mention: bool,
+    reply_to_bot: bool,
+) -> bool {
+    explicit_mention || (reply_to_bot && access.reply_mentions)
+}
+
+/// Applies the guild's AI settings, or the DM rule when `guild_id` is `None`.
+pub async fn check(
+    ctx: &Context,
+    guild_id: Option<Id<GuildMarker>>,
+    channel_id: Id<ChannelMarker>,
+    user_id: Id<UserMarker>,
+) -> Result<(), AccessDenied> {
+    let result = match guild_id {
+        Some(guild_id) => {
+            let settings = GuildSettingsService::get(ctx, guild_id.get()).await;
+            let parent_id = ctx
+                .cache
+                .channel(channel_id)
+                .and_then(|channel| channel.parent_id)
+                .map(Id::get);
+            guild_access(&settings.ai_access, channel_id.get(), parent_id)
+        }
+        None => {
+            let shared: Vec<Id<GuildMarker>> = ctx
+                .cache
+                .user_guilds(user_id)
+                .map(|guilds| guilds.iter().copied().collect())
+                .unwrap_or_default();
+            let mut settings = Vec::with_capacity(shared.len());
+            for guild_id in shared {
+                settings.push(
+                    GuildSettingsService::get(ctx, guild_id.get())
+                        .await
+                        .ai_access,
+                );
+            }
+            dm_access(&settings)
+        }
+    };
+    if let Err(denied) = result {
+        metrics::counter!("ai_access_denied_total", "reason" => denied.as_str()).increment(1);
+    }
+    result
+}
+
+#[cfg(test)]
+#[path = "tests/access.rs"]
+mod tests;
@@ -1,11 +1,13 @@
 use anyhow::Context as AnyhowContext;
 use axum::http::{HeaderMap, HeaderName, HeaderValue};
+use deadpool_redis::Pool;
 use futures::{StreamExt, stream::FuturesUnordered};
 use reqwest::{Body, header::CONTENT_TYPE};
 use std::str::FromStr;
 use twilight_model::channel::Attachment;
 
 use crate::configs::google::GOOGLE_CONFIGS;
+use crate::services::ai::file_cache::{self, CachedFile};
 use crate::services::ai::genai::Part;
 
 use async_trait::async_trait;
@@ -44,95 +46,308 @@ impl AttachmentHttp for reqwest::Client {
 
 const CONCURRENCY: usize = 5;
 
-async fn handle_attachment<H>(http: &H, a: Attachment) -> anyhow::Result<Option<(String, String)>>
+/// Largest text file downloaded for inline extraction.
+const MAX_TEXT_BYTES: u64 = 512 * 1024;
+/// PDFs above this are uploaded to Gemini instead of parsed locally.
+const MAX_PDF_BYTES: u64 = 4 * 1024 * 1024;
+/// Characters of extracted text kept per attachment.
+const MAX_INLINE_CHARS: usize = 12_000;
+
+/// Extensions read as plain text whatever MIME type Discord reports; `.rs`
+/// for one arrives as `application/rls-services+xml`.
+const TEXT_EXTENSIONS: &[&str] = &[
+    "txt", "log", "md", "rs", "toml", "json", "yaml", "yml", "csv", "ini", "cfg", "py", "js", "ts",
+    "c", "h", "cpp", "go", "java", "sh", "sql", "xml", "html", "css",
+];
+
+/// How an attachment reaches the model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum AttachmentKind {
+    /// Read locally and sent inline as text.
+    Text,
+    /// Text extracted locally when small enough, uploaded otherwise.
+    Pdf,
+    /// Uploaded through the Gemini file API.
+    Media,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkipReason {
+    Unsupported,
+    TooLarge,
+    Unreadable,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedAttachment {
+    pub filename: String,
+    pub reason: SkipReason,
+}
+
+/// What `append_attachments` added to a request.
+#[derive(Debug, Default)]
+pub struct AppendedAttachments {
+    /// Gemini file URIs, kept in history while the upload lives.
+    pub uris: Vec<String>,
+    /// Labelled text extracted locally, replayed from history as-is.
+    pub documents: Vec<String>,
+    pub skipped: Vec<SkippedAttachment>,
+}
+
+enum Prepared {
+    Inline(String),
+    Uploaded { mime_type: String, uri: String },
+    Skipped(SkipReason),
+}
+
+fn extension(filename: &str) -> Option<String> {
+    filename
+        .rsplit_once('.')
+        .map(|(_, ext)| ext.to_ascii_lowercase())
+}
+
+pub(crate) fn classify(filename: &str, content_type: Option<&str>) -> Option<AttachmentKind> {
+    let ext = extension(filename);
+    if ext
+        .as_deref()
+        .is_some_and(|ext| TEXT_EXTENSIONS.contains(&ext))
+    {
+        return Some(AttachmentKind::Text);
+    }
+    let mime = content_type
+        .map(|ct| {
+            ct.split(';')
+                .next()
+                .unwrap_or(ct)
+                .trim()
+                .to_ascii_lowercase()
+        })
+        .unwrap_or_default();
+    if mime == "application/pdf" || ext.as_deref() == Some("pdf") {
+        Some(AttachmentKind::Pdf)
+    } else if mime.starts_with("text/") || mime == "application/json" {
+        Some(AttachmentKind::Text)
+    } else if mime.starts_with("image/") || mime.starts_with("audio/") || mime.starts_with("video/")
+    {
+        Some(AttachmentKind::Media)
+    } else {
+        None
+    }
+}
+
+/// Keeps the first `MAX_INLINE_CHARS` characters, noting when text was cut.
+pub(crate) fn clip_text(text: &str) -> String {
+    let text = text.trim();
+    match text
+        .char_indices()
+        .nth(MAX_INLINE_CHARS)
+    {
+        Some((end, _)) => format!("{}\n[truncated]", &text[..end]),
+        None => text.to_string(),
+    }
+}
+
+pub(crate) fn decode_text(bytes: &[u8]) -> Option<String> {
+    let text = std::str::from_utf8(bytes).ok()?;
+    (!text.contains('\0')).then(|| clip_text(text))
+}
+
+/// Text of every page, or `None` for scans and files lopdf cannot parse.
+pub(crate) fn extract_pdf_text(bytes: &[u8]) -> Option<String> {
+    let document = lopdf::Document::load_mem(bytes).ok()?;
+    let pages: Vec<u32> = document
+        .get_pages()
+        .keys()
+        .copied()
+        .collect();
+    let text = document.extract_text(&pages).ok()?;
+    (!text.trim().is_empty()).then(|| clip_text(&text))
+}
+
+async fn download<H>(http: &H, a: &Attachment) -> anyhow::Result<Vec<u8>>
 where
     H: AttachmentHttp + Sync,
 {
-    if let Some(ct) = a.content_type.clone() {
-        let resp = http
-            .get(&a.url)
-            .await?
-            .error_for_status()?;
-        let stream = Body::wrap_stream(resp.bytes_stream());
-        let upload_url = reqwest::Url::parse_with_params(
-            "https://generativelanguage.googleapis.com/upload/v1beta/files",
-            &[("uploadType", "media")],
-        )?;
-        let mut headers = HeaderMap::new();
-        headers.append(
-            HeaderName::from_str("X-Goog-Api-Key")?,
-            HeaderValue::from_str(GOOGLE_CONFIGS.api_key.as_str())?,
-        );
-        if let Some(content_type) = &a.content_type {
-            headers.append(
-                CONTENT_TYPE,
-                HeaderValue::from_str(content_type.as_str())?,
-            );
+    let resp = http
+        .get(&a.url)
+        .await?
+        .error_for_status()?;
+    Ok(resp.bytes().await?.to_vec())
+}
+
+/// Uploads `a` to Gemini, reusing `bytes` when the caller already downloaded
+/// it and streaming it from Discord otherwise.
+async fn upload<H>(
+    http: &H,
+    pool: &Pool,
+    a: &Attachment,
+    ct: String,
+    bytes: Option<Vec<u8>>,
+) -> anyhow::Result<Prepared>
+where
+    H: AttachmentHttp + Sync,
+{
+    if let Some(file) = file_cache::lookup_attachment(pool, a).await {
+        return Ok(Prepared::Uploaded { mime_type: file.mime_type, uri: file.uri });
+    }
+    let body = match bytes {
+        Some(bytes) => Body::from(bytes),
+        None => {
+            let resp = http
+                .get(&a.url)
+                .await?
+                .error_for_status()?;
+            Body::wrap_stream(resp.bytes_stream())
         }
-        let resp = http
-            .post(upload_url, headers, stream)
-            .await?
-            .error_for_status()?;
-        let json: serde_json::Value = resp.json().await?;
-        let uri = json["file"]["uri"]
-            .as_str()
-            .context("Missing file uri")?
-            .to_string();
-        Ok(Some((ct, uri)))
-    } else {
-        Ok(None)
+    };
+    let upload_url = reqwest::Url::parse_with_params(
+        "https://generativelanguage.googleapis.com/upload/v1beta/files",
+        &[("uploadType", "media")],
+    )?;
+    let mut headers = HeaderMap::new();
+    headers.append(
+        HeaderName::from_str("X-Goog-Api-Key")?,
+        HeaderValue::from_str(GOOGLE_CONFIGS.api_key.as_str())?,
+    );
+    headers.append(CONTENT_TYPE, HeaderValue::from_str(ct.as_str())?);
+    let resp = http
+        .post(upload_url, headers, body)
+        .await?
+        .error_for_status()?;
+    let json: serde_json::Value = resp.json().await?;
+    let uri = json["file"]["uri"]
+        .as_str()
+        .context("Missing file uri")?
+        .to_string();
+    file_cache::remember(
+        pool,
+        a,
+        &CachedFile::uploaded_now(uri.clone(), ct.clone()),
+    )
+    .await;
+    Ok(Prepared::Uploaded { mime_type: ct, uri })
+}
+
+async fn handle_attachment<H>(http: &H, pool: &Pool, a: Attachment) -> anyhow::Result<Prepared>
+where
+    H: AttachmentHttp + Sync,
+{
+    let Some(kind) = classify(&a.filename, a.content_type.as_deref()) else {
+        return Ok(Prepared::Skipped(SkipReason::Unsupported));
+    };
+    match kind {
+        AttachmentKind::Text => {
+            if a.size > MAX_TEXT_BYTES {
+                return Ok(Prepared::Skipped(SkipReason::TooLarge));
+            }
+            let bytes = download(http, &a).await?;
+            Ok(match decode_text(&bytes) {
+                Some(text) => Prepared::Inline(text),
+                None => Prepared::Skipped(SkipReason::Unreadable),
+            })
+        }
+        AttachmentKind::Pdf => {
+            let bytes = if a.size <= MAX_PDF_BYTES {
+                let bytes = download(http, &a).await?;
+                // lopdf parses synchronously and can take a while on large files.
+                let (text, bytes) =
+                    tokio::task::spawn_blocking(move || (extract_pdf_text(&bytes), bytes)).await?;
+                if let Some(text) = text {
+                    return Ok(Prepared::Inline(text));
+                }
+                Some(bytes)
+            } else {
+                None
+            };
+            // Scanned or oversized PDFs still work through Gemini's own reader.
+            upload(
+                http,
+                pool,
+                &a,
+                "application/pdf".to_string(),
+                bytes,
+            )
+            .await
+        }
+        AttachmentKind::Media => match a.content_type.clone() {
+            Some(ct) => upload(http, pool, &a, ct, None).await,
+            None => Ok(Prepared::Skipped(SkipReason::Unsupported)),
+        },
     }
 }
 
 async fn run<H>(
     idx: usize,
     http: &H,
+    pool: &Pool,
     a: Attachment,
-) -> (usize, anyhow::Result<Option<(String, String)>>)
+) -> (usize, String, anyhow::Result<Prepared>)
 where
     H: AttachmentHttp + Sync,
 {
-    (idx, handle_attachment(http, a).await)
+    let filename = a.filename.clone();
+    (
+        idx,
+        filename,
+        handle_attachment(http, pool, a).await,
+    )
 }
 
 pub async fn append_attachments<H>(
     http: &H,
+    pool: &Pool,
     parts: &mut Vec<Part>,
     attachments: Vec<Attachment>,
     owner: &str,
-) -> anyhow::Result<Vec<String>>
+) -> anyhow::Result<AppendedAttachments>
 where
     H: AttachmentHttp + Sync,
 {
     let mut in_flight = FuturesUnordered::new();
     let len = attachments.len();
     let mut iter = attachments.into_iter().enumerate();
-    let mut results: Vec<Option<(String, String)>> = vec![None; len];
+    let mut results: Vec<Option<(String, Prepared)>> = (0..len).map(|_| None).collect();
 
     for _ in 0..CONCURRENCY {
         if let Some((idx, a)) = iter.next() {
-            in_flight.push(run(idx, http, a));
+            in_flight.push(run(idx, http, pool, a));
         }
     }
 
-    while let Some((idx, res)) = in_flight.next().await {
-        if let Some((ct, uri)) = res? {
-            results[idx] = Some((ct, uri));
-        }
+    while let Some((idx, filename, res)) = in_flight.next().await {
+        results[idx] = Some((filename, res?));
 
         if let Some((idx, a)) = iter.next() {
-            in_flight.push(run(idx, http, a));
+            in_flight.push(run(idx, http, pool, a));
         }
     }
 
-    let mut urls = Vec::with_capacity(results.len());
-    for res in results.into_iter().flatten() {
-        let (ct, uri) = res;
-        let label = format!("Attachment from {owner}:");
-        parts.push(Part::text(&label));
-        parts.push(Part::file_data(&ct, &uri));
-        urls.push(uri);
+    let mut appended = AppendedAttachments::default();
+    for (filename, prepared) in results.into_iter().flatten() {
+        match prepared {
+            Prepared::Inline(text) => {
+                let document = format!("Attachment {filename} from {owner}:\n{text}");
+                parts.push(Part::text(&document));
+                appended.documents.push(document);
+            }
+            Prepared::Uploaded { mime_type, uri } => {
+                let label = format!("Attachment from {owner}:");
+                parts.push(Part::text(&label));
+                parts.push(Part::file_data(&mime_type, &uri));
+                appended.uris.push(uri);
+            }
+            Prepared::Skipped(reason) => {
+                tracing::info!(%filename, ?reason, "attachment skipped");
+                appended
+                    .skipped
+                    .push(SkippedAttachment { filename, reason });
+            }
+        }
     }
 
-    Ok(urls)
+    Ok(appended)
 }
+
+#[cfg(test)]
+#[path = "tests/attachments.rs"]
+mod tests;
@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+
+const K1: f32 = 1.2;
+const B: f32 = 0.75;
+
+/// Whitespace, ASCII symbols and general/CJK punctuation. Non-ASCII letters
+/// are kept whole because Thai vowel and tone marks are not alphanumeric.
+fn is_separator(c: char) -> bool {
+    c.is_whitespace()
+        || (c.is_ascii() && !c.is_ascii_alphanumeric())
+        || matches!(c, '\u{2000}'..='\u{206F}' | '\u{3000}'..='\u{303F}')
+}
+
+/// Lowercases and splits on separators. Words in scripts written without
+/// spaces (Thai) are broken into character bigrams so a query still overlaps
+/// with text that has no word boundaries.
+pub(crate) fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    for word in text
+        .split(is_separator)
+        .filter(|w| !w.is_empty())
+    {
+        let word = word.to_lowercase();
+        if word.is_ascii() {
+            tokens.push(word);
+            continue;
+        }
+        let chars: Vec<char> = word.chars().collect();
+        if chars.len() < 2 {
+            tokens.push(word);
+            continue;
+        }
+        tokens.extend(
+            chars
+                .windows(2)
+                .map(|pair| pair.iter().collect::<String>()),
+        );
+    }
+    tokens
+}
+
+struct DocStats {
+    len: usize,
+    term_freqs: HashMap<String, u32>,
+}
+
+/// An Okapi BM25 index over a fixed set of texts, addressed by position.
+pub(crate) struct Bm25Index {
+    docs: Vec<DocStats>,
+    doc_freqs: HashMap<String, u32>,
+    avg_len: f32,
+}
+
+impl Bm25Index {
+    pub(crate) fn new<'a>(texts: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut docs = Vec::new();
+        let mut doc_freqs: HashMap<String, u32> = HashMap::new();
+        for text in texts {
+            let tokens = tokenize(text);
+            let mut term_freqs: HashMap<String, u32> = HashMap::new();
+            for token in &tokens {
+                *term_freqs
+                    .entry(token.clone())
+                    .or_default() += 1;
+            }
+            for term in term_freqs.keys() {
+                *doc_freqs
+                    .entry(term.clone())
+                    .or_default() += 1;
+            }
+            docs.push(DocStats { len: tokens.len(), term_freqs });
+        }
+        let total: usize = docs.iter().map(|d| d.len).sum();
+        let avg_len = if docs.is_empty() { 0.0 } else { total as f32 / docs.len() as f32 };
+        Self { docs, doc_freqs, avg_len }
+    }
+
+    fn idf(&self, term: &str) -> f32 {
+        let n = self.docs.len() as f32;
+        let df = self
+            .doc_freqs
+            .get(term)
+            .copied()
+            .unwrap_or(0) as f32;
+        ((n - df + 0.5) / (df + 0.5) + 1.0).ln()
+    }
+
+    /// The `limit` best-scoring texts as `(position, score)`, best first.
+    /// Texts sharing no term with the query are never returned.
+    pub(crate) fn search(&self, query: &str, limit: usize) -> Vec<(usize, f32)> {
+        let mut terms = tokenize(query);
+        terms.sort();
+        terms.dedup();
+        if terms.is_empty() || self.avg_len == 0.0 {
+            return Vec::new();
+        }
+
+        let mut scored: Vec<(usize, f32)> = self
+            .docs
+            .iter()
+            .enumerate()
+            .filter_map(|(i, doc)| {
+                let norm = K1 * (1.0 - B + B * doc.len as f32 / self.avg_len);
+                let score: f32 = terms
+                    .iter()
+                    .filter_map(|term| {
+                        let tf = *doc.term_freqs.get(term)? as f32;
+                        Some(self.idf(term) * tf * (K1 + 1.0) / (tf + norm))
+                    })
+                    .sum();
+                (score > 0.0).then_some((i, score))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored.truncate(limit);
+        scored
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/bm25.rs"]
+mod tests;
@@ -0,0 +1,257 @@
+use std::{
+    borrow::Cow,
+    sync::{Arc, LazyLock, RwLock},
+    time::{Duration, SystemTime},
+};
+
+use anyhow::{Context as _, bail};
+use serde::Deserialize;
+use tokio::task::JoinHandle;
+
+use super::scheduler::AiScheduler;
+use crate::{configs::google::GOOGLE_CONFIGS, context::Context, services::shutdown};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ModelSpec {
+    pub name: Cow<'static, str>,
+    pub rpm_limit: usize,
+    pub queue_timeout: Duration,
+    pub cooldown: Duration,
+}
+
+/// The fallback chains tried in order for each kind of request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ModelCatalogue {
+    pub chat: Vec<ModelSpec>,
+    pub summary: Vec<ModelSpec>,
+}
+
+const DEFAULT_CHAT_MODELS: &[ModelSpec] = &[
+    ModelSpec {
+        name: Cow::Borrowed("gemini-2.5-flash"),
+        rpm_limit: 10,
+        queue_timeout: Duration::from_secs(8),
+        cooldown: Duration::from_secs(20),
+    },
+    ModelSpec {
+        name: Cow::Borrowed("gemini-2.5-flash-lite"),
+        rpm_limit: 15,
+        queue_timeout: Duration::from_secs(8),
+        cooldown: Duration::from_secs(15),
+    },
+    ModelSpec {
+        name: Cow::Borrowed("gemini-2.0-flash"),
+        rpm_limit: 15,
+        queue_timeout: Duration::from_secs(8),
+        cooldown: Duration::from_secs(12),
+    },
+    ModelSpec {
+        name: Cow::Borrowed("gemini-2.0-flash-lite"),
+        rpm_limit: 30,
+        queue_timeout: Duration::from_secs(8),
+        cooldown: Duration::from_secs(10),
+    },
+    ModelSpec {
+        name: Cow::Borrowed("gemini-2.5-flash-preview"),
+        rpm_limit: 10,
+        queue_timeout: Duration::from_secs(8),
+        cooldown: Duration::from_secs(20),
+    },
+    ModelSpec {
+        name: Cow::Borrowed("gemini-2.5-flash-lite-preview"),
+        rpm_limit: 15,
+        queue_timeout: Duration::from_secs(8),
+        cooldown: Duration::from_secs(15),
+    },
+];
+
+const DEFAULT_SUMMARY_MODELS: &[ModelSpec] = &[
+    ModelSpec {
+        name: Cow::Borrowed("gemini-2.5-flash-lite"),
+        rpm_limit: 15,
+        queue_timeout: Duration::from_secs(5),
+        cooldown: Duration::from_secs(15),
+    },
+    ModelSpec {
+        name: Cow::Borrowed("gemini-2.0-flash-lite"),
+        rpm_limit: 30,
+        queue_timeout: Duration::from_secs(5),
+        cooldown: Duration::from_secs(10),
+    },
+    ModelSpec {
+        name: Cow::Borrowed("gemini-2.0-flash"),
+        rpm_limit: 15,
+        queue_timeout: Duration::from_secs(5),
+        cooldown: Duration::from_secs(12),
+    },
+    ModelSpec {
+        name: Cow::Borrowed("gemini-2.5-flash"),
+        rpm_limit: 10,
+        queue_timeout: Duration::from_secs(5),
+        cooldown: Duration::from_secs(20),
+    },
+    ModelSpec {
+        name: Cow::Borrowed("gemini-2.5-flash-lite-preview"),
+        rpm_limit: 15,
+        queue_timeout: Duration::from_secs(5),
+        cooldown: Duration::from_secs(15),
+    },
+];
+
+impl Default for ModelCatalogue {
+    fn default() -> Self {
+        Self { chat: DEFAULT_CHAT_MODELS.to_vec(), summary: DEFAULT_SUMMARY_MODELS.to_vec() }
+    }
+}
+
+impl ModelCatalogue {
+    pub fn model_names(&self) -> impl Iterator<Item = &str> {
+        self.chat
+            .iter()
+            .chain(&self.summary)
+            .map(|spec| spec.name.as_ref())
+    }
+}
+
+#[derive(Deserialize)]
+struct CatalogueFile {
+    chat: Vec<ModelSpecFile>,
+    summary: Vec<ModelSpecFile>,
+}
+
+#[derive(Deserialize)]
+struct ModelSpecFile {
+    name: String,
+    rpm_limit: usize,
+    queue_timeout_secs: u64,
+    cooldown_secs: u64,
+}
+
+impl From<ModelSpecFile> for ModelSpec {
+    fn from(spec: ModelSpecFile) -> Self {
+        Self {
+            name: Cow::Owned(spec.name),
+            rpm_limit: spec.rpm_limit,
+            queue_timeout: Duration::from_secs(spec.queue_timeout_secs),
+            cooldown: Duration::from_secs(spec.cooldown_secs),
+        }
+    }
+}
+
+static CATALOGUE: LazyLock<RwLock<Arc<ModelCatalogue>>> =
+    LazyLock::new(|| RwLock::new(Arc::new(ModelCatalogue::default())));
+
+/// Snapshot of the active catalogue. A request keeps the snapshot it started
+/// with, so a reload never changes the chain under a running fallback loop.
+pub fn current() -> Arc<ModelCatalogue> {
+    CATALOGUE
+        .read()
+        .expect("ai model catalogue poisoned")
+        .clone()
+}
+
+pub(crate) fn parse(json: &str) -> anyhow::Result<ModelCatalogue> {
+    let file: CatalogueFile = serde_json::from_str(json).context("decode ai model catalogue")?;
+    let catalogue = ModelCatalogue {
+        chat: file
+            .chat
+            .into_iter()
+            .map(ModelSpec::from)
+            .collect(),
+        summary: file
+            .summary
+            .into_iter()
+            .map(ModelSpec::from)
+            .collect(),
+    };
+    if catalogue.chat.is_empty() || catalogue.summary.is_empty() {
+        bail!("ai model catalogue must list at least one chat and one summary model");
+    }
+    if let Some(spec) = catalogue
+        .chat
+        .iter()
+        .chain(&catalogue.summary)
+        .find(|spec| spec.name.trim().is_empty())
+    {
+        bail!("ai model catalogue has a model with an empty name: {spec:?}");
+    }
+    Ok(catalogue)
+}
+
+/// Installs `catalogue` and forgets scheduler queues for models it no longer
+/// lists. Returns `false` when nothing changed.
+pub(crate) fn install(scheduler: &AiScheduler, catalogue: ModelCatalogue) -> bool {
+    install_into(&CATALOGUE, scheduler, catalogue)
+}
+
+fn install_into(
+    slot: &RwLock<Arc<ModelCatalogue>>,
+    scheduler: &AiScheduler,
+    catalogue: ModelCatalogue,
+) -> bool {
+    let mut active = slot
+        .write()
+        .expect("ai model catalogue poisoned");
+    if **active == catalogue {
+        return false;
+    }
+    scheduler.retain_models(catalogue.model_names());
+    *active = Arc::new(catalogue);
+    true
+}
+
+async fn reload(scheduler: &AiScheduler, path: &str) -> anyhow::Result<()> {
+    let json = tokio::fs::read_to_string(path)
+        .await
+        .with_context(|| format!("read ai model catalogue {path}"))?;
+    let catalogue = parse(&json)?;
+    let (chat, summary) = (catalogue.chat.len(), catalogue.summary.len());
+    if install(scheduler, catalogue) {
+        metrics::counter!("ai_model_catalogue_reloads_total").increment(1);
+        tracing::info!(path, chat, summary, "reloaded ai model catalogue");
+    }
+    Ok(())
+}
+
+async fn modified(path: &str) -> Option<SystemTime> {
+    tokio::fs::metadata(path)
+        .await
+        .ok()?
+        .modified()
+        .ok()
+}
+
+/// Polls `AI_MODELS_FILE` and swaps the catalogue when the file changes. An
+/// unreadable or invalid file keeps the last good catalogue in place.
+pub fn spawn(ctx: &Arc<Context>) -> Option<JoinHandle<()>> {
+    let path = GOOGLE_CONFIGS.models_file.clone()?;
+    let ctx = ctx.clone();
+    Some(tokio::spawn(async move {
+        let token = shutdown::get_token();
+        let mut last_modified = None;
+        let mut interval = tokio::time::interval(Duration::from_secs(
+            GOOGLE_CONFIGS.models_reload_secs.max(1),
+        ));
+        loop {
+            tokio::select! {
+                _ = token.cancelled() => break,
+                _ = interval.tick() => {
+                    let stamp = modified(&path).await;
+                    if stamp.is_some() && stamp == last_modified {
+                        continue;
+                    }
+                    match reload(&ctx.ai_scheduler, &path).await {
+                        Ok(()) => last_modified = stamp,
+                        Err(e) => {
+                            tracing::warn!(path, error = %e, "failed to reload ai model catalogue");
+                        }
+                    }
+                }
+            }
+        }
+    }))
+}
+
+#[cfg(test)]
+#[path = "tests/catalogue.rs"]
+mod tests;
@@ -0,0 +1,257 @@
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use twilight_model::{
+    channel::Message,
+    id::{
+        Id,
+        marker::{ChannelMarker, GuildMarker, MessageMarker, UserMarker},
+    },
+};
+
+use super::client::{self, AiClient};
+use super::genai::{Content, Part};
+use super::scheduler::AiOperation;
+use super::usage::UsageScope;
+use crate::{context::Context, services::spam::quarantine};
+
+pub const DEFAULT_MESSAGES: usize = 100;
+pub const MAX_MESSAGES: usize = 500;
+const PAGE_SIZE: u16 = 100;
+/// Roughly 6k tokens per chunk, well inside every summary model's context
+/// window even with the system prompt and the reply on top.
+const CHUNK_CHARS: usize = 24_000;
+const MAX_SINCE_DAYS: i64 = 7;
+
+const CHUNK_SYSTEM: &str = "You summarize Discord channel conversations. Given a transcript, list the main topics, decisions, open questions and who said what when it matters. Reply with short bullet points in the language the conversation mostly uses.";
+const MERGE_SYSTEM: &str = "You merge partial summaries of one Discord conversation, given in chronological order, into a single summary. Keep the bullet-point format and the conversation's language, and drop duplicates.";
+
+pub struct ChannelSummaryRequest {
+    pub guild_id: Option<Id<GuildMarker>>,
+    pub channel_id: Id<ChannelMarker>,
+    pub limit: usize,
+    pub since: Option<DateTime<Utc>>,
+}
+
+pub struct ChannelSummary {
+    pub text: String,
+    pub messages: usize,
+    pub tokens: u64,
+}
+
+/// Parses durations such as `90m`, `2h30m` or `1d`. Anything longer than a
+/// week, zero, or malformed is rejected.
+pub(crate) fn parse_since(raw: &str) -> Option<Duration> {
+    let mut total = Duration::zero();
+    let mut digits = String::new();
+    for c in raw.trim().chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        let value: i64 = digits.parse().ok()?;
+        digits.clear();
+        total += match c.to_ascii_lowercase() {
+            'm' => Duration::try_minutes(value)?,
+            'h' => Duration::try_hours(value)?,
+            'd' => Duration::try_days(value)?,
+            _ => return None,
+        };
+    }
+    if !digits.is_empty() || total <= Duration::zero() || total > Duration::days(MAX_SINCE_DAYS) {
+        return None;
+    }
+    Some(total)
+}
+
+/// Packs transcript lines into chunks of at most `max_chars`, never splitting
+/// a line unless it is longer than a chunk on its own.
+pub(crate) fn chunk_lines(lines: &[String], max_chars: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    for line in lines {
+        let line: String = line.chars().take(max_chars).collect();
+        if !current.is_empty() && current.chars().count() + line.chars().count() + 1 > max_chars {
+            chunks.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push('\n');
+        }
+        current.push_str(&line);
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+fn transcript_line(message: &Message) -> Option<String> {
+    let content = message.content.trim();
+    if content.is_empty() {
+        return None;
+    }
+    let at = DateTime::from_timestamp(message.timestamp.as_secs(), 0)?;
+    let name = message
+        .author
+        .global_name
+        .as_deref()
+        .unwrap_or(&message.author.name);
+    Some(format!(
+        "[{}] {name}: {content}",
+        at.format("%m-%d %H:%M")
+    ))
+}
+
+/// Walks back through the channel until `limit` human messages are collected
+/// or `since` is passed. Returned oldest first.
+async fn fetch_messages(
+    ctx: &Context,
+    channel_id: Id<ChannelMarker>,
+    limit: usize,
+    since: Option<DateTime<Utc>>,
+) -> anyhow::Result<Vec<Message>> {
+    let mut collected = Vec::with_capacity(limit);
+    let mut before: Option<Id<MessageMarker>> = None;
+
+    'pages: while collected.len() < limit {
+        let page = ctx
+            .http
+            .channel_messages_before(channel_id, before, PAGE_SIZE)
+            .await?
+            .model()
+            .await?;
+        let Some(last) = page.last() else {
+            break;
+        };
+        before = Some(last.id);
+        let exhausted = page.len() < PAGE_SIZE as usize;
+
+        for message in page {
+            if since.is_some_and(|since| message.timestamp.as_secs() < since.timestamp()) {
+                break 'pages;
+            }
+            if message.author.bot {
+                continue;
+            }
+            collected.push(message);
+            if collected.len() >= limit {
+                break 'pages;
+            }
+        }
+
+        if exhausted {
+            break;
+        }
+    }
+
+    collected.reverse();
+    Ok(collected)
+}
+
+async fn drop_quarantined(
+    ctx: &Arc<Context>,
+    guild_id: Option<Id<GuildMarker>>,
+    messages: Vec<Message>,
+) -> Vec<Message> {
+    let Some(guild_id) = guild_id else {
+        return messages;
+    };
+    let authors: HashSet<Id<UserMarker>> = messages
+        .iter()
+        .map(|m| m.author.id)
+        .collect();
+    let mut quarantined = HashMap::with_capacity(authors.len());
+    for author in authors {
+        let held = quarantine::get_token(ctx, guild_id.get(), author.get())
+            .await
+            .is_some();
+        quarantined.insert(author, held);
+    }
+    messages
+        .into_iter()
+        .filter(|m| {
+            !quarantined
+                .get(&m.author.id)
+                .copied()
+                .unwrap_or(false)
+        })
+        .collect()
+}
+
+/// Returns `None` when there is nothing left to summarize after filtering.
+pub(crate) async fn summarize_channel<C>(
+    ctx: &Arc<Context>,
+    client: &C,
+    request: ChannelSummaryRequest,
+) -> anyhow::Result<Option<ChannelSummary>>
+where
+    C: AiClient + Send + Sync,
+{
+    let limit = request.limit.clamp(1, MAX_MESSAGES);
+    let messages = fetch_messages(ctx, request.channel_id, limit, request.since).await?;
+    let messages = drop_quarantined(ctx, request.guild_id, messages).await;
+    let lines: Vec<String> = messages
+        .iter()
+        .filter_map(transcript_line)
+        .collect();
+    if lines.is_empty() {
+        return Ok(None);
+    }
+
+    let usage = UsageScope::new(ctx, request.guild_id.map(|id| id.get()), None);
+    let mut tokens = 0;
+    let mut partials = Vec::new();
+    for chunk in chunk_lines(&lines, CHUNK_CHARS) {
+        let reply = client::generate_with_summary_models(
+            client,
+            &ctx.ai_scheduler,
+            AiOperation::ChannelSummary,
+            CHUNK_SYSTEM,
+            vec![Content::from(Part::text(chunk))],
+            &client::RequestOptions::default(),
+            &usage,
+        )
+        .await?;
+        tokens += reply.usage.total_token_count;
+        if !reply.text.trim().is_empty() {
+            partials.push(reply.text);
+        }
+    }
+
+    let text = if partials.len() > 1 {
+        let joined = partials
+            .iter()
+            .enumerate()
+            .map(|(i, partial)| format!("Part {}:\n{partial}", i + 1))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let reply = client::generate_with_summary_models(
+            client,
+            &ctx.ai_scheduler,
+            AiOperation::ChannelSummary,
+            MERGE_SYSTEM,
+            vec![Content::from(Part::text(joined))],
+            &client::RequestOptions::default(),
+            &usage,
+        )
+        .await?;
+        tokens += reply.usage.total_token_count;
+        reply.text
+    } else {
+        partials.pop().unwrap_or_default()
+    };
+
+    if text.trim().is_empty() {
+        anyhow::bail!("summary models returned no text");
+    }
+
+    Ok(Some(ChannelSummary {
+        text,
+        messages: lines.len(),
+        tokens,
+    }))
+}
+
+#[cfg(test)]
+#[path = "tests/channel_summary.rs"]
+mod tests;
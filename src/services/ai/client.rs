@@ -1,14 +1,27 @@
 use std::collections::VecDeque;
 
+use super::catalogue;
 use super::models::ChatEntry;
+use super::safety::{self, ReplyStatus};
 use super::scheduler::{AdmissionConfig, AiOperation, AiScheduler};
+use super::usage::UsageScope;
 use crate::configs::google::GOOGLE_CONFIGS;
-use crate::services::ai::genai::{Auth, Client, Content, Part, Response};
+use crate::services::ai::genai::{
+    Auth, Client, Content, GenerationConfig, Part, Response, SafetySetting, UsageMetadata,
+};
 use crate::services::ai::history::parse_history;
 use async_trait::async_trait;
+use deadpool_redis::Pool;
 use tokio::sync::OnceCell;
 use tokio::time::{Duration, sleep};
 
+/// Per-request knobs that guild settings and personas layer onto a call.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RequestOptions {
+    pub safety: Vec<SafetySetting>,
+    pub generation: GenerationConfig,
+}
+
 #[async_trait]
 pub trait AiClient {
     async fn generate(
@@ -16,6 +29,7 @@ pub trait AiClient {
         model: &str,
         system: &str,
         contents: Vec<Content>,
+        options: &RequestOptions,
     ) -> anyhow::Result<Response>;
 }
 
@@ -26,9 +40,12 @@ impl AiClient for Client {
         model: &str,
         system: &str,
         contents: Vec<Content>,
+        options: &RequestOptions,
     ) -> anyhow::Result<Response> {
         self.generative_model(model)
             .with_system_instruction(system)
+            .with_safety_settings(&options.safety)
+            .with_generation_config(options.generation.clone())
             .generate_content(contents)
             .await
     }
@@ -38,86 +55,6 @@ const SYSTEM: &str = "You are a conversation summarizer. Given the chat history,
 
 pub(super) static CLIENT: OnceCell<Client> = OnceCell::const_new();
 
-#[derive(Clone, Copy)]
-pub(super) struct ModelSpec {
-    pub name: &'static str,
-    pub rpm_limit: usize,
-    pub queue_timeout: Duration,
-    pub cooldown: Duration,
-}
-
-pub(super) const MODELS: &[ModelSpec] = &[
-    ModelSpec {
-        name: "gemini-2.5-flash",
-        rpm_limit: 10,
-        queue_timeout: Duration::from_secs(8),
-        cooldown: Duration::from_secs(20),
-    },
-    ModelSpec {
-        name: "gemini-2.5-flash-lite",
-        rpm_limit: 15,
-        queue_timeout: Duration::from_secs(8),
-        cooldown: Duration::from_secs(15),
-    },
-    ModelSpec {
-        name: "gemini-2.0-flash",
-        rpm_limit: 15,
-        queue_timeout: Duration::from_secs(8),
-        cooldown: Duration::from_secs(12),
-    },
-    ModelSpec {
-        name: "gemini-2.0-flash-lite",
-        rpm_limit: 30,
-        queue_timeout: Duration::from_secs(8),
-        cooldown: Duration::from_secs(10),
-    },
-    ModelSpec {
-        name: "gemini-2.5-flash-preview",
-        rpm_limit: 10,
-        queue_timeout: Duration::from_secs(8),
-        cooldown: Duration::from_secs(20),
-    },
-    ModelSpec {
-        name: "gemini-2.5-flash-lite-preview",
-        rpm_limit: 15,
-        queue_timeout: Duration::from_secs(8),
-        cooldown: Duration::from_secs(15),
-    },
-];
-
-pub(super) const SUMMARY_MODELS: &[ModelSpec] = &[
-    ModelSpec {
-        name: "gemini-2.5-flash-lite",
-        rpm_limit: 15,
-        queue_timeout: Duration::from_secs(5),
-        cooldown: Duration::from_secs(15),
-    },
-    ModelSpec {
-        name: "gemini-2.0-flash-lite",
-        rpm_limit: 30,
-        queue_timeout: Duration::from_secs(5),
-        cooldown: Duration::from_secs(10),
-    },
-    ModelSpec {
-        name: "gemini-2.0-flash",
-        rpm_limit: 15,
-        queue_timeout: Duration::from_secs(5),
-        cooldown: Duration::from_secs(12),
-    },
-    ModelSpec {
-        name: "gemini-2.5-flash",
-        rpm_limit: 10,
-        queue_timeout: Duration::from_secs(5),
-        cooldown: Duration::from_secs(20),
-    },
-    ModelSpec {
-        name: "gemini-2.5-flash-lite-preview",
-        rpm_limit: 15,
-        queue_timeout: Duration::from_secs(5),
-        cooldown: Duration::from_secs(15),
-    },
-];
-
 const RETRY_DELAYS_MS: &[u64] = &[250, 1000];
 
 pub async fn client() -> anyhow::Result<&'static Client> {
@@ -130,7 +67,7 @@ pub async fn client() -> anyhow::Result<&'static Client> {
         .await
 }
 
-pub(super) fn extract_text(response: Response) -> String {
+pub(super) fn extract_text(response: &Response) -> String {
     response
         .candidates
         .first()
@@ -140,25 +77,65 @@ pub(super) fn extract_text(response: Response) -> String {
         .unwrap_or_default()
 }
 
-pub(super) fn is_retryable(err: &anyhow::Error) -> bool {
+pub(super) struct ModelReply {
+    pub text: String,
+    pub usage: UsageMetadata,
+    pub status: ReplyStatus,
+}
+
+pub(super) fn extract_reply(response: Response) -> ModelReply {
+    let text = extract_text(&response);
+    let status = safety::classify(&response, &text);
+    ModelReply { text, usage: response.usage_metadata, status }
+}
+
+/// Why a model call failed; everything but `Fatal` is worth retrying.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ErrorClass {
+    RateLimited,
+    Timeout,
+    Unavailable,
+    Transport,
+    Fatal,
+}
+
+impl ErrorClass {
+    pub const ALL: [ErrorClass; 5] =
+        [Self::RateLimited, Self::Timeout, Self::Unavailable, Self::Transport, Self::Fatal];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::RateLimited => "rate_limited",
+            Self::Timeout => "timeout",
+            Self::Unavailable => "unavailable",
+            Self::Transport => "transport",
+            Self::Fatal => "fatal",
+        }
+    }
+}
+
+pub(super) fn error_class(err: &anyhow::Error) -> ErrorClass {
     let msg = err.to_string().to_ascii_lowercase();
-    [
-        "resource exhausted",
-        "rate limit",
-        "too many requests",
-        "deadline exceeded",
-        "service unavailable",
-        "transport failure",
-        "connection reset",
-        "temporarily unavailable",
-        "timeout",
-        "unavailable",
-        "429",
-        "500",
-        "503",
-    ]
-    .iter()
-    .any(|marker| msg.contains(marker))
+    let any = |markers: &[&str]| {
+        markers
+            .iter()
+            .any(|marker| msg.contains(marker))
+    };
+    if any(&["resource exhausted", "rate limit", "too many requests", "429"]) {
+        ErrorClass::RateLimited
+    } else if any(&["deadline exceeded", "timeout"]) {
+        ErrorClass::Timeout
+    } else if any(&["transport failure", "connection reset"]) {
+        ErrorClass::Transport
+    } else if any(&["unavailable", "500", "503"]) {
+        ErrorClass::Unavailable
+    } else {
+        ErrorClass::Fatal
+    }
+}
+
+pub(super) fn is_retryable(err: &anyhow::Error) -> bool {
+    error_class(err) != ErrorClass::Fatal
 }
 
 pub(super) async fn generate_with_retries<C>(
@@ -166,6 +143,7 @@ pub(super) async fn generate_with_retries<C>(
     model: &str,
     system: &str,
     contents: Vec<Content>,
+    options: &RequestOptions,
 ) -> anyhow::Result<Response>
 where
     C: AiClient + Send + Sync,
@@ -174,7 +152,7 @@ where
 
     loop {
         match client
-            .generate(model, system, contents.clone())
+            .generate(model, system, contents.clone(), options)
             .await
         {
             Ok(resp) => return Ok(resp),
@@ -198,20 +176,50 @@ where
 pub(super) async fn summarize<C>(
     client: &C,
     scheduler: &AiScheduler,
+    redis: &Pool,
     history: &mut VecDeque<ChatEntry>,
     user_name: &str,
+    usage: &UsageScope<'_>,
 ) -> anyhow::Result<String>
 where
     C: AiClient + Send + Sync,
 {
-    let mut contents = parse_history(&*history, user_name).await;
+    let mut contents = parse_history(redis, &*history, user_name).await;
     contents.push(Content::from(Part::text(SYSTEM)));
 
-    for spec in SUMMARY_MODELS {
+    let reply = generate_with_summary_models(
+        client,
+        scheduler,
+        AiOperation::Summary,
+        SYSTEM,
+        contents,
+        &RequestOptions::default(),
+        usage,
+    )
+    .await?;
+    Ok(reply.text)
+}
+
+/// Runs `contents` through the summary model chain, falling back to the next
+/// model when one is saturated or fails.
+pub(super) async fn generate_with_summary_models<C>(
+    client: &C,
+    scheduler: &AiScheduler,
+    operation: AiOperation,
+    system: &str,
+    contents: Vec<Content>,
+    options: &RequestOptions,
+    usage: &UsageScope<'_>,
+) -> anyhow::Result<ModelReply>
+where
+    C: AiClient + Send + Sync,
+{
+    let catalogue = catalogue::current();
+    for (index, spec) in catalogue.summary.iter().enumerate() {
         let guard = scheduler
             .acquire(
-                spec.name,
-                AiOperation::Summary,
+                &spec.name,
+                operation,
                 AdmissionConfig { rpm_limit: spec.rpm_limit, queue_timeout: spec.queue_timeout },
             )
             .await;
@@ -219,18 +227,36 @@ where
         let guard = match guard {
             Ok(guard) => guard,
             Err(e) => {
-                tracing::warn!(model = spec.name, error = %e, "summary model queue failed");
+                tracing::warn!(model = %spec.name, error = %e, "summary model queue failed");
                 continue;
             }
         };
 
-        match generate_with_retries(client, spec.name, SYSTEM, contents.clone()).await {
-            Ok(resp) => return Ok(extract_text(resp)),
+        match generate_with_retries(
+            client,
+            &spec.name,
+            system,
+            contents.clone(),
+            options,
+        )
+        .await
+        {
+            Ok(resp) => {
+                let reply = extract_reply(resp);
+                usage
+                    .record_success(&spec.name, operation, &reply.usage, index > 0)
+                    .await;
+                return Ok(reply);
+            }
             Err(e) => {
-                if is_retryable(&e) {
+                let class = error_class(&e);
+                usage
+                    .record_error(&spec.name, operation, class)
+                    .await;
+                if class != ErrorClass::Fatal {
                     guard.cool_down(spec.cooldown).await;
                 }
-                tracing::warn!(model = spec.name, error = %e, "summary model failed");
+                tracing::warn!(model = %spec.name, error = %e, "summary model failed");
             }
         }
     }
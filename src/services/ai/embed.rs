@@ -1,11 +1,24 @@
 use twilight_model::channel::message::Embed;
-use twilight_util::builder::embed::EmbedBuilder;
+use twilight_util::builder::embed::{EmbedBuilder, EmbedFieldBuilder, EmbedFooterBuilder};
 
-use super::AiService;
+use super::access::AccessDenied;
+use super::attachments::{SkipReason, SkippedAttachment};
+use super::quota::{QuotaBucket, QuotaExceeded, QuotaKind, QuotaScope, QuotaStatus};
+use super::safety::{ReplyStatus, WithheldReason};
+use super::usage::UsageReport;
+use super::{AiReply, AiService};
 
 const COLOR: u32 = 0x5865F2;
 
 impl AiService {
+    /// Whether a message with `embeds` is one of the bot's AI answers rather
+    /// than a notice: answer embeds are untitled text in the AI colour.
+    pub fn is_answer(embeds: &[Embed]) -> bool {
+        embeds.first().is_some_and(|embed| {
+            embed.color == Some(COLOR) && embed.title.is_none() && embed.description.is_some()
+        })
+    }
+
     pub fn ai_embeds(text: &str) -> anyhow::Result<Vec<Embed>> {
         const LIMIT: usize = 1024;
         let mut embeds = Vec::new();
@@ -40,6 +53,90 @@ impl AiService {
         Ok::<_, anyhow::Error>(embeds)
     }
 
+    /// Embeds for a finished chat turn: the reply text, a notice when it was
+    /// cut off, the knowledge-base sources it cites, or only an explanation
+    /// when nothing could be shown. Attachments the model never saw are
+    /// listed last either way.
+    pub fn reply_embeds(reply: &AiReply) -> anyhow::Result<Vec<Embed>> {
+        let mut embeds = match reply.status {
+            ReplyStatus::Withheld(reason) => {
+                let mut embeds = vec![Self::withheld_embed(reason)?];
+                if !reply.skipped.is_empty() {
+                    embeds.push(skipped_embed(&reply.skipped)?);
+                }
+                return Ok(embeds);
+            }
+            ReplyStatus::Truncated => {
+                let mut embeds = Self::ai_embeds(&reply.text)?;
+                let notice = EmbedBuilder::new()
+                    .color(COLOR)
+                    .description("✂️ คำตอบถูกตัดเพราะยาวเกินขีดจำกัดของโมเดล")
+                    .validate()?
+                    .build();
+                embeds.push(notice);
+                embeds
+            }
+            ReplyStatus::Complete => {
+                let mut embeds = Self::ai_embeds(&reply.text)?;
+                if reply.cached
+                    && let Some(last) = embeds.last_mut()
+                {
+                    last.footer = Some(EmbedFooterBuilder::new("⚡ คำตอบจากแคช").build());
+                }
+                embeds
+            }
+        };
+        if !reply.sources.is_empty() {
+            let sources = EmbedBuilder::new()
+                .color(COLOR)
+                .title("📚 แหล่งข้อมูลจากเซิร์ฟเวอร์")
+                .description(reply.sources.join("\n"))
+                .validate()?
+                .build();
+            embeds.push(sources);
+        }
+        if !reply.skipped.is_empty() {
+            embeds.push(skipped_embed(&reply.skipped)?);
+        }
+        Ok(embeds)
+    }
+
+    pub fn withheld_embed(reason: WithheldReason) -> anyhow::Result<Embed> {
+        let description = match reason {
+            WithheldReason::PromptBlocked => {
+                "ข้อความของคุณถูกตัวกรองความปลอดภัยปฏิเสธ กรุณาปรับข้อความแล้วลองใหม่"
+            }
+            WithheldReason::Safety => "คำตอบถูกระงับโดยตัวกรองความปลอดภัยของเซิร์ฟเวอร์นี้",
+            WithheldReason::Recitation => "คำตอบถูกระงับเพราะคัดลอกเนื้อหาที่มีลิขสิทธิ์มากเกินไป",
+            WithheldReason::Prohibited => "คำตอบถูกระงับเพราะมีเนื้อหาต้องห้ามหรือข้อมูลส่วนบุคคล",
+            WithheldReason::NoCandidates | WithheldReason::Empty => {
+                "โมเดลไม่ได้ส่งคำตอบกลับมา กรุณาลองใหม่อีกครั้ง"
+            }
+        };
+        let embed = EmbedBuilder::new()
+            .color(COLOR)
+            .title("🛡️ ไม่สามารถแสดงคำตอบได้")
+            .description(description)
+            .validate()?
+            .build();
+        Ok(embed)
+    }
+
+    pub fn access_denied_embed(denied: AccessDenied) -> anyhow::Result<Embed> {
+        let description = match denied {
+            AccessDenied::Disabled => "ผู้ดูแลปิดการใช้งาน AI ในเซิร์ฟเวอร์นี้",
+            AccessDenied::Channel => "ใช้ AI ในช่องนี้ไม่ได้ กรุณาลองในช่องอื่น",
+            AccessDenied::DirectMessages => "เซิร์ฟเวอร์ของคุณไม่อนุญาตให้ใช้ AI ในข้อความส่วนตัว",
+        };
+        let embed = EmbedBuilder::new()
+            .color(COLOR)
+            .title("🔒 ใช้ AI ที่นี่ไม่ได้")
+            .description(description)
+            .validate()?
+            .build();
+        Ok(embed)
+    }
+
     pub fn rate_limit_embed(wait: u64) -> anyhow::Result<Embed> {
         let embed = EmbedBuilder::new()
             .color(COLOR)
@@ -59,6 +156,154 @@ impl AiService {
             .build();
         Ok(embed)
     }
+
+    pub fn quota_exceeded_embed(exceeded: &QuotaExceeded) -> anyhow::Result<Embed> {
+        let kind = match exceeded.kind {
+            QuotaKind::Requests => "จำนวนคำขอ",
+            QuotaKind::Tokens => "โทเคน",
+        };
+        let who = match exceeded.scope {
+            QuotaScope::User => "คุณ",
+            QuotaScope::Guild => "เซิร์ฟเวอร์นี้",
+        };
+        let embed = EmbedBuilder::new()
+            .color(COLOR)
+            .title("🚫 ใช้ AI ครบโควตาวันนี้แล้ว")
+            .description(format!(
+                "{who}ใช้{kind}ครบ {} แล้ว\nโควตาจะรีเซ็ต <t:{}:R>",
+                exceeded.limit,
+                exceeded.resets_at.timestamp(),
+            ))
+            .validate()?
+            .build();
+        Ok(embed)
+    }
+
+    pub fn quota_embed(status: &QuotaStatus) -> anyhow::Result<Embed> {
+        let mut builder = EmbedBuilder::new()
+            .color(COLOR)
+            .title("📊 โควตา AI วันนี้")
+            .field(EmbedFieldBuilder::new("คุณ", bucket_field(&status.user)).inline());
+        if let Some(guild) = &status.guild {
+            builder =
+                builder.field(EmbedFieldBuilder::new("เซิร์ฟเวอร์", bucket_field(guild)).inline());
+        }
+        let embed = builder
+            .field(EmbedFieldBuilder::new(
+                "รีเซ็ต",
+                format!("<t:{}:R>", status.resets_at.timestamp()),
+            ))
+            .validate()?
+            .build();
+        Ok(embed)
+    }
+
+    pub fn usage_embed(report: &UsageReport) -> anyhow::Result<Embed> {
+        let title = format!("📈 การใช้งาน AI {} วันล่าสุด", report.days);
+        if report.requests == 0 && report.errors.is_empty() {
+            let embed = EmbedBuilder::new()
+                .color(COLOR)
+                .title(title)
+                .description("ยังไม่มีการใช้งาน AI ในช่วงนี้")
+                .validate()?
+                .build();
+            return Ok(embed);
+        }
+
+        let totals = format!(
+            "คำขอ: {}\nโทเคนขาเข้า: {}\nโทเคนขาออก: {}",
+            report.requests, report.prompt_tokens, report.output_tokens
+        );
+        let users = if report.top_users.is_empty() {
+            "-".to_string()
+        } else {
+            report
+                .top_users
+                .iter()
+                .enumerate()
+                .map(|(i, user)| {
+                    format!(
+                        "{}. <@{}> — {} คำขอ, {} โทเคน",
+                        i + 1,
+                        user.user_id,
+                        user.requests,
+                        user.tokens
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        let mut models: Vec<String> = report
+            .models
+            .iter()
+            .map(|(model, requests)| format!("`{model}`: {requests}"))
+            .collect();
+        models.push(format!(
+            "สลับไปโมเดลสำรอง: {:.1}%",
+            report.fallback_rate() * 100.0
+        ));
+        let errors = if report.errors.is_empty() {
+            "ไม่มี".to_string()
+        } else {
+            report
+                .errors
+                .iter()
+                .map(|(class, count)| format!("`{}`: {count}", class.as_str()))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        let embed = EmbedBuilder::new()
+            .color(COLOR)
+            .title(title)
+            .field(EmbedFieldBuilder::new("รวม", totals).inline())
+            .field(EmbedFieldBuilder::new("โมเดล", models.join("\n")).inline())
+            .field(EmbedFieldBuilder::new("ข้อผิดพลาด", errors).inline())
+            .field(EmbedFieldBuilder::new("ผู้ใช้สูงสุด", users))
+            .validate()?
+            .build();
+        Ok(embed)
+    }
+}
+
+fn skipped_embed(skipped: &[SkippedAttachment]) -> anyhow::Result<Embed> {
+    let lines: Vec<String> = skipped
+        .iter()
+        .map(|s| {
+            let reason = match s.reason {
+                SkipReason::Unsupported => "ไม่รองรับไฟล์ชนิดนี้",
+                SkipReason::TooLarge => "ไฟล์ใหญ่เกินไป",
+                SkipReason::Unreadable => "อ่านเนื้อหาไม่ได้",
+            };
+            format!("`{}` — {reason}", s.filename.replace('`', "'"))
+        })
+        .collect();
+    let embed = EmbedBuilder::new()
+        .color(COLOR)
+        .title("📎 ไฟล์แนบที่ AI ไม่ได้อ่าน")
+        .description(lines.join("\n"))
+        .validate()?
+        .build();
+    Ok(embed)
+}
+
+fn usage_line(label: &str, used: u64, limit: Option<u64>) -> String {
+    match limit {
+        Some(limit) => format!("{label}: {used}/{limit}"),
+        None => format!("{label}: {used} (ไม่จำกัด)"),
+    }
+}
+
+fn bucket_field(bucket: &QuotaBucket) -> String {
+    format!(
+        "{}\n{}",
+        usage_line(
+            "คำขอ",
+            bucket.usage.requests,
+            bucket.limits.requests
+        ),
+        usage_line("โทเคน", bucket.usage.tokens, bucket.limits.tokens),
+    )
 }
 
 #[cfg(test)]
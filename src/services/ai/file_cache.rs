@@ -0,0 +1,104 @@
+use chrono::{DateTime, Duration, Utc};
+use deadpool_redis::Pool;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use twilight_model::channel::Attachment;
+
+use crate::{
+    configs::CACHE_PREFIX,
+    dbs::redis::{redis_get, redis_set_ex},
+};
+
+/// Gemini deletes uploaded files this long after the upload.
+const FILE_TTL_SECS: i64 = 48 * 3600;
+/// Stop handing out a URI a little before Gemini deletes the file so a
+/// request built from the cache never races the deletion.
+const REUSE_MARGIN_SECS: i64 = 600;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct CachedFile {
+    pub uri: String,
+    pub mime_type: String,
+    #[serde(with = "chrono::serde::ts_seconds")]
+    pub expires_at: DateTime<Utc>,
+}
+
+impl CachedFile {
+    pub(crate) fn uploaded_now(uri: String, mime_type: String) -> Self {
+        Self { uri, mime_type, expires_at: Utc::now() + Duration::seconds(FILE_TTL_SECS) }
+    }
+
+    fn is_usable(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at - Duration::seconds(REUSE_MARGIN_SECS) > now
+    }
+}
+
+fn digest(parts: &[&[u8]]) -> String {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Discord signs attachment URLs with a query string that changes between
+/// fetches, so only the path and the size identify the file.
+fn source_key(attachment: &Attachment) -> String {
+    let path = attachment
+        .url
+        .split('?')
+        .next()
+        .unwrap_or_default();
+    let hash = digest(&[path.as_bytes(), &attachment.size.to_be_bytes()]);
+    format!("{CACHE_PREFIX}:ai:file:source:{hash}")
+}
+
+fn uri_key(uri: &str) -> String {
+    format!(
+        "{CACHE_PREFIX}:ai:file:uri:{}",
+        digest(&[uri.as_bytes()])
+    )
+}
+
+fn record_lookup(stage: &'static str, hit: bool) {
+    metrics::counter!(
+        "ai_file_cache_lookups_total",
+        "stage" => stage,
+        "result" => if hit { "hit" } else { "miss" },
+    )
+    .increment(1);
+}
+
+async fn lookup(pool: &Pool, key: &str) -> Option<CachedFile> {
+    redis_get::<CachedFile>(pool, key)
+        .await
+        .filter(|file| file.is_usable(Utc::now()))
+}
+
+/// Returns the Gemini file already uploaded for this Discord attachment.
+pub(crate) async fn lookup_attachment(pool: &Pool, attachment: &Attachment) -> Option<CachedFile> {
+    let file = lookup(pool, &source_key(attachment)).await;
+    record_lookup("upload", file.is_some());
+    file
+}
+
+/// Returns the live cache record for a URI stored in chat history.
+pub(crate) async fn lookup_uri(pool: &Pool, uri: &str) -> Option<CachedFile> {
+    let file = lookup(pool, &uri_key(uri)).await;
+    record_lookup("history", file.is_some());
+    file
+}
+
+pub(crate) async fn remember(pool: &Pool, attachment: &Attachment, file: &CachedFile) {
+    let ttl = (file.expires_at - Utc::now()).num_seconds() - REUSE_MARGIN_SECS;
+    if ttl <= 0 {
+        return;
+    }
+    let ttl = ttl as usize;
+    redis_set_ex(pool, &source_key(attachment), file, ttl).await;
+    redis_set_ex(pool, &uri_key(&file.uri), file, ttl).await;
+}
+
+#[cfg(test)]
+#[path = "tests/file_cache.rs"]
+mod tests;
@@ -19,7 +19,13 @@ impl Client {
     }
 
     pub fn generative_model(&self, model: &str) -> GenerativeModel<'_> {
-        GenerativeModel { client: self, model: model.to_string(), system_instruction: None }
+        GenerativeModel {
+            client: self,
+            model: model.to_string(),
+            system_instruction: None,
+            safety_settings: Vec::new(),
+            generation_config: None,
+        }
     }
 }
 
@@ -27,6 +33,8 @@ pub struct GenerativeModel<'a> {
     client: &'a Client,
     model: String,
     system_instruction: Option<String>,
+    safety_settings: Vec<SafetySetting>,
+    generation_config: Option<GenerationConfig>,
 }
 
 impl GenerativeModel<'_> {
@@ -35,6 +43,16 @@ impl GenerativeModel<'_> {
         self
     }
 
+    pub fn with_safety_settings(mut self, settings: &[SafetySetting]) -> Self {
+        self.safety_settings = settings.to_vec();
+        self
+    }
+
+    pub fn with_generation_config(mut self, config: GenerationConfig) -> Self {
+        self.generation_config = (config != GenerationConfig::default()).then_some(config);
+        self
+    }
+
     pub async fn generate_content(self, contents: Vec<Content>) -> anyhow::Result<Response> {
         let url = format!(
             "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent",
@@ -51,6 +69,8 @@ impl GenerativeModel<'_> {
                 .system_instruction
                 .map(|text| Content { role: "user".to_string(), parts: vec![Part::text(text)] }),
             contents,
+            safety_settings: self.safety_settings,
+            generation_config: self.generation_config,
         };
         request = request.json(&body);
 
@@ -138,11 +158,125 @@ pub struct FileData {
 pub struct Response {
     #[serde(default)]
     pub candidates: Vec<Candidate>,
+    #[serde(rename = "usageMetadata", default)]
+    pub usage_metadata: UsageMetadata,
+    #[serde(rename = "promptFeedback")]
+    pub prompt_feedback: Option<PromptFeedback>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct Candidate {
     pub content: Option<Content>,
+    #[serde(rename = "finishReason")]
+    pub finish_reason: Option<FinishReason>,
+    #[serde(rename = "safetyRatings", default)]
+    pub safety_ratings: Vec<SafetyRating>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum FinishReason {
+    Stop,
+    MaxTokens,
+    Safety,
+    Recitation,
+    Language,
+    Blocklist,
+    ProhibitedContent,
+    Spii,
+    ImageSafety,
+    MalformedFunctionCall,
+    Other,
+    #[serde(other)]
+    Unspecified,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PromptFeedback {
+    #[serde(rename = "blockReason")]
+    pub block_reason: Option<BlockReason>,
+    #[serde(rename = "safetyRatings", default)]
+    pub safety_ratings: Vec<SafetyRating>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum BlockReason {
+    Safety,
+    Blocklist,
+    ProhibitedContent,
+    ImageSafety,
+    Other,
+    #[serde(other)]
+    Unspecified,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SafetyRating {
+    pub category: HarmCategory,
+    #[serde(default)]
+    pub probability: String,
+    #[serde(default)]
+    pub blocked: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum HarmCategory {
+    #[serde(rename = "HARM_CATEGORY_HARASSMENT")]
+    Harassment,
+    #[serde(rename = "HARM_CATEGORY_HATE_SPEECH")]
+    HateSpeech,
+    #[serde(rename = "HARM_CATEGORY_SEXUALLY_EXPLICIT")]
+    SexuallyExplicit,
+    #[serde(rename = "HARM_CATEGORY_DANGEROUS_CONTENT")]
+    DangerousContent,
+    #[serde(rename = "HARM_CATEGORY_CIVIC_INTEGRITY")]
+    CivicIntegrity,
+    #[serde(rename = "HARM_CATEGORY_UNSPECIFIED", other)]
+    Unspecified,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum HarmBlockThreshold {
+    BlockLowAndAbove,
+    BlockMediumAndAbove,
+    BlockOnlyHigh,
+    BlockNone,
+    Off,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+pub struct SafetySetting {
+    pub category: HarmCategory,
+    pub threshold: HarmBlockThreshold,
+}
+
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct GenerationConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    #[serde(
+        rename = "responseMimeType",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub response_mime_type: Option<String>,
+    /// OpenAPI-style schema the reply must follow; needs a JSON mime type.
+    #[serde(
+        rename = "responseSchema",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub response_schema: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+pub struct UsageMetadata {
+    #[serde(rename = "promptTokenCount", default)]
+    pub prompt_token_count: u64,
+    #[serde(rename = "candidatesTokenCount", default)]
+    pub candidates_token_count: u64,
+    #[serde(rename = "totalTokenCount", default)]
+    pub total_token_count: u64,
 }
 
 #[derive(Serialize)]
@@ -153,6 +287,16 @@ struct GenerateContentRequest {
     )]
     system_instruction: Option<Content>,
     contents: Vec<Content>,
+    #[serde(
+        rename = "safetySettings",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    safety_settings: Vec<SafetySetting>,
+    #[serde(
+        rename = "generationConfig",
+        skip_serializing_if = "Option::is_none"
+    )]
+    generation_config: Option<GenerationConfig>,
 }
 
 #[derive(Deserialize)]
@@ -1,4 +1,3 @@
-use chrono::Utc;
 use deadpool_redis::Pool;
 use mongodb::bson::{doc, to_bson};
 use twilight_model::id::{Id, marker::UserMarker};
@@ -9,7 +8,10 @@ use crate::{
     context::Context,
     dbs::mongo::models::ai_prompt::AiPrompt,
     dbs::redis::{redis_delete, redis_get, redis_set, redis_set_ex},
-    services::ai::genai::{Content, Part},
+    services::ai::{
+        file_cache,
+        genai::{Content, Part},
+    },
 };
 use std::{collections::VecDeque, sync::Arc};
 
@@ -76,56 +78,43 @@ pub(crate) async fn purge_prompt_cache(_pool: &Pool, user_id: u64) {
     redis_delete(_pool, &key).await;
 }
 
+/// Rebuilds Gemini contents from stored history. Attachments are only
+/// replayed while their upload is still cached; expired ones are dropped.
 pub(crate) async fn parse_history<'a>(
+    pool: &Pool,
     history: impl IntoIterator<Item = &'a ChatEntry>,
     user_name: &str,
 ) -> Vec<Content> {
-    let now = Utc::now();
-    history
-        .into_iter()
-        .map(|c| {
-            let mut parts = vec![Part::text(&c.text)];
-            let expired = now - c.created_at > chrono::Duration::hours(24);
-            for url in &c.attachments {
-                if expired {
-                    let label =
-                        format!("Attachment from {user_name} is expired and no longer accessible.");
-                    parts.push(Part::text(&label));
-                } else {
-                    let label = format!("Attachment from {user_name}:");
-                    parts.push(Part::text(&label));
-                    parts.push(Part::file_data("", url));
-                }
-            }
-            if let Some(ref_text) = &c.ref_text {
-                let owner = c
-                    .ref_author
-                    .as_deref()
-                    .unwrap_or("another user");
-                let label = format!("In reply to {owner}:");
-                parts.push(Part::text(&label));
-                parts.push(Part::text(ref_text));
-            }
-            if let Some(ref_urls) = &c.ref_attachments {
-                let owner = c
-                    .ref_author
-                    .as_deref()
-                    .unwrap_or("another user");
-                for url in ref_urls {
-                    if expired {
-                        let label =
-                            format!("Attachment from {owner} is expired and no longer accessible.");
-                        parts.push(Part::text(&label));
-                    } else {
-                        let label = format!("Attachment from {owner}:");
-                        parts.push(Part::text(&label));
-                        parts.push(Part::file_data("", url));
-                    }
-                }
-            }
-            Content { role: c.role.clone(), parts }
-        })
-        .collect()
+    let mut contents = Vec::new();
+    for c in history {
+        let mut parts = vec![Part::text(&c.text)];
+        append_cached_files(pool, &mut parts, &c.attachments, user_name).await;
+        let owner = c
+            .ref_author
+            .as_deref()
+            .unwrap_or("another user");
+        if let Some(ref_text) = &c.ref_text {
+            let label = format!("In reply to {owner}:");
+            parts.push(Part::text(&label));
+            parts.push(Part::text(ref_text));
+        }
+        if let Some(ref_urls) = &c.ref_attachments {
+            append_cached_files(pool, &mut parts, ref_urls, owner).await;
+        }
+        parts.extend(c.documents.iter().map(Part::text));
+        contents.push(Content { role: c.role.clone(), parts });
+    }
+    contents
+}
+
+async fn append_cached_files(pool: &Pool, parts: &mut Vec<Part>, uris: &[String], owner: &str) {
+    for uri in uris {
+        if let Some(file) = file_cache::lookup_uri(pool, uri).await {
+            let label = format!("Attachment from {owner}:");
+            parts.push(Part::text(&label));
+            parts.push(Part::file_data(file.mime_type, file.uri));
+        }
+    }
 }
 
 #[cfg(test)]
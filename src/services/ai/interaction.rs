@@ -1,11 +1,21 @@
 use super::{
-    KEEP_RECENT, MAX_HISTORY, attachments,
-    client::{self, MODELS, extract_text},
+    KEEP_RECENT, MAX_HISTORY,
+    attachments::{self, AppendedAttachments},
+    catalogue::ModelSpec,
+    client::{self, ModelReply, RequestOptions, extract_reply},
+    knowledge::{self, KnowledgeHit},
+    memory,
     models::ChatEntry,
+    persona,
     scheduler::{AdmissionConfig, AiOperation, AiScheduler},
+    usage::UsageScope,
+};
+use crate::services::ai::history::parse_history;
+use crate::{
+    context::Context,
+    dbs::mongo::models::{ai_memory::MemoryFact, ai_persona::AiPersona},
+    services::ai::history,
 };
-use crate::{configs::google::GOOGLE_CONFIGS, services::ai::history::parse_history};
-use crate::{context::Context, services::ai::history};
 use once_cell::sync::Lazy;
 use std::collections::{HashSet, VecDeque};
 use std::sync::Arc;
@@ -19,8 +29,15 @@ use crate::services::ai::genai::{Content, Part};
 
 pub(super) struct BuildRequest<'a> {
     pub ctx: &'a Arc<Context>,
+    pub base_prompt: &'a str,
+    pub persona: Option<&'a AiPersona>,
+    pub knowledge: &'a [KnowledgeHit],
+    pub memory: &'a [MemoryFact],
     pub prompt: Option<String>,
     pub user_name: &'a str,
+    /// The reply may be cached for other users, so the system prompt must
+    /// not name this one.
+    pub shared: bool,
     pub message: &'a str,
     pub history: &'a VecDeque<ChatEntry>,
     pub attachments: Vec<Attachment>,
@@ -35,6 +52,7 @@ pub(super) async fn spawn_summary<C>(
     client: Arc<C>,
     scheduler: AiScheduler,
     ctx: &Arc<Context>,
+    guild_id: Option<u64>,
     user_id: Id<UserMarker>,
     user_name: &str,
     history: &VecDeque<ChatEntry>,
@@ -61,8 +79,10 @@ pub(super) async fn spawn_summary<C>(
         if let Ok(summary) = client::summarize(
             client_clone.as_ref(),
             &scheduler,
+            &ctx.redis,
             &mut history,
             &user_name,
+            &UsageScope::new(&ctx, guild_id, Some(uid)),
         )
         .await
         {
@@ -85,6 +105,17 @@ pub(super) async fn spawn_summary<C>(
                 None,
             ));
             history::store_history(&ctx.redis, user_id, &latest).await;
+
+            memory::extract(
+                client_clone.as_ref(),
+                &scheduler,
+                &ctx,
+                guild_id,
+                uid,
+                &user_name,
+                &history,
+            )
+            .await;
         }
 
         RUNNING.write().await.remove(&uid);
@@ -93,11 +124,21 @@ pub(super) async fn spawn_summary<C>(
 
 pub(super) async fn build_request(
     args: BuildRequest<'_>,
-) -> anyhow::Result<(String, Vec<Content>, Vec<String>, Vec<String>)> {
+) -> anyhow::Result<(
+    String,
+    Vec<Content>,
+    AppendedAttachments,
+    AppendedAttachments,
+)> {
     let BuildRequest {
         ctx,
+        base_prompt,
+        persona,
+        knowledge,
+        memory,
         prompt,
         user_name,
+        shared,
         message,
         history,
         attachments,
@@ -106,23 +147,36 @@ pub(super) async fn build_request(
         ref_author,
     } = args;
 
-    let mut system = format!(
-        "{}\nYou are chatting with {user_name}",
-        GOOGLE_CONFIGS.base_prompt
+    let mut system = persona::compose_system(
+        base_prompt,
+        (!shared).then_some(user_name),
+        persona,
+        prompt.as_deref(),
     );
-    if let Some(p) = prompt {
-        system.push_str("\n\nUser instructions:\n");
-        system.push_str(&p);
+    if let Some(section) = memory::system_section(user_name, memory) {
+        system.push_str("\n\n");
+        system.push_str(&section);
+    }
+    if let Some(section) = knowledge::system_section(knowledge) {
+        system.push_str("\n\n");
+        system.push_str(&section);
     }
 
-    let mut contents = parse_history(history, user_name).await;
+    let mut contents = parse_history(&ctx.redis, history, user_name).await;
 
     let mut parts = vec![Part::text(message)];
-    let attachment_urls =
-        attachments::append_attachments(&ctx.reqwest, &mut parts, attachments, user_name).await?;
+    let own = attachments::append_attachments(
+        &ctx.reqwest,
+        &ctx.redis,
+        &mut parts,
+        attachments,
+        user_name,
+    )
+    .await?;
     let ref_owner = ref_author.unwrap_or("referenced user");
-    let ref_attachment_urls = attachments::append_attachments(
+    let referenced = attachments::append_attachments(
         &ctx.reqwest,
+        &ctx.redis,
         &mut parts,
         ref_attachments,
         ref_owner,
@@ -131,27 +185,27 @@ pub(super) async fn build_request(
 
     contents.push(Content::from(parts));
 
-    Ok((
-        system,
-        contents,
-        attachment_urls,
-        ref_attachment_urls,
-    ))
+    Ok((system, contents, own, referenced))
 }
 
-pub(super) async fn process_response<C>(
+/// Tries `models` in order, returning the first reply with the name of the
+/// model that gave it.
+pub(super) async fn process_response<'m, C>(
     client: &C,
     scheduler: &AiScheduler,
+    models: &'m [ModelSpec],
     system: &str,
     contents: Vec<Content>,
-) -> anyhow::Result<String>
+    options: &RequestOptions,
+    usage: &UsageScope<'_>,
+) -> anyhow::Result<(ModelReply, &'m str)>
 where
     C: client::AiClient + Send + Sync,
 {
-    for spec in MODELS {
+    for (index, spec) in models.iter().enumerate() {
         let guard = match scheduler
             .acquire(
-                spec.name,
+                &spec.name,
                 AiOperation::Chat,
                 AdmissionConfig { rpm_limit: spec.rpm_limit, queue_timeout: spec.queue_timeout },
             )
@@ -159,18 +213,42 @@ where
         {
             Ok(guard) => guard,
             Err(e) => {
-                tracing::warn!(model = spec.name, error = %e, "model queue failed");
+                tracing::warn!(model = %spec.name, error = %e, "model queue failed");
                 continue;
             }
         };
 
-        match client::generate_with_retries(client, spec.name, system, contents.clone()).await {
-            Ok(r) => return Ok(extract_text(r)),
+        match client::generate_with_retries(
+            client,
+            &spec.name,
+            system,
+            contents.clone(),
+            options,
+        )
+        .await
+        {
+            Ok(r) => {
+                let reply = extract_reply(r);
+                reply.status.record();
+                usage
+                    .record_success(
+                        &spec.name,
+                        AiOperation::Chat,
+                        &reply.usage,
+                        index > 0,
+                    )
+                    .await;
+                return Ok((reply, spec.name.as_ref()));
+            }
             Err(e) => {
-                if client::is_retryable(&e) {
+                let class = client::error_class(&e);
+                usage
+                    .record_error(&spec.name, AiOperation::Chat, class)
+                    .await;
+                if class != client::ErrorClass::Fatal {
                     guard.cool_down(spec.cooldown).await;
                 }
-                tracing::warn!(model = spec.name, error = %e, "model failed");
+                tracing::warn!(model = %spec.name, error = %e, "model failed");
             }
         };
     }
@@ -0,0 +1,329 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, LazyLock, Mutex},
+};
+
+use chrono::Utc;
+use deadpool_redis::Pool;
+use futures::StreamExt;
+use mongodb::bson::{doc, to_bson};
+use twilight_model::application::command::{CommandOptionChoice, CommandOptionChoiceValue};
+
+use super::{bm25::Bm25Index, channel_summary::chunk_lines};
+use crate::{
+    configs::CACHE_PREFIX,
+    context::Context,
+    dbs::{
+        mongo::models::ai_knowledge::AiKnowledgeDocument,
+        redis::{redis_delete, redis_get, redis_set_ex},
+    },
+    utils::ascii::ascii_contains_icase,
+};
+
+/// Discord shows at most 25 autocomplete choices.
+pub const MAX_DOCUMENTS: usize = 25;
+pub const MAX_DOCUMENT_CHARS: usize = 20_000;
+pub const MAX_TITLE_CHARS: usize = 100;
+/// Thai text is three bytes per character in UTF-8.
+pub const MAX_ATTACHMENT_BYTES: u64 = (MAX_DOCUMENT_CHARS * 3) as u64;
+/// Small enough that four chunks add about a thousand tokens to the prompt.
+const CHUNK_CHARS: usize = 1_000;
+const TOP_CHUNKS: usize = 4;
+const CACHE_TTL: usize = 3600;
+
+const SECTION_HEADER: &str = "Knowledge base excerpts from this server follow. Use them when they answer the question and cite each one you rely on as [n]. If they do not cover the question, say so instead of guessing.";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddOutcome {
+    Created { chunks: usize },
+    Replaced { chunks: usize },
+    Empty,
+    TooLong,
+    LimitReached,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KnowledgeHit {
+    pub title: String,
+    /// 1-based position of the chunk inside its document.
+    pub part: usize,
+    pub text: String,
+}
+
+fn cache_key(guild_id: u64) -> String {
+    format!("{CACHE_PREFIX}:ai:kb:{guild_id}")
+}
+
+pub(crate) fn normalize_title(raw: &str) -> Option<String> {
+    let title = raw
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!title.is_empty() && title.chars().count() <= MAX_TITLE_CHARS).then_some(title)
+}
+
+/// Whether an uploaded file looks like plain text or Markdown.
+pub(crate) fn is_text_attachment(filename: &str, content_type: Option<&str>) -> bool {
+    if let Some(content_type) = content_type
+        && (content_type.starts_with("text/plain") || content_type.starts_with("text/markdown"))
+    {
+        return true;
+    }
+    let lower = filename.to_ascii_lowercase();
+    lower.ends_with(".txt") || lower.ends_with(".md")
+}
+
+/// Splits a document into chunks of whole lines, dropping blank ones.
+pub(crate) fn chunk_document(content: &str) -> Vec<String> {
+    let lines: Vec<String> = content
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.trim().is_empty())
+        .map(str::to_string)
+        .collect();
+    chunk_lines(&lines, CHUNK_CHARS)
+}
+
+/// Every chunk of a guild's documents with their BM25 index, kept between
+/// messages so retrieval does not re-index the knowledge base each time.
+pub(crate) struct GuildIndex {
+    /// `(title, added_at)` of the indexed documents; a different list means
+    /// the knowledge base changed under the cached index.
+    version: Vec<(String, i64)>,
+    chunks: Vec<KnowledgeHit>,
+    index: Bm25Index,
+}
+
+impl GuildIndex {
+    /// The title is indexed with each chunk so a document's name matches too.
+    pub(crate) fn build(documents: &[AiKnowledgeDocument]) -> Self {
+        let chunks: Vec<KnowledgeHit> = documents
+            .iter()
+            .flat_map(|document| {
+                document
+                    .chunks
+                    .iter()
+                    .enumerate()
+                    .map(|(i, chunk)| KnowledgeHit {
+                        title: document.title.clone(),
+                        part: i + 1,
+                        text: chunk.clone(),
+                    })
+            })
+            .collect();
+        let texts: Vec<String> = chunks
+            .iter()
+            .map(|hit| format!("{}\n{}", hit.title, hit.text))
+            .collect();
+        let index = Bm25Index::new(texts.iter().map(String::as_str));
+        Self { version: version(documents), chunks, index }
+    }
+
+    /// The best `limit` chunks for `query`.
+    pub(crate) fn search(&self, query: &str, limit: usize) -> Vec<KnowledgeHit> {
+        self.index
+            .search(query, limit)
+            .into_iter()
+            .map(|(i, _)| self.chunks[i].clone())
+            .collect()
+    }
+}
+
+fn version(documents: &[AiKnowledgeDocument]) -> Vec<(String, i64)> {
+    documents
+        .iter()
+        .map(|document| (document.title.clone(), document.added_at))
+        .collect()
+}
+
+static INDEXES: LazyLock<Mutex<HashMap<u64, Arc<GuildIndex>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// The cached index for `documents`, rebuilt when they changed.
+fn guild_index(guild_id: u64, documents: &[AiKnowledgeDocument]) -> Arc<GuildIndex> {
+    let current = version(documents);
+    if let Some(index) = INDEXES
+        .lock()
+        .expect("ai knowledge index map poisoned")
+        .get(&guild_id)
+        .filter(|index| index.version == current)
+    {
+        return index.clone();
+    }
+    let index = Arc::new(GuildIndex::build(documents));
+    INDEXES
+        .lock()
+        .expect("ai knowledge index map poisoned")
+        .insert(guild_id, index.clone());
+    index
+}
+
+/// The system-instruction block listing `hits` as numbered excerpts.
+pub(crate) fn system_section(hits: &[KnowledgeHit]) -> Option<String> {
+    if hits.is_empty() {
+        return None;
+    }
+    let mut section = SECTION_HEADER.to_string();
+    for (i, hit) in hits.iter().enumerate() {
+        section.push_str(&format!(
+            "\n\n[{}] {} (part {}):\n{}",
+            i + 1,
+            hit.title,
+            hit.part,
+            hit.text
+        ));
+    }
+    Some(section)
+}
+
+/// Source labels for the excerpts the reply actually cites as `[n]`, in
+/// excerpt order.
+pub(crate) fn cited_sources(reply: &str, hits: &[KnowledgeHit]) -> Vec<String> {
+    hits.iter()
+        .enumerate()
+        .filter(|(i, _)| reply.contains(&format!("[{}]", i + 1)))
+        .map(|(i, hit)| format!("[{}] {} (ส่วนที่ {})", i + 1, hit.title, hit.part))
+        .collect()
+}
+
+pub(crate) async fn list(ctx: &Context, guild_id: u64) -> Vec<AiKnowledgeDocument> {
+    let key = cache_key(guild_id);
+    if let Some(documents) = redis_get::<Vec<AiKnowledgeDocument>>(&ctx.redis, &key).await {
+        return documents;
+    }
+
+    let mut documents = Vec::new();
+    match ctx
+        .mongo
+        .ai_knowledge
+        .find(doc! {"guild_id": guild_id as i64})
+        .await
+    {
+        Ok(mut cursor) => {
+            while let Some(Ok(document)) = cursor.next().await {
+                documents.push(document);
+            }
+        }
+        Err(e) => {
+            tracing::warn!(guild_id, error = %e, "failed to load ai knowledge base");
+            return documents;
+        }
+    }
+    documents.sort_by(|a, b| a.title.cmp(&b.title));
+
+    redis_set_ex(&ctx.redis, &key, &documents, CACHE_TTL).await;
+    documents
+}
+
+/// Stores `content` under `title`, replacing any document with that title.
+pub(crate) async fn add(
+    ctx: &Context,
+    guild_id: u64,
+    title: &str,
+    content: &str,
+    added_by: u64,
+) -> anyhow::Result<AddOutcome> {
+    if content.chars().count() > MAX_DOCUMENT_CHARS {
+        return Ok(AddOutcome::TooLong);
+    }
+    let chunks = chunk_document(content);
+    if chunks.is_empty() {
+        return Ok(AddOutcome::Empty);
+    }
+
+    let existing = list(ctx, guild_id).await;
+    let replaced = existing
+        .iter()
+        .any(|d| d.title == title);
+    if !replaced && existing.len() >= MAX_DOCUMENTS {
+        return Ok(AddOutcome::LimitReached);
+    }
+
+    let count = chunks.len();
+    let document = AiKnowledgeDocument {
+        id: None,
+        guild_id,
+        title: title.to_string(),
+        chunks,
+        added_by,
+        added_at: Utc::now().timestamp(),
+    };
+    ctx.mongo
+        .ai_knowledge
+        .update_one(
+            doc! {"guild_id": guild_id as i64, "title": title},
+            doc! {"$set": to_bson(&document)?},
+        )
+        .upsert(true)
+        .await?;
+
+    purge_cache(&ctx.redis, guild_id).await;
+    Ok(if replaced {
+        AddOutcome::Replaced { chunks: count }
+    } else {
+        AddOutcome::Created { chunks: count }
+    })
+}
+
+/// Returns `false` when the guild has no document with that title.
+pub(crate) async fn remove(ctx: &Context, guild_id: u64, title: &str) -> anyhow::Result<bool> {
+    if !list(ctx, guild_id)
+        .await
+        .iter()
+        .any(|d| d.title == title)
+    {
+        return Ok(false);
+    }
+    ctx.mongo
+        .ai_knowledge
+        .delete_one(doc! {"guild_id": guild_id as i64, "title": title})
+        .await?;
+    purge_cache(&ctx.redis, guild_id).await;
+    Ok(true)
+}
+
+/// The chunks of the guild's knowledge base that best match `query`.
+pub(crate) async fn retrieve(ctx: &Context, guild_id: u64, query: &str) -> Vec<KnowledgeHit> {
+    let documents = list(ctx, guild_id).await;
+    if documents.is_empty() {
+        return Vec::new();
+    }
+    let hits = guild_index(guild_id, &documents).search(query, TOP_CHUNKS);
+    metrics::counter!(
+        "ai_knowledge_retrievals_total",
+        "result" => if hits.is_empty() { "miss" } else { "hit" },
+    )
+    .increment(1);
+    hits
+}
+
+/// Autocomplete choices for document titles containing `input`.
+pub(crate) async fn document_choices(
+    ctx: &Context,
+    guild_id: u64,
+    input: &str,
+) -> Vec<CommandOptionChoice> {
+    list(ctx, guild_id)
+        .await
+        .into_iter()
+        .filter(|document| ascii_contains_icase(&document.title, input.trim()))
+        .take(MAX_DOCUMENTS)
+        .map(|document| CommandOptionChoice {
+            name: document.title.clone(),
+            value: CommandOptionChoiceValue::String(document.title),
+            name_localizations: None,
+        })
+        .collect()
+}
+
+pub(crate) async fn purge_cache(pool: &Pool, guild_id: u64) {
+    INDEXES
+        .lock()
+        .expect("ai knowledge index map poisoned")
+        .remove(&guild_id);
+    redis_delete(pool, &cache_key(guild_id)).await;
+}
+
+#[cfg(test)]
+#[path = "tests/knowledge.rs"]
+mod tests;
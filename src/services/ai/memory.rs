@@ -0,0 +1,252 @@
+use chrono::Utc;
+use deadpool_redis::Pool;
+use mongodb::bson::{doc, to_bson};
+use serde::Deserialize;
+use std::collections::VecDeque;
+use twilight_model::application::command::{CommandOptionChoice, CommandOptionChoiceValue};
+
+use super::{
+    client::{self, AiClient},
+    genai::{Content, Part},
+    history::parse_history,
+    models::ChatEntry,
+    scheduler::{AiOperation, AiScheduler},
+    usage::UsageScope,
+};
+use crate::{
+    configs::CACHE_PREFIX,
+    context::Context,
+    dbs::{
+        mongo::models::ai_memory::{AiMemory, MemoryFact},
+        redis::{redis_delete, redis_get, redis_set_ex},
+    },
+    utils::ascii::ascii_contains_icase,
+};
+
+/// Also the autocomplete cap for `/ai memory forget`.
+pub const MAX_FACTS: usize = 25;
+pub const MAX_KEY_CHARS: usize = 32;
+pub const MAX_VALUE_CHARS: usize = 200;
+const CACHE_TTL: usize = 3600;
+
+const EXTRACT_SYSTEM: &str = "You keep long-term memory about one Discord user. From the conversation, list durable facts about the user worth remembering across conversations, such as in-game name (ign), main frame, clan, timezone, language or preferences. Skip temporary details and facts about other people. Reply with JSON only, shaped as {\"facts\":[{\"key\":\"ign\",\"value\":\"...\"}]}. Use short lowercase keys and reuse a known key when that fact changed. Reply with {\"facts\":[]} when there is nothing new.";
+
+#[derive(Deserialize)]
+struct Extracted {
+    #[serde(default)]
+    facts: Vec<MemoryFact>,
+}
+
+fn cache_key(user_id: u64) -> String {
+    format!("{CACHE_PREFIX}:ai:memory:{user_id}")
+}
+
+/// Lowercases and collapses whitespace; `None` when empty or too long.
+pub(crate) fn normalize_key(raw: &str) -> Option<String> {
+    let key = raw
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    (!key.is_empty() && key.chars().count() <= MAX_KEY_CHARS).then_some(key)
+}
+
+/// Reads the extraction reply, tolerating code fences or prose around the
+/// JSON object. Facts with invalid keys or empty values are dropped.
+pub(crate) fn parse_facts(reply: &str) -> Vec<MemoryFact> {
+    let (Some(start), Some(end)) = (reply.find('{'), reply.rfind('}')) else {
+        return Vec::new();
+    };
+    if end < start {
+        return Vec::new();
+    }
+    let Ok(extracted) = serde_json::from_str::<Extracted>(&reply[start..=end]) else {
+        return Vec::new();
+    };
+    extracted
+        .facts
+        .into_iter()
+        .filter_map(|fact| {
+            let key = normalize_key(&fact.key)?;
+            let value: String = fact
+                .value
+                .trim()
+                .chars()
+                .take(MAX_VALUE_CHARS)
+                .collect();
+            (!value.is_empty()).then_some(MemoryFact { key, value })
+        })
+        .collect()
+}
+
+/// Applies `updates` to `existing`. A changed or new fact moves to the end,
+/// and the oldest facts are dropped past `MAX_FACTS`.
+pub(crate) fn merge(existing: &[MemoryFact], updates: Vec<MemoryFact>) -> Vec<MemoryFact> {
+    let mut facts = existing.to_vec();
+    for update in updates {
+        facts.retain(|fact| fact.key != update.key);
+        facts.push(update);
+    }
+    let overflow = facts.len().saturating_sub(MAX_FACTS);
+    facts.drain(..overflow);
+    facts
+}
+
+/// The system-instruction block listing what is remembered about the user.
+pub(crate) fn system_section(user_name: &str, facts: &[MemoryFact]) -> Option<String> {
+    if facts.is_empty() {
+        return None;
+    }
+    let mut section = format!(
+        "What you remember about {user_name} from earlier conversations (may be outdated):"
+    );
+    for fact in facts {
+        section.push_str(&format!("\n- {}: {}", fact.key, fact.value));
+    }
+    Some(section)
+}
+
+pub(crate) async fn load(ctx: &Context, user_id: u64) -> Vec<MemoryFact> {
+    let key = cache_key(user_id);
+    if let Some(facts) = redis_get::<Vec<MemoryFact>>(&ctx.redis, &key).await {
+        return facts;
+    }
+
+    let facts = match ctx
+        .mongo
+        .ai_memories
+        .find_one(doc! {"user_id": user_id as i64})
+        .await
+    {
+        Ok(memory) => memory
+            .map(|m| m.facts)
+            .unwrap_or_default(),
+        Err(e) => {
+            tracing::warn!(user_id, error = %e, "failed to load ai memory");
+            return Vec::new();
+        }
+    };
+
+    redis_set_ex(&ctx.redis, &key, &facts, CACHE_TTL).await;
+    facts
+}
+
+pub(crate) async fn store(
+    ctx: &Context,
+    user_id: u64,
+    facts: Vec<MemoryFact>,
+) -> anyhow::Result<()> {
+    let memory = AiMemory { id: None, user_id, facts, updated_at: Utc::now().timestamp() };
+    ctx.mongo
+        .ai_memories
+        .update_one(
+            doc! {"user_id": user_id as i64},
+            doc! {"$set": to_bson(&memory)?},
+        )
+        .upsert(true)
+        .await?;
+    purge_cache(&ctx.redis, user_id).await;
+    Ok(())
+}
+
+/// Returns `false` when nothing is remembered under `key`.
+pub(crate) async fn forget(ctx: &Context, user_id: u64, key: &str) -> anyhow::Result<bool> {
+    let Some(key) = normalize_key(key) else {
+        return Ok(false);
+    };
+    let mut facts = load(ctx, user_id).await;
+    let before = facts.len();
+    facts.retain(|fact| fact.key != key);
+    if facts.len() == before {
+        return Ok(false);
+    }
+    store(ctx, user_id, facts).await?;
+    Ok(true)
+}
+
+/// Asks the summary models for durable facts in `history` and merges them
+/// into the user's memory. Runs alongside history summarization.
+pub(crate) async fn extract<C>(
+    client: &C,
+    scheduler: &AiScheduler,
+    ctx: &Context,
+    guild_id: Option<u64>,
+    user_id: u64,
+    user_name: &str,
+    history: &VecDeque<ChatEntry>,
+) where
+    C: AiClient + Send + Sync,
+{
+    let existing = load(ctx, user_id).await;
+    let mut system = EXTRACT_SYSTEM.to_string();
+    if let Some(known) = system_section(user_name, &existing) {
+        system.push_str("\n\nKnown facts:\n");
+        system.push_str(&known);
+    }
+    let mut contents = parse_history(&ctx.redis, history, user_name).await;
+    contents.push(Content::from(Part::text(
+        "Extract the durable facts about the user as JSON now.",
+    )));
+
+    let result = match client::generate_with_summary_models(
+        client,
+        scheduler,
+        AiOperation::Memory,
+        &system,
+        contents,
+        &client::RequestOptions::default(),
+        &UsageScope::new(ctx, guild_id, Some(user_id)),
+    )
+    .await
+    {
+        Ok(reply) => {
+            let updates = parse_facts(&reply.text);
+            if updates.is_empty() {
+                "empty"
+            } else {
+                match store(ctx, user_id, merge(&existing, updates)).await {
+                    Ok(()) => "updated",
+                    Err(e) => {
+                        tracing::warn!(user_id, error = %e, "failed to store ai memory");
+                        "failed"
+                    }
+                }
+            }
+        }
+        Err(e) => {
+            tracing::warn!(user_id, error = %e, "ai memory extraction failed");
+            "failed"
+        }
+    };
+    metrics::counter!("ai_memory_extractions_total", "result" => result).increment(1);
+}
+
+/// Autocomplete choices for remembered keys containing `input`.
+pub(crate) async fn fact_choices(
+    ctx: &Context,
+    user_id: u64,
+    input: &str,
+) -> Vec<CommandOptionChoice> {
+    load(ctx, user_id)
+        .await
+        .into_iter()
+        .filter(|fact| ascii_contains_icase(&fact.key, input.trim()))
+        .take(MAX_FACTS)
+        .map(|fact| CommandOptionChoice {
+            name: format!("{}: {}", fact.key, fact.value)
+                .chars()
+                .take(100)
+                .collect(),
+            value: CommandOptionChoiceValue::String(fact.key),
+            name_localizations: None,
+        })
+        .collect()
+}
+
+pub(crate) async fn purge_cache(pool: &Pool, user_id: u64) {
+    redis_delete(pool, &cache_key(user_id)).await;
+}
+
+#[cfg(test)]
+#[path = "tests/memory.rs"]
+mod tests;
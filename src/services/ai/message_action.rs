@@ -0,0 +1,66 @@
+/// Message context-menu commands offered by the AI slice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageAction {
+    Ask,
+    Translate,
+}
+
+pub const ASK_COMMAND: &str = "Ask AI";
+pub const TRANSLATE_COMMAND: &str = "Translate";
+
+impl MessageAction {
+    pub const ALL: [MessageAction; 2] = [MessageAction::Ask, MessageAction::Translate];
+
+    pub fn command_name(self) -> &'static str {
+        match self {
+            MessageAction::Ask => ASK_COMMAND,
+            MessageAction::Translate => TRANSLATE_COMMAND,
+        }
+    }
+
+    pub fn thai_name(self) -> &'static str {
+        match self {
+            MessageAction::Ask => "ถาม AI",
+            MessageAction::Translate => "แปลภาษา",
+        }
+    }
+
+    pub fn from_command_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|action| action.command_name() == name)
+    }
+
+    fn instruction(self) -> &'static str {
+        match self {
+            MessageAction::Ask => {
+                "Explain the message below and answer it if it asks something. Reply in the language it is written in."
+            }
+            MessageAction::Translate => {
+                "Translate the message below. If it is in Thai, translate it into English; otherwise translate it into Thai. Reply with the translation only."
+            }
+        }
+    }
+
+    /// The user turn sent for `content`, the text of the targeted message.
+    /// The text goes in the prompt itself because the model only sees the
+    /// referenced message's attachments, not its text.
+    pub fn input(self, author: &str, content: &str) -> String {
+        let content = content.trim();
+        if content.is_empty() {
+            format!(
+                "{}\n\nThe message from {author} has only attachments.",
+                self.instruction()
+            )
+        } else {
+            format!(
+                "{}\n\nMessage from {author}:\n{content}",
+                self.instruction()
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/message_action.rs"]
+mod tests;
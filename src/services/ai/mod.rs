@@ -1,28 +1,54 @@
 use deadpool_redis::Pool;
-use twilight_model::{channel::Attachment, id::Id, id::marker::UserMarker};
+use twilight_model::{
+    channel::Attachment,
+    id::Id,
+    id::marker::{GuildMarker, UserMarker},
+};
 
+use self::attachments::SkippedAttachment;
+use self::channel_summary::{ChannelSummary, ChannelSummaryRequest};
+use self::genai::GenerationConfig;
 use self::history as hist;
 use self::models::ChatEntry;
+use self::quota::{QuotaExceeded, QuotaStatus};
+use self::safety::ReplyStatus;
 use self::scheduler::AiScheduler;
 mod interaction;
+use crate::configs::google::GOOGLE_CONFIGS;
 use crate::context::Context;
 use crate::services::ai::rate_limit::check_rate_limit;
+use crate::services::guild_settings::GuildSettingsService;
 use std::collections::VecDeque;
 use std::sync::Arc;
 
+pub mod access;
 pub mod attachments;
+pub(crate) mod bm25;
+pub mod catalogue;
+pub mod channel_summary;
 pub mod client;
 pub mod embed;
+pub(crate) mod file_cache;
 pub mod genai;
 pub(crate) mod history;
+pub mod knowledge;
+pub mod memory;
+pub mod message_action;
 pub mod models;
+pub mod moderation;
+pub mod persona;
+pub mod quota;
 mod rate_limit;
+pub(crate) mod response_cache;
+pub mod safety;
 pub mod scheduler;
+pub mod usage;
 
 const MAX_HISTORY: usize = 20;
 const KEEP_RECENT: usize = 2;
 
 pub struct AiInteraction<'a> {
+    pub guild_id: Option<Id<GuildMarker>>,
     pub user_id: Id<UserMarker>,
     pub user_name: &'a str,
     pub message: &'a str,
@@ -30,6 +56,20 @@ pub struct AiInteraction<'a> {
     pub ref_text: Option<&'a str>,
     pub ref_attachments: Vec<Attachment>,
     pub ref_author: Option<&'a str>,
+    /// Whether the turn is written into the user's chat history; one-off
+    /// context-menu actions are not.
+    pub remember: bool,
+}
+
+pub struct AiReply {
+    pub text: String,
+    pub status: ReplyStatus,
+    /// Knowledge-base excerpts the reply cites, ready to show as-is.
+    pub sources: Vec<String>,
+    /// Attachments that could not be passed to the model.
+    pub skipped: Vec<SkippedAttachment>,
+    /// Served from the response cache instead of the model.
+    pub cached: bool,
 }
 
 pub struct AiService;
@@ -63,11 +103,12 @@ impl AiService {
         ctx: &Arc<Context>,
         client: &Arc<C>,
         interaction: AiInteraction<'_>,
-    ) -> anyhow::Result<String>
+    ) -> anyhow::Result<AiReply>
     where
         C: client::AiClient + Send + Sync + 'static,
     {
         let AiInteraction {
+            guild_id,
             user_id,
             user_name,
             message,
@@ -75,6 +116,7 @@ impl AiService {
             ref_text,
             ref_attachments,
             ref_author,
+            remember,
         } = interaction;
 
         let mut history = Self::load_history(&ctx.redis, user_id).await;
@@ -83,6 +125,7 @@ impl AiService {
             Arc::clone(client),
             ctx.ai_scheduler.clone(),
             ctx,
+            guild_id.map(|id| id.get()),
             user_id,
             user_name,
             &history,
@@ -90,11 +133,106 @@ impl AiService {
         .await;
 
         let prompt = Self::get_prompt(ctx, user_id).await;
+        let settings = match guild_id {
+            Some(guild_id) => Some(GuildSettingsService::get(ctx, guild_id.get()).await),
+            None => None,
+        };
+        let persona = match guild_id {
+            Some(guild_id) => persona::selected(ctx, guild_id.get(), user_id.get()).await,
+            None => None,
+        };
+        let memory = memory::load(ctx, user_id.get()).await;
+        let knowledge = match guild_id {
+            Some(guild_id) => knowledge::retrieve(ctx, guild_id.get(), message).await,
+            None => Vec::new(),
+        };
+        let base_prompt = settings
+            .as_ref()
+            .and_then(|s| s.ai_base_prompt.as_deref())
+            .unwrap_or(&GOOGLE_CONFIGS.base_prompt);
+        let options = client::RequestOptions {
+            safety: settings
+                .as_ref()
+                .map(|s| safety::safety_settings(&s.ai_safety))
+                .unwrap_or_default(),
+            generation: GenerationConfig {
+                temperature: persona
+                    .as_ref()
+                    .and_then(|p| p.temperature),
+                ..GenerationConfig::default()
+            },
+        };
+        let models = persona::model_chain(&catalogue::current().chat, persona.as_ref());
+
+        // Only questions that read the same for everyone are shared: nothing
+        // from the user's conversation, instructions, memory or files.
+        let cacheable = response_cache::enabled()
+            && history.is_empty()
+            && prompt.is_none()
+            && memory.is_empty()
+            && attachments.is_empty()
+            && ref_text.is_none()
+            && ref_attachments.is_empty();
+        let cache_key = cacheable
+            .then(|| response_cache::normalize_question(message))
+            .flatten()
+            .map(|question| {
+                response_cache::cache_key(
+                    &question,
+                    &response_cache::CacheScope {
+                        base_prompt,
+                        persona: persona.as_ref(),
+                        knowledge: &knowledge,
+                        options: &options,
+                        model: models
+                            .first()
+                            .map(|spec| spec.name.as_ref())
+                            .unwrap_or_default(),
+                    },
+                )
+            });
+        if let Some(key) = &cache_key
+            && let Some(text) = response_cache::lookup(&ctx.redis, key).await
+        {
+            quota::record_usage(ctx, guild_id, user_id, 0).await;
+            if remember {
+                history.push_back(ChatEntry::new(
+                    "user".into(),
+                    message.to_owned(),
+                    Vec::new(),
+                    None,
+                    None,
+                    None,
+                ));
+                history.push_back(ChatEntry::new(
+                    "model".into(),
+                    text.clone(),
+                    Vec::new(),
+                    None,
+                    None,
+                    None,
+                ));
+                Self::store_history(&ctx.redis, user_id, &history).await;
+            }
+            let sources = knowledge::cited_sources(&text, &knowledge);
+            return Ok(AiReply {
+                text,
+                status: ReplyStatus::Complete,
+                sources,
+                skipped: Vec::new(),
+                cached: true,
+            });
+        }
 
         let args = interaction::BuildRequest {
             ctx,
+            base_prompt,
+            persona: persona.as_ref(),
+            knowledge: &knowledge,
+            memory: &memory,
             prompt,
             user_name,
+            shared: cache_key.is_some(),
             message,
             history: &history,
             attachments,
@@ -102,43 +240,150 @@ impl AiService {
             ref_attachments,
             ref_author,
         };
-        let (system, contents, attachment_urls, ref_attachment_urls) =
-            interaction::build_request(args).await?;
-
-        let text = interaction::process_response(
+        let (system, contents, own, referenced) = interaction::build_request(args).await?;
+        let skipped: Vec<SkippedAttachment> = own
+            .skipped
+            .into_iter()
+            .chain(referenced.skipped)
+            .collect();
+        let (reply, model) = interaction::process_response(
             client.as_ref(),
             &ctx.ai_scheduler,
+            &models,
             &system,
             contents,
+            &options,
+            &usage::UsageScope::new(
+                ctx,
+                guild_id.map(|id| id.get()),
+                Some(user_id.get()),
+            ),
         )
         .await?;
+        quota::record_usage(
+            ctx,
+            guild_id,
+            user_id,
+            reply.usage.total_token_count,
+        )
+        .await;
+        if let ReplyStatus::Withheld(reason) = reply.status {
+            tracing::info!(
+                user_id = user_id.get(),
+                ?reason,
+                "ai reply withheld"
+            );
+            return Ok(AiReply {
+                text: String::new(),
+                status: reply.status,
+                sources: Vec::new(),
+                skipped,
+                cached: false,
+            });
+        }
+        let text = reply.text;
+        // The key names the primary model, so a fallback's answer is not
+        // stored under it.
+        let primary = models
+            .first()
+            .is_some_and(|spec| spec.name.as_ref() == model);
+        if let Some(key) = &cache_key
+            && primary
+            && reply.status == ReplyStatus::Complete
+        {
+            response_cache::store(&ctx.redis, key, &text).await;
+        }
+
+        if remember {
+            let documents = own
+                .documents
+                .into_iter()
+                .chain(referenced.documents)
+                .collect();
+            history.push_back(
+                ChatEntry::new(
+                    "user".into(),
+                    message.to_owned(),
+                    own.uris,
+                    ref_text.map(|t| t.to_string()),
+                    if referenced.uris.is_empty() { None } else { Some(referenced.uris) },
+                    ref_author.map(|t| t.to_string()),
+                )
+                .with_documents(documents),
+            );
+            history.push_back(ChatEntry::new(
+                "model".into(),
+                text.clone(),
+                Vec::new(),
+                None,
+                None,
+                None,
+            ));
+            Self::store_history(&ctx.redis, user_id, &history).await;
+        }
 
-        history.push_back(ChatEntry::new(
-            "user".into(),
-            message.to_owned(),
-            attachment_urls,
-            ref_text.map(|t| t.to_string()),
-            if ref_attachment_urls.is_empty() { None } else { Some(ref_attachment_urls) },
-            ref_author.map(|t| t.to_string()),
-        ));
-        history.push_back(ChatEntry::new(
-            "model".into(),
-            text.clone(),
-            Vec::new(),
-            None,
-            None,
-            None,
-        ));
-        Self::store_history(&ctx.redis, user_id, &history).await;
-
-        Ok(text)
+        let sources = knowledge::cited_sources(&text, &knowledge);
+        Ok(AiReply { text, status: reply.status, sources, skipped, cached: false })
+    }
+
+    pub async fn summarize_channel<C>(
+        ctx: &Arc<Context>,
+        client: &Arc<C>,
+        user: Id<UserMarker>,
+        request: ChannelSummaryRequest,
+    ) -> anyhow::Result<Option<ChannelSummary>>
+    where
+        C: client::AiClient + Send + Sync,
+    {
+        let guild_id = request.guild_id;
+        let summary = channel_summary::summarize_channel(ctx, client.as_ref(), request).await?;
+        if let Some(summary) = &summary {
+            quota::record_usage(ctx, guild_id, user, summary.tokens).await;
+        }
+        Ok(summary)
     }
 
     pub async fn check_rate_limit(ctx: &Arc<Context>, user: Id<UserMarker>) -> Option<u64> {
         check_rate_limit(ctx, user).await
     }
 
-    pub fn scheduler() -> AiScheduler {
-        AiScheduler::new()
+    pub async fn check_quota(
+        ctx: &Arc<Context>,
+        guild_id: Option<Id<GuildMarker>>,
+        user: Id<UserMarker>,
+    ) -> Option<QuotaExceeded> {
+        quota::check_quota(ctx, guild_id, user).await
+    }
+
+    pub async fn quota_status(
+        ctx: &Arc<Context>,
+        guild_id: Option<Id<GuildMarker>>,
+        user: Id<UserMarker>,
+    ) -> QuotaStatus {
+        quota::quota_status(ctx, guild_id, user).await
+    }
+
+    pub async fn purge_persona_cache(pool: &Pool, guild_id: u64) {
+        persona::purge_cache(pool, guild_id).await;
+    }
+
+    pub async fn purge_persona_choice_cache(pool: &Pool, guild_id: u64, user_id: u64) {
+        persona::purge_choice_cache(pool, guild_id, user_id).await;
+    }
+
+    pub async fn purge_knowledge_cache(pool: &Pool, guild_id: u64) {
+        knowledge::purge_cache(pool, guild_id).await;
+    }
+
+    pub async fn purge_memory_cache(pool: &Pool, user_id: u64) {
+        memory::purge_cache(pool, user_id).await;
+    }
+
+    pub fn scheduler(redis: &Pool) -> AiScheduler {
+        if GOOGLE_CONFIGS.distributed_scheduler {
+            AiScheduler::with_redis(redis.clone())
+        } else {
+            AiScheduler::new()
+        }
     }
 }
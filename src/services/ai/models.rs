@@ -17,6 +17,9 @@ pub(crate) struct ChatEntry {
     pub ref_attachments: Option<Vec<String>>,
     #[serde(default)]
     pub ref_author: Option<String>,
+    /// Attachment text extracted locally, already labelled with its owner.
+    #[serde(default)]
+    pub documents: Vec<String>,
     #[serde(default = "utc_now", with = "chrono::serde::ts_seconds")]
     pub created_at: DateTime<Utc>,
 }
@@ -37,7 +40,13 @@ impl ChatEntry {
             ref_text,
             ref_attachments,
             ref_author,
+            documents: Vec::new(),
             created_at: Utc::now(),
         }
     }
+
+    pub fn with_documents(mut self, documents: Vec<String>) -> Self {
+        self.documents = documents;
+        self
+    }
 }
@@ -0,0 +1,296 @@
+use deadpool_redis::Pool;
+use futures::StreamExt;
+use mongodb::bson::{doc, to_bson};
+use twilight_model::application::command::{CommandOptionChoice, CommandOptionChoiceValue};
+
+use super::catalogue::ModelSpec;
+use crate::{
+    configs::CACHE_PREFIX,
+    context::Context,
+    dbs::{
+        mongo::models::ai_persona::{AiPersona, AiPersonaChoice},
+        redis::{redis_delete, redis_get, redis_set_ex},
+    },
+    utils::ascii::ascii_starts_with_icase,
+};
+
+/// Discord shows at most 25 autocomplete choices.
+pub const MAX_PERSONAS: usize = 25;
+pub const MAX_NAME_CHARS: usize = 32;
+const CACHE_TTL: usize = 3600;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveOutcome {
+    Created,
+    Updated,
+    LimitReached,
+}
+
+fn list_key(guild_id: u64) -> String {
+    format!("{CACHE_PREFIX}:ai:personas:{guild_id}")
+}
+
+fn choice_key(guild_id: u64, user_id: u64) -> String {
+    format!("{CACHE_PREFIX}:ai:persona-choice:{guild_id}:{user_id}")
+}
+
+/// Lowercases the name and turns spaces into dashes so `Code Reviewer` and
+/// `code-reviewer` are the same persona. Returns `None` for empty names,
+/// names that are too long, or names with anything but letters, digits, `-`
+/// and `_`.
+pub(crate) fn normalize_name(raw: &str) -> Option<String> {
+    let name: String = raw
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+        .to_lowercase();
+    let valid = !name.is_empty()
+        && name.chars().count() <= MAX_NAME_CHARS
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_');
+    valid.then_some(name)
+}
+
+/// Splits a comma-separated model list and checks every entry against the
+/// chat chain. Returns the first unknown name as the error.
+pub(crate) fn parse_models(raw: &str, chain: &[ModelSpec]) -> Result<Vec<String>, String> {
+    let mut models = Vec::new();
+    for name in raw
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+    {
+        if !chain
+            .iter()
+            .any(|spec| spec.name == name)
+        {
+            return Err(name.to_string());
+        }
+        if !models.iter().any(|m| m == name) {
+            models.push(name.to_string());
+        }
+    }
+    Ok(models)
+}
+
+/// The persona's allowed models in the persona's order. Falls back to the
+/// full chain when the persona allows everything or none of its models are
+/// still in the catalogue.
+pub(crate) fn model_chain(chain: &[ModelSpec], persona: Option<&AiPersona>) -> Vec<ModelSpec> {
+    let Some(persona) = persona.filter(|p| !p.models.is_empty()) else {
+        return chain.to_vec();
+    };
+    let allowed: Vec<ModelSpec> = persona
+        .models
+        .iter()
+        .filter_map(|name| {
+            chain
+                .iter()
+                .find(|spec| spec.name == name.as_str())
+                .cloned()
+        })
+        .collect();
+    if allowed.is_empty() {
+        tracing::warn!(
+            guild_id = persona.guild_id,
+            persona = %persona.name,
+            "persona models are no longer in the catalogue; using the full chat chain"
+        );
+        return chain.to_vec();
+    }
+    allowed
+}
+
+/// Layers the system prompt: guild (or global) base prompt, then the
+/// persona, then the user's own `/ai prompt` instructions. `user_name` is
+/// `None` for replies that may be cached and shared with other users.
+pub(crate) fn compose_system(
+    base: &str,
+    user_name: Option<&str>,
+    persona: Option<&AiPersona>,
+    user_prompt: Option<&str>,
+) -> String {
+    let mut system = base.to_string();
+    if let Some(user_name) = user_name {
+        system.push_str(&format!("\nYou are chatting with {user_name}"));
+    }
+    if let Some(persona) = persona {
+        system.push_str(&format!(
+            "\n\nPersona \"{}\":\n{}",
+            persona.name, persona.system_prompt
+        ));
+    }
+    if let Some(p) = user_prompt {
+        system.push_str("\n\nUser instructions:\n");
+        system.push_str(p);
+    }
+    system
+}
+
+pub(crate) async fn list(ctx: &Context, guild_id: u64) -> Vec<AiPersona> {
+    let key = list_key(guild_id);
+    if let Some(personas) = redis_get::<Vec<AiPersona>>(&ctx.redis, &key).await {
+        return personas;
+    }
+
+    let mut personas = Vec::new();
+    match ctx
+        .mongo
+        .ai_personas
+        .find(doc! {"guild_id": guild_id as i64})
+        .await
+    {
+        Ok(mut cursor) => {
+            while let Some(Ok(persona)) = cursor.next().await {
+                personas.push(persona);
+            }
+        }
+        Err(e) => {
+            tracing::warn!(guild_id, error = %e, "failed to load ai personas");
+            return personas;
+        }
+    }
+    personas.sort_by(|a, b| a.name.cmp(&b.name));
+
+    redis_set_ex(&ctx.redis, &key, &personas, CACHE_TTL).await;
+    personas
+}
+
+/// Autocomplete choices for the guild's personas whose name starts with
+/// `input`.
+pub(crate) async fn persona_choices(
+    ctx: &Context,
+    guild_id: u64,
+    input: &str,
+) -> Vec<CommandOptionChoice> {
+    list(ctx, guild_id)
+        .await
+        .into_iter()
+        .filter(|persona| ascii_starts_with_icase(&persona.name, input.trim()))
+        .take(MAX_PERSONAS)
+        .map(|persona| CommandOptionChoice {
+            name: persona.name.clone(),
+            value: CommandOptionChoiceValue::String(persona.name),
+            name_localizations: None,
+        })
+        .collect()
+}
+
+pub(crate) async fn find(ctx: &Context, guild_id: u64, name: &str) -> Option<AiPersona> {
+    list(ctx, guild_id)
+        .await
+        .into_iter()
+        .find(|persona| persona.name == name)
+}
+
+pub(crate) async fn save(ctx: &Context, persona: AiPersona) -> anyhow::Result<SaveOutcome> {
+    let existing = list(ctx, persona.guild_id).await;
+    let outcome = if existing
+        .iter()
+        .any(|p| p.name == persona.name)
+    {
+        SaveOutcome::Updated
+    } else if existing.len() >= MAX_PERSONAS {
+        return Ok(SaveOutcome::LimitReached);
+    } else {
+        SaveOutcome::Created
+    };
+
+    ctx.mongo
+        .ai_personas
+        .update_one(
+            doc! {"guild_id": persona.guild_id as i64, "name": persona.name.as_str()},
+            doc! {
+                "$set": {
+                    "guild_id": persona.guild_id as i64,
+                    "name": persona.name.as_str(),
+                    "system_prompt": persona.system_prompt.as_str(),
+                    "models": to_bson(&persona.models)?,
+                    "temperature": persona.temperature.map(f64::from),
+                }
+            },
+        )
+        .upsert(true)
+        .await?;
+
+    purge_cache(&ctx.redis, persona.guild_id).await;
+    Ok(outcome)
+}
+
+/// Returns `false` when the guild has no persona with that name.
+pub(crate) async fn remove(ctx: &Context, guild_id: u64, name: &str) -> anyhow::Result<bool> {
+    if find(ctx, guild_id, name)
+        .await
+        .is_none()
+    {
+        return Ok(false);
+    }
+    ctx.mongo
+        .ai_personas
+        .delete_one(doc! {"guild_id": guild_id as i64, "name": name})
+        .await?;
+    purge_cache(&ctx.redis, guild_id).await;
+    Ok(true)
+}
+
+/// The persona the user picked in this guild, if it still exists.
+pub(crate) async fn selected(ctx: &Context, guild_id: u64, user_id: u64) -> Option<AiPersona> {
+    let key = choice_key(guild_id, user_id);
+    let choice = match redis_get::<Option<String>>(&ctx.redis, &key).await {
+        Some(choice) => choice,
+        None => {
+            let choice = ctx
+                .mongo
+                .ai_persona_choices
+                .find_one(doc! {"guild_id": guild_id as i64, "user_id": user_id as i64})
+                .await
+                .ok()
+                .flatten()
+                .map(|choice| choice.persona);
+            redis_set_ex(&ctx.redis, &key, &choice, CACHE_TTL).await;
+            choice
+        }
+    };
+    find(ctx, guild_id, &choice?).await
+}
+
+/// `None` clears the choice so the user is back on the guild base prompt.
+pub(crate) async fn select(
+    ctx: &Context,
+    guild_id: u64,
+    user_id: u64,
+    persona: Option<&str>,
+) -> anyhow::Result<()> {
+    let filter = doc! {"guild_id": guild_id as i64, "user_id": user_id as i64};
+    match persona {
+        Some(name) => {
+            let choice = AiPersonaChoice { id: None, guild_id, user_id, persona: name.to_string() };
+            ctx.mongo
+                .ai_persona_choices
+                .update_one(filter, doc! {"$set": to_bson(&choice)?})
+                .upsert(true)
+                .await?;
+        }
+        None => {
+            ctx.mongo
+                .ai_persona_choices
+                .delete_one(filter)
+                .await?;
+        }
+    }
+    purge_choice_cache(&ctx.redis, guild_id, user_id).await;
+    Ok(())
+}
+
+pub(crate) async fn purge_cache(pool: &Pool, guild_id: u64) {
+    redis_delete(pool, &list_key(guild_id)).await;
+}
+
+pub(crate) async fn purge_choice_cache(pool: &Pool, guild_id: u64, user_id: u64) {
+    redis_delete(pool, &choice_key(guild_id, user_id)).await;
+}
+
+#[cfg(test)]
+#[path = "tests/persona.rs"]
+mod tests;
@@ -0,0 +1,247 @@
+use chrono::{DateTime, Days, NaiveTime, Utc};
+use twilight_model::id::{
+    Id,
+    marker::{GuildMarker, UserMarker},
+};
+
+use crate::{
+    configs::{CACHE_PREFIX, google::GOOGLE_CONFIGS},
+    context::Context,
+    dbs::{
+        mongo::models::guild_settings::AiQuotaSettings,
+        redis::{redis_get, redis_incr_by},
+    },
+    services::guild_settings::GuildSettingsService,
+};
+
+/// Counters outlive the day they belong to by this much so a late write near
+/// midnight cannot resurrect an already-expired key.
+const KEY_GRACE_SECS: i64 = 3600;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum QuotaScope {
+    User,
+    Guild,
+}
+
+impl QuotaScope {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::User => "user",
+            Self::Guild => "guild",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum QuotaKind {
+    Requests,
+    Tokens,
+}
+
+impl QuotaKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Requests => "requests",
+            Self::Tokens => "tokens",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct QuotaUsage {
+    pub requests: u64,
+    pub tokens: u64,
+}
+
+/// `None` means the budget is unlimited.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct QuotaLimits {
+    pub requests: Option<u64>,
+    pub tokens: Option<u64>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct QuotaBucket {
+    pub usage: QuotaUsage,
+    pub limits: QuotaLimits,
+}
+
+impl QuotaBucket {
+    fn exceeded(&self) -> Option<(QuotaKind, u64)> {
+        if let Some(limit) = self.limits.requests
+            && self.usage.requests >= limit
+        {
+            return Some((QuotaKind::Requests, limit));
+        }
+        if let Some(limit) = self.limits.tokens
+            && self.usage.tokens >= limit
+        {
+            return Some((QuotaKind::Tokens, limit));
+        }
+        None
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct QuotaStatus {
+    pub user: QuotaBucket,
+    pub guild: Option<QuotaBucket>,
+    pub resets_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct QuotaExceeded {
+    pub scope: QuotaScope,
+    pub kind: QuotaKind,
+    pub limit: u64,
+    pub resets_at: DateTime<Utc>,
+}
+
+pub(crate) fn next_reset(now: DateTime<Utc>) -> DateTime<Utc> {
+    now.date_naive()
+        .checked_add_days(Days::new(1))
+        .unwrap_or(now.date_naive())
+        .and_time(NaiveTime::MIN)
+        .and_utc()
+}
+
+fn non_zero(limit: Option<u64>) -> Option<u64> {
+    limit.filter(|v| *v > 0)
+}
+
+pub(crate) fn resolve_limits(settings: Option<&AiQuotaSettings>) -> (QuotaLimits, QuotaLimits) {
+    let settings = settings.copied().unwrap_or_default();
+    let user = QuotaLimits {
+        requests: non_zero(Some(
+            settings
+                .user_requests
+                .unwrap_or(GOOGLE_CONFIGS.daily_user_requests),
+        )),
+        tokens: non_zero(Some(
+            settings
+                .user_tokens
+                .unwrap_or(GOOGLE_CONFIGS.daily_user_tokens),
+        )),
+    };
+    let guild = QuotaLimits {
+        requests: non_zero(settings.guild_requests),
+        tokens: non_zero(settings.guild_tokens),
+    };
+    (user, guild)
+}
+
+fn counter_key(scope: QuotaScope, id: u64, kind: QuotaKind, now: DateTime<Utc>) -> String {
+    format!(
+        "{CACHE_PREFIX}:ai:quota:{}:{id}:{}:{}",
+        scope.as_str(),
+        now.format("%Y%m%d"),
+        kind.as_str(),
+    )
+}
+
+async fn load_usage(ctx: &Context, scope: QuotaScope, id: u64, now: DateTime<Utc>) -> QuotaUsage {
+    let requests = redis_get::<u64>(
+        &ctx.redis,
+        &counter_key(scope, id, QuotaKind::Requests, now),
+    )
+    .await
+    .unwrap_or_default();
+    let tokens = redis_get::<u64>(
+        &ctx.redis,
+        &counter_key(scope, id, QuotaKind::Tokens, now),
+    )
+    .await
+    .unwrap_or_default();
+    QuotaUsage { requests, tokens }
+}
+
+pub(crate) async fn quota_status(
+    ctx: &Context,
+    guild_id: Option<Id<GuildMarker>>,
+    user: Id<UserMarker>,
+) -> QuotaStatus {
+    let now = Utc::now();
+    let settings = match guild_id {
+        Some(guild_id) => Some(
+            GuildSettingsService::get(ctx, guild_id.get())
+                .await
+                .ai_quota,
+        ),
+        None => None,
+    };
+    let (user_limits, guild_limits) = resolve_limits(settings.as_ref());
+
+    let user = QuotaBucket {
+        usage: load_usage(ctx, QuotaScope::User, user.get(), now).await,
+        limits: user_limits,
+    };
+    let guild = match guild_id {
+        Some(guild_id) => Some(QuotaBucket {
+            usage: load_usage(ctx, QuotaScope::Guild, guild_id.get(), now).await,
+            limits: guild_limits,
+        }),
+        None => None,
+    };
+
+    QuotaStatus { user, guild, resets_at: next_reset(now) }
+}
+
+pub(crate) async fn check_quota(
+    ctx: &Context,
+    guild_id: Option<Id<GuildMarker>>,
+    user: Id<UserMarker>,
+) -> Option<QuotaExceeded> {
+    let status = quota_status(ctx, guild_id, user).await;
+    let buckets = [(QuotaScope::User, Some(status.user)), (QuotaScope::Guild, status.guild)];
+    buckets
+        .into_iter()
+        .find_map(|(scope, bucket)| {
+            let (kind, limit) = bucket?.exceeded()?;
+            metrics::counter!(
+                "ai_quota_rejections_total",
+                "scope" => scope.as_str(),
+                "kind" => kind.as_str(),
+            )
+            .increment(1);
+            Some(QuotaExceeded { scope, kind, limit, resets_at: status.resets_at })
+        })
+}
+
+pub(crate) async fn record_usage(
+    ctx: &Context,
+    guild_id: Option<Id<GuildMarker>>,
+    user: Id<UserMarker>,
+    tokens: u64,
+) {
+    let now = Utc::now();
+    let ttl = ((next_reset(now) - now).num_seconds() + KEY_GRACE_SECS).max(1) as usize;
+
+    let mut scopes = vec![(QuotaScope::User, user.get())];
+    if let Some(guild_id) = guild_id {
+        scopes.push((QuotaScope::Guild, guild_id.get()));
+    }
+
+    for (scope, id) in scopes {
+        redis_incr_by(
+            &ctx.redis,
+            &counter_key(scope, id, QuotaKind::Requests, now),
+            1,
+            ttl,
+        )
+        .await;
+        if tokens > 0 {
+            redis_incr_by(
+                &ctx.redis,
+                &counter_key(scope, id, QuotaKind::Tokens, now),
+                tokens,
+                ttl,
+            )
+            .await;
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/quota.rs"]
+mod tests;
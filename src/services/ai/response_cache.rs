@@ -0,0 +1,112 @@
+use deadpool_redis::Pool;
+use sha2::{Digest, Sha256};
+
+use super::{client::RequestOptions, knowledge::KnowledgeHit};
+use crate::{
+    configs::{CACHE_PREFIX, google::GOOGLE_CONFIGS},
+    dbs::{
+        mongo::models::ai_persona::AiPersona,
+        redis::{redis_get, redis_set_ex},
+    },
+};
+
+/// Longest question worth caching; long messages are rarely repeated verbatim.
+const MAX_QUESTION_CHARS: usize = 300;
+
+/// Everything besides the question that shapes a reply.
+pub(crate) struct CacheScope<'a> {
+    pub base_prompt: &'a str,
+    pub persona: Option<&'a AiPersona>,
+    pub knowledge: &'a [KnowledgeHit],
+    pub options: &'a RequestOptions,
+    pub model: &'a str,
+}
+
+pub(crate) fn enabled() -> bool {
+    GOOGLE_CONFIGS.response_cache_ttl_secs > 0
+}
+
+/// Lowercases, collapses whitespace and drops trailing punctuation so
+/// trivially different spellings of a question share an entry.
+pub(crate) fn normalize_question(question: &str) -> Option<String> {
+    let normalized = question
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    let normalized = normalized
+        .trim_end_matches(|c: char| c.is_ascii_punctuation() || c.is_whitespace())
+        .to_string();
+    (!normalized.is_empty() && normalized.chars().count() <= MAX_QUESTION_CHARS)
+        .then_some(normalized)
+}
+
+/// Strips dated or preview suffixes: `gemini-2.5-flash-preview-05-20` and
+/// `gemini-2.5-flash-001` both belong to `gemini-2.5-flash`.
+pub(crate) fn model_family(model: &str) -> &str {
+    let mut family = model;
+    while let Some((head, tail)) = family.rsplit_once('-') {
+        let suffix = tail.chars().all(|c| c.is_ascii_digit())
+            || matches!(tail, "preview" | "latest" | "exp");
+        if !suffix {
+            break;
+        }
+        family = head;
+    }
+    family
+}
+
+pub(crate) fn cache_key(question: &str, scope: &CacheScope<'_>) -> String {
+    let mut hasher = Sha256::new();
+    let mut field = |bytes: &[u8]| {
+        hasher.update((bytes.len() as u64).to_be_bytes());
+        hasher.update(bytes);
+    };
+    field(question.as_bytes());
+    field(scope.base_prompt.as_bytes());
+    if let Some(persona) = scope.persona {
+        field(persona.name.as_bytes());
+        field(persona.system_prompt.as_bytes());
+    }
+    for hit in scope.knowledge {
+        field(hit.title.as_bytes());
+        field(&hit.part.to_be_bytes());
+        field(hit.text.as_bytes());
+    }
+    field(
+        serde_json::to_string(&scope.options.safety)
+            .unwrap_or_default()
+            .as_bytes(),
+    );
+    field(
+        serde_json::to_string(&scope.options.generation)
+            .unwrap_or_default()
+            .as_bytes(),
+    );
+    field(model_family(scope.model).as_bytes());
+    format!(
+        "{CACHE_PREFIX}:ai:answer:{}",
+        hex::encode(hasher.finalize())
+    )
+}
+
+pub(crate) async fn lookup(pool: &Pool, key: &str) -> Option<String> {
+    let answer = redis_get::<String>(pool, key).await;
+    let result = if answer.is_some() { "hit" } else { "miss" };
+    metrics::counter!("ai_response_cache_total", "result" => result).increment(1);
+    answer
+}
+
+pub(crate) async fn store(pool: &Pool, key: &str, answer: &str) {
+    redis_set_ex(
+        pool,
+        key,
+        &answer,
+        GOOGLE_CONFIGS.response_cache_ttl_secs as usize,
+    )
+    .await;
+}
+
+#[cfg(test)]
+#[path = "tests/response_cache.rs"]
+mod tests;
@@ -0,0 +1,113 @@
+use crate::{
+    dbs::mongo::models::guild_settings::{AiSafetySettings, AiSafetyThreshold},
+    services::ai::genai::{
+        BlockReason, FinishReason, HarmBlockThreshold, HarmCategory, Response, SafetySetting,
+    },
+};
+
+/// How a chat reply ended. `Truncated` still carries usable text; `Withheld`
+/// means there is nothing to show the user.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReplyStatus {
+    Complete,
+    Truncated,
+    Withheld(WithheldReason),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WithheldReason {
+    /// The prompt itself was rejected before any candidate was generated.
+    PromptBlocked,
+    Safety,
+    Recitation,
+    /// Blocklist, prohibited content or personal data filters.
+    Prohibited,
+    NoCandidates,
+    Empty,
+}
+
+impl WithheldReason {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::PromptBlocked => "prompt_blocked",
+            Self::Safety => "safety",
+            Self::Recitation => "recitation",
+            Self::Prohibited => "prohibited",
+            Self::NoCandidates => "no_candidates",
+            Self::Empty => "empty",
+        }
+    }
+}
+
+impl ReplyStatus {
+    pub(super) fn record(self) {
+        let reason = match self {
+            Self::Complete => return,
+            Self::Truncated => "max_tokens",
+            Self::Withheld(reason) => reason.as_str(),
+        };
+        metrics::counter!("ai_reply_blocked_total", "reason" => reason).increment(1);
+    }
+}
+
+pub(super) fn classify(response: &Response, text: &str) -> ReplyStatus {
+    if response
+        .prompt_feedback
+        .as_ref()
+        .and_then(|feedback| feedback.block_reason)
+        .is_some_and(|reason| reason != BlockReason::Unspecified)
+    {
+        return ReplyStatus::Withheld(WithheldReason::PromptBlocked);
+    }
+
+    let Some(candidate) = response.candidates.first() else {
+        return ReplyStatus::Withheld(WithheldReason::NoCandidates);
+    };
+
+    match candidate.finish_reason {
+        Some(FinishReason::MaxTokens) => ReplyStatus::Truncated,
+        Some(FinishReason::Safety | FinishReason::ImageSafety) => {
+            ReplyStatus::Withheld(WithheldReason::Safety)
+        }
+        Some(FinishReason::Recitation) => ReplyStatus::Withheld(WithheldReason::Recitation),
+        Some(FinishReason::Blocklist | FinishReason::ProhibitedContent | FinishReason::Spii) => {
+            ReplyStatus::Withheld(WithheldReason::Prohibited)
+        }
+        _ if text.trim().is_empty() => ReplyStatus::Withheld(WithheldReason::Empty),
+        _ => ReplyStatus::Complete,
+    }
+}
+
+pub(crate) fn safety_settings(settings: &AiSafetySettings) -> Vec<SafetySetting> {
+    [
+        (HarmCategory::Harassment, settings.harassment),
+        (HarmCategory::HateSpeech, settings.hate_speech),
+        (
+            HarmCategory::SexuallyExplicit,
+            settings.sexually_explicit,
+        ),
+        (
+            HarmCategory::DangerousContent,
+            settings.dangerous_content,
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(category, threshold)| {
+        Some(SafetySetting { category, threshold: threshold_for(threshold?) })
+    })
+    .collect()
+}
+
+fn threshold_for(threshold: AiSafetyThreshold) -> HarmBlockThreshold {
+    match threshold {
+        AiSafetyThreshold::Off => HarmBlockThreshold::Off,
+        AiSafetyThreshold::BlockNone => HarmBlockThreshold::BlockNone,
+        AiSafetyThreshold::BlockOnlyHigh => HarmBlockThreshold::BlockOnlyHigh,
+        AiSafetyThreshold::BlockMediumAndAbove => HarmBlockThreshold::BlockMediumAndAbove,
+        AiSafetyThreshold::BlockLowAndAbove => HarmBlockThreshold::BlockLowAndAbove,
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/safety.rs"]
+mod tests;
@@ -1,22 +1,34 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
 use anyhow::anyhow;
+use deadpool_redis::Pool;
 use tokio::sync::Mutex as AsyncMutex;
 
+use crate::{
+    configs::CACHE_PREFIX,
+    dbs::redis::{redis_extend_cooldown, redis_window_admit},
+};
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum AiOperation {
     Chat,
     Summary,
+    ChannelSummary,
+    Memory,
+    Moderation,
 }
 
 impl AiOperation {
-    fn as_str(self) -> &'static str {
+    pub fn as_str(self) -> &'static str {
         match self {
             Self::Chat => "chat",
             Self::Summary => "summary",
+            Self::ChannelSummary => "channel_summary",
+            Self::Memory => "memory",
+            Self::Moderation => "moderation",
         }
     }
 }
@@ -34,12 +46,23 @@ pub struct AiScheduler {
 
 struct AiSchedulerInner {
     rpm_window: Duration,
-    models: Mutex<HashMap<&'static str, Arc<ModelQueue>>>,
+    backend: AdmissionBackend,
+    models: Mutex<HashMap<Arc<str>, Arc<ModelQueue>>>,
+}
+
+/// Where the per-model sliding window and cooldown live. `Redis` shares them
+/// between every bot instance pointed at the same Redis; the local queue state
+/// is still kept so admission can fall back to it when Redis is unreachable.
+enum AdmissionBackend {
+    Memory,
+    Redis(Pool),
 }
 
 struct ModelQueue {
     state: AsyncMutex<ModelState>,
     waiting: AtomicUsize,
+    /// Admitted requests whose [`ScheduleGuard`] is still alive.
+    in_flight: AtomicUsize,
 }
 
 #[derive(Default)]
@@ -59,19 +82,34 @@ impl AiScheduler {
         Self::with_window(Duration::from_secs(60))
     }
 
+    pub fn with_redis(redis: Pool) -> Self {
+        Self::with_backend(
+            Duration::from_secs(60),
+            AdmissionBackend::Redis(redis),
+        )
+    }
+
     pub(crate) fn with_window(rpm_window: Duration) -> Self {
+        Self::with_backend(rpm_window, AdmissionBackend::Memory)
+    }
+
+    fn with_backend(rpm_window: Duration, backend: AdmissionBackend) -> Self {
         Self {
-            inner: Arc::new(AiSchedulerInner { rpm_window, models: Mutex::new(HashMap::new()) }),
+            inner: Arc::new(AiSchedulerInner {
+                rpm_window,
+                backend,
+                models: Mutex::new(HashMap::new()),
+            }),
         }
     }
 
     pub async fn acquire(
         &self,
-        model: &'static str,
+        model: &str,
         operation: AiOperation,
         cfg: AdmissionConfig,
     ) -> anyhow::Result<ScheduleGuard> {
-        let queue = self.queue(model);
+        let (model, queue) = self.queue(model);
         let enqueued_at = Instant::now();
         let deadline = enqueued_at + cfg.queue_timeout;
 
@@ -79,26 +117,25 @@ impl AiScheduler {
             .waiting
             .fetch_add(1, Ordering::Relaxed)
             + 1;
-        metrics::gauge!("ai_scheduler_queue_depth", "model" => model).set(depth as f64);
+        metrics::gauge!("ai_scheduler_queue_depth", "model" => model.to_string()).set(depth as f64);
 
         loop {
             let now = Instant::now();
-            let mut state = queue.state.lock().await;
-            state.prune(self.inner.rpm_window, now);
-
-            if let Some(wait) = state.required_wait(now, cfg.rpm_limit, self.inner.rpm_window) {
-                drop(state);
-
+            if let Some(wait) = self
+                .admit(&model, &queue, cfg.rpm_limit, now)
+                .await
+            {
                 let remaining = deadline.saturating_duration_since(now);
                 if wait >= remaining {
                     let depth = queue
                         .waiting
                         .fetch_sub(1, Ordering::Relaxed)
                         .saturating_sub(1);
-                    metrics::gauge!("ai_scheduler_queue_depth", "model" => model).set(depth as f64);
+                    metrics::gauge!("ai_scheduler_queue_depth", "model" => model.to_string())
+                        .set(depth as f64);
                     metrics::counter!(
                         "ai_scheduler_requests_total",
-                        "model" => model,
+                        "model" => model.to_string(),
                         "operation" => operation.as_str(),
                         "result" => "timeout",
                     )
@@ -112,79 +149,197 @@ impl AiScheduler {
                 continue;
             }
 
-            state.recent.push_back(now);
-            drop(state);
-
             let depth = queue
                 .waiting
                 .fetch_sub(1, Ordering::Relaxed)
                 .saturating_sub(1);
-            metrics::gauge!("ai_scheduler_queue_depth", "model" => model).set(depth as f64);
+            metrics::gauge!("ai_scheduler_queue_depth", "model" => model.to_string())
+                .set(depth as f64);
             metrics::histogram!(
                 "ai_scheduler_queue_wait_seconds",
-                "model" => model,
+                "model" => model.to_string(),
                 "operation" => operation.as_str(),
             )
             .record(enqueued_at.elapsed().as_secs_f64());
             metrics::counter!(
                 "ai_scheduler_requests_total",
-                "model" => model,
+                "model" => model.to_string(),
                 "operation" => operation.as_str(),
                 "result" => "admitted",
             )
             .increment(1);
-            return Ok(ScheduleGuard { scheduler: self.clone(), model, operation });
+            queue
+                .in_flight
+                .fetch_add(1, Ordering::Relaxed);
+            return Ok(ScheduleGuard { scheduler: self.clone(), queue, model, operation });
         }
     }
 
-    pub async fn cool_down(&self, model: &'static str, operation: AiOperation, duration: Duration) {
-        let queue = self.queue(model);
+    pub async fn cool_down(&self, model: &str, operation: AiOperation, duration: Duration) {
+        let (model, queue) = self.queue(model);
+        queue
+            .cool_down(&model, operation, duration)
+            .await;
+        self.share_cool_down(&model, duration)
+            .await;
+    }
+
+    /// Records one admission for `model`, or returns how long to wait first.
+    async fn admit(
+        &self,
+        model: &str,
+        queue: &ModelQueue,
+        rpm_limit: usize,
+        now: Instant,
+    ) -> Option<Duration> {
+        if let AdmissionBackend::Redis(redis) = &self.inner.backend {
+            match redis_window_admit(
+                redis,
+                &window_key(model),
+                &cooldown_key(model),
+                self.inner.rpm_window,
+                rpm_limit,
+            )
+            .await
+            {
+                Ok(wait) => return wait,
+                Err(e) => {
+                    tracing::warn!(
+                        model,
+                        error = %e,
+                        "shared ai admission failed; falling back to local window"
+                    );
+                    metrics::counter!(
+                        "ai_scheduler_backend_errors_total",
+                        "model" => model.to_string(),
+                    )
+                    .increment(1);
+                }
+            }
+        }
+
         let mut state = queue.state.lock().await;
-        let until = Instant::now() + duration;
-        state.cooldown_until = Some(match state.cooldown_until {
-            Some(current) if current > until => current,
-            _ => until,
-        });
-        metrics::counter!(
-            "ai_scheduler_cooldowns_total",
-            "model" => model,
-            "operation" => operation.as_str(),
-        )
-        .increment(1);
+        state.prune(self.inner.rpm_window, now);
+        let wait = state.required_wait(now, rpm_limit, self.inner.rpm_window);
+        if wait.is_none() {
+            state.recent.push_back(now);
+        }
+        wait
     }
 
-    fn queue(&self, model: &'static str) -> Arc<ModelQueue> {
+    async fn share_cool_down(&self, model: &str, duration: Duration) {
+        if let AdmissionBackend::Redis(redis) = &self.inner.backend
+            && let Err(e) = redis_extend_cooldown(redis, &cooldown_key(model), duration).await
+        {
+            tracing::warn!(model, error = %e, "failed to share ai cooldown");
+            metrics::counter!(
+                "ai_scheduler_backend_errors_total",
+                "model" => model.to_string(),
+            )
+            .increment(1);
+        }
+    }
+
+    /// Drops the queues of models that are no longer in the catalogue. A queue
+    /// that still has waiters or an outstanding [`ScheduleGuard`] is kept until
+    /// a later call, so in-flight requests can still report a cooldown.
+    pub fn retain_models<'a>(&self, names: impl IntoIterator<Item = &'a str>) {
+        let keep: HashSet<&str> = names.into_iter().collect();
         let mut models = self
             .inner
             .models
             .lock()
             .expect("ai scheduler model map poisoned");
-        models
-            .entry(model)
-            .or_insert_with(|| {
-                Arc::new(ModelQueue {
-                    state: AsyncMutex::new(ModelState::default()),
-                    waiting: AtomicUsize::new(0),
-                })
-            })
-            .clone()
+        models.retain(|name, queue| keep.contains(name.as_ref()) || !queue.is_idle());
+    }
+
+    #[cfg(test)]
+    pub(crate) fn tracked_models(&self) -> Vec<Arc<str>> {
+        self.inner
+            .models
+            .lock()
+            .expect("ai scheduler model map poisoned")
+            .keys()
+            .cloned()
+            .collect()
+    }
+
+    fn queue(&self, model: &str) -> (Arc<str>, Arc<ModelQueue>) {
+        let mut models = self
+            .inner
+            .models
+            .lock()
+            .expect("ai scheduler model map poisoned");
+        if let Some((name, queue)) = models.get_key_value(model) {
+            return (name.clone(), queue.clone());
+        }
+        let name: Arc<str> = Arc::from(model);
+        let queue = Arc::new(ModelQueue {
+            state: AsyncMutex::new(ModelState::default()),
+            waiting: AtomicUsize::new(0),
+            in_flight: AtomicUsize::new(0),
+        });
+        models.insert(name.clone(), queue.clone());
+        (name, queue)
     }
 }
 
+fn window_key(model: &str) -> String {
+    format!("{CACHE_PREFIX}:ai:scheduler:{model}:window")
+}
+
+fn cooldown_key(model: &str) -> String {
+    format!("{CACHE_PREFIX}:ai:scheduler:{model}:cooldown")
+}
+
 pub struct ScheduleGuard {
     scheduler: AiScheduler,
-    model: &'static str,
+    queue: Arc<ModelQueue>,
+    model: Arc<str>,
     operation: AiOperation,
 }
 
 impl ScheduleGuard {
     pub async fn cool_down(self, duration: Duration) {
+        self.queue
+            .cool_down(&self.model, self.operation, duration)
+            .await;
         self.scheduler
-            .cool_down(self.model, self.operation, duration)
+            .share_cool_down(&self.model, duration)
             .await;
     }
 }
 
+impl Drop for ScheduleGuard {
+    fn drop(&mut self) {
+        self.queue
+            .in_flight
+            .fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl ModelQueue {
+    /// No request is waiting on or holding this queue.
+    fn is_idle(&self) -> bool {
+        self.waiting.load(Ordering::Relaxed) == 0 && self.in_flight.load(Ordering::Relaxed) == 0
+    }
+
+    async fn cool_down(&self, model: &str, operation: AiOperation, duration: Duration) {
+        let mut state = self.state.lock().await;
+        let until = Instant::now() + duration;
+        state.cooldown_until = Some(match state.cooldown_until {
+            Some(current) if current > until => current,
+            _ => until,
+        });
+        metrics::counter!(
+            "ai_scheduler_cooldowns_total",
+            "model" => model.to_string(),
+            "operation" => operation.as_str(),
+        )
+        .increment(1);
+    }
+}
+
 impl ModelState {
     fn prune(&mut self, rpm_window: Duration, now: Instant) {
         while self
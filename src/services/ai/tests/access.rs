@@ -0,0 +1,75 @@
+use super::*;
+
+fn access(mode: AiChannelMode, channels: &[u64]) -> AiAccessSettings {
+    AiAccessSettings {
+        channel_mode: mode,
+        channels: channels.to_vec(),
+        ..AiAccessSettings::default()
+    }
+}
+
+#[test]
+fn defaults_answer_mentions_in_every_channel_only() {
+    let open = AiAccessSettings::default();
+    assert_eq!(guild_access(&open, 1, None), Ok(()));
+    assert!(addresses_bot(&open, true, false));
+    assert!(!addresses_bot(&open, false, true));
+    assert_eq!(
+        dm_access([&open]),
+        Err(AccessDenied::DirectMessages)
+    );
+}
+
+#[test]
+fn allow_and_deny_lists_follow_thread_parents() {
+    let allow = access(AiChannelMode::Allow, &[10]);
+    assert!(channel_allowed(&allow, 10, None));
+    assert!(channel_allowed(&allow, 11, Some(10)));
+    assert!(!channel_allowed(&allow, 12, None));
+
+    let deny = access(AiChannelMode::Deny, &[10]);
+    assert!(!channel_allowed(&deny, 10, None));
+    assert!(!channel_allowed(&deny, 11, Some(10)));
+    assert!(channel_allowed(&deny, 12, None));
+}
+
+#[test]
+fn disabled_guilds_refuse_before_channel_rules() {
+    let mut closed = access(AiChannelMode::Deny, &[10]);
+    closed.enabled = false;
+    assert_eq!(
+        guild_access(&closed, 10, None),
+        Err(AccessDenied::Disabled)
+    );
+    closed.enabled = true;
+    assert_eq!(
+        guild_access(&closed, 10, None),
+        Err(AccessDenied::Channel)
+    );
+}
+
+#[test]
+fn replies_count_only_when_the_guild_says_so() {
+    let settings = AiAccessSettings::default();
+    assert!(!addresses_bot(&settings, false, true));
+    assert!(addresses_bot(&settings, true, true));
+    assert!(!addresses_bot(&settings, false, false));
+
+    let replies = AiAccessSettings { reply_mentions: true, ..AiAccessSettings::default() };
+    assert!(addresses_bot(&replies, false, true));
+}
+
+#[test]
+fn direct_messages_need_one_shared_guild_that_allows_them() {
+    let no_dms = AiAccessSettings::default();
+    let disabled =
+        AiAccessSettings { enabled: false, allow_dms: true, ..AiAccessSettings::default() };
+    let open = AiAccessSettings { allow_dms: true, ..AiAccessSettings::default() };
+
+    assert_eq!(dm_access([]), Err(AccessDenied::DirectMessages));
+    assert_eq!(
+        dm_access([&no_dms, &disabled]),
+        Err(AccessDenied::DirectMessages)
+    );
+    assert_eq!(dm_access([&no_dms, &open]), Ok(()));
+}
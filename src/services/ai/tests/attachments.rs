@@ -0,0 +1,149 @@
+use super::*;
+use crate::{context::mock_reqwest::MockReqwest, dbs::redis::new_pool};
+use twilight_model::id::Id;
+
+fn attachment(id: u64, filename: &str, content_type: Option<&str>, size: u64) -> Attachment {
+    Attachment {
+        content_type: content_type.map(str::to_string),
+        ephemeral: false,
+        duration_secs: None,
+        filename: filename.to_string(),
+        flags: None,
+        description: None,
+        height: None,
+        id: Id::new(id),
+        proxy_url: String::new(),
+        size,
+        title: None,
+        url: format!("https://cdn.discordapp.com/attachments/1/{id}/{filename}"),
+        waveform: None,
+        width: None,
+    }
+}
+
+#[test]
+fn classify_prefers_known_text_extensions_over_discord_mime() {
+    assert_eq!(
+        classify("main.rs", Some("application/rls-services+xml")),
+        Some(AttachmentKind::Text)
+    );
+    assert_eq!(
+        classify("crash.LOG", None),
+        Some(AttachmentKind::Text)
+    );
+    assert_eq!(
+        classify("notes", Some("text/plain; charset=utf-8")),
+        Some(AttachmentKind::Text)
+    );
+    assert_eq!(
+        classify("guide.pdf", None),
+        Some(AttachmentKind::Pdf)
+    );
+    assert_eq!(
+        classify("cat.png", Some("image/png")),
+        Some(AttachmentKind::Media)
+    );
+    assert_eq!(
+        classify("game.exe", Some("application/x-msdownload")),
+        None
+    );
+}
+
+#[test]
+fn decode_text_rejects_binary_and_clips_long_files() {
+    assert_eq!(
+        decode_text(b"  hello\n").as_deref(),
+        Some("hello")
+    );
+    assert_eq!(decode_text(b"bin\0ary"), None);
+    assert_eq!(decode_text(&[0xff, 0xfe]), None);
+
+    let long = "ก".repeat(MAX_INLINE_CHARS + 10);
+    let clipped = decode_text(long.as_bytes()).unwrap();
+    assert!(clipped.ends_with("\n[truncated]"));
+    assert_eq!(
+        clipped.chars().count(),
+        MAX_INLINE_CHARS + "\n[truncated]".len()
+    );
+}
+
+/// A one-page PDF whose only text is "Hello from a PDF".
+const HELLO_PDF: &[u8] = include_bytes!("data/hello.pdf");
+
+#[test]
+fn extract_pdf_text_rejects_files_that_are_not_pdfs() {
+    assert_eq!(extract_pdf_text(b"not a pdf"), None);
+}
+
+#[test]
+fn extract_pdf_text_reads_every_page() {
+    assert_eq!(
+        extract_pdf_text(HELLO_PDF).as_deref(),
+        Some("Hello from a PDF")
+    );
+}
+
+#[tokio::test]
+async fn small_pdfs_are_inlined_without_an_upload() {
+    let http = MockReqwest::new();
+    let pdf = attachment(
+        7_037_004,
+        "guide.pdf",
+        Some("application/pdf"),
+        HELLO_PDF.len() as u64,
+    );
+    http.add_json_response(&pdf.url, std::str::from_utf8(HELLO_PDF).unwrap());
+
+    let mut parts = Vec::new();
+    let appended = append_attachments(&http, &new_pool(), &mut parts, vec![pdf], "Alice")
+        .await
+        .expect("a readable PDF never reaches the upload path");
+
+    let document = "Attachment guide.pdf from Alice:\nHello from a PDF".to_string();
+    assert_eq!(parts, vec![Part::text(&document)]);
+    assert!(appended.uris.is_empty());
+}
+
+#[tokio::test]
+async fn text_is_inlined_and_unusable_files_are_reported() {
+    let http = MockReqwest::new();
+    let log = attachment(7_037_001, "crash.log", None, 12);
+    http.add_json_response(&log.url, "panic at 42\n");
+
+    let mut parts = Vec::new();
+    let appended = append_attachments(
+        &http,
+        &new_pool(),
+        &mut parts,
+        vec![
+            log,
+            attachment(
+                7_037_002,
+                "game.exe",
+                Some("application/x-msdownload"),
+                10,
+            ),
+            attachment(
+                7_037_003,
+                "dump.txt",
+                Some("text/plain"),
+                MAX_TEXT_BYTES + 1,
+            ),
+        ],
+        "Alice",
+    )
+    .await
+    .expect("text attachments never reach the upload path");
+
+    let document = "Attachment crash.log from Alice:\npanic at 42".to_string();
+    assert_eq!(parts, vec![Part::text(&document)]);
+    assert_eq!(appended.documents, vec![document]);
+    assert!(appended.uris.is_empty());
+    assert_eq!(
+        appended.skipped,
+        vec![
+            SkippedAttachment { filename: "game.exe".to_string(), reason: SkipReason::Unsupported },
+            SkippedAttachment { filename: "dump.txt".to_string(), reason: SkipReason::TooLarge },
+        ]
+    );
+}
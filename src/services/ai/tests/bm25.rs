@@ -0,0 +1,47 @@
+use super::*;
+
+#[test]
+fn tokenize_splits_words_and_thai_bigrams() {
+    assert_eq!(
+        tokenize("Dojo schedule: Friday 20:00!"),
+        ["dojo", "schedule", "friday", "20", "00"]
+    );
+    assert_eq!(tokenize("กฎแคลน"), ["กฎ", "ฎแ", "แค", "คล", "ลน"]);
+    // Vowel and tone marks stay inside the word instead of splitting it.
+    assert_eq!(tokenize("ดีจ้า").len(), "ดีจ้า".chars().count() - 1);
+}
+
+#[test]
+fn search_ranks_matching_texts_and_skips_the_rest() {
+    let index = Bm25Index::new([
+        "Clan rules: be kind and no spamming in chat.",
+        "The dojo opens every Friday at 20:00 for clan raids.",
+        "Trading tips for the market.",
+    ]);
+
+    let hits = index.search("when does dojo open on friday", 5);
+    assert_eq!(hits.first().map(|h| h.0), Some(1));
+    assert!(hits.iter().all(|h| h.0 != 2));
+
+    assert!(index.search("riven", 5).is_empty());
+    assert!(index.search("   ", 5).is_empty());
+}
+
+#[test]
+fn rarer_terms_weigh_more() {
+    let index = Bm25Index::new(["clan clan rules", "clan dojo", "clan market"]);
+    let hits = index.search("clan dojo", 3);
+    assert_eq!(hits[0].0, 1);
+}
+
+#[test]
+fn search_respects_the_limit() {
+    let index = Bm25Index::new(["raid", "raid night", "raid tips"]);
+    assert_eq!(index.search("raid", 2).len(), 2);
+}
+
+#[test]
+fn empty_index_returns_nothing() {
+    let index = Bm25Index::new(std::iter::empty());
+    assert!(index.search("anything", 3).is_empty());
+}
@@ -0,0 +1,77 @@
+use super::*;
+
+const FILE: &str = r#"{
+    "chat": [
+        {"name": "gemini-3-flash", "rpm_limit": 20, "queue_timeout_secs": 6, "cooldown_secs": 15}
+    ],
+    "summary": [
+        {"name": "gemini-2.5-flash-lite", "rpm_limit": 15, "queue_timeout_secs": 5, "cooldown_secs": 15}
+    ]
+}"#;
+
+#[test]
+fn default_catalogue_matches_builtin_chains() {
+    let catalogue = ModelCatalogue::default();
+    assert_eq!(catalogue.chat[0].name, "gemini-2.5-flash");
+    assert_eq!(catalogue.chat.len(), 6);
+    assert_eq!(catalogue.summary[0].name, "gemini-2.5-flash-lite");
+    assert_eq!(catalogue.summary.len(), 5);
+}
+
+#[test]
+fn parse_reads_seconds_into_durations() {
+    let catalogue = parse(FILE).unwrap();
+    assert_eq!(
+        catalogue.chat,
+        vec![ModelSpec {
+            name: Cow::Borrowed("gemini-3-flash"),
+            rpm_limit: 20,
+            queue_timeout: Duration::from_secs(6),
+            cooldown: Duration::from_secs(15),
+        }]
+    );
+}
+
+#[test]
+fn parse_rejects_empty_chains() {
+    let err = parse(r#"{"chat": [], "summary": []}"#).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("at least one chat")
+    );
+}
+
+#[tokio::test]
+async fn install_swaps_catalogue_and_prunes_idle_queues() {
+    // A private slot, so tests reading the global catalogue never see this one.
+    let slot = RwLock::new(Arc::new(ModelCatalogue::default()));
+    let scheduler = AiScheduler::new();
+    scheduler
+        .cool_down(
+            "gemini-2.0-flash",
+            crate::services::ai::scheduler::AiOperation::Chat,
+            Duration::from_millis(1),
+        )
+        .await;
+
+    assert!(install_into(
+        &slot,
+        &scheduler,
+        parse(FILE).unwrap()
+    ));
+    assert_eq!(
+        slot.read().unwrap().chat[0].name,
+        "gemini-3-flash"
+    );
+    assert!(
+        !scheduler
+            .tracked_models()
+            .iter()
+            .any(|name| name.as_ref() == "gemini-2.0-flash")
+    );
+    assert!(!install_into(
+        &slot,
+        &scheduler,
+        parse(FILE).unwrap()
+    ));
+}
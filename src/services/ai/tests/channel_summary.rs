@@ -0,0 +1,154 @@
+use super::*;
+use crate::context::ContextBuilder;
+use twilight_model::{
+    channel::message::{MessageFlags, MessageType},
+    user::User,
+    util::datetime::Timestamp,
+};
+
+fn make_message(
+    id: u64,
+    channel_id: u64,
+    author: u64,
+    bot: bool,
+    content: &str,
+    at: i64,
+) -> Message {
+    Message {
+        activity: None,
+        application: None,
+        application_id: None,
+        attachments: Vec::new(),
+        author: User {
+            accent_color: None,
+            avatar: None,
+            avatar_decoration: None,
+            avatar_decoration_data: None,
+            banner: None,
+            bot,
+            discriminator: 0,
+            email: None,
+            flags: None,
+            global_name: None,
+            id: Id::new(author),
+            locale: None,
+            mfa_enabled: None,
+            name: format!("user{author}"),
+            premium_type: None,
+            primary_guild: None,
+            public_flags: None,
+            system: None,
+            verified: None,
+        },
+        call: None,
+        channel_id: Id::new(channel_id),
+        components: Vec::new(),
+        content: content.to_owned(),
+        edited_timestamp: None,
+        embeds: Vec::new(),
+        flags: Some(MessageFlags::empty()),
+        guild_id: None,
+        id: Id::new(id),
+        #[allow(deprecated)]
+        interaction: None,
+        interaction_metadata: None,
+        kind: MessageType::Regular,
+        member: None,
+        mention_channels: Vec::new(),
+        mention_everyone: false,
+        mention_roles: Vec::new(),
+        mentions: Vec::new(),
+        message_snapshots: Vec::new(),
+        pinned: false,
+        poll: None,
+        reactions: Vec::new(),
+        reference: None,
+        referenced_message: None,
+        role_subscription_data: None,
+        sticker_items: Vec::new(),
+        timestamp: Timestamp::from_secs(at).unwrap(),
+        thread: None,
+        tts: false,
+        webhook_id: None,
+    }
+}
+
+#[test]
+fn parse_since_accepts_compound_durations() {
+    assert_eq!(parse_since("90m"), Some(Duration::minutes(90)));
+    assert_eq!(parse_since("2h30m"), Some(Duration::minutes(150)));
+    assert_eq!(parse_since("1D"), Some(Duration::days(1)));
+}
+
+#[test]
+fn parse_since_rejects_malformed_or_long_durations() {
+    for raw in ["", "0h", "15", "h", "2w", "8d", "1h-5m"] {
+        assert_eq!(parse_since(raw), None, "{raw:?}");
+    }
+}
+
+#[test]
+fn chunk_lines_keeps_lines_whole_under_the_limit() {
+    let lines: Vec<String> = ["aaaa", "bbbb", "cccc"]
+        .into_iter()
+        .map(String::from)
+        .collect();
+    assert_eq!(
+        chunk_lines(&lines, 9),
+        vec!["aaaa\nbbbb".to_string(), "cccc".to_string()]
+    );
+    assert_eq!(
+        chunk_lines(&["x".repeat(12)], 5),
+        vec!["xxxxx".to_string()]
+    );
+}
+
+#[tokio::test]
+async fn fetch_pages_back_skipping_bots_and_stopping_at_since() {
+    let ctx = ContextBuilder::new()
+        .watchers(false)
+        .build()
+        .await
+        .expect("failed to build context");
+    let channel = 7_031_001;
+    let base = 1_800_000_000;
+    let mut messages: Vec<Message> = (1..=250)
+        .map(|i| {
+            make_message(
+                i,
+                channel,
+                10 + i % 3,
+                false,
+                &format!("m{i}"),
+                base + i as i64,
+            )
+        })
+        .collect();
+    messages.push(make_message(
+        251,
+        channel,
+        99,
+        true,
+        "bot noise",
+        base + 251,
+    ));
+    ctx.http
+        .add_channel_messages(Id::new(channel), messages);
+
+    let fetched = fetch_messages(&ctx, Id::new(channel), 150, None)
+        .await
+        .unwrap();
+    assert_eq!(fetched.len(), 150);
+    assert_eq!(fetched.first().unwrap().id, Id::new(101));
+    assert_eq!(fetched.last().unwrap().id, Id::new(250));
+
+    let since = DateTime::from_timestamp(base + 241, 0);
+    let fetched = fetch_messages(&ctx, Id::new(channel), 150, since)
+        .await
+        .unwrap();
+    let ids: Vec<u64> = fetched
+        .iter()
+        .map(|m| m.id.get())
+        .collect();
+    assert_eq!(ids, (241..=250).collect::<Vec<_>>());
+}
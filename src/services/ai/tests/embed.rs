@@ -1,4 +1,5 @@
 use super::*;
+use crate::services::ai::attachments::{SkipReason, SkippedAttachment};
 
 #[test]
 fn test_ai_embeds_empty() {
@@ -42,3 +43,137 @@ fn test_unavailable_embed() {
     );
     assert_eq!(embed.color, Some(COLOR));
 }
+
+#[test]
+fn test_reply_embeds_replace_withheld_text_with_reason() {
+    let reply = AiReply {
+        text: String::new(),
+        status: ReplyStatus::Withheld(WithheldReason::Safety),
+        sources: vec!["[1] Rules (ส่วนที่ 1)".to_string()],
+        skipped: Vec::new(),
+        cached: false,
+    };
+    let embeds = AiService::reply_embeds(&reply).unwrap();
+    assert_eq!(embeds.len(), 1);
+    assert_eq!(
+        embeds[0].title.as_deref(),
+        Some("🛡️ ไม่สามารถแสดงคำตอบได้")
+    );
+}
+
+#[test]
+fn test_reply_embeds_append_truncation_notice() {
+    let reply = AiReply {
+        text: "partial".to_string(),
+        status: ReplyStatus::Truncated,
+        sources: Vec::new(),
+        skipped: Vec::new(),
+        cached: false,
+    };
+    let embeds = AiService::reply_embeds(&reply).unwrap();
+    assert_eq!(embeds.len(), 2);
+    assert_eq!(embeds[0].description.as_deref(), Some("partial"));
+    assert!(
+        embeds[1]
+            .description
+            .as_deref()
+            .is_some_and(|d| d.starts_with("✂️"))
+    );
+}
+
+#[test]
+fn test_reply_embeds_list_cited_sources_last() {
+    let reply = AiReply {
+        text: "Raids start at 20:00 [1]".to_string(),
+        status: ReplyStatus::Complete,
+        sources: vec!["[1] Dojo schedule (ส่วนที่ 2)".to_string()],
+        skipped: Vec::new(),
+        cached: false,
+    };
+    let embeds = AiService::reply_embeds(&reply).unwrap();
+    assert_eq!(embeds.len(), 2);
+    assert_eq!(
+        embeds[1].title.as_deref(),
+        Some("📚 แหล่งข้อมูลจากเซิร์ฟเวอร์")
+    );
+    assert_eq!(
+        embeds[1].description.as_deref(),
+        Some("[1] Dojo schedule (ส่วนที่ 2)")
+    );
+}
+
+#[test]
+fn test_reply_embeds_list_skipped_attachments_even_when_withheld() {
+    let reply = AiReply {
+        text: String::new(),
+        status: ReplyStatus::Withheld(WithheldReason::Safety),
+        sources: Vec::new(),
+        skipped: vec![SkippedAttachment {
+            filename: "game.exe".to_string(),
+            reason: SkipReason::Unsupported,
+        }],
+        cached: false,
+    };
+    let embeds = AiService::reply_embeds(&reply).unwrap();
+    assert_eq!(embeds.len(), 2);
+    assert_eq!(
+        embeds[1].description.as_deref(),
+        Some("`game.exe` — ไม่รองรับไฟล์ชนิดนี้")
+    );
+}
+
+#[test]
+fn test_reply_embeds_mark_cached_answers_in_the_footer() {
+    let reply = AiReply {
+        text: "Umbra Forma drops from the Profit-Taker.".to_string(),
+        status: ReplyStatus::Complete,
+        sources: Vec::new(),
+        skipped: Vec::new(),
+        cached: true,
+    };
+    let embeds = AiService::reply_embeds(&reply).unwrap();
+    assert_eq!(embeds.len(), 1);
+    assert_eq!(
+        embeds[0]
+            .footer
+            .as_ref()
+            .map(|f| f.text.as_str()),
+        Some("⚡ คำตอบจากแคช")
+    );
+}
+
+#[test]
+fn test_usage_embed_shows_fallback_rate_and_errors() {
+    use crate::services::ai::{client::ErrorClass, usage::UserUsage};
+
+    let report = UsageReport {
+        days: 7,
+        requests: 4,
+        prompt_tokens: 100,
+        output_tokens: 40,
+        fallbacks: 1,
+        top_users: vec![UserUsage { user_id: 42, requests: 4, tokens: 140 }],
+        models: vec![("gemini-2.5-flash".to_string(), 4)],
+        errors: vec![(ErrorClass::RateLimited, 2)],
+    };
+    let embed = AiService::usage_embed(&report).unwrap();
+    let fields: Vec<&str> = embed
+        .fields
+        .iter()
+        .map(|f| f.value.as_str())
+        .collect();
+    assert!(fields[1].ends_with("สลับไปโมเดลสำรอง: 25.0%"));
+    assert_eq!(fields[2], "`rate_limited`: 2");
+    assert_eq!(fields[3], "1. <@42> — 4 คำขอ, 140 โทเคน");
+}
+
+#[test]
+fn test_is_answer_skips_notices() {
+    assert!(!AiService::is_answer(&[]));
+    assert!(AiService::is_answer(
+        &AiService::ai_embeds("สวัสดี").unwrap()
+    ));
+    assert!(!AiService::is_answer(&[
+        AiService::unavailable_embed().unwrap()
+    ]));
+}
@@ -0,0 +1,75 @@
+use super::*;
+use crate::{
+    context::mock_reqwest::MockReqwest,
+    dbs::redis::new_pool,
+    services::ai::{attachments::append_attachments, genai::Part},
+};
+use twilight_model::id::Id;
+
+fn attachment(id: u64, query: &str) -> Attachment {
+    Attachment {
+        content_type: Some("image/png".to_string()),
+        ephemeral: false,
+        duration_secs: None,
+        filename: "cat.png".to_string(),
+        flags: None,
+        description: None,
+        height: None,
+        id: Id::new(id),
+        proxy_url: String::new(),
+        size: 1024,
+        title: None,
+        url: format!("https://cdn.discordapp.com/attachments/1/{id}/cat.png?{query}"),
+        waveform: None,
+        width: None,
+    }
+}
+
+#[tokio::test]
+async fn cached_upload_is_reused_for_a_resigned_url() {
+    let pool = new_pool();
+    let file = CachedFile::uploaded_now(
+        "https://generativelanguage.googleapis.com/v1beta/files/cat-30001".to_string(),
+        "image/png".to_string(),
+    );
+    remember(&pool, &attachment(7_030_001, "ex=1&hm=a"), &file).await;
+
+    // MockReqwest panics on any download or upload, so this only passes on a hit.
+    let mut parts = Vec::new();
+    let appended = append_attachments(
+        &MockReqwest::new(),
+        &pool,
+        &mut parts,
+        vec![attachment(7_030_001, "ex=2&hm=b")],
+        "Alice",
+    )
+    .await
+    .expect("cached attachment should not be uploaded again");
+
+    assert_eq!(appended.uris, vec![file.uri.clone()]);
+    assert_eq!(
+        parts,
+        vec![Part::text("Attachment from Alice:"), Part::file_data("image/png", &file.uri),]
+    );
+}
+
+#[tokio::test]
+async fn files_near_gemini_expiry_are_not_reused() {
+    let pool = new_pool();
+    let file = CachedFile {
+        uri: "https://generativelanguage.googleapis.com/v1beta/files/cat-30002".to_string(),
+        mime_type: "image/png".to_string(),
+        expires_at: Utc::now() + Duration::seconds(REUSE_MARGIN_SECS + 60),
+    };
+    let source = attachment(7_030_002, "ex=1");
+    remember(&pool, &source, &file).await;
+    assert!(
+        lookup_uri(&pool, &file.uri)
+            .await
+            .is_some()
+    );
+
+    let stale = CachedFile { expires_at: Utc::now() + Duration::seconds(60), ..file.clone() };
+    redis_set_ex(&pool, &uri_key(&stale.uri), &stale, 60).await;
+    assert_eq!(lookup_uri(&pool, &stale.uri).await, None);
+}
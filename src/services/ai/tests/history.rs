@@ -1,33 +1,68 @@
 use super::*;
-use chrono::Duration;
+use crate::{dbs::redis::new_pool, services::ai::file_cache::CachedFile};
+use chrono::{Duration, Utc};
+use twilight_model::channel::Attachment;
 
-fn build_entry(created_at: chrono::DateTime<Utc>) -> ChatEntry {
+fn build_entry(file: &str, ref_file: &str) -> ChatEntry {
     ChatEntry {
         role: "user".to_string(),
         text: "hello".to_string(),
-        attachments: vec!["https://example.com/file.png".to_string()],
+        attachments: vec![file.to_string()],
         ref_text: Some("reply".to_string()),
-        ref_attachments: Some(vec!["https://example.com/ref.png".to_string()]),
+        ref_attachments: Some(vec![ref_file.to_string()]),
         ref_author: Some("Bob".to_string()),
-        created_at,
+        documents: vec!["Attachment notes.txt from Alice:\nfarm plan".to_string()],
+        created_at: Utc::now() - Duration::hours(1),
+    }
+}
+
+fn source(id: u64) -> Attachment {
+    Attachment {
+        content_type: Some("image/png".to_string()),
+        ephemeral: false,
+        duration_secs: None,
+        filename: "file.png".to_string(),
+        flags: None,
+        description: None,
+        height: None,
+        id: Id::new(id),
+        proxy_url: String::new(),
+        size: 64,
+        title: None,
+        url: format!("https://cdn.discordapp.com/attachments/1/{id}/file.png"),
+        waveform: None,
+        width: None,
     }
 }
 
 #[tokio::test]
-async fn test_parse_history_non_expired() {
-    let entry = build_entry(Utc::now() - Duration::hours(1));
-    let result = parse_history([&entry], "Alice").await;
+async fn test_parse_history_replays_cached_files() {
+    let pool = new_pool();
+    let file = "https://generativelanguage.googleapis.com/v1beta/files/history-1";
+    let ref_file = "https://generativelanguage.googleapis.com/v1beta/files/history-2";
+    for (id, uri, mime) in [(7_030_101, file, "image/png"), (7_030_102, ref_file, "image/jpeg")] {
+        file_cache::remember(
+            &pool,
+            &source(id),
+            &CachedFile::uploaded_now(uri.to_string(), mime.to_string()),
+        )
+        .await;
+    }
+
+    let entry = build_entry(file, ref_file);
+    let result = parse_history(&pool, [&entry], "Alice").await;
     assert_eq!(result.len(), 1);
     let content = &result[0];
 
     let expected_parts = vec![
         Part::text("hello"),
         Part::text("Attachment from Alice:"),
-        Part::file_data("", "https://example.com/file.png"),
+        Part::file_data("image/png", file),
         Part::text("In reply to Bob:"),
         Part::text("reply"),
         Part::text("Attachment from Bob:"),
-        Part::file_data("", "https://example.com/ref.png"),
+        Part::file_data("image/jpeg", ref_file),
+        Part::text("Attachment notes.txt from Alice:\nfarm plan"),
     ];
 
     assert_eq!(content.role, "user");
@@ -35,18 +70,21 @@ async fn test_parse_history_non_expired() {
 }
 
 #[tokio::test]
-async fn test_parse_history_expired() {
-    let entry = build_entry(Utc::now() - Duration::hours(72));
-    let result = parse_history([&entry], "Alice").await;
+async fn test_parse_history_drops_expired_files() {
+    let pool = new_pool();
+    let entry = build_entry(
+        "https://generativelanguage.googleapis.com/v1beta/files/history-gone-1",
+        "https://generativelanguage.googleapis.com/v1beta/files/history-gone-2",
+    );
+    let result = parse_history(&pool, [&entry], "Alice").await;
     assert_eq!(result.len(), 1);
     let content = &result[0];
 
     let expected_parts = vec![
         Part::text("hello"),
-        Part::text("Attachment from Alice is expired and no longer accessible."),
         Part::text("In reply to Bob:"),
         Part::text("reply"),
-        Part::text("Attachment from Bob is expired and no longer accessible."),
+        Part::text("Attachment notes.txt from Alice:\nfarm plan"),
     ];
 
     assert_eq!(content.role, "user");
@@ -0,0 +1,180 @@
+use super::*;
+use crate::context::ContextBuilder;
+
+fn document(title: &str, chunks: &[&str]) -> AiKnowledgeDocument {
+    AiKnowledgeDocument {
+        id: None,
+        guild_id: 1,
+        title: title.to_string(),
+        chunks: chunks
+            .iter()
+            .map(|c| c.to_string())
+            .collect(),
+        added_by: 2,
+        added_at: 0,
+    }
+}
+
+#[test]
+fn titles_are_collapsed_and_bounded() {
+    assert_eq!(
+        normalize_title("  Clan   rules "),
+        Some("Clan rules".to_string())
+    );
+    assert_eq!(normalize_title("   "), None);
+    assert_eq!(
+        normalize_title(&"x".repeat(MAX_TITLE_CHARS + 1)),
+        None
+    );
+}
+
+#[test]
+fn only_text_and_markdown_uploads_are_accepted() {
+    assert!(is_text_attachment("rules.txt", None));
+    assert!(is_text_attachment("GUIDE.MD", None));
+    assert!(is_text_attachment(
+        "notes",
+        Some("text/plain; charset=utf-8")
+    ));
+    assert!(!is_text_attachment("map.png", Some("image/png")));
+    assert!(!is_text_attachment("guide.pdf", None));
+}
+
+#[test]
+fn documents_are_chunked_by_lines_without_blanks() {
+    let line = "a".repeat(600);
+    let content = format!("{line}\n\n\n{line}\r\n{line}\n");
+    let chunks = chunk_document(&content);
+    assert_eq!(chunks.len(), 3);
+    assert!(chunks.iter().all(|c| c == &line));
+    assert!(chunk_document("\n  \n").is_empty());
+}
+
+#[test]
+fn search_returns_the_matching_chunk_with_its_part() {
+    let documents = [
+        document(
+            "Clan rules",
+            &["Be kind.", "No begging for items."],
+        ),
+        document(
+            "Dojo schedule",
+            &["Open daily.", "Raids every Friday at 20:00."],
+        ),
+    ];
+    let hits = GuildIndex::build(&documents).search("what time are friday raids", 2);
+    assert_eq!(
+        hits.first(),
+        Some(&KnowledgeHit {
+            title: "Dojo schedule".to_string(),
+            part: 2,
+            text: "Raids every Friday at 20:00.".to_string(),
+        })
+    );
+}
+
+#[test]
+fn guild_index_is_reused_until_the_documents_change() {
+    let guild_id = 7_033_001;
+    let mut documents = vec![document("Clan rules", &["Be kind."])];
+    let first = guild_index(guild_id, &documents);
+    assert!(Arc::ptr_eq(
+        &first,
+        &guild_index(guild_id, &documents)
+    ));
+
+    documents[0].added_at = 1;
+    documents[0].chunks = vec!["Raids on Friday.".to_string()];
+    let rebuilt = guild_index(guild_id, &documents);
+    assert!(!Arc::ptr_eq(&first, &rebuilt));
+    assert_eq!(
+        rebuilt.search("friday", 1)[0].text,
+        "Raids on Friday."
+    );
+}
+
+#[test]
+fn system_section_numbers_excerpts_and_citations_follow_them() {
+    let hits = vec![
+        KnowledgeHit { title: "Rules".into(), part: 1, text: "Be kind.".into() },
+        KnowledgeHit { title: "Dojo".into(), part: 3, text: "Fridays.".into() },
+    ];
+    let section = system_section(&hits).unwrap();
+    assert!(section.starts_with(SECTION_HEADER));
+    assert!(section.ends_with("[1] Rules (part 1):\nBe kind.\n\n[2] Dojo (part 3):\nFridays."));
+    assert_eq!(system_section(&[]), None);
+
+    assert_eq!(
+        cited_sources("Raids are on Fridays [2].", &hits),
+        ["[2] Dojo (ส่วนที่ 3)"]
+    );
+    assert!(cited_sources("No idea.", &hits).is_empty());
+}
+
+#[tokio::test]
+async fn add_replace_retrieve_and_remove() {
+    let ctx = ContextBuilder::new()
+        .watchers(false)
+        .build()
+        .await
+        .expect("failed to build context");
+    let guild_id = 7_033_001;
+
+    assert_eq!(
+        add(&ctx, guild_id, "Dojo", "Raids on Friday.", 1)
+            .await
+            .unwrap(),
+        AddOutcome::Created { chunks: 1 }
+    );
+    assert_eq!(
+        add(&ctx, guild_id, "Dojo", "Raids on Saturday.", 1)
+            .await
+            .unwrap(),
+        AddOutcome::Replaced { chunks: 1 }
+    );
+    assert_eq!(
+        add(&ctx, guild_id, "Empty", " \n ", 1)
+            .await
+            .unwrap(),
+        AddOutcome::Empty
+    );
+    assert_eq!(
+        add(
+            &ctx,
+            guild_id,
+            "Big",
+            &"x".repeat(MAX_DOCUMENT_CHARS + 1),
+            1
+        )
+        .await
+        .unwrap(),
+        AddOutcome::TooLong
+    );
+
+    let hits = retrieve(&ctx, guild_id, "saturday raids").await;
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].text, "Raids on Saturday.");
+    assert_eq!(
+        document_choices(&ctx, guild_id, "do")
+            .await
+            .len(),
+        1
+    );
+
+    assert!(
+        remove(&ctx, guild_id, "Dojo")
+            .await
+            .unwrap()
+    );
+    assert!(
+        !remove(&ctx, guild_id, "Dojo")
+            .await
+            .unwrap()
+    );
+    assert!(
+        retrieve(&ctx, guild_id, "saturday")
+            .await
+            .is_empty()
+    );
+    purge_cache(&ctx.redis, guild_id).await;
+}
@@ -0,0 +1,97 @@
+use super::*;
+use crate::context::ContextBuilder;
+
+fn fact(key: &str, value: &str) -> MemoryFact {
+    MemoryFact { key: key.to_string(), value: value.to_string() }
+}
+
+#[test]
+fn keys_are_lowercased_and_bounded() {
+    assert_eq!(
+        normalize_key("  Main   Frame "),
+        Some("main frame".to_string())
+    );
+    assert_eq!(normalize_key(" "), None);
+    assert_eq!(
+        normalize_key(&"k".repeat(MAX_KEY_CHARS + 1)),
+        None
+    );
+}
+
+#[test]
+fn parse_facts_reads_fenced_json_and_drops_bad_entries() {
+    let reply = "```json\n{\"facts\":[{\"key\":\"IGN\",\"value\":\" Tenno01 \"},{\"key\":\"\",\"value\":\"x\"},{\"key\":\"clan\",\"value\":\"  \"}]}\n```";
+    assert_eq!(parse_facts(reply), [fact("ign", "Tenno01")]);
+    assert!(parse_facts("no facts here").is_empty());
+    assert!(parse_facts("{not json}").is_empty());
+    assert!(parse_facts("{\"facts\":[]}").is_empty());
+}
+
+#[test]
+fn merge_replaces_by_key_and_keeps_the_newest() {
+    let existing = [fact("ign", "old"), fact("timezone", "UTC+7")];
+    assert_eq!(
+        merge(
+            &existing,
+            vec![fact("ign", "new"), fact("clan", "Lotus")]
+        ),
+        [fact("timezone", "UTC+7"), fact("ign", "new"), fact("clan", "Lotus")]
+    );
+
+    let full: Vec<MemoryFact> = (0..MAX_FACTS)
+        .map(|i| fact(&format!("k{i}"), "v"))
+        .collect();
+    let merged = merge(&full, vec![fact("extra", "v")]);
+    assert_eq!(merged.len(), MAX_FACTS);
+    assert_eq!(merged[0].key, "k1");
+    assert_eq!(merged[MAX_FACTS - 1].key, "extra");
+}
+
+#[test]
+fn system_section_lists_facts() {
+    assert_eq!(system_section("alice", &[]), None);
+    assert_eq!(
+        system_section("alice", &[fact("ign", "Tenno01")]).unwrap(),
+        "What you remember about alice from earlier conversations (may be outdated):\n- ign: Tenno01"
+    );
+}
+
+#[tokio::test]
+async fn store_load_and_forget() {
+    let ctx = ContextBuilder::new()
+        .watchers(false)
+        .build()
+        .await
+        .expect("failed to build context");
+    let user_id = 7_035_001;
+
+    assert!(load(&ctx, user_id).await.is_empty());
+    store(
+        &ctx,
+        user_id,
+        vec![fact("ign", "Tenno01"), fact("timezone", "UTC+7")],
+    )
+    .await
+    .unwrap();
+    assert_eq!(load(&ctx, user_id).await.len(), 2);
+
+    let choices = fact_choices(&ctx, user_id, "TIME").await;
+    assert_eq!(choices.len(), 1);
+    assert_eq!(choices[0].name, "timezone: UTC+7");
+
+    assert!(
+        forget(&ctx, user_id, "IGN")
+            .await
+            .unwrap()
+    );
+    assert!(
+        !forget(&ctx, user_id, "ign")
+            .await
+            .unwrap()
+    );
+    assert_eq!(
+        load(&ctx, user_id).await,
+        [fact("timezone", "UTC+7")]
+    );
+    purge_cache(&ctx.redis, user_id).await;
+}
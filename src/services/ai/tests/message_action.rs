@@ -0,0 +1,25 @@
+use super::*;
+
+#[test]
+fn command_names_round_trip() {
+    for action in MessageAction::ALL {
+        assert_eq!(
+            MessageAction::from_command_name(action.command_name()),
+            Some(action)
+        );
+    }
+    assert_eq!(MessageAction::from_command_name("ai"), None);
+}
+
+#[test]
+fn input_carries_the_target_text() {
+    let input = MessageAction::Translate.input("alice", "  สวัสดีครับ \n");
+    assert!(input.starts_with("Translate the message below."));
+    assert!(input.ends_with("\n\nMessage from alice:\nสวัสดีครับ"));
+}
+
+#[test]
+fn input_mentions_attachment_only_messages() {
+    let input = MessageAction::Ask.input("bob", "   ");
+    assert!(input.ends_with("The message from bob has only attachments."));
+}
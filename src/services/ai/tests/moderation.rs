@@ -0,0 +1,47 @@
+use super::*;
+
+#[test]
+fn contains_link_finds_urls_and_invites() {
+    assert!(contains_link(
+        "free nitro at HTTPS://dlscord.gift/abc"
+    ));
+    assert!(contains_link("join discord.gg/free-skins"));
+    assert!(contains_link("see www.example.com"));
+    assert!(!contains_link("anyone up for a Kuva lich hunt?"));
+}
+
+#[test]
+fn joined_recently_ignores_unknown_and_old_members() {
+    let now = 1_700_000_000;
+    assert!(joined_recently(Some(now - 60), now));
+    assert!(joined_recently(Some(now - RECENT_JOIN_SECS), now));
+    assert!(!joined_recently(
+        Some(now - RECENT_JOIN_SECS - 1),
+        now
+    ));
+    assert!(!joined_recently(None, now));
+}
+
+#[test]
+fn parse_verdict_requires_the_schema_and_clamps_confidence() {
+    let verdict = parse_verdict(
+        r#" {"is_scam":true,"confidence":1.4,"category":"nitro","reason":"fake gift"} "#,
+    )
+    .unwrap();
+    assert_eq!(verdict.confidence, 1.0);
+    assert!(verdict.blocks());
+
+    assert_eq!(parse_verdict(r#"{"is_scam":true}"#), None);
+    assert_eq!(parse_verdict("yes, this is a scam"), None);
+}
+
+#[test]
+fn low_confidence_scams_do_not_block() {
+    let verdict = ScamVerdict {
+        is_scam: true,
+        confidence: BLOCK_CONFIDENCE - 0.1,
+        category: "other".to_string(),
+        reason: "unsure".to_string(),
+    };
+    assert!(!verdict.blocks());
+}
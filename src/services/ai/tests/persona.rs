@@ -0,0 +1,200 @@
+use super::*;
+use crate::context::ContextBuilder;
+use std::{borrow::Cow, time::Duration};
+
+fn spec(name: &'static str) -> ModelSpec {
+    ModelSpec {
+        name: Cow::Borrowed(name),
+        rpm_limit: 10,
+        queue_timeout: Duration::from_secs(1),
+        cooldown: Duration::from_secs(1),
+    }
+}
+
+fn persona(guild_id: u64, name: &str, models: &[&str]) -> AiPersona {
+    AiPersona {
+        id: None,
+        guild_id,
+        name: name.to_string(),
+        system_prompt: format!("You are {name}."),
+        models: models
+            .iter()
+            .map(|m| m.to_string())
+            .collect(),
+        temperature: None,
+    }
+}
+
+#[test]
+fn names_are_normalized_and_validated() {
+    assert_eq!(
+        normalize_name("  Code Reviewer "),
+        Some("code-reviewer".to_string())
+    );
+    assert_eq!(
+        normalize_name("ครู_ไทย"),
+        Some("ครู_ไทย".to_string())
+    );
+    assert_eq!(normalize_name(""), None);
+    assert_eq!(normalize_name("no/slashes"), None);
+    assert_eq!(
+        normalize_name(&"a".repeat(MAX_NAME_CHARS + 1)),
+        None
+    );
+}
+
+#[test]
+fn parse_models_rejects_unknown_and_dedupes() {
+    let chain = [spec("fast"), spec("smart")];
+    assert_eq!(
+        parse_models("smart, fast ,smart,", &chain),
+        Ok(vec!["smart".to_string(), "fast".to_string()])
+    );
+    assert_eq!(
+        parse_models("fast,huge", &chain),
+        Err("huge".to_string())
+    );
+}
+
+#[test]
+fn model_chain_follows_persona_order_and_falls_back() {
+    let chain = [spec("a"), spec("b"), spec("c")];
+    let names = |specs: Vec<ModelSpec>| {
+        specs
+            .into_iter()
+            .map(|s| s.name.into_owned())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(names(model_chain(&chain, None)), ["a", "b", "c"]);
+    assert_eq!(
+        names(model_chain(
+            &chain,
+            Some(&persona(1, "p", &["c", "a"]))
+        )),
+        ["c", "a"]
+    );
+    assert_eq!(
+        names(model_chain(
+            &chain,
+            Some(&persona(1, "p", &["gone"]))
+        )),
+        ["a", "b", "c"]
+    );
+}
+
+#[test]
+fn system_prompt_layers_base_persona_then_user() {
+    let p = persona(1, "pirate", &[]);
+    let system = compose_system(
+        "Base.",
+        Some("alice"),
+        Some(&p),
+        Some("Be brief."),
+    );
+    assert_eq!(
+        system,
+        "Base.\nYou are chatting with alice\n\nPersona \"pirate\":\nYou are pirate.\n\nUser instructions:\nBe brief."
+    );
+    assert_eq!(
+        compose_system("Base.", Some("bob"), None, None),
+        "Base.\nYou are chatting with bob"
+    );
+    assert_eq!(compose_system("Base.", None, None, None), "Base.");
+}
+
+#[tokio::test]
+async fn selection_follows_saved_and_removed_personas() {
+    let ctx = ContextBuilder::new()
+        .watchers(false)
+        .build()
+        .await
+        .expect("failed to build context");
+    let guild_id = 7_032_001;
+    let user_id = 7_032_002;
+
+    assert_eq!(
+        save(&ctx, persona(guild_id, "helper", &[]))
+            .await
+            .unwrap(),
+        SaveOutcome::Created
+    );
+    let mut updated = persona(guild_id, "helper", &[]);
+    updated.temperature = Some(0.5);
+    assert_eq!(
+        save(&ctx, updated).await.unwrap(),
+        SaveOutcome::Updated
+    );
+    assert_eq!(list(&ctx, guild_id).await.len(), 1);
+
+    assert!(
+        selected(&ctx, guild_id, user_id)
+            .await
+            .is_none()
+    );
+    select(&ctx, guild_id, user_id, Some("helper"))
+        .await
+        .unwrap();
+    let chosen = selected(&ctx, guild_id, user_id)
+        .await
+        .expect("persona selected");
+    assert_eq!(chosen.temperature, Some(0.5));
+
+    let choices = persona_choices(&ctx, guild_id, "HEL").await;
+    assert_eq!(choices.len(), 1);
+    assert_eq!(choices[0].name, "helper");
+
+    assert!(
+        remove(&ctx, guild_id, "helper")
+            .await
+            .unwrap()
+    );
+    assert!(
+        !remove(&ctx, guild_id, "helper")
+            .await
+            .unwrap()
+    );
+    assert!(
+        selected(&ctx, guild_id, user_id)
+            .await
+            .is_none()
+    );
+
+    select(&ctx, guild_id, user_id, None)
+        .await
+        .unwrap();
+    purge_choice_cache(&ctx.redis, guild_id, user_id).await;
+}
+
+#[tokio::test]
+async fn save_refuses_past_the_autocomplete_limit() {
+    let ctx = ContextBuilder::new()
+        .watchers(false)
+        .build()
+        .await
+        .expect("failed to build context");
+    let guild_id = 7_032_101;
+
+    for i in 0..MAX_PERSONAS {
+        let name = format!("p{i}");
+        assert_eq!(
+            save(&ctx, persona(guild_id, &name, &[]))
+                .await
+                .unwrap(),
+            SaveOutcome::Created
+        );
+    }
+    assert_eq!(
+        save(&ctx, persona(guild_id, "extra", &[]))
+            .await
+            .unwrap(),
+        SaveOutcome::LimitReached
+    );
+    assert_eq!(
+        save(&ctx, persona(guild_id, "p0", &[]))
+            .await
+            .unwrap(),
+        SaveOutcome::Updated
+    );
+    purge_cache(&ctx.redis, guild_id).await;
+}
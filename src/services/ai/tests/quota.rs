@@ -0,0 +1,89 @@
+use super::*;
+use crate::{context::ContextBuilder, services::guild_settings::GuildSettingsService};
+use chrono::TimeZone;
+
+#[test]
+fn next_reset_is_the_following_utc_midnight() {
+    let now = Utc
+        .with_ymd_and_hms(2026, 3, 31, 23, 59, 30)
+        .unwrap();
+    let reset = next_reset(now);
+    assert_eq!(
+        reset,
+        Utc.with_ymd_and_hms(2026, 4, 1, 0, 0, 0)
+            .unwrap()
+    );
+}
+
+#[test]
+fn resolve_limits_treats_zero_as_unlimited() {
+    let settings = AiQuotaSettings {
+        user_requests: Some(0),
+        user_tokens: Some(5_000),
+        guild_requests: Some(100),
+        guild_tokens: None,
+    };
+    let (user, guild) = resolve_limits(Some(&settings));
+    assert_eq!(
+        user,
+        QuotaLimits { requests: None, tokens: Some(5_000) }
+    );
+    assert_eq!(
+        guild,
+        QuotaLimits { requests: Some(100), tokens: None }
+    );
+}
+
+#[test]
+fn bucket_reports_request_limit_before_token_limit() {
+    let bucket = QuotaBucket {
+        usage: QuotaUsage { requests: 3, tokens: 900 },
+        limits: QuotaLimits { requests: Some(3), tokens: Some(500) },
+    };
+    assert_eq!(bucket.exceeded(), Some((QuotaKind::Requests, 3)));
+
+    let bucket = QuotaBucket {
+        usage: QuotaUsage { requests: 2, tokens: 900 },
+        limits: QuotaLimits { requests: Some(3), tokens: Some(500) },
+    };
+    assert_eq!(bucket.exceeded(), Some((QuotaKind::Tokens, 500)));
+}
+
+#[tokio::test]
+async fn guild_budget_blocks_after_recorded_usage() {
+    let ctx = ContextBuilder::new()
+        .watchers(false)
+        .build()
+        .await
+        .expect("failed to build context");
+    let guild_id = Id::<GuildMarker>::new(7_026_001);
+    let user = Id::<UserMarker>::new(7_026_002);
+
+    GuildSettingsService::set_ai_quota(
+        &ctx,
+        guild_id.get(),
+        AiQuotaSettings { guild_requests: Some(2), ..AiQuotaSettings::default() },
+    )
+    .await
+    .expect("failed to set quota");
+
+    assert_eq!(
+        check_quota(&ctx, Some(guild_id), user).await,
+        None
+    );
+    record_usage(&ctx, Some(guild_id), user, 120).await;
+    record_usage(&ctx, Some(guild_id), user, 80).await;
+
+    let status = quota_status(&ctx, Some(guild_id), user).await;
+    assert_eq!(
+        status.user.usage,
+        QuotaUsage { requests: 2, tokens: 200 }
+    );
+
+    let exceeded = check_quota(&ctx, Some(guild_id), user)
+        .await
+        .expect("guild budget should be exhausted");
+    assert_eq!(exceeded.scope, QuotaScope::Guild);
+    assert_eq!(exceeded.kind, QuotaKind::Requests);
+    assert_eq!(exceeded.limit, 2);
+}
@@ -0,0 +1,111 @@
+use super::*;
+use crate::{dbs::redis::new_pool, services::ai::genai::GenerationConfig};
+
+fn scope<'a>(
+    base_prompt: &'a str,
+    knowledge: &'a [KnowledgeHit],
+    options: &'a RequestOptions,
+    model: &'a str,
+) -> CacheScope<'a> {
+    CacheScope { base_prompt, persona: None, knowledge, options, model }
+}
+
+#[test]
+fn normalize_question_ignores_case_spacing_and_trailing_punctuation() {
+    assert_eq!(
+        normalize_question("  How do I get   Umbra Forma?? ").as_deref(),
+        Some("how do i get umbra forma")
+    );
+    assert_eq!(normalize_question(" ?! "), None);
+    assert_eq!(
+        normalize_question(&"a".repeat(MAX_QUESTION_CHARS + 1)),
+        None
+    );
+}
+
+#[test]
+fn model_family_strips_version_and_preview_suffixes() {
+    assert_eq!(
+        model_family("gemini-2.5-flash-preview-05-20"),
+        "gemini-2.5-flash"
+    );
+    assert_eq!(
+        model_family("gemini-2.0-flash-001"),
+        "gemini-2.0-flash"
+    );
+    assert_eq!(model_family("gemini-2.5-pro"), "gemini-2.5-pro");
+}
+
+#[test]
+fn cache_key_changes_with_prompt_knowledge_and_options() {
+    let options = RequestOptions::default();
+    let base = cache_key(
+        "q",
+        &scope("base", &[], &options, "gemini-2.5-flash"),
+    );
+
+    assert_eq!(
+        base,
+        cache_key(
+            "q",
+            &scope("base", &[], &options, "gemini-2.5-flash-001")
+        )
+    );
+    assert_ne!(
+        base,
+        cache_key(
+            "q",
+            &scope("other", &[], &options, "gemini-2.5-flash")
+        )
+    );
+    assert_ne!(
+        base,
+        cache_key(
+            "q",
+            &scope("base", &[], &options, "gemini-2.5-pro")
+        )
+    );
+
+    let hit = KnowledgeHit { title: "Rules".to_string(), part: 1, text: "No spam".to_string() };
+    assert_ne!(
+        base,
+        cache_key(
+            "q",
+            &scope(
+                "base",
+                std::slice::from_ref(&hit),
+                &options,
+                "gemini-2.5-flash"
+            )
+        )
+    );
+
+    let warm = RequestOptions {
+        generation: GenerationConfig { temperature: Some(1.5), ..GenerationConfig::default() },
+        ..RequestOptions::default()
+    };
+    assert_ne!(
+        base,
+        cache_key(
+            "q",
+            &scope("base", &[], &warm, "gemini-2.5-flash")
+        )
+    );
+}
+
+#[tokio::test]
+async fn stored_answers_are_found_by_key() {
+    let pool = new_pool();
+    let options = RequestOptions::default();
+    let key = cache_key(
+        "how do i get umbra forma 7039",
+        &scope("base", &[], &options, "gemini-2.5-flash"),
+    );
+    assert_eq!(lookup(&pool, &key).await, None);
+
+    redis_set_ex(&pool, &key, &"Profit-Taker", 60).await;
+    assert_eq!(
+        lookup(&pool, &key).await.as_deref(),
+        Some("Profit-Taker")
+    );
+}
@@ -0,0 +1,82 @@
+use super::*;
+
+fn response(json: &str) -> Response {
+    serde_json::from_str(json).expect("valid Gemini response")
+}
+
+#[test]
+fn blocked_prompt_is_withheld() {
+    let resp = response(
+        r#"{"promptFeedback":{"blockReason":"SAFETY","safetyRatings":[
+            {"category":"HARM_CATEGORY_HARASSMENT","probability":"HIGH","blocked":true}]}}"#,
+    );
+    assert_eq!(
+        classify(&resp, ""),
+        ReplyStatus::Withheld(WithheldReason::PromptBlocked)
+    );
+}
+
+#[test]
+fn finish_reasons_map_to_statuses() {
+    let cases = [
+        ("STOP", "hello", ReplyStatus::Complete),
+        ("MAX_TOKENS", "partial", ReplyStatus::Truncated),
+        (
+            "SAFETY",
+            "",
+            ReplyStatus::Withheld(WithheldReason::Safety),
+        ),
+        (
+            "RECITATION",
+            "",
+            ReplyStatus::Withheld(WithheldReason::Recitation),
+        ),
+        (
+            "SPII",
+            "",
+            ReplyStatus::Withheld(WithheldReason::Prohibited),
+        ),
+        (
+            "STOP",
+            "  ",
+            ReplyStatus::Withheld(WithheldReason::Empty),
+        ),
+        ("SOMETHING_NEW", "text", ReplyStatus::Complete),
+    ];
+    for (reason, text, expected) in cases {
+        let resp = response(&format!(
+            r#"{{"candidates":[{{"finishReason":"{reason}"}}]}}"#
+        ));
+        assert_eq!(
+            classify(&resp, text),
+            expected,
+            "finish reason {reason}"
+        );
+    }
+}
+
+#[test]
+fn missing_candidates_are_withheld() {
+    let resp = response("{}");
+    assert_eq!(
+        classify(&resp, ""),
+        ReplyStatus::Withheld(WithheldReason::NoCandidates)
+    );
+}
+
+#[test]
+fn only_configured_categories_are_sent() {
+    let settings = AiSafetySettings {
+        harassment: Some(AiSafetyThreshold::BlockOnlyHigh),
+        dangerous_content: Some(AiSafetyThreshold::Off),
+        ..AiSafetySettings::default()
+    };
+    let body = serde_json::to_value(safety_settings(&settings)).unwrap();
+    assert_eq!(
+        body,
+        serde_json::json!([
+            {"category": "HARM_CATEGORY_HARASSMENT", "threshold": "BLOCK_ONLY_HIGH"},
+            {"category": "HARM_CATEGORY_DANGEROUS_CONTENT", "threshold": "OFF"},
+        ])
+    );
+}
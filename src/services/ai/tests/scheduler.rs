@@ -67,3 +67,88 @@ async fn cooldown_blocks_until_it_expires() {
 
     assert!(start.elapsed() >= Duration::from_millis(20));
 }
+
+#[tokio::test]
+async fn retain_models_keeps_queues_with_guards_in_flight() {
+    let scheduler = AiScheduler::with_window(Duration::from_millis(50));
+    let guard = scheduler
+        .acquire(
+            "gemini-retired",
+            AiOperation::Chat,
+            AdmissionConfig { rpm_limit: 1, queue_timeout: Duration::from_millis(10) },
+        )
+        .await
+        .unwrap();
+
+    scheduler.retain_models(["gemini-2.5-flash"]);
+    assert_eq!(
+        scheduler.tracked_models(),
+        vec![Arc::<str>::from("gemini-retired")]
+    );
+
+    guard
+        .cool_down(Duration::from_millis(5))
+        .await;
+    scheduler.retain_models(["gemini-2.5-flash"]);
+    assert!(scheduler.tracked_models().is_empty());
+}
+
+#[tokio::test]
+async fn redis_backend_shares_the_window_between_schedulers() {
+    let redis = crate::dbs::redis::new_pool();
+    let first = AiScheduler::with_backend(
+        Duration::from_millis(200),
+        AdmissionBackend::Redis(redis.clone()),
+    );
+    let second = AiScheduler::with_backend(
+        Duration::from_millis(200),
+        AdmissionBackend::Redis(redis),
+    );
+    let cfg = AdmissionConfig { rpm_limit: 1, queue_timeout: Duration::from_millis(20) };
+
+    let _guard = first
+        .acquire("gemini-shared-window", AiOperation::Chat, cfg)
+        .await
+        .unwrap();
+    let result = second
+        .acquire("gemini-shared-window", AiOperation::Chat, cfg)
+        .await;
+
+    assert!(
+        result.is_err(),
+        "second instance should see the shared window"
+    );
+}
+
+#[tokio::test]
+async fn redis_backend_shares_cooldowns_between_schedulers() {
+    let redis = crate::dbs::redis::new_pool();
+    let first = AiScheduler::with_backend(
+        Duration::from_millis(10),
+        AdmissionBackend::Redis(redis.clone()),
+    );
+    let second = AiScheduler::with_backend(
+        Duration::from_millis(10),
+        AdmissionBackend::Redis(redis),
+    );
+
+    first
+        .cool_down(
+            "gemini-shared-cooldown",
+            AiOperation::Chat,
+            Duration::from_millis(25),
+        )
+        .await;
+
+    let start = std::time::Instant::now();
+    let _guard = second
+        .acquire(
+            "gemini-shared-cooldown",
+            AiOperation::Chat,
+            AdmissionConfig { rpm_limit: 5, queue_timeout: Duration::from_millis(60) },
+        )
+        .await
+        .unwrap();
+
+    assert!(start.elapsed() >= Duration::from_millis(20));
+}
@@ -0,0 +1,110 @@
+use super::*;
+use crate::context::ContextBuilder;
+
+fn row(user_id: Option<u64>, model: &str, requests: i64, tokens: i64) -> AiUsageDaily {
+    AiUsageDaily {
+        day: "2026-10-19".to_string(),
+        guild_id: Some(7_040_001),
+        user_id,
+        model: model.to_string(),
+        operation: "chat".to_string(),
+        requests,
+        prompt_tokens: tokens,
+        output_tokens: tokens,
+        ..AiUsageDaily::default()
+    }
+}
+
+#[test]
+fn build_report_ranks_users_models_and_errors() {
+    let rows = vec![
+        row(Some(1), "gemini-2.5-flash", 3, 100),
+        row(Some(2), "gemini-2.5-flash", 1, 500),
+        AiUsageDaily { fallbacks: 2, ..row(Some(1), "gemini-2.0-flash", 2, 10) },
+        AiUsageDaily {
+            errors_rate_limited: 4,
+            errors_fatal: 1,
+            ..row(None, "gemini-2.5-flash", 0, 0)
+        },
+    ];
+
+    let report = build_report(&rows, 7);
+
+    assert_eq!(report.requests, 6);
+    assert_eq!(report.fallbacks, 2);
+    assert!((report.fallback_rate() - 2.0 / 6.0).abs() < f64::EPSILON);
+    assert_eq!(
+        report.top_users,
+        vec![
+            UserUsage { user_id: 2, requests: 1, tokens: 1000 },
+            UserUsage { user_id: 1, requests: 5, tokens: 220 },
+        ]
+    );
+    assert_eq!(
+        report.models,
+        vec![("gemini-2.5-flash".to_string(), 4), ("gemini-2.0-flash".to_string(), 2)]
+    );
+    assert_eq!(
+        report.errors,
+        vec![(ErrorClass::RateLimited, 4), (ErrorClass::Fatal, 1)]
+    );
+}
+
+#[tokio::test]
+async fn recorded_calls_roll_up_into_the_guild_report() {
+    let ctx = ContextBuilder::new()
+        .watchers(false)
+        .build()
+        .await
+        .expect("failed to build context");
+    let guild_id = 7_040_002;
+    let scope = UsageScope::new(&ctx, Some(guild_id), Some(42));
+    let usage =
+        UsageMetadata { prompt_token_count: 30, candidates_token_count: 12, total_token_count: 42 };
+
+    scope
+        .record_success(
+            "gemini-2.5-flash",
+            AiOperation::Chat,
+            &usage,
+            false,
+        )
+        .await;
+    scope
+        .record_success(
+            "gemini-2.5-flash",
+            AiOperation::Chat,
+            &usage,
+            true,
+        )
+        .await;
+    scope
+        .record_error(
+            "gemini-2.5-pro",
+            AiOperation::Chat,
+            ErrorClass::Timeout,
+        )
+        .await;
+    UsageScope::new(&ctx, Some(guild_id + 1), Some(42))
+        .record_success(
+            "gemini-2.5-flash",
+            AiOperation::Chat,
+            &usage,
+            false,
+        )
+        .await;
+
+    let report = report(&ctx, guild_id, 7)
+        .await
+        .expect("failed to build usage report");
+
+    assert_eq!(report.requests, 2);
+    assert_eq!(report.prompt_tokens, 60);
+    assert_eq!(report.output_tokens, 24);
+    assert_eq!(report.fallbacks, 1);
+    assert_eq!(
+        report.top_users,
+        vec![UserUsage { user_id: 42, requests: 2, tokens: 84 }]
+    );
+    assert_eq!(report.errors, vec![(ErrorClass::Timeout, 1)]);
+}
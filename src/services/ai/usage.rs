@@ -0,0 +1,235 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+use futures::StreamExt;
+use mongodb::bson::{Document, doc};
+
+use super::{client::ErrorClass, genai::UsageMetadata, scheduler::AiOperation};
+use crate::{context::Context, dbs::mongo::models::ai_usage::AiUsageDaily};
+
+pub const MAX_REPORT_DAYS: i64 = 30;
+const TOP_USERS: usize = 5;
+
+/// Who a model call is accounted to.
+pub struct UsageScope<'a> {
+    ctx: &'a Context,
+    guild_id: Option<u64>,
+    user_id: Option<u64>,
+}
+
+impl<'a> UsageScope<'a> {
+    pub fn new(ctx: &'a Context, guild_id: Option<u64>, user_id: Option<u64>) -> Self {
+        Self { ctx, guild_id, user_id }
+    }
+
+    fn guild_label(&self) -> String {
+        self.guild_id
+            .map(|id| id.to_string())
+            .unwrap_or_else(|| "dm".to_string())
+    }
+
+    pub(crate) async fn record_success(
+        &self,
+        model: &str,
+        operation: AiOperation,
+        usage: &UsageMetadata,
+        fallback: bool,
+    ) {
+        let labels = [
+            ("model", model.to_string()),
+            ("operation", operation.as_str().to_string()),
+            ("guild", self.guild_label()),
+        ];
+        metrics::counter!("ai_prompt_tokens_total", &labels).increment(usage.prompt_token_count);
+        metrics::counter!("ai_output_tokens_total", &labels)
+            .increment(usage.candidates_token_count);
+        let result = if fallback { "fallback" } else { "primary" };
+        metrics::counter!(
+            "ai_model_requests_total",
+            "model" => model.to_string(),
+            "operation" => operation.as_str(),
+            "result" => result
+        )
+        .increment(1);
+
+        self.increment(
+            model,
+            operation,
+            doc! {
+                "requests": 1_i64,
+                "prompt_tokens": usage.prompt_token_count as i64,
+                "output_tokens": usage.candidates_token_count as i64,
+                "fallbacks": i64::from(fallback),
+            },
+        )
+        .await;
+    }
+
+    pub(crate) async fn record_error(
+        &self,
+        model: &str,
+        operation: AiOperation,
+        class: ErrorClass,
+    ) {
+        metrics::counter!(
+            "ai_model_errors_total",
+            "model" => model.to_string(),
+            "operation" => operation.as_str(),
+            "class" => class.as_str()
+        )
+        .increment(1);
+
+        self.increment(
+            model,
+            operation,
+            doc! { format!("errors_{}", class.as_str()): 1_i64 },
+        )
+        .await;
+    }
+
+    async fn increment(&self, model: &str, operation: AiOperation, inc: Document) {
+        let filter = doc! {
+            "day": day_key(Utc::now()),
+            "guild_id": self.guild_id.map(|id| id as i64),
+            "user_id": self.user_id.map(|id| id as i64),
+            "model": model,
+            "operation": operation.as_str(),
+        };
+        if let Err(e) = self
+            .ctx
+            .mongo
+            .ai_usage
+            .update_one(filter, doc! { "$inc": inc })
+            .upsert(true)
+            .await
+        {
+            tracing::warn!(model, error = %e, "failed to record ai usage");
+        }
+    }
+}
+
+pub(crate) fn day_key(at: DateTime<Utc>) -> String {
+    at.format("%Y-%m-%d").to_string()
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UserUsage {
+    pub user_id: u64,
+    pub requests: i64,
+    pub tokens: i64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UsageReport {
+    pub days: i64,
+    pub requests: i64,
+    pub prompt_tokens: i64,
+    pub output_tokens: i64,
+    pub fallbacks: i64,
+    /// Heaviest members by tokens, then requests.
+    pub top_users: Vec<UserUsage>,
+    /// Requests answered per model, busiest first.
+    pub models: Vec<(String, i64)>,
+    /// Failed model calls per error class, most frequent first.
+    pub errors: Vec<(ErrorClass, i64)>,
+}
+
+impl UsageReport {
+    pub fn fallback_rate(&self) -> f64 {
+        if self.requests == 0 { 0.0 } else { self.fallbacks as f64 / self.requests as f64 }
+    }
+}
+
+fn error_count(row: &AiUsageDaily, class: ErrorClass) -> i64 {
+    match class {
+        ErrorClass::RateLimited => row.errors_rate_limited,
+        ErrorClass::Timeout => row.errors_timeout,
+        ErrorClass::Unavailable => row.errors_unavailable,
+        ErrorClass::Transport => row.errors_transport,
+        ErrorClass::Fatal => row.errors_fatal,
+    }
+}
+
+pub(crate) fn build_report(rows: &[AiUsageDaily], days: i64) -> UsageReport {
+    let mut report = UsageReport { days, ..UsageReport::default() };
+    let mut users: HashMap<u64, UserUsage> = HashMap::new();
+    let mut models: HashMap<&str, i64> = HashMap::new();
+    let mut errors: HashMap<&'static str, (ErrorClass, i64)> = HashMap::new();
+
+    for row in rows {
+        report.requests += row.requests;
+        report.prompt_tokens += row.prompt_tokens;
+        report.output_tokens += row.output_tokens;
+        report.fallbacks += row.fallbacks;
+        if row.requests > 0 {
+            *models
+                .entry(row.model.as_str())
+                .or_default() += row.requests;
+        }
+        if let Some(user_id) = row.user_id {
+            let user = users
+                .entry(user_id)
+                .or_insert(UserUsage { user_id, ..UserUsage::default() });
+            user.requests += row.requests;
+            user.tokens += row.prompt_tokens + row.output_tokens;
+        }
+        for class in ErrorClass::ALL {
+            let count = error_count(row, class);
+            if count > 0 {
+                errors
+                    .entry(class.as_str())
+                    .or_insert((class, 0))
+                    .1 += count;
+            }
+        }
+    }
+
+    let mut top_users: Vec<UserUsage> = users
+        .into_values()
+        .filter(|user| user.requests > 0)
+        .collect();
+    top_users.sort_by(|a, b| {
+        b.tokens
+            .cmp(&a.tokens)
+            .then(b.requests.cmp(&a.requests))
+            .then(a.user_id.cmp(&b.user_id))
+    });
+    top_users.truncate(TOP_USERS);
+    report.top_users = top_users;
+
+    let mut models: Vec<(String, i64)> = models
+        .into_iter()
+        .map(|(model, requests)| (model.to_string(), requests))
+        .collect();
+    models.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    report.models = models;
+
+    let mut errors: Vec<(ErrorClass, i64)> = errors.into_values().collect();
+    errors.sort_by(|a, b| {
+        b.1.cmp(&a.1)
+            .then(a.0.as_str().cmp(b.0.as_str()))
+    });
+    report.errors = errors;
+
+    report
+}
+
+/// Usage in `guild_id` over the last `days` days, today included.
+pub async fn report(ctx: &Context, guild_id: u64, days: i64) -> anyhow::Result<UsageReport> {
+    let days = days.clamp(1, MAX_REPORT_DAYS);
+    let since = day_key(Utc::now() - Duration::days(days - 1));
+    let mut cursor = ctx
+        .mongo
+        .ai_usage
+        .find(doc! {"guild_id": guild_id as i64, "day": {"$gte": since}})
+        .await?;
+    let mut rows = Vec::new();
+    while let Some(row) = cursor.next().await {
+        rows.push(row?);
+    }
+    Ok(build_report(&rows, days))
+}
+
+#[cfg(test)]
+#[path = "tests/usage.rs"]
+mod tests;
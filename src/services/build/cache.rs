@@ -17,6 +17,8 @@ use crate::{
 use crate::utils::http::HttpProvider;
 use std::sync::Arc;
 
+use super::item::{ItemRecord, RawComponent, RawDrop, STAT_KEYS};
+
 const ITEMS_URL: &str =
     "https://raw.githubusercontent.com/WFCD/warframe-items/master/data/json/All.json";
 const REDIS_KEY: &str = "discord-bot:build-items";
@@ -26,17 +28,74 @@ pub(crate) static ITEMS: Lazy<RwLock<Vec<String>>> = Lazy::new(|| RwLock::new(Ve
 pub(crate) static LAST_UPDATE: Lazy<AtomicU64> = Lazy::new(|| AtomicU64::new(0));
 pub(crate) static ITEMS_ETAG: Lazy<RwLock<Option<String>>> = Lazy::new(|| RwLock::new(None));
 
+/// Every item in `All.json`, whatever its category, for `/warframe item`.
+pub(crate) static RECORDS: Lazy<RwLock<Vec<ItemRecord>>> = Lazy::new(|| RwLock::new(Vec::new()));
+
 #[derive(Deserialize)]
-struct Item {
-    name: String,
-    category: String,
-    #[serde(rename = "productCategory")]
-    product_category: Option<String>,
+#[serde(rename_all = "camelCase")]
+pub(super) struct Item {
+    pub name: String,
+    pub category: String,
+    pub product_category: Option<String>,
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+    pub mastery_req: Option<u8>,
+    pub description: Option<String>,
+    pub aura: Option<String>,
+    #[serde(default)]
+    pub polarities: Vec<String>,
+    #[serde(default)]
+    pub drops: Vec<RawDrop>,
+    #[serde(default)]
+    pub components: Vec<RawComponent>,
+    pub build_price: Option<u32>,
+    pub build_time: Option<u32>,
+    pub wikia_url: Option<String>,
+    pub vaulted: Option<bool>,
+    health: Option<f64>,
+    shield: Option<f64>,
+    armor: Option<f64>,
+    power: Option<f64>,
+    sprint_speed: Option<f64>,
+    total_damage: Option<f64>,
+    critical_chance: Option<f64>,
+    critical_multiplier: Option<f64>,
+    proc_chance: Option<f64>,
+    multishot: Option<f64>,
+    fire_rate: Option<f64>,
+    magazine_size: Option<f64>,
+    reload_time: Option<f64>,
+    range: Option<f64>,
+}
+
+impl Item {
+    /// Values in `STAT_KEYS` order.
+    pub(super) fn stats(&self) -> [Option<f64>; STAT_KEYS.len()] {
+        [
+            self.health,
+            self.shield,
+            self.armor,
+            self.power,
+            self.sprint_speed,
+            self.total_damage,
+            self.critical_chance,
+            self.critical_multiplier,
+            self.proc_chance,
+            self.multishot,
+            self.fire_rate,
+            self.magazine_size,
+            self.reload_time,
+            self.range,
+        ]
+    }
 }
 
 #[derive(Serialize, Deserialize)]
 struct StoredItems {
     names: Vec<String>,
+    /// Missing from lists stored before item lookups existed.
+    #[serde(default)]
+    records: Vec<ItemRecord>,
     etag: Option<String>,
 }
 
@@ -64,15 +123,42 @@ fn filter(item: &Item) -> bool {
     false
 }
 
-async fn load_from_redis(pool: &Pool) -> Option<Vec<String>> {
-    if let Some(stored) = redis_get::<StoredItems>(pool, REDIS_KEY).await {
-        *ITEMS_ETAG.write().await = stored.etag;
-        let mut names = stored.names;
-        names.sort_unstable_by(|a, b| cmp_ignore_ascii_case(a, b));
-        names.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
-        return Some(names);
+/// Sorted and deduplicated by name, the order `ITEMS` is kept in.
+fn sort_records(records: &mut Vec<ItemRecord>) {
+    records.sort_unstable_by(|a, b| cmp_ignore_ascii_case(&a.name, &b.name));
+    records.dedup_by(|a, b| a.name.eq_ignore_ascii_case(&b.name));
+}
+
+/// The names `/warframe build` offers and a record for every item.
+fn split_items(fetched: Vec<Item>) -> (Vec<String>, Vec<ItemRecord>) {
+    let mut names = Vec::new();
+    let mut records = Vec::with_capacity(fetched.len());
+    for item in fetched {
+        if filter(&item) {
+            names.push(item.name.clone());
+        }
+        records.push(ItemRecord::from(item));
     }
-    None
+    names.sort_unstable_by(|a, b| cmp_ignore_ascii_case(a, b));
+    names.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+    sort_records(&mut records);
+    (names, records)
+}
+
+async fn load_from_redis(pool: &Pool) -> Option<(Vec<String>, Vec<ItemRecord>)> {
+    let stored = redis_get::<StoredItems>(pool, REDIS_KEY).await?;
+    // Without records the list must be downloaded again, so its ETag is
+    // not kept either.
+    if stored.records.is_empty() && !stored.names.is_empty() {
+        return None;
+    }
+    *ITEMS_ETAG.write().await = stored.etag;
+    let mut names = stored.names;
+    names.sort_unstable_by(|a, b| cmp_ignore_ascii_case(a, b));
+    names.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+    let mut records = stored.records;
+    sort_records(&mut records);
+    Some((names, records))
 }
 
 pub(crate) async fn update_items<H>(client: &H, pool: &Pool) -> anyhow::Result<()>
@@ -109,18 +195,13 @@ where
         .and_then(|v| v.to_str().ok())
         .map(|s| s.to_string());
     let fetched: Vec<Item> = resp.json().await?;
-    let mut names: Vec<String> = fetched
-        .into_iter()
-        .filter(filter)
-        .map(|item| item.name)
-        .collect();
-    names.sort_unstable_by(|a, b| cmp_ignore_ascii_case(a, b));
-    names.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+    let (names, records) = split_items(fetched);
     *ITEMS.write().await = names.clone();
+    *RECORDS.write().await = records.clone();
     redis_set(
         pool,
         REDIS_KEY,
-        &StoredItems { names, etag: new_etag.clone() },
+        &StoredItems { names, records, etag: new_etag.clone() },
     )
     .await;
     *ITEMS_ETAG.write().await = new_etag;
@@ -138,8 +219,9 @@ use super::BuildService;
 
 impl BuildService {
     pub async fn init(ctx: Arc<Context>) {
-        if let Some(data) = load_from_redis(&ctx.redis).await {
-            *ITEMS.write().await = data;
+        if let Some((names, records)) = load_from_redis(&ctx.redis).await {
+            *ITEMS.write().await = names;
+            *RECORDS.write().await = records;
             LAST_UPDATE.store(
                 SystemTime::now()
                     .duration_since(UNIX_EPOCH)
@@ -176,6 +258,49 @@ impl BuildService {
         }
     }
 
+    pub async fn find_record(name: &str) -> Option<ItemRecord> {
+        let records = RECORDS.read().await;
+        let idx = records
+            .partition_point(|r| cmp_ignore_ascii_case(&r.name, name) == std::cmp::Ordering::Less);
+        records
+            .get(idx)
+            .filter(|r| r.name.eq_ignore_ascii_case(name))
+            .cloned()
+    }
+
+    /// Item names for `/warframe item`, from every category.
+    pub async fn search_records(prefix: &str) -> Vec<String> {
+        collect_prefix_icase(&RECORDS.read().await, prefix, |r| &r.name)
+    }
+
+    pub async fn search_records_with_update<H>(client: &H, pool: &Pool, prefix: &str) -> Vec<String>
+    where
+        H: HttpProvider + Sync,
+    {
+        let mut results = Self::search_records(prefix).await;
+        if results.is_empty() {
+            Self::maybe_refresh(client, pool).await;
+            results = Self::search_records(prefix).await;
+        }
+        results
+    }
+
+    /// Like `find_record`, refreshing a stale list once before giving up.
+    pub async fn find_record_with_update<H>(
+        client: &H,
+        pool: &Pool,
+        name: &str,
+    ) -> Option<ItemRecord>
+    where
+        H: HttpProvider + Sync,
+    {
+        if let Some(record) = Self::find_record(name).await {
+            return Some(record);
+        }
+        Self::maybe_refresh(client, pool).await;
+        Self::find_record(name).await
+    }
+
     pub async fn search_with_update<H>(client: &H, pool: &Pool, prefix: &str) -> Vec<String>
     where
         H: HttpProvider + Sync,
@@ -188,3 +313,7 @@ impl BuildService {
         results
     }
 }
+
+#[cfg(test)]
+#[path = "tests/cache.rs"]
+mod tests;
@@ -4,18 +4,74 @@ use twilight_model::{
     channel::message::Embed,
     id::{Id, marker::GuildMarker},
 };
-use twilight_util::builder::embed::{EmbedAuthorBuilder, EmbedBuilder, ImageSource};
+use twilight_util::builder::embed::{
+    EmbedAuthorBuilder, EmbedBuilder, EmbedFieldBuilder, ImageSource,
+};
 
-use crate::utils::embed::footer_with_icon;
+use crate::utils::{embed::footer_with_icon, locale::Lang};
 
 use super::{
     BuildService,
     client::{BuildData, MAX_BUILDS},
+    item::{ItemDrop, ItemRecord},
 };
 
 const COLOR: u32 = 0xF1C40F;
 const BASE_URL: &str = "https://overframe.gg";
 const ICON_URL: &str = "https://static.overframe.gg/static/images/logos/logo-64.png";
+/// Components listed before the rest are summarized as a count.
+const MAX_COMPONENTS: usize = 8;
+const MAX_DESCRIPTION: usize = 300;
+
+fn stat_label(key: &str) -> &str {
+    match key {
+        "health" => "Health",
+        "shield" => "Shield",
+        "armor" => "Armor",
+        "power" => "Energy",
+        "sprintSpeed" => "Sprint Speed",
+        "totalDamage" => "Damage",
+        "criticalChance" => "Crit Chance",
+        "criticalMultiplier" => "Crit Multiplier",
+        "procChance" => "Status Chance",
+        "multishot" => "Multishot",
+        "fireRate" => "Fire Rate",
+        "magazineSize" => "Magazine",
+        "reloadTime" => "Reload",
+        "range" => "Range",
+        _ => key,
+    }
+}
+
+fn format_number(value: f64) -> String {
+    if value.fract() == 0.0 { format!("{value:.0}") } else { format!("{value:.2}") }
+}
+
+fn format_stat(key: &str, value: f64) -> String {
+    match key {
+        "criticalChance" | "procChance" => format!("{:.1}%", value * 100.0),
+        "criticalMultiplier" => format!("{}x", format_number(value)),
+        "reloadTime" => format!("{}s", format_number(value)),
+        _ => format_number(value),
+    }
+}
+
+fn drop_line(drop: &ItemDrop) -> String {
+    format!("{} — {:.2}%", drop.location, drop.chance * 100.0)
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .map(|first| {
+            first
+                .to_uppercase()
+                .chain(chars)
+                .collect()
+        })
+        .unwrap_or_default()
+}
 
 impl BuildService {
     pub(super) fn build_not_found_embed(
@@ -90,4 +146,143 @@ impl BuildService {
             Ok(embeds)
         }
     }
+
+    pub fn item_not_found_embed(
+        guild: &Reference<'_, Id<GuildMarker>, CachedGuild>,
+        lang: Lang,
+    ) -> anyhow::Result<Embed> {
+        let mut footer = footer_with_icon(guild)?;
+        footer.text = guild.name().to_string();
+        let embed = EmbedBuilder::new()
+            .color(COLOR)
+            .title(lang.pick("ไม่พบไอเทม", "Item not found"))
+            .description(lang.pick(
+                "กรุณาเลือกไอเทมจากรายการที่แนะนำ",
+                "Please pick an item from the suggestions",
+            ))
+            .footer(footer)
+            .build();
+        Ok(embed)
+    }
+
+    pub fn item_embed(
+        guild: &Reference<'_, Id<GuildMarker>, CachedGuild>,
+        record: &ItemRecord,
+        lang: Lang,
+    ) -> anyhow::Result<Embed> {
+        let mut footer = footer_with_icon(guild)?;
+        footer.text = format!("{} · WFCD", guild.name());
+
+        let mut description = record
+            .description
+            .as_deref()
+            .map(|d| match d.char_indices().nth(MAX_DESCRIPTION) {
+                Some((end, _)) => format!("{}…", &d[..end]),
+                None => d.to_string(),
+            })
+            .unwrap_or_default();
+        if record.vaulted {
+            if !description.is_empty() {
+                description.push_str("\n\n");
+            }
+            description.push_str(lang.pick("🔒 อยู่ใน Prime Vault", "🔒 In the Prime Vault"));
+        }
+
+        let mut builder = EmbedBuilder::new()
+            .color(COLOR)
+            .title(&record.name)
+            .field(
+                EmbedFieldBuilder::new(
+                    lang.pick("ประเภท", "Type"),
+                    record
+                        .kind
+                        .as_deref()
+                        .unwrap_or(&record.category),
+                )
+                .inline(),
+            );
+        if !description.is_empty() {
+            builder = builder.description(description);
+        }
+        if let Some(wiki) = &record.wiki {
+            builder = builder.url(wiki);
+        }
+        if let Some(mastery) = record.mastery {
+            builder =
+                builder.field(EmbedFieldBuilder::new("Mastery", format!("MR {mastery}")).inline());
+        }
+        if record.aura.is_some() || !record.polarities.is_empty() {
+            let mut slots = Vec::new();
+            if let Some(aura) = &record.aura {
+                slots.push(format!("Aura {}", capitalize(aura)));
+            }
+            slots.extend(
+                record
+                    .polarities
+                    .iter()
+                    .map(|p| capitalize(p)),
+            );
+            builder = builder.field(EmbedFieldBuilder::new("Polarity", slots.join(", ")).inline());
+        }
+        if !record.stats.is_empty() {
+            let lines = record
+                .stats
+                .iter()
+                .map(|(key, value)| {
+                    format!(
+                        "{}: **{}**",
+                        stat_label(key),
+                        format_stat(key, *value)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            builder = builder.field(EmbedFieldBuilder::new(
+                lang.pick("ค่าพื้นฐาน", "Base stats"),
+                lines,
+            ));
+        }
+        if !record.drops.is_empty() {
+            let lines = record
+                .drops
+                .iter()
+                .map(drop_line)
+                .collect::<Vec<_>>()
+                .join("\n");
+            builder = builder.field(EmbedFieldBuilder::new(
+                lang.pick("แหล่งดรอป", "Drops"),
+                lines,
+            ));
+        }
+        if !record.components.is_empty() {
+            let mut lines: Vec<String> = record
+                .components
+                .iter()
+                .take(MAX_COMPONENTS)
+                .map(|c| match c.drops.first() {
+                    Some(drop) => format!("{}× {} · {}", c.count, c.name, drop_line(drop)),
+                    None => format!("{}× {}", c.count, c.name),
+                })
+                .collect();
+            if record.components.len() > MAX_COMPONENTS {
+                lines.push(format!(
+                    "{} {}",
+                    lang.pick("และอีก", "and"),
+                    record.components.len() - MAX_COMPONENTS
+                ));
+            }
+            if let Some(price) = record.build_price {
+                let hours = record.build_time.unwrap_or_default() / 3600;
+                lines.push(format!("{price} credits · {hours}h"));
+            }
+            builder = builder.field(EmbedFieldBuilder::new(
+                lang.pick("ส่วนประกอบ", "Components"),
+                lines.join("\n"),
+            ));
+        }
+        Ok(builder
+            .footer(footer)
+            .validate()?
+            .build())
+    }
 }
@@ -0,0 +1,152 @@
+use serde::{Deserialize, Serialize};
+
+use super::cache::Item;
+
+/// Drop locations kept per item or component, best chance first.
+pub(super) const MAX_DROPS: usize = 3;
+
+/// `All.json` keys shown as base stats, in display order.
+pub(super) const STAT_KEYS: [&str; 14] = [
+    "health",
+    "shield",
+    "armor",
+    "power",
+    "sprintSpeed",
+    "totalDamage",
+    "criticalChance",
+    "criticalMultiplier",
+    "procChance",
+    "multishot",
+    "fireRate",
+    "magazineSize",
+    "reloadTime",
+    "range",
+];
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ItemDrop {
+    pub location: String,
+    /// Between 0 and 1.
+    pub chance: f64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ItemComponent {
+    pub name: String,
+    pub count: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub drops: Vec<ItemDrop>,
+}
+
+/// What `/warframe item` shows, trimmed from WFCD's record so the whole
+/// list stays small in Redis.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ItemRecord {
+    pub name: String,
+    pub category: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mastery: Option<u8>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// `(key, value)` for the keys in `STAT_KEYS` the item has.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stats: Vec<(String, f64)>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aura: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub polarities: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub drops: Vec<ItemDrop>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<ItemComponent>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_price: Option<u32>,
+    /// Seconds in the foundry.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_time: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wiki: Option<String>,
+    #[serde(default)]
+    pub vaulted: bool,
+}
+
+#[derive(Deserialize)]
+pub(super) struct RawDrop {
+    location: String,
+    chance: Option<f64>,
+}
+
+#[derive(Deserialize)]
+pub(super) struct RawComponent {
+    name: String,
+    #[serde(rename = "itemCount", default)]
+    item_count: u32,
+    #[serde(default)]
+    drops: Vec<RawDrop>,
+}
+
+fn best_drops(drops: Vec<RawDrop>) -> Vec<ItemDrop> {
+    let mut drops: Vec<ItemDrop> = drops
+        .into_iter()
+        .filter_map(|d| {
+            d.chance
+                .map(|chance| ItemDrop { location: d.location, chance })
+        })
+        .collect();
+    drops.sort_by(|a, b| b.chance.total_cmp(&a.chance));
+    let mut best: Vec<ItemDrop> = Vec::with_capacity(MAX_DROPS);
+    for drop in drops {
+        if best.len() == MAX_DROPS {
+            break;
+        }
+        if !best
+            .iter()
+            .any(|b| b.location == drop.location)
+        {
+            best.push(drop);
+        }
+    }
+    best
+}
+
+impl From<Item> for ItemRecord {
+    fn from(item: Item) -> Self {
+        let stats = STAT_KEYS
+            .iter()
+            .zip(item.stats())
+            .filter_map(|(key, value)| value.map(|v| (key.to_string(), v)))
+            .collect();
+        Self {
+            name: item.name,
+            category: item.category,
+            kind: item.kind,
+            mastery: item.mastery_req,
+            description: item
+                .description
+                .filter(|d| !d.is_empty()),
+            stats,
+            aura: item.aura,
+            polarities: item.polarities,
+            drops: best_drops(item.drops),
+            components: item
+                .components
+                .into_iter()
+                .map(|c| ItemComponent {
+                    name: c.name,
+                    count: c.item_count,
+                    drops: best_drops(c.drops),
+                })
+                .collect(),
+            build_price: item.build_price,
+            build_time: item.build_time,
+            wiki: item.wikia_url,
+            vaulted: item.vaulted.unwrap_or(false),
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/item.rs"]
+mod tests;
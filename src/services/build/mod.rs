@@ -1,6 +1,7 @@
 pub mod cache;
 pub mod client;
 pub mod embed;
+pub mod item;
 
 use twilight_cache_inmemory::{Reference, model::CachedGuild};
 use twilight_model::{
@@ -0,0 +1,27 @@
+use serde_json::json;
+
+use super::*;
+
+#[test]
+fn test_split_items_keeps_records_for_every_category() {
+    let fetched: Vec<Item> = serde_json::from_value(json!([
+        {"name": "Rubico Prime", "category": "Primary"},
+        {"name": "Neurodes", "category": "Misc"},
+        {"name": "Serration", "category": "Mods"},
+        {"name": "Lato", "category": "Misc", "productCategory": "Pistols"},
+        {"name": "Excalibur", "category": "Warframes"},
+    ]))
+    .unwrap();
+
+    let (names, records) = split_items(fetched);
+
+    assert_eq!(names, ["Excalibur", "Lato", "Rubico Prime"]);
+    let records: Vec<&str> = records
+        .iter()
+        .map(|r| r.name.as_str())
+        .collect();
+    assert_eq!(
+        records,
+        ["Excalibur", "Lato", "Neurodes", "Rubico Prime", "Serration"]
+    );
+}
@@ -0,0 +1,101 @@
+use serde_json::json;
+
+use super::*;
+
+fn parse(value: serde_json::Value) -> ItemRecord {
+    let item: Item = serde_json::from_value(value).unwrap();
+    ItemRecord::from(item)
+}
+
+#[test]
+fn test_record_from_weapon() {
+    let record = parse(json!({
+        "name": "Rubico Prime",
+        "category": "Primary",
+        "type": "Sniper Rifle",
+        "masteryReq": 13,
+        "description": "A sniper rifle.",
+        "polarities": ["madurai", "naramon"],
+        "criticalChance": 0.38,
+        "criticalMultiplier": 3.0,
+        "procChance": 0.225,
+        "totalDamage": 187,
+        "uniqueName": "/Lotus/Weapons/Tenno/LongGuns/PrimeRubico",
+        "vaulted": true,
+        "wikiaUrl": "https://warframe.fandom.com/wiki/Rubico_Prime",
+        "components": [{
+            "name": "Barrel",
+            "itemCount": 1,
+            "drops": [
+                {"location": "Lith R1 Relic", "chance": 0.02, "rarity": "Rare"},
+                {"location": "Meso R2 Relic", "chance": 0.11, "rarity": "Uncommon"},
+            ],
+        }, {
+            "name": "Orokin Cell",
+            "itemCount": 10,
+        }],
+        "buildPrice": 25000,
+        "buildTime": 86400,
+    }));
+
+    assert_eq!(record.kind.as_deref(), Some("Sniper Rifle"));
+    assert_eq!(record.mastery, Some(13));
+    assert!(record.vaulted);
+    assert_eq!(
+        record.stats,
+        vec![
+            ("totalDamage".to_string(), 187.0),
+            ("criticalChance".to_string(), 0.38),
+            ("criticalMultiplier".to_string(), 3.0),
+            ("procChance".to_string(), 0.225),
+        ]
+    );
+    assert_eq!(record.components.len(), 2);
+    assert_eq!(
+        record.components[0].drops[0].location,
+        "Meso R2 Relic"
+    );
+    assert!(record.components[1].drops.is_empty());
+    assert_eq!(record.build_time, Some(86400));
+}
+
+#[test]
+fn test_best_drops_keeps_top_locations() {
+    let record = parse(json!({
+        "name": "Neurodes",
+        "category": "Misc",
+        "drops": [
+            {"location": "Earth/Lith", "chance": 0.05},
+            {"location": "Deimos/Cambion Drift", "chance": 0.2},
+            {"location": "Earth/Lith", "chance": 0.3},
+            {"location": "Eris/Xini", "chance": 0.1},
+            {"location": "Unknown", "chance": null},
+            {"location": "Void/Mot", "chance": 0.15},
+        ],
+    }));
+
+    let locations: Vec<&str> = record
+        .drops
+        .iter()
+        .map(|d| d.location.as_str())
+        .collect();
+    assert_eq!(locations.len(), MAX_DROPS);
+    assert_eq!(
+        locations,
+        ["Earth/Lith", "Deimos/Cambion Drift", "Void/Mot"]
+    );
+}
+
+#[test]
+fn test_compact_serialization() {
+    let record = parse(json!({"name": "Excalibur", "category": "Warframes"}));
+    let stored = serde_json::to_value(&record).unwrap();
+    assert_eq!(
+        stored,
+        json!({"name": "Excalibur", "category": "Warframes", "vaulted": false})
+    );
+    assert_eq!(
+        serde_json::from_value::<ItemRecord>(stored).unwrap(),
+        record
+    );
+}
@@ -0,0 +1,137 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use tokio::{sync::RwLock, task::JoinHandle};
+
+use crate::{
+    context::Context,
+    dbs::redis::{redis_get, redis_set},
+    services::shutdown,
+    utils::ascii::collect_prefix_icase,
+};
+
+use super::{FarmItem, FarmService, client};
+
+const REDIS_KEY: &str = "discord-bot:farm-drops";
+/// The drop tables change with game updates, a few times a month.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+/// Shortest wait before the next check, so a failed start-up download is
+/// retried without hammering the server.
+const RETRY_DELAY: Duration = Duration::from_secs(10 * 60);
+
+pub(crate) static ITEMS: Lazy<RwLock<Vec<FarmItem>>> = Lazy::new(|| RwLock::new(Vec::new()));
+pub(crate) static ETAG: Lazy<RwLock<Option<String>>> = Lazy::new(|| RwLock::new(None));
+static UPDATED_AT: Lazy<AtomicU64> = Lazy::new(|| AtomicU64::new(0));
+
+#[derive(Serialize, Deserialize)]
+struct StoredDrops {
+    items: Vec<FarmItem>,
+    etag: Option<String>,
+    /// Unix seconds of the last successful check, changed or not; missing
+    /// from tables stored before it was tracked, which makes them stale.
+    #[serde(default)]
+    updated_at: u64,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// How long until tables checked at `updated_at` are due again.
+fn until_stale(updated_at: u64, now: u64) -> Duration {
+    REFRESH_INTERVAL.saturating_sub(Duration::from_secs(
+        now.saturating_sub(updated_at),
+    ))
+}
+
+impl FarmService {
+    async fn update(ctx: &Context) -> anyhow::Result<()> {
+        let etag = ETAG.read().await.clone();
+        if let Some((rows, new_etag)) = client::fetch_drops(&ctx.reqwest, etag.as_deref()).await? {
+            *ITEMS.write().await = client::build_index(rows);
+            *ETAG.write().await = new_etag;
+        }
+        let updated_at = now_secs();
+        UPDATED_AT.store(updated_at, Ordering::Relaxed);
+        redis_set(
+            &ctx.redis,
+            REDIS_KEY,
+            &StoredDrops {
+                items: ITEMS.read().await.clone(),
+                etag: ETAG.read().await.clone(),
+                updated_at,
+            },
+        )
+        .await;
+        Ok(())
+    }
+
+    /// Loads the stored drop tables, checking them again right away when
+    /// they are missing or older than `REFRESH_INTERVAL`.
+    pub async fn init(ctx: Arc<Context>) {
+        if let Some(stored) = redis_get::<StoredDrops>(&ctx.redis, REDIS_KEY).await {
+            *ITEMS.write().await = stored.items;
+            *ETAG.write().await = stored.etag;
+            UPDATED_AT.store(stored.updated_at, Ordering::Relaxed);
+        }
+        if until_stale(UPDATED_AT.load(Ordering::Relaxed), now_secs()).is_zero()
+            && let Err(e) = Self::update(&ctx).await
+        {
+            tracing::warn!(error = %e, "failed to update farm drops");
+        }
+    }
+
+    /// Loads the drop tables, then re-checks them every `REFRESH_INTERVAL`
+    /// counted from the last check; an unchanged ETag skips the download.
+    pub fn spawn(ctx: &Arc<Context>) -> JoinHandle<()> {
+        let ctx = ctx.clone();
+        tokio::spawn(async move {
+            Self::init(ctx.clone()).await;
+            let token = shutdown::get_token();
+            let due = until_stale(UPDATED_AT.load(Ordering::Relaxed), now_secs());
+            let start = tokio::time::Instant::now() + due.max(RETRY_DELAY);
+            let mut interval = tokio::time::interval_at(start, REFRESH_INTERVAL);
+            loop {
+                tokio::select! {
+                    _ = token.cancelled() => break,
+                    _ = interval.tick() => {
+                        if let Err(e) = Self::update(&ctx).await {
+                            tracing::warn!(error = %e, "failed to update farm drops");
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    pub async fn search(prefix: &str) -> Vec<String> {
+        let items = ITEMS.read().await;
+        if items.is_empty() {
+            return Vec::new();
+        }
+        collect_prefix_icase(&items, prefix, |item| &item.name)
+    }
+
+    pub async fn find_item(name: &str) -> Option<FarmItem> {
+        ITEMS
+            .read()
+            .await
+            .iter()
+            .find(|item| item.name.eq_ignore_ascii_case(name))
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/cache.rs"]
+mod tests;
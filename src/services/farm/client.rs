@@ -0,0 +1,153 @@
+use std::collections::{BTreeMap, HashSet};
+
+use serde::Deserialize;
+
+use crate::utils::{ascii::cmp_ignore_ascii_case, http::HttpProvider};
+
+use super::{FarmItem, MissionSource, RelicSource};
+
+const DROPS_URL: &str = "https://drops.warframestat.us/data/all.slim.json";
+/// Relic tiers in fissure order, matching `Fissure::tier`.
+pub(super) const TIERS: [&str; 5] = ["Lith", "Meso", "Neo", "Axi", "Requiem"];
+/// Mission drop tables kept per item, best chance first.
+pub(super) const MAX_MISSIONS: usize = 5;
+
+/// One row of the drop tables; `chance` is a percentage.
+#[derive(Deserialize)]
+pub(super) struct DropRow {
+    pub place: String,
+    pub item: String,
+    #[serde(default)]
+    pub rarity: String,
+    pub chance: f64,
+}
+
+/// `Lith X1 Relic (Intact)` as `("Lith X1", Some("Intact"))`.
+pub(super) fn relic_name(place: &str) -> Option<(&str, Option<&str>)> {
+    let (base, state) = match place.strip_suffix(')') {
+        Some(rest) => {
+            let (base, state) = rest.rsplit_once(" (")?;
+            (base, Some(state))
+        }
+        None => (place, None),
+    };
+    let name = base.strip_suffix(" Relic")?;
+    let tier = name.split_whitespace().next()?;
+    TIERS
+        .contains(&tier)
+        .then_some((name, state))
+}
+
+pub(super) fn tier_index(relic: &str) -> usize {
+    relic
+        .split_whitespace()
+        .next()
+        .and_then(|tier| TIERS.iter().position(|t| *t == tier))
+        .unwrap_or(TIERS.len())
+}
+
+/// Groups drop rows by item. A relic no mission drops any more is vaulted.
+pub(super) fn build_index(rows: Vec<DropRow>) -> Vec<FarmItem> {
+    let droppable: HashSet<String> = rows
+        .iter()
+        .filter(|row| relic_name(&row.place).is_none())
+        .filter_map(|row| relic_name(&row.item).map(|(name, _)| name.to_string()))
+        .collect();
+
+    let mut relics: BTreeMap<String, BTreeMap<String, RelicSource>> = BTreeMap::new();
+    let mut missions: BTreeMap<String, Vec<MissionSource>> = BTreeMap::new();
+    for row in rows {
+        match relic_name(&row.place) {
+            Some((relic, state)) => {
+                let source = RelicSource {
+                    vaulted: !droppable.contains(relic),
+                    relic: relic.to_string(),
+                    rarity: row.rarity,
+                    chance: row.chance,
+                };
+                let by_relic = relics.entry(row.item).or_default();
+                // Intact odds are the ones a fresh relic is opened with.
+                if state.is_none_or(|s| s == "Intact") {
+                    by_relic.insert(source.relic.clone(), source);
+                } else {
+                    by_relic
+                        .entry(source.relic.clone())
+                        .or_insert(source);
+                }
+            }
+            None => missions
+                .entry(row.item)
+                .or_default()
+                .push(MissionSource { place: row.place, rarity: row.rarity, chance: row.chance }),
+        }
+    }
+
+    let names: HashSet<String> = relics
+        .keys()
+        .chain(missions.keys())
+        .cloned()
+        .collect();
+    let mut items: Vec<FarmItem> = names
+        .into_iter()
+        .map(|name| {
+            let mut item_relics: Vec<RelicSource> = relics
+                .remove(&name)
+                .map(|by_relic| by_relic.into_values().collect())
+                .unwrap_or_default();
+            item_relics.sort_by(|a, b| {
+                a.vaulted
+                    .cmp(&b.vaulted)
+                    .then(tier_index(&a.relic).cmp(&tier_index(&b.relic)))
+                    .then(a.relic.cmp(&b.relic))
+            });
+            let mut item_missions = missions
+                .remove(&name)
+                .unwrap_or_default();
+            item_missions.sort_by(|a, b| b.chance.total_cmp(&a.chance));
+            let mut seen = HashSet::new();
+            item_missions.retain(|m| seen.insert(m.place.clone()));
+            item_missions.truncate(MAX_MISSIONS);
+            FarmItem { name, relics: item_relics, missions: item_missions }
+        })
+        .collect();
+    items.sort_unstable_by(|a, b| cmp_ignore_ascii_case(&a.name, &b.name));
+    items.dedup_by(|a, b| a.name.eq_ignore_ascii_case(&b.name));
+    items
+}
+
+/// The drop tables, or `None` when `etag` still matches.
+pub(super) async fn fetch_drops<H>(
+    client: &H,
+    etag: Option<&str>,
+) -> anyhow::Result<Option<(Vec<DropRow>, Option<String>)>>
+where
+    H: HttpProvider + Sync,
+{
+    use reqwest::header::{ETAG, HeaderMap, HeaderValue, IF_NONE_MATCH};
+    let mut headers = HeaderMap::new();
+    if let Some(tag) = etag
+        && let Ok(v) = HeaderValue::from_str(tag)
+    {
+        headers.insert(IF_NONE_MATCH, v);
+    }
+    let resp = client
+        .as_reqwest()
+        .get(DROPS_URL)
+        .headers(headers)
+        .send()
+        .await?
+        .error_for_status()?;
+    if resp.status() == reqwest::StatusCode::NOT_MODIFIED {
+        return Ok(None);
+    }
+    let new_etag = resp
+        .headers()
+        .get(ETAG)
+        .and_then(|v| v.to_str().ok())
+        .map(|s| s.to_string());
+    Ok(Some((resp.json().await?, new_etag)))
+}
+
+#[cfg(test)]
+#[path = "tests/client.rs"]
+mod tests;
@@ -0,0 +1,121 @@
+use twilight_cache_inmemory::{Reference, model::CachedGuild};
+use twilight_model::{
+    channel::message::Embed,
+    id::{Id, marker::GuildMarker},
+};
+use twilight_util::builder::embed::{EmbedBuilder, EmbedFieldBuilder};
+
+use crate::{
+    utils::{
+        embed::{footer_with_icon, join_lines},
+        locale::Lang,
+    },
+    warframe::api::Fissure,
+};
+
+use super::{FarmItem, FarmService, RelicSource};
+
+const COLOR: u32 = 0x1ABC9C;
+
+/// `Hepit (Void)` as `Hepit`.
+fn node_name(node: &str) -> &str {
+    node.split_once(" (")
+        .map_or(node, |(name, _)| name)
+}
+
+impl FarmService {
+    /// An open fissure of the relic's tier, preferring normal ones over
+    /// Steel Path and Void Storms.
+    pub(super) fn open_fissure<'a>(relic: &str, fissures: &'a [Fissure]) -> Option<&'a Fissure> {
+        let tier = relic.split_whitespace().next()?;
+        fissures
+            .iter()
+            .filter(|f| f.tier == tier)
+            .min_by_key(|f| (f.is_storm, f.is_hard))
+    }
+
+    pub(super) fn relic_line(relic: &RelicSource, fissures: &[Fissure], lang: Lang) -> String {
+        let status = if relic.vaulted {
+            lang.pick("🔒 Vault แล้ว", "🔒 Vaulted")
+                .to_string()
+        } else {
+            match Self::open_fissure(&relic.relic, fissures) {
+                Some(fissure) => {
+                    let tier = &fissure.tier;
+                    let node = node_name(&fissure.node);
+                    let kind = if fissure.is_storm {
+                        " (Void Storm)"
+                    } else if fissure.is_hard {
+                        " (Steel Path)"
+                    } else {
+                        ""
+                    };
+                    match lang {
+                        Lang::Th => format!("รอยแยก {tier} เปิดอยู่ที่ {node}{kind}"),
+                        Lang::En => format!("{tier} fissure open now on {node}{kind}"),
+                    }
+                }
+                None => lang
+                    .pick("ไม่มีรอยแยกที่เปิดอยู่", "no fissure open")
+                    .to_string(),
+            }
+        };
+        format!(
+            "**{}** · {} {:.2}% — {status}",
+            relic.relic, relic.rarity, relic.chance
+        )
+    }
+
+    pub fn build_embed(
+        guild: &Reference<'_, Id<GuildMarker>, CachedGuild>,
+        item: &FarmItem,
+        fissures: &[Fissure],
+        lang: Lang,
+    ) -> anyhow::Result<Embed> {
+        let mut footer = footer_with_icon(guild)?;
+        footer.text = format!("{} · drops.warframestat.us", guild.name());
+        let mut builder = EmbedBuilder::new()
+            .color(COLOR)
+            .title(format!(
+                "{} {}",
+                lang.pick("แหล่งฟาร์ม", "Where to farm"),
+                item.name
+            ));
+        if !item.relics.is_empty() {
+            let lines: Vec<String> = item
+                .relics
+                .iter()
+                .map(|relic| Self::relic_line(relic, fissures, lang))
+                .collect();
+            builder = builder.field(EmbedFieldBuilder::new(
+                lang.pick("Relic ที่ดรอป", "Relics"),
+                join_lines(&lines),
+            ));
+        }
+        if !item.missions.is_empty() {
+            let lines: Vec<String> = item
+                .missions
+                .iter()
+                .map(|m| format!("{} · {} {:.2}%", m.place, m.rarity, m.chance))
+                .collect();
+            builder = builder.field(EmbedFieldBuilder::new(
+                lang.pick("ภารกิจ", "Missions"),
+                join_lines(&lines),
+            ));
+        }
+        if !item.relics.is_empty() && item.relics.iter().all(|r| r.vaulted) {
+            builder = builder.description(lang.pick(
+                "relic ทั้งหมดอยู่ใน Vault แล้ว ซื้อจากผู้เล่นอื่นได้ที่ /warframe market",
+                "Every relic is vaulted; try /warframe market to buy it from other players",
+            ));
+        }
+        Ok(builder
+            .footer(footer)
+            .validate()?
+            .build())
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/embed.rs"]
+mod tests;
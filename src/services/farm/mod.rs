@@ -0,0 +1,70 @@
+pub mod cache;
+pub mod client;
+pub mod embed;
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use twilight_cache_inmemory::{Reference, model::CachedGuild};
+use twilight_model::{
+    channel::message::Embed,
+    id::{Id, marker::GuildMarker},
+};
+
+use crate::{
+    context::Context, services::build::BuildService, utils::locale::Lang, warframe::worldstate,
+};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RelicSource {
+    /// `Lith X1`, without the `Relic` suffix.
+    pub relic: String,
+    pub rarity: String,
+    /// Percent chance from an intact relic.
+    pub chance: f64,
+    pub vaulted: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MissionSource {
+    pub place: String,
+    pub rarity: String,
+    pub chance: f64,
+}
+
+/// Everywhere one item drops, open relics first.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FarmItem {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub relics: Vec<RelicSource>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub missions: Vec<MissionSource>,
+}
+
+pub struct FarmService;
+
+impl FarmService {
+    pub async fn farm_embed(
+        ctx: &Arc<Context>,
+        guild: &Reference<'_, Id<GuildMarker>, CachedGuild>,
+        item: &str,
+        lang: Lang,
+    ) -> anyhow::Result<Embed> {
+        let Some(item) = Self::find_item(item).await else {
+            return BuildService::item_not_found_embed(guild, lang);
+        };
+        // The drop tables still answer the question without fissures.
+        let fissures = if item.relics.iter().any(|r| !r.vaulted) {
+            worldstate::fissures(ctx)
+                .await
+                .unwrap_or_else(|e| {
+                    tracing::warn!(error = %e, "failed to fetch fissures for farm lookup");
+                    Vec::new()
+                })
+        } else {
+            Vec::new()
+        };
+        Self::build_embed(guild, &item, &fissures, lang)
+    }
+}
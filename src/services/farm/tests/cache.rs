@@ -0,0 +1,17 @@
+use super::*;
+
+#[test]
+fn test_until_stale_counts_from_the_last_check() {
+    let hour = 60 * 60;
+    assert_eq!(
+        until_stale(1_000_000, 1_000_000),
+        REFRESH_INTERVAL
+    );
+    assert_eq!(
+        until_stale(1_000_000, 1_000_000 + hour),
+        REFRESH_INTERVAL - Duration::from_secs(hour)
+    );
+    assert!(until_stale(1_000_000, 1_000_000 + 6 * hour).is_zero());
+    // Tables stored before the timestamp existed are stale.
+    assert!(until_stale(0, 1_000_000).is_zero());
+}
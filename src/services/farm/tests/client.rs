@@ -0,0 +1,125 @@
+use super::*;
+
+fn row(place: &str, item: &str, rarity: &str, chance: f64) -> DropRow {
+    DropRow { place: place.into(), item: item.into(), rarity: rarity.into(), chance }
+}
+
+#[test]
+fn test_relic_name() {
+    assert_eq!(
+        relic_name("Lith X1 Relic"),
+        Some(("Lith X1", None))
+    );
+    assert_eq!(
+        relic_name("Axi A1 Relic (Radiant)"),
+        Some(("Axi A1", Some("Radiant")))
+    );
+    assert_eq!(relic_name("Earth/Mantle (Capture)"), None);
+    assert_eq!(relic_name("Forma Blueprint"), None);
+    assert_eq!(relic_name("Vanguard Relic"), None);
+}
+
+#[test]
+fn test_build_index() {
+    let items = build_index(vec![
+        row(
+            "Axi A1 Relic (Radiant)",
+            "Akstiletto Prime Barrel",
+            "Uncommon",
+            20.0,
+        ),
+        row(
+            "Axi A1 Relic (Intact)",
+            "Akstiletto Prime Barrel",
+            "Uncommon",
+            11.0,
+        ),
+        row(
+            "Lith X1 Relic (Intact)",
+            "Akstiletto Prime Barrel",
+            "Rare",
+            2.0,
+        ),
+        row(
+            "Void/Hepit (Capture)",
+            "Lith X1 Relic",
+            "Common",
+            33.33,
+        ),
+        row(
+            "Earth/Mantle (Capture)",
+            "Neurodes",
+            "Uncommon",
+            5.0,
+        ),
+        row(
+            "Earth/Lith (Excavation), Rotation A",
+            "Neurodes",
+            "Uncommon",
+            12.5,
+        ),
+        row(
+            "Earth/Lith (Excavation), Rotation B",
+            "Neurodes",
+            "Uncommon",
+            12.5,
+        ),
+    ]);
+
+    let names: Vec<&str> = items
+        .iter()
+        .map(|i| i.name.as_str())
+        .collect();
+    assert_eq!(
+        names,
+        ["Akstiletto Prime Barrel", "Lith X1 Relic", "Neurodes"]
+    );
+
+    let barrel = &items[0];
+    assert_eq!(
+        barrel.relics,
+        vec![
+            RelicSource {
+                relic: "Lith X1".into(),
+                rarity: "Rare".into(),
+                chance: 2.0,
+                vaulted: false
+            },
+            RelicSource {
+                relic: "Axi A1".into(),
+                rarity: "Uncommon".into(),
+                chance: 11.0,
+                vaulted: true
+            },
+        ]
+    );
+    assert!(barrel.missions.is_empty());
+
+    let neurodes = &items[2];
+    assert!(neurodes.relics.is_empty());
+    assert_eq!(neurodes.missions.len(), 3);
+    assert_eq!(
+        neurodes.missions[2].place,
+        "Earth/Mantle (Capture)"
+    );
+}
+
+#[test]
+fn test_missions_are_capped() {
+    let rows = (0..MAX_MISSIONS + 3)
+        .map(|i| {
+            row(
+                &format!("Node{i}"),
+                "Argon Crystal",
+                "Rare",
+                i as f64,
+            )
+        })
+        .collect();
+    let items = build_index(rows);
+    assert_eq!(items[0].missions.len(), MAX_MISSIONS);
+    assert_eq!(
+        items[0].missions[0].chance,
+        (MAX_MISSIONS + 2) as f64
+    );
+}
@@ -0,0 +1,48 @@
+use super::*;
+
+fn fissure(tier: &str, node: &str, is_hard: bool, is_storm: bool) -> Fissure {
+    Fissure {
+        id: format!("{tier}-{node}"),
+        node: node.into(),
+        mission_type: "Capture".into(),
+        enemy: "Corrupted".into(),
+        tier: tier.into(),
+        tier_num: 1,
+        expiry: "2030-01-01T00:00:00.000Z".into(),
+        is_storm,
+        is_hard,
+    }
+}
+
+fn relic(name: &str, vaulted: bool) -> RelicSource {
+    RelicSource { relic: name.into(), rarity: "Rare".into(), chance: 2.0, vaulted }
+}
+
+#[test]
+fn test_open_fissure_prefers_normal() {
+    let fissures = vec![
+        fissure("Lith", "Hepit (Void)", true, false),
+        fissure("Lith", "Mot (Void)", false, false),
+        fissure("Meso", "Io (Jupiter)", false, false),
+    ];
+    let open = FarmService::open_fissure("Lith X1", &fissures).unwrap();
+    assert_eq!(open.node, "Mot (Void)");
+    assert!(FarmService::open_fissure("Axi A1", &fissures).is_none());
+}
+
+#[test]
+fn test_relic_line() {
+    let fissures = vec![fissure("Lith", "Hepit (Void)", false, false)];
+    assert_eq!(
+        FarmService::relic_line(&relic("Lith X1", false), &fissures, Lang::En),
+        "**Lith X1** · Rare 2.00% — Lith fissure open now on Hepit"
+    );
+    assert_eq!(
+        FarmService::relic_line(&relic("Neo N1", false), &fissures, Lang::En),
+        "**Neo N1** · Rare 2.00% — no fissure open"
+    );
+    assert_eq!(
+        FarmService::relic_line(&relic("Lith X1", true), &fissures, Lang::Th),
+        "**Lith X1** · Rare 2.00% — 🔒 Vault แล้ว"
+    );
+}
@@ -1,11 +1,13 @@
 use deadpool_redis::Pool;
-use mongodb::bson::doc;
+use mongodb::bson::{doc, to_bson};
 
 use crate::{
     configs::CACHE_PREFIX,
     context::Context,
     dbs::{
-        mongo::models::guild_settings::GuildSettings,
+        mongo::models::guild_settings::{
+            AiAccessSettings, AiQuotaSettings, AiSafetySettings, GuildSettings, ScamTextMode,
+        },
         redis::{redis_delete, redis_get, redis_set_ex},
     },
 };
@@ -28,7 +30,7 @@ impl GuildSettingsService {
             .await
             .ok()
             .flatten()
-            .unwrap_or(GuildSettings { id: None, guild_id, scam_detect_enabled: false });
+            .unwrap_or(GuildSettings { guild_id, ..GuildSettings::default() });
 
         redis_set_ex(&ctx.redis, &redis_key, &settings, CACHE_TTL).await;
         settings
@@ -64,6 +66,126 @@ impl GuildSettingsService {
         Ok(())
     }
 
+    pub async fn set_scam_text_mode(
+        ctx: &Context,
+        guild_id: u64,
+        mode: ScamTextMode,
+    ) -> anyhow::Result<()> {
+        ctx.mongo
+            .guild_settings
+            .update_one(
+                doc! {"guild_id": guild_id as i64},
+                doc! {
+                    "$set": {
+                        "guild_id": guild_id as i64,
+                        "scam_text_mode": to_bson(&mode)?,
+                    }
+                },
+            )
+            .upsert(true)
+            .await?;
+
+        Self::purge_cache(&ctx.redis, guild_id).await;
+
+        Ok(())
+    }
+
+    pub async fn set_ai_quota(
+        ctx: &Context,
+        guild_id: u64,
+        quota: AiQuotaSettings,
+    ) -> anyhow::Result<()> {
+        ctx.mongo
+            .guild_settings
+            .update_one(
+                doc! {"guild_id": guild_id as i64},
+                doc! {
+                    "$set": {
+                        "guild_id": guild_id as i64,
+                        "ai_quota": to_bson(&quota)?,
+                    }
+                },
+            )
+            .upsert(true)
+            .await?;
+
+        Self::purge_cache(&ctx.redis, guild_id).await;
+
+        Ok(())
+    }
+
+    pub async fn set_ai_safety(
+        ctx: &Context,
+        guild_id: u64,
+        safety: AiSafetySettings,
+    ) -> anyhow::Result<()> {
+        ctx.mongo
+            .guild_settings
+            .update_one(
+                doc! {"guild_id": guild_id as i64},
+                doc! {
+                    "$set": {
+                        "guild_id": guild_id as i64,
+                        "ai_safety": to_bson(&safety)?,
+                    }
+                },
+            )
+            .upsert(true)
+            .await?;
+
+        Self::purge_cache(&ctx.redis, guild_id).await;
+
+        Ok(())
+    }
+
+    pub async fn set_ai_base_prompt(
+        ctx: &Context,
+        guild_id: u64,
+        prompt: Option<String>,
+    ) -> anyhow::Result<()> {
+        ctx.mongo
+            .guild_settings
+            .update_one(
+                doc! {"guild_id": guild_id as i64},
+                doc! {
+                    "$set": {
+                        "guild_id": guild_id as i64,
+                        "ai_base_prompt": prompt,
+                    }
+                },
+            )
+            .upsert(true)
+            .await?;
+
+        Self::purge_cache(&ctx.redis, guild_id).await;
+
+        Ok(())
+    }
+
+    pub async fn set_ai_access(
+        ctx: &Context,
+        guild_id: u64,
+        access: &AiAccessSettings,
+    ) -> anyhow::Result<()> {
+        ctx.mongo
+            .guild_settings
+            .update_one(
+                doc! {"guild_id": guild_id as i64},
+                doc! {
+                    "$set": {
+                        "guild_id": guild_id as i64,
+                        "ai_access": to_bson(access)?,
+                    }
+                },
+            )
+            .upsert(true)
+            .await?;
+
+        Self::purge_cache(&ctx.redis, guild_id).await;
+
+        Ok(())
+    }
+
     pub async fn purge_cache(pool: &Pool, guild_id: u64) {
         redis_delete(pool, &cache_key(guild_id)).await;
     }
@@ -90,7 +212,7 @@ mod tests {
         let key = cache_key(guild_id);
         ctx.redis_set_ex(
             &key,
-            &GuildSettings { id: None, guild_id, scam_detect_enabled: true },
+            &GuildSettings { guild_id, scam_detect_enabled: true, ..GuildSettings::default() },
             CACHE_TTL,
         )
         .await;
@@ -144,4 +266,22 @@ mod tests {
         assert_eq!(cached.guild_id, guild_id);
         assert!(!cached.scam_detect_enabled);
     }
+
+    #[tokio::test]
+    async fn scam_text_mode_round_trips_through_storage() {
+        let ctx = ContextBuilder::new()
+            .watchers(false)
+            .build()
+            .await
+            .expect("failed to build context");
+        let guild_id = 7_006_003;
+
+        GuildSettingsService::set_scam_text_mode(&ctx, guild_id, ScamTextMode::Shadow)
+            .await
+            .expect("failed to set scam text mode");
+
+        let settings = GuildSettingsService::get(&ctx, guild_id).await;
+        assert_eq!(settings.scam_text_mode, ScamTextMode::Shadow);
+        assert!(!settings.scam_detect_enabled);
+    }
 }
@@ -95,6 +95,16 @@ impl MarketService {
         results
     }
 
+    /// Slugs are not sorted, so this scans; only expired sessions need it.
+    pub(super) async fn find_by_slug(slug: &str) -> Option<MarketEntry> {
+        ITEMS
+            .read()
+            .await
+            .iter()
+            .find(|e| e.slug == slug && !e.item_id.is_empty())
+            .cloned()
+    }
+
     pub(super) async fn find_item(name: &str) -> Option<MarketEntry> {
         let items = ITEMS.read().await;
         let idx =
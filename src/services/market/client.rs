@@ -16,6 +16,7 @@ use super::{MarketKind, cache::MarketEntry};
 
 const ITEMS_URL: &str = "https://api.warframe.market/v2/items";
 const ORDERS_URL: &str = "https://api.warframe.market/v2/orders/itemId";
+const STATISTICS_URL: &str = "https://api.warframe.market/v1/items";
 pub(super) const ITEM_URL: &str = "https://warframe.market/items/";
 
 #[derive(Deserialize, Serialize)]
@@ -50,6 +51,8 @@ pub(super) struct OrderUser {
     #[serde(alias = "ingameName")]
     pub ingame_name: String,
     pub status: String,
+    #[serde(default)]
+    pub reputation: i32,
 }
 
 #[derive(Deserialize, Serialize)]
@@ -63,6 +66,36 @@ pub(super) struct Order {
     pub mod_rank: Option<u8>,
 }
 
+#[derive(Deserialize, Serialize)]
+struct StatisticsResponse {
+    payload: StatisticsPayload,
+}
+
+#[derive(Deserialize, Serialize)]
+struct StatisticsPayload {
+    statistics_closed: ClosedStatistics,
+}
+
+/// Closed-trade buckets: hourly for the last 48 hours, daily for 90 days.
+#[derive(Deserialize, Serialize, Default)]
+pub(super) struct ClosedStatistics {
+    #[serde(rename = "48hours", default)]
+    pub hours: Vec<StatPoint>,
+    #[serde(rename = "90days", default)]
+    pub days: Vec<StatPoint>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub(super) struct StatPoint {
+    pub datetime: String,
+    pub volume: u32,
+    pub min_price: f64,
+    pub max_price: f64,
+    pub median: f64,
+    #[serde(default)]
+    pub mod_rank: Option<u8>,
+}
+
 pub(super) async fn load_from_redis(pool: &Pool, key: &str) -> Option<Vec<MarketEntry>> {
     if let Some(mut stored) = redis_get::<Vec<MarketEntry>>(pool, key).await {
         stored.sort_unstable_by(|a, b| cmp_ignore_ascii_case(&a.name, &b.name));
@@ -113,6 +146,16 @@ where
     Ok(data.data)
 }
 
+pub(super) async fn fetch_statistics<H>(client: &H, slug: &str) -> anyhow::Result<ClosedStatistics>
+where
+    H: HttpProvider + Sync,
+{
+    let data: StatisticsResponse = client
+        .get_json(&format!("{STATISTICS_URL}/{slug}/statistics"))
+        .await?;
+    Ok(data.payload.statistics_closed)
+}
+
 pub(super) async fn fetch_orders_map<H>(
     client: &H,
     item_id: &str,
@@ -128,7 +171,9 @@ where
     let mut by_rank: BTreeMap<u8, Vec<super::session::OrderInfo>> = BTreeMap::new();
     let mut max_rank: Option<u8> = None;
     for o in orders {
-        if o.user.status != "ingame" || o.order_type == kind.action() {
+        // Offline users cannot answer; online ones are kept for the
+        // session's status filter.
+        if o.user.status == "offline" || o.order_type == kind.action() {
             continue;
         }
         let rank = o.mod_rank.unwrap_or(0);
@@ -146,6 +191,8 @@ where
                 quantity: o.quantity,
                 platinum: o.platinum,
                 ign: o.user.ingame_name,
+                reputation: o.user.reputation,
+                ingame: o.user.status == "ingame",
             });
     }
     for vec in by_rank.values_mut() {
@@ -5,9 +5,12 @@ use twilight_model::{
 };
 use twilight_util::builder::embed::{EmbedBuilder, EmbedFieldBuilder};
 
-use crate::utils::embed::footer_with_icon;
+use crate::utils::{embed::footer_with_icon, locale::Lang};
 
-use super::{MarketKind, MarketService, client, session};
+use super::{
+    MarketKind, MarketService, client, session,
+    stats::{self, PriceStats, WindowStats},
+};
 
 const COLOR: u32 = 0xF1C40F;
 
@@ -38,17 +41,97 @@ impl MarketService {
             .build())
     }
 
+    fn format_plat(value: f64) -> String {
+        if value.fract() == 0.0 { format!("{value:.0}p") } else { format!("{value:.1}p") }
+    }
+
+    fn window_line(label: &str, window: &WindowStats) -> String {
+        match (window.median, window.min, window.max) {
+            (Some(median), Some(min), Some(max)) => format!(
+                "{label}: **{}** · {} ชิ้น · {}–{}",
+                Self::format_plat(median),
+                window.volume,
+                Self::format_plat(min),
+                Self::format_plat(max)
+            ),
+            _ => format!("{label}: ไม่มีการซื้อขาย"),
+        }
+    }
+
+    pub(super) fn build_price_embed(
+        guild: &Reference<'_, Id<GuildMarker>, CachedGuild>,
+        item: &str,
+        url: &str,
+        stats: &PriceStats,
+        rank: Option<u8>,
+    ) -> anyhow::Result<Embed> {
+        let mut footer = footer_with_icon(guild)?;
+        footer.text = guild.name().to_string();
+        let title = match rank {
+            Some(r) if stats.max_rank.is_some() => format!("ราคา {item} [Rank {r}]"),
+            _ => format!("ราคา {item}"),
+        };
+        let mut builder = EmbedBuilder::new()
+            .color(COLOR)
+            .title(title)
+            .url(format!("{}{}", client::ITEM_URL, url));
+        let mut fields = 0;
+        for (r, rank_stats) in &stats.by_rank {
+            if stats.max_rank.is_some() && rank.is_some_and(|wanted| wanted != *r) {
+                continue;
+            }
+            let mut value = [
+                Self::window_line("48 ชม.", &rank_stats.recent),
+                Self::window_line("90 วัน", &rank_stats.quarter),
+            ]
+            .join("\n");
+            if rank_stats.trend.len() > 1 {
+                value.push_str(&format!(
+                    "\n`{}`",
+                    stats::sparkline(&rank_stats.trend)
+                ));
+            }
+            let name = match stats.max_rank {
+                Some(_) => format!("Rank {r}"),
+                None => "ราคากลาง".to_string(),
+            };
+            builder = builder.field(EmbedFieldBuilder::new(name, value));
+            fields += 1;
+        }
+        builder = if fields == 0 {
+            builder.description("ยังไม่มีประวัติการซื้อขาย")
+        } else {
+            builder.description(format!(
+                "ราคากลางจากการซื้อขายที่ปิดแล้ว · แนวโน้ม {} วัน",
+                stats::TREND_DAYS
+            ))
+        };
+        Ok(builder
+            .footer(footer)
+            .validate()?
+            .build())
+    }
+
     fn sanitize_code_block(text: &str) -> String {
         text.replace('`', "")
     }
 
+    /// The in-game chat line that answers an order, safe inside a code block.
+    pub(super) fn whisper_line(ign: &str, kind: &MarketKind, item: &str, platinum: u32) -> String {
+        format!(
+            "/w {} Hi! I want to {}: \"{}\" for {platinum} platinum. (warframe.market)",
+            Self::sanitize_code_block(ign),
+            kind.action(),
+            Self::sanitize_code_block(item),
+        )
+    }
+
     fn build_fields(
         orders: &[session::OrderInfo],
         item: &str,
         kind: &MarketKind,
         rank: Option<u8>,
     ) -> Vec<EmbedField> {
-        let item = Self::sanitize_code_block(item);
         orders
             .iter()
             .take(5)
@@ -60,11 +143,8 @@ impl MarketService {
                         o.quantity, o.platinum
                     ),
                     format!(
-                        "```/w {} Hi! I want to {}: \"{}\" for {} platinum. (warframe.market)```",
-                        Self::sanitize_code_block(&o.ign),
-                        kind.action(),
-                        item,
-                        o.platinum
+                        "```{}```",
+                        Self::whisper_line(&o.ign, kind, item, o.platinum)
                     ),
                 )
                 .build()
@@ -79,6 +159,7 @@ impl MarketService {
         kind: &MarketKind,
         rank: Option<u8>,
         orders: Vec<session::OrderInfo>,
+        lang: Lang,
     ) -> anyhow::Result<Embed> {
         let mut footer = footer_with_icon(guild)?;
         footer.text = if let Some(r) = rank {
@@ -87,14 +168,20 @@ impl MarketService {
             guild.name().to_string()
         };
         let title = if let Some(r) = rank {
-            format!("{} {} [Rank {}]", kind.label(), item, r)
+            format!("{} {} [Rank {}]", kind.label(lang), item, r)
         } else {
-            format!("{} {}", kind.label(), item)
+            format!("{} {}", kind.label(lang), item)
         };
         let mut builder = EmbedBuilder::new()
             .color(COLOR)
             .title(title)
             .url(format!("{}{}", client::ITEM_URL, url));
+        if orders.is_empty() {
+            builder = builder.description(lang.pick(
+                "ไม่มีออเดอร์ที่ตรงกับตัวกรอง",
+                "No orders match the filters",
+            ));
+        }
         for field in Self::build_fields(&orders, item, kind, rank) {
             builder = builder.field(field);
         }
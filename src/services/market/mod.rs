@@ -2,24 +2,20 @@ pub mod cache;
 pub mod client;
 pub mod embed;
 pub mod session;
+pub mod stats;
+pub mod watch;
 
-use std::{
-    collections::{BTreeMap, HashMap},
-    sync::Arc,
-    time::Instant,
-};
-
-use tokio::{sync::RwLock, time::sleep};
+use std::{collections::BTreeMap, sync::Arc};
 
-use once_cell::sync::Lazy;
-use std::time::Duration;
-use tokio_util::sync::CancellationToken;
+use serde::{Deserialize, Serialize};
 use twilight_cache_inmemory::{Reference, model::CachedGuild};
 use twilight_model::{
     application::interaction::{Interaction, message_component::MessageComponentInteractionData},
     channel::message::{
-        Embed,
-        component::{ActionRow, Button, ButtonStyle, Component},
+        Embed, MessageFlags,
+        component::{
+            ActionRow, Button, ButtonStyle, Component, SelectMenu, SelectMenuOption, SelectMenuType,
+        },
     },
     http::interaction::{InteractionResponse, InteractionResponseType},
     id::{
@@ -29,14 +25,18 @@ use twilight_model::{
 };
 use twilight_util::builder::InteractionResponseDataBuilder;
 
-use crate::context::Context;
+use crate::{
+    context::Context,
+    utils::{locale::Lang, session::MessageSessions},
+};
 
-pub use session::{MarketSession, OrderInfo};
+pub use session::{MarketSession, OrderFilter, OrderInfo, SortOrder, StatusFilter};
 
-static SESSIONS: Lazy<RwLock<HashMap<Id<MessageMarker>, MarketSession>>> =
-    Lazy::new(|| RwLock::new(HashMap::new()));
+/// Choices offered by the minimum-quantity menu.
+const MIN_QUANTITIES: [u32; 5] = [1, 2, 5, 10, 20];
 
-#[derive(Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum MarketKind {
     Buy,
     Sell,
@@ -50,17 +50,25 @@ impl MarketKind {
         }
     }
 
-    fn action(&self) -> &str {
+    pub fn action(&self) -> &'static str {
         match self {
             MarketKind::Buy => "buy",
             MarketKind::Sell => "sell",
         }
     }
 
-    fn label(&self) -> &str {
+    pub fn from_action(action: &str) -> Option<Self> {
+        match action {
+            "buy" => Some(MarketKind::Buy),
+            "sell" => Some(MarketKind::Sell),
+            _ => None,
+        }
+    }
+
+    fn label(&self, lang: Lang) -> &'static str {
         match self {
-            MarketKind::Buy => "ผู้ขาย",
-            MarketKind::Sell => "ผู้ซื้อ",
+            MarketKind::Buy => lang.pick("ผู้ขาย", "Sellers of"),
+            MarketKind::Sell => lang.pick("ผู้ซื้อ", "Buyers of"),
         }
     }
 }
@@ -68,24 +76,19 @@ impl MarketKind {
 pub struct MarketService;
 
 impl MarketService {
-    const SESSION_TTL: Duration = Duration::from_secs(900);
-
-    fn spawn_expiration(message_id: Id<MessageMarker>, token: CancellationToken) {
-        tokio::spawn(async move {
-            tokio::select! {
-                _ = token.cancelled() => {},
-                _ = sleep(Self::SESSION_TTL) => {
-                    SESSIONS.write().await.remove(&message_id);
-                }
-            }
-        });
-    }
+    /// Idle time before a market message's buttons need a re-fetch.
+    const SESSIONS: MessageSessions = MessageSessions::new("market", 900);
 
     pub fn embed_for_session(
         guild: &Reference<'_, Id<GuildMarker>, CachedGuild>,
         session: &MarketSession,
+        lang: Lang,
     ) -> anyhow::Result<Embed> {
-        let orders = session.slice().to_vec();
+        let orders = session
+            .slice()
+            .into_iter()
+            .cloned()
+            .collect();
         Self::build_embed(
             guild,
             &session.item,
@@ -93,6 +96,7 @@ impl MarketService {
             &session.kind,
             session.max_rank.map(|_| session.rank),
             orders,
+            lang,
         )
     }
 
@@ -118,8 +122,7 @@ impl MarketService {
                     rank: 0,
                     page: 1,
                     max_rank,
-                    last_used: Instant::now(),
-                    expire_token: CancellationToken::new(),
+                    filter: OrderFilter::default(),
                 };
                 Ok(Some(session))
             }
@@ -130,92 +133,276 @@ impl MarketService {
         }
     }
 
-    pub fn components(session: &MarketSession) -> Vec<Component> {
-        let mut buttons = Vec::with_capacity(5);
-        buttons.push(Component::Button(Button {
+    fn button(custom_id: String, label: &str, disabled: bool, style: ButtonStyle) -> Component {
+        Component::Button(Button {
             id: None,
-            custom_id: Some("market_prev_page".into()),
-            disabled: session.page <= 1,
+            custom_id: Some(custom_id),
+            disabled,
             emoji: None,
-            label: Some("ก่อนหน้า".into()),
-            style: ButtonStyle::Primary,
+            label: Some(label.to_string()),
+            style,
             url: None,
             sku_id: None,
-        }));
-        buttons.push(Component::Button(Button {
+        })
+    }
+
+    fn select(custom_id: String, options: Vec<(String, String, bool)>) -> Component {
+        Component::ActionRow(ActionRow {
             id: None,
-            custom_id: Some("market_next_page".into()),
-            disabled: session.page >= session.lpage(),
-            emoji: None,
-            label: Some("ถัดไป".into()),
-            style: ButtonStyle::Primary,
-            url: None,
-            sku_id: None,
-        }));
-        if let Some(max) = session.max_rank {
-            buttons.push(Component::Button(Button {
+            components: vec![Component::SelectMenu(SelectMenu {
                 id: None,
-                custom_id: Some("market_next_rank".into()),
-                disabled: session.rank >= max,
-                emoji: None,
-                label: Some("เพิ่ม Rank".into()),
-                style: ButtonStyle::Primary,
-                url: None,
-                sku_id: None,
-            }));
-            buttons.push(Component::Button(Button {
+                channel_types: None,
+                custom_id,
+                default_values: None,
+                disabled: false,
+                kind: SelectMenuType::Text,
+                max_values: Some(1),
+                min_values: Some(1),
+                options: Some(
+                    options
+                        .into_iter()
+                        .map(|(label, value, default)| SelectMenuOption {
+                            default,
+                            description: None,
+                            emoji: None,
+                            label,
+                            value,
+                        })
+                        .collect(),
+                ),
+                placeholder: None,
+                required: None,
+            })],
+        })
+    }
+
+    pub fn components(session: &MarketSession, lang: Lang) -> Vec<Component> {
+        let mut buttons = Vec::with_capacity(5);
+        buttons.push(Self::button(
+            session.custom_id("market_prev_page"),
+            lang.pick("ก่อนหน้า", "Previous"),
+            session.page <= 1,
+            ButtonStyle::Primary,
+        ));
+        buttons.push(Self::button(
+            session.custom_id("market_next_page"),
+            lang.pick("ถัดไป", "Next"),
+            session.page >= session.lpage(),
+            ButtonStyle::Primary,
+        ));
+        if let Some(max) = session.max_rank {
+            buttons.push(Self::button(
+                session.custom_id("market_next_rank"),
+                lang.pick("เพิ่ม Rank", "Rank up"),
+                session.rank >= max,
+                ButtonStyle::Primary,
+            ));
+            buttons.push(Self::button(
+                session.custom_id("market_prev_rank"),
+                lang.pick("ลด Rank", "Rank down"),
+                session.rank == 0,
+                ButtonStyle::Primary,
+            ));
+        }
+        buttons.push(Self::button(
+            session.custom_id("market_refresh"),
+            lang.pick("รีโหลด", "Refresh"),
+            false,
+            ButtonStyle::Primary,
+        ));
+        let mut rows = vec![Component::ActionRow(ActionRow { id: None, components: buttons })];
+
+        let filter = session.filter;
+        rows.push(Self::select(
+            session.custom_id("market_status"),
+            [
+                (
+                    lang.pick("เฉพาะในเกม", "In game only"),
+                    StatusFilter::Ingame,
+                    "ingame",
+                ),
+                (
+                    lang.pick("ออนไลน์ทั้งหมด", "Online"),
+                    StatusFilter::Online,
+                    "online",
+                ),
+            ]
+            .into_iter()
+            .map(|(label, status, value)| {
+                (
+                    label.to_string(),
+                    value.to_string(),
+                    filter.status == status,
+                )
+            })
+            .collect(),
+        ));
+        rows.push(Self::select(
+            session.custom_id("market_sort"),
+            [
+                (
+                    lang.pick("เรียงตามราคา", "Sort by price"),
+                    SortOrder::Price,
+                    "price",
+                ),
+                (
+                    lang.pick("เรียงตามจำนวน", "Sort by quantity"),
+                    SortOrder::Quantity,
+                    "quantity",
+                ),
+                (
+                    lang.pick("เรียงตามชื่อเสียง", "Sort by reputation"),
+                    SortOrder::Reputation,
+                    "reputation",
+                ),
+            ]
+            .into_iter()
+            .map(|(label, sort, value)| {
+                (
+                    label.to_string(),
+                    value.to_string(),
+                    filter.sort == sort,
+                )
+            })
+            .collect(),
+        ));
+        rows.push(Self::select(
+            session.custom_id("market_min_quantity"),
+            MIN_QUANTITIES
+                .iter()
+                .map(|&quantity| {
+                    (
+                        format!(
+                            "{} {quantity}",
+                            lang.pick("จำนวนขั้นต่ำ", "Minimum quantity")
+                        ),
+                        quantity.to_string(),
+                        filter.min_quantity == quantity,
+                    )
+                })
+                .collect(),
+        ));
+
+        let whispers: Vec<Component> = session
+            .slice()
+            .iter()
+            .enumerate()
+            .map(|(i, order)| {
+                Self::button(
+                    session.custom_id(&format!("market_whisper_{i}")),
+                    &format!("/w {}", order.ign),
+                    false,
+                    ButtonStyle::Secondary,
+                )
+            })
+            .collect();
+        if !whispers.is_empty() {
+            rows.push(Component::ActionRow(ActionRow {
                 id: None,
-                custom_id: Some("market_prev_rank".into()),
-                disabled: session.rank == 0,
-                emoji: None,
-                label: Some("ลด Rank".into()),
-                style: ButtonStyle::Primary,
-                url: None,
-                sku_id: None,
+                components: whispers,
             }));
         }
-        buttons.push(Component::Button(Button {
-            id: None,
-            custom_id: Some("market_refresh".into()),
-            disabled: false,
-            emoji: None,
-            label: Some("รีโหลด".into()),
-            style: ButtonStyle::Primary,
-            url: None,
-            sku_id: None,
-        }));
-        vec![Component::ActionRow(ActionRow { id: None, components: buttons })]
+        rows
     }
 
-    pub async fn insert_session(message_id: Id<MessageMarker>, session: MarketSession) {
-        let mut session = session;
-        session.touch();
-        let token = session.expire_token.clone();
-        SESSIONS
-            .write()
-            .await
-            .insert(message_id, session);
-        Self::spawn_expiration(message_id, token);
+    /// The order behind the whisper button `custom_id` on `components`. The
+    /// button's label names the seller, so an order that moved or sold since
+    /// the message was drawn is not mistaken for whoever took its place.
+    fn whisper_order<'a>(
+        session: &'a MarketSession,
+        components: &[Component],
+        custom_id: &str,
+    ) -> Option<&'a OrderInfo> {
+        let ign = components
+            .iter()
+            .filter_map(|row| match row {
+                Component::ActionRow(row) => Some(&row.components),
+                _ => None,
+            })
+            .flatten()
+            .find_map(|component| match component {
+                Component::Button(button) if button.custom_id.as_deref() == Some(custom_id) => {
+                    button
+                        .label
+                        .as_deref()?
+                        .strip_prefix("/w ")
+                }
+                _ => None,
+            })?;
+        session
+            .visible()
+            .into_iter()
+            .find(|order| order.ign == ign)
     }
 
-    async fn get_session_mut(message_id: Id<MessageMarker>) -> Option<MarketSession> {
-        SESSIONS
-            .write()
-            .await
-            .remove(&message_id)
-            .map(|mut s| {
-                s.touch();
-                s
-            })
+    pub async fn insert_session(
+        ctx: &Context,
+        message_id: Id<MessageMarker>,
+        session: &MarketSession,
+    ) {
+        Self::SESSIONS
+            .insert(&ctx.redis, message_id, session)
+            .await;
+    }
+
+    /// Re-fetches the orders behind an expired session, keeping the rank and
+    /// page its button was showing where they still exist.
+    async fn restore_session(
+        ctx: &Arc<Context>,
+        state: session::SessionRef,
+    ) -> Option<MarketSession> {
+        let entry = Self::find_by_slug(&state.slug).await?;
+        let (orders, max_rank) = match client::fetch_orders_map(
+            &ctx.reqwest,
+            &entry.item_id,
+            &state.kind,
+        )
+        .await
+        {
+            Ok(fetched) => fetched,
+            Err(e) => {
+                tracing::warn!(slug = %state.slug, error = %e, "failed to restore market session");
+                return None;
+            }
+        };
+        if orders.is_empty() {
+            return None;
+        }
+        let mut session = MarketSession {
+            item: entry.name,
+            item_id: entry.item_id,
+            slug: entry.slug,
+            kind: state.kind,
+            orders,
+            rank: state.rank.min(max_rank.unwrap_or(0)),
+            page: 1,
+            max_rank,
+            filter: state.filter,
+        };
+        session.page = state.page.clamp(1, session.lpage());
+        Some(session)
     }
 
-    async fn store_session(message_id: Id<MessageMarker>, session: MarketSession) {
-        let token = session.expire_token.clone();
-        SESSIONS
-            .write()
+    async fn respond_ephemeral(ctx: &Context, interaction: &Interaction, content: &str) {
+        let data = InteractionResponseDataBuilder::new()
+            .content(content)
+            .flags(MessageFlags::EPHEMERAL)
+            .build();
+        if let Err(e) = ctx
+            .http
+            .interaction(interaction.application_id)
+            .create_response(
+                interaction.id,
+                &interaction.token,
+                &InteractionResponse {
+                    kind: InteractionResponseType::ChannelMessageWithSource,
+                    data: Some(data),
+                },
+            )
             .await
-            .insert(message_id, session);
-        Self::spawn_expiration(message_id, token);
+        {
+            tracing::warn!(error = %e, "failed to send market reply");
+        }
     }
 
     async fn refresh(ctx: &Arc<Context>, session: &mut MarketSession) {
@@ -235,15 +422,54 @@ impl MarketService {
         interaction: Interaction,
         data: MessageComponentInteractionData,
     ) {
-        let Some(message) = interaction.message else {
+        let Some(message) = interaction.message.as_ref() else {
             return;
         };
         let message_id = message.id;
-        let Some(mut session) = Self::get_session_mut(message_id).await else {
+        let lang = Lang::of(&interaction);
+        let (action, state) = session::parse_custom_id(&data.custom_id);
+        let session = Self::SESSIONS
+            .load_or_restore(&ctx.redis, message_id, state, |state| {
+                Self::restore_session(&ctx, state)
+            })
+            .await;
+        let Some((mut session, restored)) = session else {
+            Self::respond_ephemeral(
+                &ctx,
+                &interaction,
+                lang.pick(
+                    "ข้อมูลตลาดนี้หมดอายุแล้ว กรุณาใช้ /warframe market อีกครั้ง",
+                    "This market listing has expired, please run /warframe market again",
+                ),
+            )
+            .await;
             return;
         };
 
-        match data.custom_id.as_str() {
+        if action.starts_with("market_whisper_") {
+            let content = match Self::whisper_order(&session, &message.components, &data.custom_id)
+            {
+                Some(order) => format!(
+                    "```{}```",
+                    Self::whisper_line(
+                        &order.ign,
+                        &session.kind,
+                        &session.item,
+                        order.platinum
+                    )
+                ),
+                None => lang
+                    .pick("ออเดอร์นี้ไม่อยู่แล้ว", "That order is gone")
+                    .to_string(),
+            };
+            Self::respond_ephemeral(&ctx, &interaction, &content).await;
+            Self::insert_session(&ctx, message_id, &session).await;
+            return;
+        }
+
+        let selected = data.values.first().map(String::as_str);
+
+        match action {
             "market_prev_page" if session.page > 1 => {
                 session.page -= 1;
             }
@@ -262,18 +488,42 @@ impl MarketService {
                 session.rank -= 1;
                 session.page = 1;
             }
-            "market_refresh" => {
+            // A restored session was fetched just now.
+            "market_refresh" if !restored => {
                 Self::refresh(&ctx, &mut session).await;
             }
+            "market_status" => {
+                match selected {
+                    Some("ingame") => session.filter.status = StatusFilter::Ingame,
+                    Some("online") => session.filter.status = StatusFilter::Online,
+                    _ => {}
+                }
+                session.page = 1;
+            }
+            "market_sort" => {
+                match selected {
+                    Some("price") => session.filter.sort = SortOrder::Price,
+                    Some("quantity") => session.filter.sort = SortOrder::Quantity,
+                    Some("reputation") => session.filter.sort = SortOrder::Reputation,
+                    _ => {}
+                }
+                session.page = 1;
+            }
+            "market_min_quantity" => {
+                if let Some(quantity) = selected.and_then(|v| v.parse().ok()) {
+                    session.filter.min_quantity = quantity;
+                }
+                session.page = 1;
+            }
             _ => {}
         }
 
         if let Some(guild_ref) = interaction
             .guild_id
             .and_then(|id| ctx.cache.guild(id))
-            && let Ok(embed) = Self::embed_for_session(&guild_ref, &session)
+            && let Ok(embed) = Self::embed_for_session(&guild_ref, &session, lang)
         {
-            let components = Self::components(&session);
+            let components = Self::components(&session, lang);
             let data = InteractionResponseDataBuilder::new()
                 .embeds([embed])
                 .components(components.clone())
@@ -295,7 +545,7 @@ impl MarketService {
             }
         }
 
-        Self::store_session(message_id, session).await;
+        Self::insert_session(&ctx, message_id, &session).await;
     }
 
     pub async fn market_embed(
@@ -322,6 +572,8 @@ impl MarketService {
                             quantity: o.quantity,
                             platinum: o.platinum,
                             ign: o.user.ingame_name,
+                            reputation: o.user.reputation,
+                            ingame: true,
                         });
                 }
                 if by_rank.is_empty() {
@@ -344,6 +596,7 @@ impl MarketService {
                     &kind,
                     if by_rank.len() > 1 { Some(rank) } else { None },
                     orders.clone(),
+                    Lang::default(),
                 )
             }
             Err(e) => {
@@ -353,3 +606,7 @@ impl MarketService {
         }
     }
 }
+
+#[cfg(test)]
+#[path = "tests/mod.rs"]
+mod tests;
@@ -1,56 +1,185 @@
-use std::{collections::BTreeMap, time::Instant};
-use tokio_util::sync::CancellationToken;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
-#[derive(Clone)]
+use super::MarketKind;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct OrderInfo {
     pub quantity: u32,
     pub platinum: u32,
     pub ign: String,
+    #[serde(default)]
+    pub reputation: i32,
+    /// Online but not in game otherwise; sessions saved before the status
+    /// filter existed held in-game orders only.
+    #[serde(default = "in_game_default")]
+    pub ingame: bool,
+}
+
+fn in_game_default() -> bool {
+    true
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatusFilter {
+    #[default]
+    Ingame,
+    /// In game or online on the site.
+    Online,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    /// Best price first, as fetched.
+    #[default]
+    Price,
+    Quantity,
+    Reputation,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrderFilter {
+    pub status: StatusFilter,
+    pub sort: SortOrder,
+    pub min_quantity: u32,
+}
+
+impl Default for OrderFilter {
+    fn default() -> Self {
+        Self { status: StatusFilter::default(), sort: SortOrder::default(), min_quantity: 1 }
+    }
+}
+
+impl OrderFilter {
+    /// Compact form for custom ids, e.g. `ip1`.
+    pub fn encode(&self) -> String {
+        let status = match self.status {
+            StatusFilter::Ingame => 'i',
+            StatusFilter::Online => 'o',
+        };
+        let sort = match self.sort {
+            SortOrder::Price => 'p',
+            SortOrder::Quantity => 'q',
+            SortOrder::Reputation => 'r',
+        };
+        format!("{status}{sort}{}", self.min_quantity)
+    }
+
+    pub fn decode(raw: &str) -> Option<Self> {
+        let mut chars = raw.chars();
+        let status = match chars.next()? {
+            'i' => StatusFilter::Ingame,
+            'o' => StatusFilter::Online,
+            _ => return None,
+        };
+        let sort = match chars.next()? {
+            'p' => SortOrder::Price,
+            'q' => SortOrder::Quantity,
+            'r' => SortOrder::Reputation,
+            _ => return None,
+        };
+        let min_quantity = chars.as_str().parse().ok()?;
+        Some(Self { status, sort, min_quantity })
+    }
 }
 
-#[derive(Clone)]
+/// The state behind one market message, kept in Redis so its buttons work
+/// from any replica and across restarts.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct MarketSession {
     pub item: String,
     pub item_id: String,
     pub slug: String,
-    pub kind: super::MarketKind,
+    pub kind: MarketKind,
     pub orders: BTreeMap<u8, Vec<OrderInfo>>,
     pub rank: u8,
     pub page: usize,
     pub max_rank: Option<u8>,
-    pub last_used: Instant,
-    pub expire_token: CancellationToken,
+    #[serde(default)]
+    pub filter: OrderFilter,
+}
+
+/// What a button carries so its session can be rebuilt after Redis has
+/// let it expire.
+#[derive(Debug, PartialEq, Eq)]
+pub struct SessionRef {
+    pub kind: MarketKind,
+    pub rank: u8,
+    pub page: usize,
+    pub filter: OrderFilter,
+    pub slug: String,
+}
+
+/// Splits `market_next_page:buy:0:1:ip1:slug` into the action and the
+/// session it came from. Buttons from before sessions moved to Redis carry
+/// only the action, and ones from before filters lack the `ip1` part.
+pub fn parse_custom_id(custom_id: &str) -> (&str, Option<SessionRef>) {
+    let Some((action, rest)) = custom_id.split_once(':') else {
+        return (custom_id, None);
+    };
+    let mut parts = rest.splitn(4, ':');
+    let state = (|| {
+        let kind = MarketKind::from_action(parts.next()?)?;
+        let rank = parts.next()?.parse().ok()?;
+        let page = parts.next()?.parse().ok()?;
+        let rest = parts.next()?;
+        // Slugs never contain ':'.
+        let (filter, slug) = match rest.split_once(':') {
+            Some((filter, slug)) => (OrderFilter::decode(filter)?, slug),
+            None => (OrderFilter::default(), rest),
+        };
+        if slug.is_empty() {
+            return None;
+        }
+        Some(SessionRef { kind, rank, page, filter, slug: slug.to_string() })
+    })();
+    (action, state)
 }
 
 impl MarketSession {
-    pub fn lpage(&self) -> usize {
-        self.orders
+    /// Orders for the current rank that pass the filter, in display order.
+    pub fn visible(&self) -> Vec<&OrderInfo> {
+        let mut orders: Vec<&OrderInfo> = self
+            .orders
             .get(&self.rank)
-            .map(|v| v.len().div_ceil(5))
-            .unwrap_or(1)
+            .map(|v| v.iter().collect())
+            .unwrap_or_default();
+        orders.retain(|o| {
+            o.quantity >= self.filter.min_quantity
+                && (o.ingame || self.filter.status == StatusFilter::Online)
+        });
+        match self.filter.sort {
+            SortOrder::Price => {}
+            SortOrder::Quantity => orders.sort_by_key(|o| std::cmp::Reverse(o.quantity)),
+            SortOrder::Reputation => orders.sort_by_key(|o| std::cmp::Reverse(o.reputation)),
+        }
+        orders
+    }
+
+    pub fn lpage(&self) -> usize {
+        self.visible().len().div_ceil(5).max(1)
     }
 
-    pub fn slice(&self) -> &[OrderInfo] {
+    pub fn slice(&self) -> Vec<&OrderInfo> {
         let start = (self.page.saturating_sub(1)) * 5;
-        let orders = self
-            .orders
-            .get(&self.rank)
-            .map(Vec::as_slice)
-            .unwrap_or(&[]);
-        let end = start + 5;
-        if start >= orders.len() {
-            &[]
-        } else if end > orders.len() {
-            &orders[start..]
-        } else {
-            &orders[start..end]
-        }
+        self.visible()
+            .into_iter()
+            .skip(start)
+            .take(5)
+            .collect()
     }
 
-    pub fn touch(&mut self) {
-        self.last_used = Instant::now();
-        self.expire_token.cancel();
-        self.expire_token = CancellationToken::new();
+    pub fn custom_id(&self, action: &str) -> String {
+        format!(
+            "{action}:{}:{}:{}:{}:{}",
+            self.kind.action(),
+            self.rank,
+            self.page,
+            self.filter.encode(),
+            self.slug
+        )
     }
 }
 
@@ -0,0 +1,186 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use twilight_cache_inmemory::{Reference, model::CachedGuild};
+use twilight_model::{
+    channel::message::Embed,
+    id::{Id, marker::GuildMarker},
+};
+
+use crate::{
+    configs::CACHE_PREFIX,
+    context::Context,
+    dbs::redis::{redis_get, redis_set_ex},
+};
+
+use super::{
+    MarketService,
+    client::{self, ClosedStatistics, StatPoint},
+};
+
+/// warframe.market rebuilds its statistics hourly.
+const STATS_TTL: usize = 60 * 60;
+/// Daily medians drawn in the trend sparkline.
+pub(super) const TREND_DAYS: usize = 30;
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Closed trades over one window; prices are `None` without any volume.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WindowStats {
+    pub median: Option<f64>,
+    pub volume: u32,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankStats {
+    pub recent: WindowStats,
+    pub quarter: WindowStats,
+    /// Daily medians, oldest first.
+    pub trend: Vec<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceStats {
+    pub by_rank: BTreeMap<u8, RankStats>,
+    /// Set when the item has ranks, as in `MarketSession::max_rank`.
+    pub max_rank: Option<u8>,
+}
+
+/// Buckets weighted by volume, so a busy hour counts more than a quiet one.
+fn summarize(points: &[&StatPoint]) -> WindowStats {
+    let mut traded: Vec<&StatPoint> = points
+        .iter()
+        .copied()
+        .filter(|p| p.volume > 0)
+        .collect();
+    let volume = traded.iter().map(|p| p.volume).sum();
+    if traded.is_empty() {
+        return WindowStats::default();
+    }
+    let min = traded
+        .iter()
+        .map(|p| p.min_price)
+        .fold(f64::INFINITY, f64::min);
+    let max = traded
+        .iter()
+        .map(|p| p.max_price)
+        .fold(f64::NEG_INFINITY, f64::max);
+    traded.sort_by(|a, b| a.median.total_cmp(&b.median));
+    let mut seen = 0;
+    let mut median = None;
+    for p in &traded {
+        seen += p.volume;
+        if seen * 2 >= volume {
+            median = Some(p.median);
+            break;
+        }
+    }
+    WindowStats { median, volume, min: Some(min), max: Some(max) }
+}
+
+fn by_rank(points: &[StatPoint]) -> BTreeMap<u8, Vec<&StatPoint>> {
+    let mut grouped: BTreeMap<u8, Vec<&StatPoint>> = BTreeMap::new();
+    for p in points {
+        grouped
+            .entry(p.mod_rank.unwrap_or(0))
+            .or_default()
+            .push(p);
+    }
+    grouped
+}
+
+pub(super) fn group(stats: &ClosedStatistics) -> PriceStats {
+    let max_rank = stats
+        .hours
+        .iter()
+        .chain(&stats.days)
+        .filter_map(|p| p.mod_rank)
+        .max();
+    let mut hours = by_rank(&stats.hours);
+    let mut days = by_rank(&stats.days);
+    let ranks: Vec<u8> = hours
+        .keys()
+        .chain(days.keys())
+        .copied()
+        .collect();
+
+    let mut grouped = BTreeMap::new();
+    for rank in ranks {
+        if grouped.contains_key(&rank) {
+            continue;
+        }
+        let recent = hours.remove(&rank).unwrap_or_default();
+        let mut quarter = days.remove(&rank).unwrap_or_default();
+        quarter.sort_by(|a, b| a.datetime.cmp(&b.datetime));
+        let trend: Vec<f64> = quarter
+            .iter()
+            .filter(|p| p.volume > 0)
+            .map(|p| p.median)
+            .collect();
+        let trend = trend[trend.len().saturating_sub(TREND_DAYS)..].to_vec();
+        grouped.insert(
+            rank,
+            RankStats { recent: summarize(&recent), quarter: summarize(&quarter), trend },
+        );
+    }
+    PriceStats { by_rank: grouped, max_rank }
+}
+
+/// One block character per value, scaled between the lowest and highest.
+pub fn sparkline(values: &[f64]) -> String {
+    let low = values
+        .iter()
+        .copied()
+        .fold(f64::INFINITY, f64::min);
+    let high = values
+        .iter()
+        .copied()
+        .fold(f64::NEG_INFINITY, f64::max);
+    let span = high - low;
+    values
+        .iter()
+        .map(|v| {
+            if span <= f64::EPSILON {
+                SPARKS[SPARKS.len() / 2]
+            } else {
+                let level = ((v - low) / span * (SPARKS.len() - 1) as f64).round() as usize;
+                SPARKS[level.min(SPARKS.len() - 1)]
+            }
+        })
+        .collect()
+}
+
+impl MarketService {
+    async fn statistics(ctx: &Arc<Context>, slug: &str) -> anyhow::Result<ClosedStatistics> {
+        let key = format!("{CACHE_PREFIX}:market:stats:{slug}");
+        if let Some(stats) = redis_get(&ctx.redis, &key).await {
+            return Ok(stats);
+        }
+        let stats = client::fetch_statistics(&ctx.reqwest, slug).await?;
+        redis_set_ex(&ctx.redis, &key, &stats, STATS_TTL).await;
+        Ok(stats)
+    }
+
+    pub async fn price_embed(
+        ctx: &Arc<Context>,
+        guild: &Reference<'_, Id<GuildMarker>, CachedGuild>,
+        item: &str,
+        rank: Option<u8>,
+    ) -> anyhow::Result<Embed> {
+        let Some(entry) = Self::find_item(item).await else {
+            return Self::not_found_embed(guild);
+        };
+        match Self::statistics(ctx, &entry.slug).await {
+            Ok(stats) => Self::build_price_embed(guild, item, &entry.slug, &group(&stats), rank),
+            Err(e) => {
+                tracing::warn!(slug = %entry.slug, error = %e, "failed to fetch market statistics");
+                Self::error_embed(guild)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/stats.rs"]
+mod tests;
@@ -124,42 +124,77 @@ async fn test_fetch_orders_map() {
                     platinum: 10,
                     quantity: 1,
                     order_type: "sell".into(),
-                    user: OrderUser { ingame_name: "s1".into(), status: "ingame".into() },
+                    user: OrderUser {
+                        ingame_name: "s1".into(),
+                        status: "ingame".into(),
+                        reputation: 0,
+                    },
                     mod_rank: Some(2),
                 },
                 Order {
                     platinum: 12,
                     quantity: 1,
                     order_type: "sell".into(),
-                    user: OrderUser { ingame_name: "s2".into(), status: "ingame".into() },
+                    user: OrderUser {
+                        ingame_name: "s2".into(),
+                        status: "ingame".into(),
+                        reputation: 0,
+                    },
                     mod_rank: None,
                 },
                 Order {
                     platinum: 5,
                     quantity: 1,
                     order_type: "buy".into(),
-                    user: OrderUser { ingame_name: "b1".into(), status: "ingame".into() },
+                    user: OrderUser {
+                        ingame_name: "b1".into(),
+                        status: "ingame".into(),
+                        reputation: 0,
+                    },
                     mod_rank: Some(3),
                 },
                 Order {
                     platinum: 30,
                     quantity: 1,
                     order_type: "buy".into(),
-                    user: OrderUser { ingame_name: "b2".into(), status: "ingame".into() },
+                    user: OrderUser {
+                        ingame_name: "b2".into(),
+                        status: "ingame".into(),
+                        reputation: 0,
+                    },
                     mod_rank: Some(1),
                 },
                 Order {
                     platinum: 25,
                     quantity: 1,
                     order_type: "buy".into(),
-                    user: OrderUser { ingame_name: "b3".into(), status: "ingame".into() },
+                    user: OrderUser {
+                        ingame_name: "b3".into(),
+                        status: "ingame".into(),
+                        reputation: 0,
+                    },
                     mod_rank: Some(1),
                 },
+                Order {
+                    platinum: 11,
+                    quantity: 1,
+                    order_type: "sell".into(),
+                    user: OrderUser {
+                        ingame_name: "web".into(),
+                        status: "online".into(),
+                        reputation: 7,
+                    },
+                    mod_rank: Some(2),
+                },
                 Order {
                     platinum: 99,
                     quantity: 1,
                     order_type: "sell".into(),
-                    user: OrderUser { ingame_name: "off".into(), status: "offline".into() },
+                    user: OrderUser {
+                        ingame_name: "off".into(),
+                        status: "offline".into(),
+                        reputation: 0,
+                    },
                     mod_rank: Some(2),
                 },
             ],
@@ -170,7 +205,12 @@ async fn test_fetch_orders_map() {
         .await
         .unwrap();
     assert_eq!(buy_max, Some(2));
+    assert_eq!(buy_map[&2].len(), 2);
     assert_eq!(buy_map[&2][0].platinum, 10);
+    assert!(buy_map[&2][0].ingame);
+    assert_eq!(buy_map[&2][1].ign, "web");
+    assert!(!buy_map[&2][1].ingame);
+    assert_eq!(buy_map[&2][1].reputation, 7);
     assert_eq!(buy_map[&0][0].platinum, 12);
 
     let (sell_map, sell_max) = fetch_orders_map(&client, "item", &MarketKind::Sell)
@@ -91,7 +91,13 @@ fn test_error_embed_footer_and_title() {
 #[test]
 fn test_build_fields_limits_and_format() {
     let orders = (1..=6)
-        .map(|i| session::OrderInfo { quantity: i, platinum: i * 10, ign: format!("User{i}") })
+        .map(|i| session::OrderInfo {
+            quantity: i,
+            platinum: i * 10,
+            ign: format!("User{i}"),
+            reputation: 0,
+            ingame: true,
+        })
         .collect::<Vec<_>>();
     let fields = MarketService::build_fields(&orders, "item", &MarketKind::Buy, Some(3));
     assert_eq!(fields.len(), 5);
@@ -109,7 +115,13 @@ fn test_build_fields_limits_and_format() {
 
 #[test]
 fn test_build_fields_strips_backticks() {
-    let orders = vec![session::OrderInfo { quantity: 1, platinum: 10, ign: "Us`er".into() }];
+    let orders = vec![session::OrderInfo {
+        quantity: 1,
+        platinum: 10,
+        ign: "Us`er".into(),
+        reputation: 0,
+        ingame: true,
+    }];
     let fields = MarketService::build_fields(&orders, "``` bad `item`", &MarketKind::Buy, None);
     assert_eq!(fields.len(), 1);
     assert_eq!(
@@ -128,8 +140,20 @@ fn test_build_embed_footer_title_url_and_fields() {
         .guild(guild.id)
         .expect("guild ref");
     let orders = vec![
-        session::OrderInfo { quantity: 1, platinum: 50, ign: "Tester".into() },
-        session::OrderInfo { quantity: 2, platinum: 60, ign: "Tester2".into() },
+        session::OrderInfo {
+            quantity: 1,
+            platinum: 50,
+            ign: "Tester".into(),
+            reputation: 0,
+            ingame: true,
+        },
+        session::OrderInfo {
+            quantity: 2,
+            platinum: 60,
+            ign: "Tester2".into(),
+            reputation: 0,
+            ingame: true,
+        },
     ];
     let embed = MarketService::build_embed(
         &guild_ref,
@@ -138,6 +162,7 @@ fn test_build_embed_footer_title_url_and_fields() {
         &MarketKind::Sell,
         Some(1),
         orders.clone(),
+        Lang::Th,
     )
     .unwrap();
     assert_eq!(
@@ -0,0 +1,110 @@
+use super::*;
+
+fn session_with(orders: usize) -> MarketSession {
+    let entries = (0..orders)
+        .map(|i| OrderInfo {
+            quantity: 1,
+            platinum: 10 + i as u32,
+            ign: format!("u{i}"),
+            reputation: 0,
+            ingame: true,
+        })
+        .collect();
+    MarketSession {
+        item: "Item".to_string(),
+        item_id: "item-id".to_string(),
+        slug: "item_slug".to_string(),
+        kind: MarketKind::Buy,
+        orders: BTreeMap::from([(0, entries)]),
+        rank: 0,
+        page: 1,
+        max_rank: None,
+        filter: OrderFilter::default(),
+    }
+}
+
+fn row(component: &Component) -> &[Component] {
+    match component {
+        Component::ActionRow(row) => &row.components,
+        _ => panic!("expected an action row"),
+    }
+}
+
+#[test]
+fn components_offer_filters_and_one_whisper_per_order() {
+    let session = session_with(2);
+    let rows = MarketService::components(&session, Lang::En);
+    assert_eq!(rows.len(), 5);
+
+    let Component::Button(prev) = &row(&rows[0])[0] else {
+        panic!("expected a button");
+    };
+    assert_eq!(prev.label.as_deref(), Some("Previous"));
+    assert!(prev.disabled);
+
+    let Component::SelectMenu(status) = &row(&rows[1])[0] else {
+        panic!("expected a select menu");
+    };
+    let (action, state) = session::parse_custom_id(&status.custom_id);
+    assert_eq!(action, "market_status");
+    assert_eq!(
+        state.map(|s| s.slug),
+        Some("item_slug".to_string())
+    );
+    let defaults: Vec<&str> = status
+        .options
+        .as_ref()
+        .unwrap()
+        .iter()
+        .filter(|o| o.default)
+        .map(|o| o.value.as_str())
+        .collect();
+    assert_eq!(defaults, vec!["ingame"]);
+
+    let whispers = row(&rows[4]);
+    assert_eq!(whispers.len(), 2);
+    let Component::Button(first) = &whispers[0] else {
+        panic!("expected a button");
+    };
+    assert_eq!(first.label.as_deref(), Some("/w u0"));
+    assert!(
+        first
+            .custom_id
+            .as_deref()
+            .unwrap()
+            .starts_with("market_whisper_0:")
+    );
+}
+
+#[test]
+fn whisper_follows_the_seller_on_the_clicked_button() {
+    let drawn = session_with(2);
+    let rows = MarketService::components(&drawn, Lang::En);
+    let second = drawn.custom_id("market_whisper_1");
+
+    let order = MarketService::whisper_order(&drawn, &rows, &second).unwrap();
+    assert_eq!(order.ign, "u1");
+
+    // u0 sold out, so u1 now sits at index 0 and index 1 is empty.
+    let mut current = session_with(2);
+    current
+        .orders
+        .get_mut(&0)
+        .unwrap()
+        .remove(0);
+    let order = MarketService::whisper_order(&current, &rows, &second).unwrap();
+    assert_eq!(order.ign, "u1");
+
+    let first = drawn.custom_id("market_whisper_0");
+    assert!(MarketService::whisper_order(&current, &rows, &first).is_none());
+}
+
+#[test]
+fn components_skip_whisper_row_without_orders() {
+    let rows = MarketService::components(&session_with(0), Lang::Th);
+    assert_eq!(rows.len(), 4);
+    let Component::Button(prev) = &row(&rows[0])[0] else {
+        panic!("expected a button");
+    };
+    assert_eq!(prev.label.as_deref(), Some("ก่อนหน้า"));
+}
@@ -1,4 +1,3 @@
-use super::super::MarketKind;
 use super::*;
 
 fn build_session(rank: u8, orders: BTreeMap<u8, Vec<OrderInfo>>) -> MarketSession {
@@ -11,8 +10,7 @@ fn build_session(rank: u8, orders: BTreeMap<u8, Vec<OrderInfo>>) -> MarketSessio
         rank,
         page: 1,
         max_rank: None,
-        last_used: Instant::now(),
-        expire_token: CancellationToken::new(),
+        filter: OrderFilter::default(),
     }
 }
 
@@ -20,7 +18,13 @@ fn build_session(rank: u8, orders: BTreeMap<u8, Vec<OrderInfo>>) -> MarketSessio
 fn test_lpage() {
     let mut map = BTreeMap::new();
     let entries: Vec<OrderInfo> = (0..7)
-        .map(|i| OrderInfo { quantity: i, platinum: i, ign: format!("u{i}") })
+        .map(|i| OrderInfo {
+            quantity: i + 1,
+            platinum: i,
+            ign: format!("u{i}"),
+            reputation: 0,
+            ingame: true,
+        })
         .collect();
     map.insert(2, entries);
 
@@ -35,7 +39,13 @@ fn test_lpage() {
 fn test_slice() {
     let mut map = BTreeMap::new();
     let entries: Vec<OrderInfo> = (0..6)
-        .map(|i| OrderInfo { quantity: i, platinum: i, ign: format!("u{i}") })
+        .map(|i| OrderInfo {
+            quantity: i + 1,
+            platinum: i,
+            ign: format!("u{i}"),
+            reputation: 0,
+            ingame: true,
+        })
         .collect();
     map.insert(0, entries);
 
@@ -52,14 +62,59 @@ fn test_slice() {
 }
 
 #[test]
-fn test_touch() {
-    let mut session = build_session(0, BTreeMap::new());
-    let old_token = session.expire_token.clone();
-    let old_time = session.last_used;
+fn session_survives_a_json_round_trip() {
+    let mut map = BTreeMap::new();
+    map.insert(
+        3,
+        vec![OrderInfo {
+            quantity: 1,
+            platinum: 9,
+            ign: "u".to_string(),
+            reputation: 0,
+            ingame: true,
+        }],
+    );
+    let mut session = build_session(3, map);
+    session.max_rank = Some(5);
+
+    let json = serde_json::to_string(&session).unwrap();
+    let restored: MarketSession = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, session);
+}
+
+#[test]
+fn custom_id_round_trips_session_state() {
+    let mut session = build_session(2, BTreeMap::new());
+    session.page = 3;
+    session.kind = MarketKind::Sell;
+    let custom_id = session.custom_id("market_next_page");
+    assert_eq!(
+        custom_id,
+        "market_next_page:sell:2:3:ip1:item-slug"
+    );
 
-    session.touch();
+    let (action, state) = parse_custom_id(&custom_id);
+    assert_eq!(action, "market_next_page");
+    assert_eq!(
+        state,
+        Some(SessionRef {
+            kind: MarketKind::Sell,
+            rank: 2,
+            page: 3,
+            filter: OrderFilter::default(),
+            slug: "item-slug".to_string()
+        })
+    );
+}
 
-    assert!(old_token.is_cancelled());
-    assert!(!session.expire_token.is_cancelled());
-    assert!(session.last_used > old_time);
+#[test]
+fn legacy_custom_id_has_no_state() {
+    assert_eq!(
+        parse_custom_id("market_refresh"),
+        ("market_refresh", None)
+    );
+    assert_eq!(
+        parse_custom_id("market_refresh:buy:x:1:s").1,
+        None
+    );
 }
@@ -0,0 +1,83 @@
+use super::*;
+
+fn point(
+    datetime: &str,
+    volume: u32,
+    min: f64,
+    max: f64,
+    median: f64,
+    rank: Option<u8>,
+) -> StatPoint {
+    StatPoint {
+        datetime: datetime.to_string(),
+        volume,
+        min_price: min,
+        max_price: max,
+        median,
+        mod_rank: rank,
+    }
+}
+
+#[test]
+fn sparkline_scales_between_extremes() {
+    assert_eq!(sparkline(&[1.0, 8.0]), "▁█");
+    assert_eq!(sparkline(&[10.0, 15.0, 20.0]), "▁▅█");
+    assert_eq!(sparkline(&[5.0, 5.0, 5.0]), "▅▅▅");
+    assert_eq!(sparkline(&[]), "");
+}
+
+#[test]
+fn summarize_weights_median_by_volume_and_skips_empty_buckets() {
+    let a = point("2026-01-01", 1, 10.0, 12.0, 11.0, None);
+    let b = point("2026-01-02", 9, 15.0, 30.0, 20.0, None);
+    let empty = point("2026-01-03", 0, 1.0, 100.0, 50.0, None);
+    let stats = summarize(&[&a, &b, &empty]);
+    assert_eq!(stats.median, Some(20.0));
+    assert_eq!(stats.volume, 10);
+    assert_eq!(stats.min, Some(10.0));
+    assert_eq!(stats.max, Some(30.0));
+    assert_eq!(summarize(&[&empty]), WindowStats::default());
+}
+
+#[test]
+fn group_splits_ranks_and_orders_trend() {
+    let stats = ClosedStatistics {
+        hours: vec![
+            point("2026-01-05T10", 2, 10.0, 12.0, 11.0, Some(0)),
+            point("2026-01-05T11", 1, 50.0, 60.0, 55.0, Some(5)),
+        ],
+        days: vec![
+            point("2026-01-03", 4, 9.0, 14.0, 12.0, Some(0)),
+            point("2026-01-01", 3, 8.0, 10.0, 9.0, Some(0)),
+            point("2026-01-02", 0, 0.0, 0.0, 0.0, Some(0)),
+            point("2026-01-01", 1, 45.0, 50.0, 48.0, Some(5)),
+        ],
+    };
+    let grouped = group(&stats);
+    assert_eq!(grouped.max_rank, Some(5));
+    assert_eq!(
+        grouped
+            .by_rank
+            .keys()
+            .copied()
+            .collect::<Vec<_>>(),
+        vec![0, 5]
+    );
+    let base = &grouped.by_rank[&0];
+    assert_eq!(base.trend, vec![9.0, 12.0]);
+    assert_eq!(base.recent.median, Some(11.0));
+    assert_eq!(base.quarter.volume, 7);
+    assert_eq!(grouped.by_rank[&5].recent.median, Some(55.0));
+}
+
+#[test]
+fn group_without_ranks_has_no_max_rank() {
+    let stats = ClosedStatistics {
+        hours: Vec::new(),
+        days: vec![point("2026-01-01", 3, 8.0, 10.0, 9.0, None)],
+    };
+    let grouped = group(&stats);
+    assert_eq!(grouped.max_rank, None);
+    assert_eq!(grouped.by_rank[&0].recent, WindowStats::default());
+    assert!(grouped.by_rank[&0].trend.len() == 1);
+}
@@ -0,0 +1,114 @@
+use super::*;
+use crate::context::ContextBuilder;
+use crate::context::mock_http::MessageOp;
+
+async fn build_context() -> Arc<Context> {
+    Arc::new(
+        ContextBuilder::new()
+            .watchers(false)
+            .build()
+            .await
+            .expect("failed to build Context"),
+    )
+}
+
+fn watch(user_id: u64, kind: &str, price: u32, rank: Option<u8>) -> PriceWatch {
+    PriceWatch {
+        id: None,
+        guild_id: 1,
+        user_id,
+        item: "Primed Flow".to_string(),
+        item_id: format!("watch-item-{user_id}"),
+        slug: "primed_flow".to_string(),
+        kind: kind.to_string(),
+        price,
+        rank,
+        created_at: 0,
+    }
+}
+
+fn order(ign: &str, platinum: u32) -> OrderInfo {
+    OrderInfo { quantity: 1, platinum, ign: ign.to_string(), reputation: 0, ingame: true }
+}
+
+#[test]
+fn matching_respects_side_price_and_rank() {
+    let orders = BTreeMap::from([
+        (0, vec![order("a", 20), order("b", 60)]),
+        (10, vec![order("c", 45), order("d", 90)]),
+    ]);
+
+    let buy = watch(1, "buy", 50, None);
+    let found: Vec<&str> = PriceWatchService::matching(&buy, MarketKind::Buy, &orders)
+        .iter()
+        .map(|(_, o)| o.ign.as_str())
+        .collect();
+    assert_eq!(found, vec!["a", "c"]);
+
+    let buy_max = watch(1, "buy", 50, Some(10));
+    let found = PriceWatchService::matching(&buy_max, MarketKind::Buy, &orders);
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].0, 10);
+
+    let sell = watch(1, "sell", 60, None);
+    let found: Vec<&str> = PriceWatchService::matching(&sell, MarketKind::Sell, &orders)
+        .iter()
+        .map(|(_, o)| o.ign.as_str())
+        .collect();
+    assert_eq!(found, vec!["b", "d"]);
+}
+
+#[test]
+fn describe_shows_side_and_rank() {
+    assert_eq!(
+        PriceWatchService::describe(&watch(1, "buy", 50, Some(10))),
+        "buy Primed Flow ≤ 50p [Rank 10]"
+    );
+    assert_eq!(
+        PriceWatchService::describe(&watch(1, "sell", 80, None)),
+        "sell Primed Flow ≥ 80p"
+    );
+}
+
+#[tokio::test]
+async fn poll_sends_matching_orders_once() {
+    let ctx = build_context().await;
+    let user_id = 5151;
+    let watched = watch(user_id, "buy", 50, None);
+    ctx.mongo
+        .price_watches
+        .insert_one(watched.clone())
+        .await
+        .unwrap();
+    ctx.reqwest.add_json_response(
+        &format!(
+            "https://api.warframe.market/v2/orders/itemId/{}",
+            watched.item_id
+        ),
+        "{\"data\":[{\"platinum\":40,\"quantity\":1,\"type\":\"sell\",\"user\":{\"ingameName\":\"Cheap\",\"status\":\"ingame\"}},{\"platinum\":70,\"quantity\":1,\"type\":\"sell\",\"user\":{\"ingameName\":\"Dear\",\"status\":\"ingame\"}}]}",
+    );
+
+    let dms = |ctx: &Context| {
+        ctx.http
+            .messages
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|m| matches!(m.kind, MessageOp::Create) && m.channel_id.get() == user_id)
+            .map(|m| m.embeds.clone())
+            .collect::<Vec<_>>()
+    };
+    PriceWatchService::poll(&ctx).await;
+    let sent = dms(&ctx);
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0][0].fields.len(), 1);
+    assert!(
+        sent[0][0].fields[0]
+            .value
+            .contains("/w Cheap")
+    );
+
+    // The same order stays claimed for the cooldown.
+    PriceWatchService::poll(&ctx).await;
+    assert_eq!(dms(&ctx).len(), 1);
+}
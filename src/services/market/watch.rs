@@ -0,0 +1,332 @@
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
+use chrono::Utc;
+use futures::StreamExt;
+use mongodb::bson::doc;
+use tokio::task::JoinHandle;
+use twilight_model::{
+    channel::message::Embed,
+    id::{Id, marker::UserMarker},
+};
+use twilight_util::builder::embed::{EmbedBuilder, EmbedFieldBuilder};
+
+use crate::{
+    configs::CACHE_PREFIX,
+    context::Context,
+    dbs::{mongo::models::price_watch::PriceWatch, redis::redis_set_nx_ex},
+    open_dm,
+    services::shutdown,
+};
+
+use super::{MarketKind, MarketService, OrderInfo, client};
+
+pub const MAX_WATCHES_PER_USER: usize = 10;
+const POLL_INTERVAL: Duration = Duration::from_secs(5 * 60);
+/// Spacing between order fetches; warframe.market allows about three
+/// requests a second.
+const REQUEST_GAP: Duration = Duration::from_millis(500);
+/// An unchanged order is not sent to the same member again within this.
+const ORDER_COOLDOWN: usize = 24 * 60 * 60;
+const MAX_ORDERS_PER_DM: usize = 5;
+const COLOR: u32 = 0xF1C40F;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AddOutcome {
+    Added(PriceWatch),
+    Duplicate,
+    LimitReached,
+    NotFound,
+}
+
+pub struct PriceWatchService;
+
+impl PriceWatchService {
+    pub async fn list(ctx: &Context, guild_id: u64, user_id: u64) -> Vec<PriceWatch> {
+        let mut watches = Vec::new();
+        match ctx
+            .mongo
+            .price_watches
+            .find(doc! {"guild_id": guild_id as i64, "user_id": user_id as i64})
+            .await
+        {
+            Ok(mut cursor) => {
+                while let Some(Ok(watch)) = cursor.next().await {
+                    watches.push(watch);
+                }
+            }
+            Err(e) => {
+                tracing::warn!(guild_id, user_id, error = %e, "failed to load price watches");
+            }
+        }
+        watches.sort_by_key(|watch| watch.created_at);
+        watches
+    }
+
+    pub async fn add(
+        ctx: &Context,
+        guild_id: u64,
+        user_id: u64,
+        item: &str,
+        kind: MarketKind,
+        price: u32,
+        rank: Option<u8>,
+    ) -> anyhow::Result<AddOutcome> {
+        let Some(entry) = MarketService::find_item(item).await else {
+            return Ok(AddOutcome::NotFound);
+        };
+        let existing = Self::list(ctx, guild_id, user_id).await;
+        if existing.iter().any(|watch| {
+            watch.item_id == entry.item_id && watch.kind == kind.action() && watch.rank == rank
+        }) {
+            return Ok(AddOutcome::Duplicate);
+        }
+        if existing.len() >= MAX_WATCHES_PER_USER {
+            return Ok(AddOutcome::LimitReached);
+        }
+        let watch = PriceWatch {
+            id: None,
+            guild_id,
+            user_id,
+            item: entry.name,
+            item_id: entry.item_id,
+            slug: entry.slug,
+            kind: kind.action().to_string(),
+            price,
+            rank,
+            created_at: Utc::now().timestamp(),
+        };
+        ctx.mongo
+            .price_watches
+            .insert_one(watch.clone())
+            .await?;
+        Ok(AddOutcome::Added(watch))
+    }
+
+    /// Removes the `index`th (1-based) watch as shown by `list`.
+    pub async fn remove(
+        ctx: &Context,
+        guild_id: u64,
+        user_id: u64,
+        index: usize,
+    ) -> anyhow::Result<Option<PriceWatch>> {
+        let watches = Self::list(ctx, guild_id, user_id).await;
+        let Some(watch) = index
+            .checked_sub(1)
+            .and_then(|i| watches.get(i))
+        else {
+            return Ok(None);
+        };
+        ctx.mongo
+            .price_watches
+            .delete_one(doc! {
+                "guild_id": guild_id as i64,
+                "user_id": user_id as i64,
+                "item_id": &watch.item_id,
+                "kind": &watch.kind,
+                "rank": watch.rank.map(i32::from),
+            })
+            .await?;
+        Ok(Some(watch.clone()))
+    }
+
+    /// `Item ≤ 50p [Rank 10]`, as shown in lists and replies.
+    pub fn describe(watch: &PriceWatch) -> String {
+        let comparison = if watch.kind == "sell" { "≥" } else { "≤" };
+        let rank = watch
+            .rank
+            .map_or(String::new(), |r| format!(" [Rank {r}]"));
+        format!(
+            "{} {} {comparison} {}p{rank}",
+            watch.kind, watch.item, watch.price
+        )
+    }
+
+    pub fn reply_embed(description: &str) -> anyhow::Result<Embed> {
+        let embed = EmbedBuilder::new()
+            .color(COLOR)
+            .title("🔔 Price watches")
+            .description(description)
+            .validate()?
+            .build();
+        Ok(embed)
+    }
+
+    /// Orders that satisfy `watch`, best price first within each rank.
+    pub(crate) fn matching<'a>(
+        watch: &PriceWatch,
+        kind: MarketKind,
+        orders: &'a BTreeMap<u8, Vec<OrderInfo>>,
+    ) -> Vec<(u8, &'a OrderInfo)> {
+        orders
+            .iter()
+            .filter(|(rank, _)| {
+                watch
+                    .rank
+                    .is_none_or(|wanted| wanted == **rank)
+            })
+            .flat_map(|(rank, orders)| orders.iter().map(move |o| (*rank, o)))
+            // Only players in game can take a whisper.
+            .filter(|(_, o)| o.ingame)
+            .filter(|(_, o)| match kind {
+                MarketKind::Buy => o.platinum <= watch.price,
+                MarketKind::Sell => o.platinum >= watch.price,
+            })
+            .collect()
+    }
+
+    /// Redis key claiming one order for one member; overlapping watches
+    /// share it, and a price change makes it a new order.
+    pub(crate) fn order_key(watch: &PriceWatch, rank: u8, order: &OrderInfo) -> String {
+        format!(
+            "{CACHE_PREFIX}:market:watch:{}:{}:{}:{rank}:{}:{}",
+            watch.user_id, watch.item_id, watch.kind, order.ign, order.platinum
+        )
+    }
+
+    fn alert_embed(
+        watch: &PriceWatch,
+        kind: MarketKind,
+        orders: &[(u8, &OrderInfo)],
+    ) -> anyhow::Result<Embed> {
+        let mut builder = EmbedBuilder::new()
+            .color(COLOR)
+            .title(format!("🔔 {}", Self::describe(watch)))
+            .url(format!("{}{}", client::ITEM_URL, watch.slug));
+        for (rank, order) in orders {
+            builder = builder.field(EmbedFieldBuilder::new(
+                format!(
+                    "Quantity : {} | Price : {} platinum. [ Item Rank : {rank} ]",
+                    order.quantity, order.platinum
+                ),
+                format!(
+                    "```{}```",
+                    MarketService::whisper_line(&order.ign, &kind, &watch.item, order.platinum)
+                ),
+            ));
+        }
+        Ok(builder.validate()?.build())
+    }
+
+    async fn notify(
+        ctx: &Context,
+        watch: &PriceWatch,
+        kind: MarketKind,
+        matches: &[(u8, &OrderInfo)],
+    ) {
+        let mut fresh = Vec::new();
+        for (rank, order) in matches {
+            if fresh.len() == MAX_ORDERS_PER_DM {
+                break;
+            }
+            if redis_set_nx_ex(
+                &ctx.redis,
+                &Self::order_key(watch, *rank, order),
+                &1,
+                ORDER_COOLDOWN,
+            )
+            .await
+            {
+                fresh.push((*rank, *order));
+            }
+        }
+        if fresh.is_empty() {
+            return;
+        }
+        let embed = match Self::alert_embed(watch, kind, &fresh) {
+            Ok(embed) => embed,
+            Err(e) => {
+                tracing::warn!(error = %e, "failed to build price watch embed");
+                return;
+            }
+        };
+        let user_id: Id<UserMarker> = Id::new(watch.user_id);
+        let dm = match open_dm!(ctx.http, user_id).await {
+            Ok(dm) => dm,
+            Err(e) => {
+                tracing::warn!(user_id = watch.user_id, error = %e, "failed to open dm for price watch");
+                return;
+            }
+        };
+        match ctx
+            .http
+            .create_message(dm.id)
+            .embeds(&[embed])
+            .await
+        {
+            Ok(_) => metrics::counter!("price_watch_alerts_sent_total").increment(1),
+            Err(e) => {
+                tracing::warn!(user_id = watch.user_id, error = %e, "failed to send price watch")
+            }
+        }
+    }
+
+    async fn load_all(ctx: &Context) -> Vec<PriceWatch> {
+        let mut watches = Vec::new();
+        match ctx
+            .mongo
+            .price_watches
+            .find(doc! {})
+            .await
+        {
+            Ok(mut cursor) => {
+                while let Some(Ok(watch)) = cursor.next().await {
+                    watches.push(watch);
+                }
+            }
+            Err(e) => tracing::warn!(error = %e, "failed to load price watches"),
+        }
+        watches
+    }
+
+    /// Fetches each watched item once per side of the market, however many
+    /// members watch it.
+    pub(crate) async fn poll(ctx: &Arc<Context>) {
+        let mut batches: BTreeMap<(String, MarketKind), Vec<PriceWatch>> = BTreeMap::new();
+        for watch in Self::load_all(ctx).await {
+            let Some(kind) = MarketKind::from_action(&watch.kind) else {
+                tracing::warn!(kind = %watch.kind, "skipping price watch with unknown kind");
+                continue;
+            };
+            batches
+                .entry((watch.item_id.clone(), kind))
+                .or_default()
+                .push(watch);
+        }
+        for (i, ((item_id, kind), watches)) in batches.into_iter().enumerate() {
+            if i > 0 {
+                tokio::time::sleep(REQUEST_GAP).await;
+            }
+            let orders = match client::fetch_orders_map(&ctx.reqwest, &item_id, &kind).await {
+                Ok((orders, _)) => orders,
+                Err(e) => {
+                    tracing::warn!(item_id, error = %e, "failed to fetch orders for price watch");
+                    continue;
+                }
+            };
+            for watch in &watches {
+                let matches = Self::matching(watch, kind, &orders);
+                if !matches.is_empty() {
+                    Self::notify(ctx, watch, kind, &matches).await;
+                }
+            }
+        }
+    }
+
+    pub fn spawn(ctx: &Arc<Context>) -> JoinHandle<()> {
+        let ctx = ctx.clone();
+        tokio::spawn(async move {
+            let token = shutdown::get_token();
+            let mut interval = tokio::time::interval(POLL_INTERVAL);
+            loop {
+                tokio::select! {
+                    _ = token.cancelled() => break,
+                    _ = interval.tick() => Self::poll(&ctx).await,
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/watch.rs"]
+mod tests;
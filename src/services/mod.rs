@@ -2,12 +2,14 @@ pub mod ai;
 pub mod broadcast;
 pub mod build;
 pub mod channel;
+pub mod farm;
 pub mod guild_settings;
 pub mod health;
 pub mod introduction;
 pub mod latency;
 pub mod market;
 pub mod notification;
+pub mod riven;
 pub mod role;
 pub mod role_message;
 pub mod scam_detect;
@@ -16,3 +18,4 @@ pub mod spam;
 pub mod status;
 pub mod status_message;
 pub mod verification;
+pub mod worldstate_alert;
@@ -20,7 +20,7 @@ use crate::{
 };
 use std::sync::Arc;
 
-use worker::{next_monday_duration_from, notify_loop, notify_umbra_loop};
+use worker::{next_monday_duration_from, notify_baro_loop, notify_loop, notify_umbra_loop};
 
 pub struct NotificationService;
 
@@ -33,7 +33,7 @@ impl NotificationService {
         ch: Channel,
         token: CancellationToken,
     ) -> Vec<JoinHandle<()>> {
-        let mut handles = Vec::with_capacity(3);
+        let mut handles = Vec::with_capacity(4);
         let channel_id = Id::new(ch.channel_id);
         if let Some(role) = RoleService::get_by_type(ctx, ch.guild_id, &RoleEnum::Helminth).await {
             handles.push(notify_loop(
@@ -66,6 +66,7 @@ impl NotificationService {
                 token.clone(),
             ));
         }
+        handles.push(notify_baro_loop(ctx, channel_id, token.clone()));
         handles
     }
 
@@ -1,6 +1,10 @@
 use super::*;
+use crate::configs::notifications::BARO_REMINDER_HOURS;
+use crate::context::mock_http::{MessageOp, MessageRecord};
 use crate::context::{ContextBuilder, mock_http::MockClient as Client};
 use crate::dbs::mongo::models::role::Role;
+use crate::warframe::api::{VoidTrader, VoidTraderItem};
+use chrono::DateTime;
 
 async fn build_context() -> Arc<Context> {
     let ctx = ContextBuilder::new()
@@ -12,6 +16,10 @@ async fn build_context() -> Arc<Context> {
     Arc::new(ctx)
 }
 
+/// The seeded Helminth reminder plus the Baro tracker every notification
+/// channel gets.
+const TASKS_PER_GUILD: usize = 2;
+
 async fn settle() {
     for _ in 0..64 {
         tokio::task::yield_now().await;
@@ -80,7 +88,7 @@ async fn test_reload_guild_concurrent_no_task_leak() {
             .unwrap_or(0)
     };
     assert_eq!(
-        tracked_len, 1,
+        tracked_len, TASKS_PER_GUILD,
         "exactly one set of tasks should remain tracked in HANDLES"
     );
 
@@ -89,7 +97,7 @@ async fn test_reload_guild_concurrent_no_task_leak() {
         .num_alive_tasks();
     assert_eq!(
         after.saturating_sub(before),
-        1,
+        TASKS_PER_GUILD,
         "the losing call's tasks must have been aborted, not left running untracked"
     );
 
@@ -113,7 +121,10 @@ async fn test_reload_guild_sequential_replaces_tasks() {
     let after_first = tokio::runtime::Handle::current()
         .metrics()
         .num_alive_tasks();
-    assert_eq!(after_first.saturating_sub(before), 1);
+    assert_eq!(
+        after_first.saturating_sub(before),
+        TASKS_PER_GUILD
+    );
 
     NotificationService::reload_guild(&ctx, guild_id).await;
     settle().await;
@@ -132,7 +143,7 @@ async fn test_reload_guild_sequential_replaces_tasks() {
             .map(|v| v.len())
             .unwrap_or(0)
     };
-    assert_eq!(tracked_len, 1);
+    assert_eq!(tracked_len, TASKS_PER_GUILD);
 
     clear_handles(guild_id).await;
 }
@@ -162,7 +173,7 @@ async fn test_startup_merge_does_not_clobber_concurrent_reload() {
             .unwrap_or(0)
     };
     assert_eq!(
-        tracked_len, 1,
+        tracked_len, TASKS_PER_GUILD,
         "a reload racing startup should remain the only tracked task set"
     );
 
@@ -171,9 +182,141 @@ async fn test_startup_merge_does_not_clobber_concurrent_reload() {
         .num_alive_tasks();
     assert_eq!(
         after.saturating_sub(before),
-        1,
+        TASKS_PER_GUILD,
         "startup-spawned duplicates must be aborted instead of leaking untracked"
     );
 
     clear_handles(guild_id).await;
 }
+
+fn trader(id: &str, active: bool, activation: DateTime<Utc>, expiry: DateTime<Utc>) -> VoidTrader {
+    VoidTrader {
+        id: id.to_string(),
+        location: "Strata Relay (Earth)".to_string(),
+        activation: activation.to_rfc3339(),
+        expiry: expiry.to_rfc3339(),
+        active,
+        inventory: vec![VoidTraderItem {
+            item: "Primed Flow".to_string(),
+            ducats: 350,
+            credits: 250_000,
+        }],
+    }
+}
+
+fn created_in(ctx: &Context, channel_id: u64) -> Vec<MessageRecord> {
+    ctx.http
+        .messages
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|m| matches!(m.kind, MessageOp::Create) && m.channel_id.get() == channel_id)
+        .cloned()
+        .collect()
+}
+
+#[tokio::test]
+async fn test_baro_step_waits_for_arrival() {
+    let ctx = build_context().await;
+    let now = Utc::now();
+    let activation = now + chrono::Duration::days(3);
+    let trader = trader(
+        "baro-wait",
+        false,
+        activation,
+        activation + chrono::Duration::days(2),
+    );
+
+    let next = worker::baro_step(&ctx, Id::new(9_200_001), &trader, now).await;
+
+    assert_eq!(next.timestamp(), activation.timestamp());
+    assert!(created_in(&ctx, 9_200_001).is_empty());
+}
+
+#[tokio::test]
+async fn test_baro_step_announces_once_then_reminds_before_leaving() {
+    let ctx = build_context().await;
+    let channel_id = Id::new(9_200_002);
+    let now = Utc::now();
+    let expiry = now + chrono::Duration::days(2);
+    let trader = trader(
+        "baro-visit",
+        true,
+        now - chrono::Duration::hours(1),
+        expiry,
+    );
+
+    let next = worker::baro_step(&ctx, channel_id, &trader, now).await;
+    let reminder_at = expiry - chrono::Duration::hours(*BARO_REMINDER_HOURS as i64);
+    assert_eq!(next.timestamp(), reminder_at.timestamp());
+    let sent = created_in(&ctx, channel_id.get());
+    assert_eq!(sent.len(), 1);
+    assert_eq!(
+        sent[0].embeds[0].title.as_deref(),
+        Some("Baro Ki'Teer has arrived at Strata Relay (Earth)")
+    );
+
+    // Waking at the reminder must not repeat the arrival.
+    let next = worker::baro_step(&ctx, channel_id, &trader, reminder_at).await;
+    assert_eq!(next.timestamp(), expiry.timestamp());
+    let sent = created_in(&ctx, channel_id.get());
+    assert_eq!(sent.len(), 2);
+    assert!(
+        sent[1]
+            .content
+            .as_deref()
+            .unwrap()
+            .starts_with(NOTIFICATIONS.baro_leaving)
+    );
+}
+
+#[tokio::test]
+async fn test_baro_step_retries_an_arrival_that_failed_to_send() {
+    let ctx = build_context().await;
+    let channel_id = Id::new(9_200_003);
+    let now = Utc::now();
+    let trader = trader(
+        "baro-retry",
+        true,
+        now - chrono::Duration::hours(1),
+        now + chrono::Duration::days(2),
+    );
+
+    ctx.http.fail_next_create_message();
+    worker::baro_step(&ctx, channel_id, &trader, now).await;
+    assert!(created_in(&ctx, channel_id.get()).is_empty());
+
+    worker::baro_step(&ctx, channel_id, &trader, now).await;
+    assert_eq!(created_in(&ctx, channel_id.get()).len(), 1);
+}
+
+#[tokio::test]
+async fn test_baro_step_resends_only_the_arrival_messages_that_failed() {
+    let ctx = build_context().await;
+    let channel_id = Id::new(9_200_004);
+    let now = Utc::now();
+    let mut trader = trader(
+        "baro-partial",
+        true,
+        now - chrono::Duration::hours(1),
+        now + chrono::Duration::days(2),
+    );
+    trader.inventory = (0..400)
+        .map(|i| VoidTraderItem {
+            item: format!("Primed Mod Number {i}"),
+            ducats: 300,
+            credits: 200_000,
+        })
+        .collect();
+    let total = crate::warframe::worldstate::void_trader_arrival_messages(&trader)
+        .unwrap()
+        .len();
+    assert!(total > 2);
+
+    ctx.http.fail_nth_create_message(2);
+    worker::baro_step(&ctx, channel_id, &trader, now).await;
+    assert_eq!(created_in(&ctx, channel_id.get()).len(), 1);
+
+    worker::baro_step(&ctx, channel_id, &trader, now).await;
+    assert_eq!(created_in(&ctx, channel_id.get()).len(), total);
+}
@@ -6,7 +6,22 @@ use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
 use twilight_model::id::{Id, marker::ChannelMarker};
 
-use crate::{configs::notifications::NOTIFICATIONS, context::Context};
+use crate::{
+    configs::{
+        CACHE_PREFIX,
+        notifications::{BARO_REMINDER_HOURS, NOTIFICATIONS},
+    },
+    context::Context,
+    dbs::redis::{redis_delete, redis_set_nx_ex},
+    warframe::{api::VoidTrader, utils::format_time, worldstate},
+};
+
+/// Waits at least this long between worldstate checks, e.g. while the API
+/// has not yet flipped Baro to active after his activation time.
+const BARO_MIN_WAIT_SECS: i64 = 60;
+const BARO_RETRY_SECS: i64 = 600;
+/// Longer than one Baro visit, so each announcement is claimed only once.
+const BARO_CLAIM_TTL: usize = 7 * 24 * 60 * 60;
 
 pub(crate) fn next_monday_duration_from(now: DateTime<Utc>) -> Duration {
     let w = now.weekday().number_from_monday();
@@ -116,3 +131,174 @@ pub(crate) fn notify_umbra_loop(
         }
     })
 }
+
+/// Sleeps until `target`, waking every ten minutes so a suspended host does
+/// not oversleep; `false` once `token` is cancelled.
+async fn sleep_until(target: DateTime<Utc>, token: &CancellationToken) -> bool {
+    loop {
+        let remaining = (target - Utc::now())
+            .to_std()
+            .unwrap_or_default();
+        if remaining.is_zero() {
+            return true;
+        }
+        tokio::select! {
+            _ = token.cancelled() => return false,
+            _ = tokio::time::sleep(remaining.min(Duration::from_secs(600))) => {}
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum BaroNotice {
+    Arrival,
+    Leaving,
+}
+
+impl BaroNotice {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Arrival => "arrival",
+            Self::Leaving => "leaving",
+        }
+    }
+}
+
+/// Claims `key` and runs `send`, releasing the claim again if it fails so
+/// the next wake-up retries instead of losing the message for the rest of
+/// the claim's lifetime.
+async fn send_claimed(
+    ctx: &Context,
+    key: &str,
+    send: impl Future<Output = anyhow::Result<()>>,
+) -> anyhow::Result<()> {
+    if !redis_set_nx_ex(&ctx.redis, key, &1, BARO_CLAIM_TTL).await {
+        return Ok(());
+    }
+    let result = send.await;
+    if result.is_err() {
+        redis_delete(&ctx.redis, key).await;
+    }
+    result
+}
+
+/// Each message of the announcement is claimed on its own, so a retry only
+/// re-sends the ones that did not go out.
+async fn send_arrival(
+    ctx: &Context,
+    channel_id: Id<ChannelMarker>,
+    trader: &VoidTrader,
+    key: &str,
+) -> anyhow::Result<()> {
+    for (i, embeds) in worldstate::void_trader_arrival_messages(trader)?
+        .into_iter()
+        .enumerate()
+    {
+        send_claimed(ctx, &format!("{key}:{i}"), async {
+            ctx.http
+                .create_message(channel_id)
+                .embeds(&embeds)
+                .await
+                .map(|_| ())
+        })
+        .await?;
+    }
+    Ok(())
+}
+
+async fn send_baro(
+    ctx: &Context,
+    channel_id: Id<ChannelMarker>,
+    notice: BaroNotice,
+    trader: &VoidTrader,
+) {
+    let key = format!(
+        "{CACHE_PREFIX}:wf:baro:{}:{}:{}",
+        notice.as_str(),
+        channel_id.get(),
+        trader.id
+    );
+    let result = match notice {
+        BaroNotice::Arrival => send_arrival(ctx, channel_id, trader, &key).await,
+        BaroNotice::Leaving => {
+            send_claimed(ctx, &key, async {
+                ctx.http
+                    .create_message(channel_id)
+                    .content(&format!(
+                        "{} {}",
+                        NOTIFICATIONS.baro_leaving,
+                        format_time(&trader.expiry)
+                    ))
+                    .await
+                    .map(|_| ())
+            })
+            .await
+        }
+    };
+    if let Err(e) = result {
+        tracing::warn!(
+            channel_id = channel_id.get(),
+            notice = notice.as_str(),
+            error = %e,
+            "failed to send baro notification",
+        );
+    }
+}
+
+/// Announces what is due for `trader` and returns when to look again.
+pub(crate) async fn baro_step(
+    ctx: &Context,
+    channel_id: Id<ChannelMarker>,
+    trader: &VoidTrader,
+    now: DateTime<Utc>,
+) -> DateTime<Utc> {
+    let parse = |s: &str| {
+        DateTime::parse_from_rfc3339(s)
+            .ok()
+            .map(|t| t.with_timezone(&Utc))
+    };
+    let (Some(activation), Some(expiry)) = (parse(&trader.activation), parse(&trader.expiry))
+    else {
+        return now + chrono::Duration::seconds(BARO_RETRY_SECS);
+    };
+    if !trader.active || now < activation {
+        return activation;
+    }
+
+    send_baro(ctx, channel_id, BaroNotice::Arrival, trader).await;
+    if *BARO_REMINDER_HOURS == 0 {
+        return expiry;
+    }
+    let reminder_at = expiry - chrono::Duration::hours(*BARO_REMINDER_HOURS as i64);
+    if now < reminder_at {
+        return reminder_at;
+    }
+    if now < expiry {
+        send_baro(ctx, channel_id, BaroNotice::Leaving, trader).await;
+    }
+    expiry
+}
+
+pub(crate) fn notify_baro_loop(
+    ctx: &Arc<Context>,
+    channel_id: Id<ChannelMarker>,
+    token: CancellationToken,
+) -> JoinHandle<()> {
+    let ctx = ctx.clone();
+    tokio::spawn(async move {
+        loop {
+            let now = Utc::now();
+            let next = match worldstate::void_trader(&ctx).await {
+                Ok(trader) => baro_step(&ctx, channel_id, &trader, now).await,
+                Err(e) => {
+                    tracing::warn!(error = %e, "failed to fetch void trader");
+                    now + chrono::Duration::seconds(BARO_RETRY_SECS)
+                }
+            };
+            let next = next.max(Utc::now() + chrono::Duration::seconds(BARO_MIN_WAIT_SECS));
+            if !sleep_until(next, &token).await {
+                return;
+            }
+        }
+    })
+}
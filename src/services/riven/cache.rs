@@ -0,0 +1,181 @@
+use std::{
+    cmp,
+    sync::atomic::{AtomicU64, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use crate::{
+    context::Context,
+    utils::ascii::{ascii_starts_with_icase, cmp_ignore_ascii_case, collect_prefix_icase},
+};
+
+use super::{
+    RivenService, client,
+    session::{self, normalize_stat},
+};
+use std::sync::Arc;
+
+const REDIS_KEY: &str = "discord-bot:riven-weapons";
+const UPDATE_SECS: u16 = 60 * 60;
+/// Discord's limit on an autocomplete choice's value.
+const CHOICE_LIMIT: usize = 100;
+/// Discord shows at most 25 autocomplete choices.
+const MAX_CHOICES: usize = 25;
+
+/// A riven weapon or stat: its display name and warframe.market slug.
+#[derive(Clone, Serialize, Deserialize)]
+pub(super) struct RivenEntry {
+    pub name: String,
+    pub slug: String,
+}
+
+static WEAPONS: Lazy<RwLock<Vec<RivenEntry>>> = Lazy::new(|| RwLock::new(Vec::new()));
+static STATS: Lazy<RwLock<Vec<RivenEntry>>> = Lazy::new(|| RwLock::new(Vec::new()));
+static LAST_UPDATE: Lazy<AtomicU64> = Lazy::new(|| AtomicU64::new(0));
+
+/// warframe.market's slug for a stat typed as its slug or its name. With no
+/// stat list loaded the normalized input is passed through for
+/// warframe.market to judge.
+pub(super) fn match_stat(stats: &[RivenEntry], raw: &str) -> Option<String> {
+    let wanted = normalize_stat(raw);
+    if wanted.is_empty() {
+        return None;
+    }
+    if stats.is_empty() {
+        return Some(wanted);
+    }
+    stats
+        .iter()
+        .find(|e| e.slug == wanted || normalize_stat(&e.name) == wanted)
+        .map(|e| e.slug.clone())
+}
+
+/// Suggestions for a comma-separated stat option: the stats already typed
+/// stay in front and the one being typed is completed by name.
+pub(super) fn complete_stats(stats: &[RivenEntry], input: &str) -> Vec<String> {
+    let (picked, typing) = match input.rsplit_once(',') {
+        Some((picked, typing)) => (picked.trim(), typing.trim()),
+        None => ("", input.trim()),
+    };
+    let chosen = session::parse_stats(picked);
+    stats
+        .iter()
+        .filter(|e| ascii_starts_with_icase(&e.name, typing))
+        .filter(|e| !chosen.contains(&e.slug) && !chosen.contains(&normalize_stat(&e.name)))
+        .map(
+            |e| {
+                if picked.is_empty() { e.name.clone() } else { format!("{picked}, {}", e.name) }
+            },
+        )
+        .filter(|choice| choice.len() <= CHOICE_LIMIT)
+        .take(MAX_CHOICES)
+        .collect()
+}
+
+impl RivenService {
+    pub async fn init(ctx: Arc<Context>) {
+        if let Some(data) = client::load_from_redis(&ctx.redis, REDIS_KEY).await {
+            *WEAPONS.write().await = data.weapons;
+            *STATS.write().await = data.stats;
+            LAST_UPDATE.store(
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_secs(),
+                Ordering::Relaxed,
+            );
+        } else if let Err(e) = client::update(
+            &ctx.reqwest,
+            REDIS_KEY,
+            &WEAPONS,
+            &STATS,
+            &LAST_UPDATE,
+            &ctx.redis,
+        )
+        .await
+        {
+            tracing::warn!(error = %e, "failed to update riven weapons");
+        }
+    }
+
+    pub async fn search(prefix: &str) -> Vec<String> {
+        let weapons = WEAPONS.read().await;
+        if weapons.is_empty() {
+            return Vec::new();
+        }
+        collect_prefix_icase(&weapons, prefix, |e| &e.name)
+    }
+
+    async fn maybe_refresh(ctx: &Arc<Context>) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let last = LAST_UPDATE.load(Ordering::Relaxed);
+        if now.saturating_sub(last) > UPDATE_SECS as u64
+            && let Err(e) = client::update(
+                &ctx.reqwest,
+                REDIS_KEY,
+                &WEAPONS,
+                &STATS,
+                &LAST_UPDATE,
+                &ctx.redis,
+            )
+            .await
+        {
+            tracing::warn!(error = %e, "failed to update riven weapons");
+        }
+    }
+
+    pub async fn search_with_update(ctx: &Arc<Context>, prefix: &str) -> Vec<String> {
+        let mut results = Self::search(prefix).await;
+        if results.is_empty() {
+            Self::maybe_refresh(ctx).await;
+            results = Self::search(prefix).await;
+        }
+        results
+    }
+
+    pub async fn search_stats_with_update(ctx: &Arc<Context>, input: &str) -> Vec<String> {
+        if STATS.read().await.is_empty() {
+            Self::maybe_refresh(ctx).await;
+        }
+        complete_stats(&STATS.read().await, input)
+    }
+
+    pub(super) async fn find_stat(raw: &str) -> Option<String> {
+        match_stat(&STATS.read().await, raw)
+    }
+
+    /// Slugs are not sorted, so this scans; only expired sessions need it.
+    pub(super) async fn find_weapon_by_slug(slug: &str) -> Option<RivenEntry> {
+        WEAPONS
+            .read()
+            .await
+            .iter()
+            .find(|e| e.slug == slug)
+            .cloned()
+    }
+
+    pub(super) async fn find_weapon(name: &str) -> Option<RivenEntry> {
+        let weapons = WEAPONS.read().await;
+        let idx = weapons
+            .partition_point(|e| cmp_ignore_ascii_case(&e.name, name) == cmp::Ordering::Less);
+        if idx < weapons.len()
+            && cmp_ignore_ascii_case(&weapons[idx].name, name) == cmp::Ordering::Equal
+            && !weapons[idx].slug.is_empty()
+        {
+            Some(weapons[idx].clone())
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/cache.rs"]
+mod tests;
@@ -0,0 +1,254 @@
+use std::sync::atomic::AtomicU64;
+
+use deadpool_redis::Pool;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use crate::{
+    dbs::redis::{redis_get, redis_set},
+    utils::{ascii::cmp_ignore_ascii_case, http::HttpProvider},
+};
+
+use super::{
+    cache::RivenEntry,
+    session::{AuctionInfo, RivenQuery},
+};
+
+const WEAPONS_URL: &str = "https://api.warframe.market/v1/riven/items";
+const ATTRIBUTES_URL: &str = "https://api.warframe.market/v1/riven/attributes";
+const AUCTIONS_URL: &str = "https://api.warframe.market/v1/auctions/search";
+pub(super) const AUCTION_URL: &str = "https://warframe.market/auction/";
+pub(super) const SEARCH_PAGE_URL: &str =
+    "https://warframe.market/auctions/search?type=riven&weapon_url_name=";
+
+#[derive(Deserialize, Serialize)]
+struct WeaponsResponse {
+    payload: WeaponsPayload,
+}
+
+#[derive(Deserialize, Serialize)]
+struct WeaponsPayload {
+    items: Vec<RivenWeapon>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct RivenWeapon {
+    url_name: String,
+    item_name: String,
+}
+
+#[derive(Deserialize, Serialize)]
+struct AttributesResponse {
+    payload: AttributesPayload,
+}
+
+#[derive(Deserialize, Serialize)]
+struct AttributesPayload {
+    attributes: Vec<AttributeDef>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct AttributeDef {
+    url_name: String,
+    effect: String,
+}
+
+/// Weapons and stats as kept in Redis, each sorted by name.
+#[derive(Default, Deserialize, Serialize)]
+pub(super) struct StoredRiven {
+    pub weapons: Vec<RivenEntry>,
+    pub stats: Vec<RivenEntry>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub(super) struct AuctionsResponse {
+    payload: AuctionsPayload,
+}
+
+#[derive(Deserialize, Serialize)]
+struct AuctionsPayload {
+    auctions: Vec<Auction>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub(super) struct AuctionOwner {
+    pub ingame_name: String,
+    pub status: String,
+    #[serde(default)]
+    pub reputation: i32,
+}
+
+#[derive(Deserialize, Serialize)]
+pub(super) struct RivenAttribute {
+    pub url_name: String,
+    pub value: f64,
+    pub positive: bool,
+}
+
+#[derive(Deserialize, Serialize)]
+pub(super) struct RivenItem {
+    pub name: String,
+    #[serde(default)]
+    pub mod_rank: u8,
+    #[serde(default)]
+    pub re_rolls: u32,
+    #[serde(default)]
+    pub mastery_level: u8,
+    #[serde(default)]
+    pub polarity: String,
+    #[serde(default)]
+    pub attributes: Vec<RivenAttribute>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub(super) struct Auction {
+    pub id: String,
+    pub buyout_price: Option<u32>,
+    pub starting_price: u32,
+    #[serde(default)]
+    pub top_bid: Option<u32>,
+    pub is_direct_sell: bool,
+    #[serde(default)]
+    pub closed: bool,
+    pub owner: AuctionOwner,
+    pub item: RivenItem,
+}
+
+impl Auction {
+    /// What it costs right now: the buyout for direct sales, otherwise the
+    /// highest bid or the opening price.
+    fn price(&self) -> u32 {
+        match (self.is_direct_sell, self.buyout_price) {
+            (true, Some(buyout)) => buyout,
+            _ => self
+                .top_bid
+                .unwrap_or(self.starting_price),
+        }
+    }
+}
+
+fn sort_entries(entries: &mut Vec<RivenEntry>) {
+    entries.sort_unstable_by(|a, b| cmp_ignore_ascii_case(&a.name, &b.name));
+    entries.dedup_by(|a, b| a.name.eq_ignore_ascii_case(&b.name));
+}
+
+pub(super) async fn load_from_redis(pool: &Pool, key: &str) -> Option<StoredRiven> {
+    let mut stored = redis_get::<StoredRiven>(pool, key).await?;
+    sort_entries(&mut stored.weapons);
+    sort_entries(&mut stored.stats);
+    Some(stored)
+}
+
+/// Refreshes the riven weapons and the stats warframe.market knows.
+pub(super) async fn update<H>(
+    client: &H,
+    key: &str,
+    weapons: &Lazy<RwLock<Vec<RivenEntry>>>,
+    stats: &Lazy<RwLock<Vec<RivenEntry>>>,
+    last_update: &Lazy<AtomicU64>,
+    pool: &Pool,
+) -> anyhow::Result<()>
+where
+    H: HttpProvider + Sync,
+{
+    let data: WeaponsResponse = client.get_json(WEAPONS_URL).await?;
+    let attributes: AttributesResponse = client.get_json(ATTRIBUTES_URL).await?;
+    let mut stored = StoredRiven {
+        weapons: data
+            .payload
+            .items
+            .into_iter()
+            .map(|w| RivenEntry { name: w.item_name, slug: w.url_name })
+            .collect(),
+        stats: attributes
+            .payload
+            .attributes
+            .into_iter()
+            .map(|a| RivenEntry { name: a.effect, slug: a.url_name })
+            .collect(),
+    };
+    sort_entries(&mut stored.weapons);
+    sort_entries(&mut stored.stats);
+    redis_set(pool, key, &stored).await;
+    *weapons.write().await = stored.weapons;
+    *stats.write().await = stored.stats;
+    last_update.store(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+        std::sync::atomic::Ordering::Relaxed,
+    );
+    Ok(())
+}
+
+/// The auction search for `query`, with every value percent-encoded.
+pub(super) fn search_url(query: &RivenQuery) -> anyhow::Result<reqwest::Url> {
+    let positive = query.positive.join(",");
+    let mut params = vec![
+        ("type", "riven"),
+        ("weapon_url_name", query.weapon_slug.as_str()),
+        ("sort_by", "price_asc"),
+    ];
+    if !positive.is_empty() {
+        params.push(("positive_stats", positive.as_str()));
+    }
+    if let Some(negative) = &query.negative {
+        params.push(("negative_stats", negative.as_str()));
+    }
+    Ok(reqwest::Url::parse_with_params(
+        AUCTIONS_URL,
+        &params,
+    )?)
+}
+
+/// Open auctions from sellers who are online, cheapest first, within
+/// `query.max_price`.
+pub(super) async fn fetch_auctions<H>(
+    client: &H,
+    query: &RivenQuery,
+) -> anyhow::Result<Vec<AuctionInfo>>
+where
+    H: HttpProvider + Sync,
+{
+    let data: AuctionsResponse = client
+        .get_json(search_url(query)?.as_str())
+        .await?;
+    let mut auctions: Vec<AuctionInfo> = data
+        .payload
+        .auctions
+        .into_iter()
+        .filter(|a| !a.closed && a.owner.status != "offline")
+        .filter(|a| {
+            query
+                .max_price
+                .is_none_or(|max| a.price() <= max)
+        })
+        .map(|a| AuctionInfo {
+            price: a.price(),
+            id: a.id,
+            direct: a.is_direct_sell,
+            ign: a.owner.ingame_name,
+            ingame: a.owner.status == "ingame",
+            reputation: a.owner.reputation,
+            name: a.item.name,
+            rank: a.item.mod_rank,
+            rerolls: a.item.re_rolls,
+            mastery: a.item.mastery_level,
+            polarity: a.item.polarity,
+            attributes: a
+                .item
+                .attributes
+                .into_iter()
+                .map(|attr| (attr.url_name, attr.value, attr.positive))
+                .collect(),
+        })
+        .collect();
+    auctions.sort_by_key(|a| a.price);
+    Ok(auctions)
+}
+
+#[cfg(test)]
+#[path = "tests/client.rs"]
+mod tests;
@@ -0,0 +1,165 @@
+use twilight_cache_inmemory::{Reference, model::CachedGuild};
+use twilight_model::{
+    channel::message::Embed,
+    id::{Id, marker::GuildMarker},
+};
+use twilight_util::builder::embed::{EmbedBuilder, EmbedFieldBuilder};
+
+use crate::utils::{embed::footer_with_icon, locale::Lang};
+
+use super::{
+    RivenService, client,
+    session::{AuctionInfo, RivenQuery, RivenSession},
+};
+
+const COLOR: u32 = 0x9B59B6;
+
+impl RivenService {
+    fn query_line(query: &RivenQuery, lang: Lang) -> String {
+        let mut parts = Vec::new();
+        if !query.positive.is_empty() {
+            parts.push(format!("+ {}", query.positive.join(", ")));
+        }
+        if let Some(negative) = &query.negative {
+            parts.push(format!("− {negative}"));
+        }
+        if let Some(max) = query.max_price {
+            parts.push(format!("≤ {max}p"));
+        }
+        if parts.is_empty() {
+            lang.pick("ทุก riven", "Any riven")
+                .to_string()
+        } else {
+            parts.join(" · ")
+        }
+    }
+
+    fn auction_field(weapon: &str, auction: &AuctionInfo, lang: Lang) -> EmbedFieldBuilder {
+        let price_kind = if auction.direct {
+            lang.pick("ซื้อทันที", "buyout")
+        } else {
+            lang.pick("ประมูล", "bid")
+        };
+        let stats = auction
+            .attributes
+            .iter()
+            .map(|(stat, value, _)| format!("{value:+.1} {stat}"))
+            .collect::<Vec<_>>()
+            .join(" · ");
+        let status = if auction.ingame {
+            lang.pick("ในเกม", "in game")
+        } else {
+            lang.pick("ออนไลน์", "online")
+        };
+        let value = format!(
+            "{stats}\nMR {} · Rank {} · {} rolls · {}\n[{}]({}{}) · rep {} · {status}",
+            auction.mastery,
+            auction.rank,
+            auction.rerolls,
+            auction.polarity,
+            auction.ign.replace(['[', ']'], ""),
+            client::AUCTION_URL,
+            auction.id,
+            auction.reputation,
+        );
+        EmbedFieldBuilder::new(
+            format!(
+                "{weapon} {} — {}p ({price_kind})",
+                auction.name, auction.price
+            ),
+            value,
+        )
+    }
+
+    pub fn build_embed(
+        guild: &Reference<'_, Id<GuildMarker>, CachedGuild>,
+        session: &RivenSession,
+        lang: Lang,
+    ) -> anyhow::Result<Embed> {
+        let mut footer = footer_with_icon(guild)?;
+        footer.text = format!(
+            "{} · {} {}/{}",
+            guild.name(),
+            lang.pick("หน้า", "Page"),
+            session.page,
+            session.lpage()
+        );
+        let query = &session.query;
+        let mut description = Self::query_line(query, lang);
+        if session.auctions.is_empty() {
+            description.push_str(lang.pick(
+                "\nไม่พบ riven ที่ตรงกับเงื่อนไข",
+                "\nNo rivens match the search",
+            ));
+        }
+        let mut builder = EmbedBuilder::new()
+            .color(COLOR)
+            .title(format!("Riven {}", query.weapon))
+            .url(format!(
+                "{}{}",
+                client::SEARCH_PAGE_URL,
+                query.weapon_slug
+            ))
+            .description(description);
+        for auction in session.slice() {
+            builder = builder.field(Self::auction_field(&query.weapon, auction, lang));
+        }
+        Ok(builder
+            .footer(footer)
+            .validate()?
+            .build())
+    }
+
+    pub fn error_embed(
+        guild: &Reference<'_, Id<GuildMarker>, CachedGuild>,
+        lang: Lang,
+    ) -> anyhow::Result<Embed> {
+        let mut footer = footer_with_icon(guild)?;
+        footer.text = guild.name().to_string();
+        Ok(EmbedBuilder::new()
+            .color(COLOR)
+            .title(lang.pick("เกิดข้อผิดพลาด", "Something went wrong"))
+            .description(lang.pick("กรุณาลองอีกครั้ง ภายหลัง", "Please try again later"))
+            .footer(footer)
+            .build())
+    }
+
+    pub fn not_found_embed(
+        guild: &Reference<'_, Id<GuildMarker>, CachedGuild>,
+        lang: Lang,
+    ) -> anyhow::Result<Embed> {
+        let mut footer = footer_with_icon(guild)?;
+        footer.text = guild.name().to_string();
+        Ok(EmbedBuilder::new()
+            .color(COLOR)
+            .title(lang.pick("ไม่พบอาวุธ", "Weapon not found"))
+            .description(lang.pick(
+                "กรุณาเลือกอาวุธจากรายการที่แนะนำ",
+                "Please pick a weapon from the suggestions",
+            ))
+            .footer(footer)
+            .build())
+    }
+
+    pub fn unknown_stat_embed(
+        guild: &Reference<'_, Id<GuildMarker>, CachedGuild>,
+        stat: &str,
+        lang: Lang,
+    ) -> anyhow::Result<Embed> {
+        let mut footer = footer_with_icon(guild)?;
+        footer.text = guild.name().to_string();
+        let description = match lang {
+            Lang::Th => format!("`{stat}` ไม่ใช่ค่าของ riven กรุณาเลือกจากรายการที่แนะนำ"),
+            Lang::En => {
+                format!("`{stat}` is not a riven stat, please pick one from the suggestions")
+            }
+        };
+        Ok(EmbedBuilder::new()
+            .color(COLOR)
+            .title(lang.pick("ไม่รู้จักค่านี้", "Unknown stat"))
+            .description(description)
+            .footer(footer)
+            .validate()?
+            .build())
+    }
+}
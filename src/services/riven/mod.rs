@@ -0,0 +1,245 @@
+pub mod cache;
+pub mod client;
+pub mod embed;
+pub mod session;
+
+use std::sync::Arc;
+
+use twilight_model::{
+    application::interaction::{Interaction, message_component::MessageComponentInteractionData},
+    channel::message::{
+        MessageFlags,
+        component::{ActionRow, Button, ButtonStyle, Component},
+    },
+    http::interaction::{InteractionResponse, InteractionResponseType},
+    id::{Id, marker::MessageMarker},
+};
+use twilight_util::builder::InteractionResponseDataBuilder;
+
+use crate::{
+    context::Context,
+    utils::{locale::Lang, session::MessageSessions},
+};
+
+pub use session::{AuctionInfo, RivenQuery, RivenSession};
+
+pub struct RivenService;
+
+/// What `/warframe riven` made of its options.
+pub enum RivenSearch {
+    Found(RivenSession),
+    UnknownWeapon,
+    /// A stat, as typed, that is not in warframe.market's list.
+    UnknownStat(String),
+}
+
+impl RivenService {
+    /// Idle time before a riven message's buttons need a re-fetch.
+    const SESSIONS: MessageSessions = MessageSessions::new("riven", 900);
+
+    /// Resolves the weapon and stats, then runs the search.
+    pub async fn create_session(
+        ctx: &Arc<Context>,
+        weapon: &str,
+        positive: Option<&str>,
+        negative: Option<&str>,
+        max_price: Option<u32>,
+    ) -> anyhow::Result<RivenSearch> {
+        let Some(entry) = Self::find_weapon(weapon).await else {
+            return Ok(RivenSearch::UnknownWeapon);
+        };
+        let mut stats: Vec<String> = Vec::new();
+        for raw in positive
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|raw| !raw.is_empty())
+        {
+            let Some(stat) = Self::find_stat(raw).await else {
+                return Ok(RivenSearch::UnknownStat(raw.to_string()));
+            };
+            if !stats.contains(&stat) {
+                stats.push(stat);
+            }
+        }
+        stats.truncate(session::MAX_POSITIVE_STATS);
+        let negative = match negative
+            .map(str::trim)
+            .filter(|raw| !raw.is_empty())
+        {
+            Some(raw) => match Self::find_stat(raw).await {
+                Some(stat) => Some(stat),
+                None => return Ok(RivenSearch::UnknownStat(raw.to_string())),
+            },
+            None => None,
+        };
+        let query = RivenQuery {
+            weapon: entry.name,
+            weapon_slug: entry.slug,
+            positive: stats,
+            negative,
+            max_price,
+        };
+        let auctions = client::fetch_auctions(&ctx.reqwest, &query).await?;
+        Ok(RivenSearch::Found(RivenSession {
+            query,
+            auctions,
+            page: 1,
+        }))
+    }
+
+    fn button(session: &RivenSession, action: &str, label: &str, disabled: bool) -> Component {
+        Component::Button(Button {
+            id: None,
+            custom_id: Some(session.custom_id(action)),
+            disabled,
+            emoji: None,
+            label: Some(label.to_string()),
+            style: ButtonStyle::Primary,
+            url: None,
+            sku_id: None,
+        })
+    }
+
+    pub fn components(session: &RivenSession, lang: Lang) -> Vec<Component> {
+        let buttons = vec![
+            Self::button(
+                session,
+                "riven_prev_page",
+                lang.pick("ก่อนหน้า", "Previous"),
+                session.page <= 1,
+            ),
+            Self::button(
+                session,
+                "riven_next_page",
+                lang.pick("ถัดไป", "Next"),
+                session.page >= session.lpage(),
+            ),
+            Self::button(
+                session,
+                "riven_refresh",
+                lang.pick("รีโหลด", "Refresh"),
+                false,
+            ),
+        ];
+        vec![Component::ActionRow(ActionRow { id: None, components: buttons })]
+    }
+
+    pub async fn insert_session(
+        ctx: &Context,
+        message_id: Id<MessageMarker>,
+        session: &RivenSession,
+    ) {
+        Self::SESSIONS
+            .insert(&ctx.redis, message_id, session)
+            .await;
+    }
+
+    /// Re-runs the search behind an expired session, keeping the page its
+    /// button was showing where it still exists.
+    async fn restore_session(
+        ctx: &Arc<Context>,
+        page: usize,
+        mut query: RivenQuery,
+    ) -> Option<RivenSession> {
+        if let Some(entry) = Self::find_weapon_by_slug(&query.weapon_slug).await {
+            query.weapon = entry.name;
+        }
+        let auctions = match client::fetch_auctions(&ctx.reqwest, &query).await {
+            Ok(auctions) => auctions,
+            Err(e) => {
+                tracing::warn!(weapon = %query.weapon_slug, error = %e, "failed to restore riven session");
+                return None;
+            }
+        };
+        let mut session = RivenSession { query, auctions, page: 1 };
+        session.page = page.clamp(1, session.lpage());
+        Some(session)
+    }
+
+    async fn respond(ctx: &Context, interaction: &Interaction, response: InteractionResponse) {
+        if let Err(e) = ctx
+            .http
+            .interaction(interaction.application_id)
+            .create_response(interaction.id, &interaction.token, &response)
+            .await
+        {
+            tracing::warn!(error = %e, "failed to respond to riven component");
+        }
+    }
+
+    pub async fn handle_component(
+        ctx: Arc<Context>,
+        interaction: Interaction,
+        data: MessageComponentInteractionData,
+    ) {
+        let Some(message) = interaction.message.as_ref() else {
+            return;
+        };
+        let message_id = message.id;
+        let lang = Lang::of(&interaction);
+        let (action, state) = session::parse_custom_id(&data.custom_id);
+        let session = Self::SESSIONS
+            .load_or_restore(&ctx.redis, message_id, state, |(page, query)| {
+                Self::restore_session(&ctx, page, query)
+            })
+            .await;
+        let Some((mut session, restored)) = session else {
+            let data = InteractionResponseDataBuilder::new()
+                .content(lang.pick(
+                    "ผลการค้นหานี้หมดอายุแล้ว กรุณาใช้ /warframe riven อีกครั้ง",
+                    "This search has expired, please run /warframe riven again",
+                ))
+                .flags(MessageFlags::EPHEMERAL)
+                .build();
+            Self::respond(
+                &ctx,
+                &interaction,
+                InteractionResponse {
+                    kind: InteractionResponseType::ChannelMessageWithSource,
+                    data: Some(data),
+                },
+            )
+            .await;
+            return;
+        };
+
+        match action {
+            "riven_prev_page" if session.page > 1 => session.page -= 1,
+            "riven_next_page" if session.page < session.lpage() => session.page += 1,
+            // A restored session was fetched just now.
+            "riven_refresh" if !restored => {
+                match client::fetch_auctions(&ctx.reqwest, &session.query).await {
+                    Ok(auctions) => {
+                        session.auctions = auctions;
+                        session.page = 1;
+                    }
+                    Err(e) => tracing::warn!(error = %e, "failed to refresh riven auctions"),
+                }
+            }
+            _ => {}
+        }
+
+        if let Some(guild_ref) = interaction
+            .guild_id
+            .and_then(|id| ctx.cache.guild(id))
+            && let Ok(embed) = Self::build_embed(&guild_ref, &session, lang)
+        {
+            let data = InteractionResponseDataBuilder::new()
+                .embeds([embed])
+                .components(Self::components(&session, lang))
+                .build();
+            Self::respond(
+                &ctx,
+                &interaction,
+                InteractionResponse {
+                    kind: InteractionResponseType::UpdateMessage,
+                    data: Some(data),
+                },
+            )
+            .await;
+        }
+
+        Self::insert_session(&ctx, message_id, &session).await;
+    }
+}
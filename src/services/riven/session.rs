@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+
+pub const PAGE_SIZE: usize = 5;
+/// Discord's limit on a component's custom id.
+const CUSTOM_ID_LIMIT: usize = 100;
+pub const MAX_POSITIVE_STATS: usize = 3;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AuctionInfo {
+    pub id: String,
+    pub price: u32,
+    /// Buyout price rather than the current bid.
+    pub direct: bool,
+    pub ign: String,
+    pub ingame: bool,
+    pub reputation: i32,
+    /// The riven's own name, e.g. `Crita-satilis`.
+    pub name: String,
+    pub rank: u8,
+    pub rerolls: u32,
+    pub mastery: u8,
+    pub polarity: String,
+    /// `(stat, value, positive)` as warframe.market lists them.
+    pub attributes: Vec<(String, f64, bool)>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RivenQuery {
+    pub weapon: String,
+    pub weapon_slug: String,
+    pub positive: Vec<String>,
+    pub negative: Option<String>,
+    pub max_price: Option<u32>,
+}
+
+/// Turns `Critical Chance` into warframe.market's `critical_chance`.
+pub fn normalize_stat(raw: &str) -> String {
+    raw.split_whitespace()
+        .collect::<Vec<_>>()
+        .join("_")
+        .to_lowercase()
+}
+
+/// Comma-separated stat names, normalized, without blanks or repeats.
+pub fn parse_stats(raw: &str) -> Vec<String> {
+    let mut stats: Vec<String> = Vec::new();
+    for stat in raw.split(',').map(normalize_stat) {
+        if !stat.is_empty() && !stats.contains(&stat) {
+            stats.push(stat);
+        }
+    }
+    stats
+}
+
+impl RivenQuery {
+    /// `slug|positive,positive|negative|max`, for custom ids; the weapon's
+    /// display name is looked up again from the slug.
+    pub fn encode(&self) -> String {
+        format!(
+            "{}|{}|{}|{}",
+            self.weapon_slug,
+            self.positive.join(","),
+            self.negative.as_deref().unwrap_or(""),
+            self.max_price
+                .map(|p| p.to_string())
+                .unwrap_or_default()
+        )
+    }
+
+    pub fn decode(raw: &str) -> Option<Self> {
+        let mut parts = raw.split('|');
+        let weapon_slug = parts
+            .next()
+            .filter(|slug| !slug.is_empty())?
+            .to_string();
+        let positive = parse_stats(parts.next()?);
+        let negative = Some(parts.next()?)
+            .filter(|n| !n.is_empty())
+            .map(str::to_string);
+        let max_price = match parts.next()? {
+            "" => None,
+            max => Some(max.parse().ok()?),
+        };
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Self { weapon: weapon_slug.clone(), weapon_slug, positive, negative, max_price })
+    }
+}
+
+/// One riven search behind a message, stored in Redis like a market
+/// session.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RivenSession {
+    pub query: RivenQuery,
+    pub auctions: Vec<AuctionInfo>,
+    pub page: usize,
+}
+
+impl RivenSession {
+    pub fn lpage(&self) -> usize {
+        self.auctions
+            .len()
+            .div_ceil(PAGE_SIZE)
+            .max(1)
+    }
+
+    pub fn slice(&self) -> &[AuctionInfo] {
+        let start = (self.page.saturating_sub(1) * PAGE_SIZE).min(self.auctions.len());
+        let end = (start + PAGE_SIZE).min(self.auctions.len());
+        &self.auctions[start..end]
+    }
+
+    /// `action:page:query`, or just the action when the query is too long
+    /// to carry; such a button cannot outlive its Redis session.
+    pub fn custom_id(&self, action: &str) -> String {
+        let id = format!("{action}:{}:{}", self.page, self.query.encode());
+        if id.len() <= CUSTOM_ID_LIMIT { id } else { action.to_string() }
+    }
+}
+
+pub fn parse_custom_id(custom_id: &str) -> (&str, Option<(usize, RivenQuery)>) {
+    let Some((action, rest)) = custom_id.split_once(':') else {
+        return (custom_id, None);
+    };
+    let state = rest
+        .split_once(':')
+        .and_then(|(page, query)| Some((page.parse().ok()?, RivenQuery::decode(query)?)));
+    (action, state)
+}
+
+#[cfg(test)]
+#[path = "tests/session.rs"]
+mod tests;
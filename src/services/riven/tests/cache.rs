@@ -0,0 +1,59 @@
+use super::*;
+
+fn stats() -> Vec<RivenEntry> {
+    [
+        ("Critical Chance", "critical_chance"),
+        ("Critical Damage", "critical_damage"),
+        ("Damage to Corpus", "damage_vs_corpus"),
+        ("Multishot", "multishot"),
+    ]
+    .into_iter()
+    .map(|(name, slug)| RivenEntry { name: name.into(), slug: slug.into() })
+    .collect()
+}
+
+#[test]
+fn test_match_stat_accepts_names_and_slugs() {
+    let stats = stats();
+    assert_eq!(
+        match_stat(&stats, "Critical Chance").as_deref(),
+        Some("critical_chance")
+    );
+    assert_eq!(
+        match_stat(&stats, "damage to corpus").as_deref(),
+        Some("damage_vs_corpus")
+    );
+    assert_eq!(
+        match_stat(&stats, "damage_vs_corpus").as_deref(),
+        Some("damage_vs_corpus")
+    );
+    assert_eq!(match_stat(&stats, "crit"), None);
+    assert_eq!(match_stat(&stats, " "), None);
+    // Without a list warframe.market decides.
+    assert_eq!(
+        match_stat(&[], "Recoil").as_deref(),
+        Some("recoil")
+    );
+}
+
+#[test]
+fn test_complete_stats_keeps_earlier_picks() {
+    let stats = stats();
+    assert_eq!(
+        complete_stats(&stats, "crit"),
+        vec!["Critical Chance", "Critical Damage"]
+    );
+    assert_eq!(
+        complete_stats(&stats, "Critical Chance, c"),
+        vec!["Critical Chance, Critical Damage"]
+    );
+    assert_eq!(
+        complete_stats(&stats, "multishot,"),
+        vec![
+            "multishot, Critical Chance",
+            "multishot, Critical Damage",
+            "multishot, Damage to Corpus",
+        ]
+    );
+    assert!(complete_stats(&stats, "zzz").is_empty());
+}
@@ -0,0 +1,123 @@
+use serde_json::json;
+
+use super::*;
+
+struct MockClient {
+    auctions: serde_json::Value,
+}
+
+#[async_trait::async_trait]
+impl HttpProvider for MockClient {
+    async fn get_json<T>(&self, url: &str) -> anyhow::Result<T>
+    where
+        T: serde::de::DeserializeOwned + Send,
+    {
+        assert!(url.starts_with(AUCTIONS_URL));
+        Ok(serde_json::from_value(self.auctions.clone()).unwrap())
+    }
+
+    fn as_reqwest(&self) -> &reqwest::Client {
+        unimplemented!()
+    }
+}
+
+fn query(max_price: Option<u32>) -> RivenQuery {
+    RivenQuery {
+        weapon: "Rubico".into(),
+        weapon_slug: "rubico".into(),
+        positive: vec!["critical_chance".into(), "multishot".into()],
+        negative: Some("recoil".into()),
+        max_price,
+    }
+}
+
+fn auction(id: &str, price: u32, status: &str, closed: bool) -> serde_json::Value {
+    json!({
+        "id": id,
+        "buyout_price": price,
+        "starting_price": price,
+        "is_direct_sell": true,
+        "closed": closed,
+        "owner": {"ingame_name": format!("{id}-owner"), "status": status, "reputation": 3},
+        "item": {
+            "name": "crita-satilis",
+            "mod_rank": 8,
+            "re_rolls": 4,
+            "mastery_level": 12,
+            "polarity": "madurai",
+            "attributes": [{"url_name": "critical_chance", "value": 180.5, "positive": true}]
+        }
+    })
+}
+
+#[test]
+fn test_search_url() {
+    assert_eq!(
+        search_url(&query(Some(500)))
+            .unwrap()
+            .as_str(),
+        "https://api.warframe.market/v1/auctions/search?type=riven&weapon_url_name=rubico&sort_by=price_asc&positive_stats=critical_chance%2Cmultishot&negative_stats=recoil"
+    );
+    let bare = RivenQuery { positive: vec![], negative: None, ..query(None) };
+    assert_eq!(
+        search_url(&bare).unwrap().as_str(),
+        "https://api.warframe.market/v1/auctions/search?type=riven&weapon_url_name=rubico&sort_by=price_asc"
+    );
+    // A slug restored from a custom id cannot smuggle in extra parameters.
+    let hostile = RivenQuery { weapon_slug: "rubico&type=item".into(), ..bare };
+    assert_eq!(
+        search_url(&hostile)
+            .unwrap()
+            .query_pairs()
+            .filter(|(key, _)| key == "type")
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn test_auction_price() {
+    let mut bid: Auction = serde_json::from_value(auction("a", 300, "ingame", false)).unwrap();
+    bid.is_direct_sell = false;
+    bid.starting_price = 100;
+    assert_eq!(bid.price(), 100);
+    bid.top_bid = Some(150);
+    assert_eq!(bid.price(), 150);
+}
+
+#[tokio::test]
+async fn test_fetch_auctions_filters_and_sorts() {
+    let client = MockClient {
+        auctions: json!({"payload": {"auctions": [
+            auction("pricey", 900, "ingame", false),
+            auction("cheap", 200, "online", false),
+            auction("offline", 100, "offline", false),
+            auction("closed", 50, "ingame", true),
+            auction("mid", 400, "ingame", false),
+        ]}}),
+    };
+
+    let all = fetch_auctions(&client, &query(None))
+        .await
+        .unwrap();
+    let ids: Vec<&str> = all
+        .iter()
+        .map(|a| a.id.as_str())
+        .collect();
+    assert_eq!(ids, ["cheap", "mid", "pricey"]);
+    assert!(!all[0].ingame);
+    assert!(all[1].ingame);
+    assert_eq!(
+        all[1].attributes,
+        vec![("critical_chance".to_string(), 180.5, true)]
+    );
+
+    let capped = fetch_auctions(&client, &query(Some(400)))
+        .await
+        .unwrap();
+    let ids: Vec<&str> = capped
+        .iter()
+        .map(|a| a.id.as_str())
+        .collect();
+    assert_eq!(ids, ["cheap", "mid"]);
+}
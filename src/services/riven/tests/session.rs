@@ -0,0 +1,98 @@
+use super::*;
+
+fn auction(i: usize) -> AuctionInfo {
+    AuctionInfo {
+        id: format!("a{i}"),
+        price: (i as u32 + 1) * 10,
+        direct: true,
+        ign: format!("seller{i}"),
+        ingame: true,
+        reputation: 0,
+        name: "crita-satilis".into(),
+        rank: 0,
+        rerolls: 0,
+        mastery: 8,
+        polarity: "madurai".into(),
+        attributes: vec![],
+    }
+}
+
+fn query() -> RivenQuery {
+    RivenQuery {
+        weapon: "rubico".into(),
+        weapon_slug: "rubico".into(),
+        positive: vec!["critical_chance".into(), "multishot".into()],
+        negative: Some("recoil".into()),
+        max_price: Some(500),
+    }
+}
+
+#[test]
+fn test_parse_stats() {
+    assert_eq!(
+        parse_stats(" Critical Chance, multishot ,,critical_chance"),
+        vec!["critical_chance".to_string(), "multishot".to_string()]
+    );
+    assert!(parse_stats("").is_empty());
+}
+
+#[test]
+fn test_query_round_trip() {
+    let encoded = query().encode();
+    assert_eq!(
+        encoded,
+        "rubico|critical_chance,multishot|recoil|500"
+    );
+    assert_eq!(RivenQuery::decode(&encoded), Some(query()));
+
+    let bare = RivenQuery { positive: vec![], negative: None, max_price: None, ..query() };
+    assert_eq!(bare.encode(), "rubico|||");
+    assert_eq!(RivenQuery::decode("rubico|||"), Some(bare));
+
+    assert_eq!(RivenQuery::decode("|||"), None);
+    assert_eq!(RivenQuery::decode("rubico||"), None);
+    assert_eq!(RivenQuery::decode("rubico|||abc"), None);
+}
+
+#[test]
+fn test_paging() {
+    let mut session =
+        RivenSession { query: query(), auctions: (0..12).map(auction).collect(), page: 1 };
+    assert_eq!(session.lpage(), 3);
+    assert_eq!(session.slice().len(), PAGE_SIZE);
+    session.page = 3;
+    assert_eq!(session.slice().len(), 2);
+    assert_eq!(session.slice()[0].id, "a10");
+
+    let empty = RivenSession { query: query(), auctions: vec![], page: 1 };
+    assert_eq!(empty.lpage(), 1);
+    assert!(empty.slice().is_empty());
+}
+
+#[test]
+fn test_custom_id_round_trip() {
+    let session = RivenSession { query: query(), auctions: vec![], page: 2 };
+    let id = session.custom_id("riven_next_page");
+    assert_eq!(
+        id,
+        "riven_next_page:2:rubico|critical_chance,multishot|recoil|500"
+    );
+    let (action, state) = parse_custom_id(&id);
+    assert_eq!(action, "riven_next_page");
+    assert_eq!(state, Some((2, query())));
+
+    assert_eq!(
+        parse_custom_id("riven_refresh"),
+        ("riven_refresh", None)
+    );
+}
+
+#[test]
+fn test_custom_id_too_long() {
+    let long = RivenQuery { weapon_slug: "x".repeat(CUSTOM_ID_LIMIT), ..query() };
+    let session = RivenSession { query: long, auctions: vec![], page: 1 };
+    assert_eq!(
+        session.custom_id("riven_refresh"),
+        "riven_refresh"
+    );
+}
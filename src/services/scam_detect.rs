@@ -14,12 +14,18 @@ use twilight_model::{
 use crate::{
     configs::scam_detect::{SCAM_DETECT_CONFIG, ScamDetectConfig},
     context::Context,
-    services::{broadcast::BroadcastService, spam},
+    dbs::mongo::models::guild_settings::ScamTextMode,
+    services::{
+        ai::{self, moderation, usage::UsageScope},
+        broadcast::BroadcastService,
+        spam,
+    },
 };
 
 #[derive(Clone)]
 pub struct ScamDetectQueue {
     tx: Option<mpsc::Sender<ScamScanJob>>,
+    text_tx: Option<mpsc::Sender<ScamTextJob>>,
     config: Arc<ScamDetectConfig>,
 }
 
@@ -31,6 +37,14 @@ struct ScamScanJob {
     enqueued_at: Instant,
 }
 
+struct ScamTextJob {
+    ctx: Arc<Context>,
+    message: Message,
+    quarantine_channel_id: u64,
+    mode: ScamTextMode,
+    enqueued_at: Instant,
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct ScanResponse {
     pub is_spam: bool,
@@ -76,7 +90,8 @@ impl ScamDetectQueue {
     pub fn from_env() -> Self {
         let config = Arc::new(SCAM_DETECT_CONFIG.clone());
         let Some(url) = config.url.clone() else {
-            return Self::disabled_with_config(config);
+            let text_tx = Some(spawn_text_worker(config.clone()));
+            return Self { text_tx, ..Self::disabled_with_config(config) };
         };
 
         let client = ReqwestClient::builder()
@@ -93,7 +108,7 @@ impl ScamDetectQueue {
     }
 
     fn disabled_with_config(config: Arc<ScamDetectConfig>) -> Self {
-        Self { tx: None, config }
+        Self { tx: None, text_tx: None, config }
     }
 
     pub fn with_detector(config: Arc<ScamDetectConfig>, detector: Arc<dyn ScamDetector>) -> Self {
@@ -104,6 +119,7 @@ impl ScamDetectQueue {
         let (tx, mut rx) = mpsc::channel(config.queue_capacity);
         let permits = Arc::new(Semaphore::new(config.workers));
         let worker_config = config.clone();
+        let text_tx = Some(spawn_text_worker(config.clone()));
 
         tokio::spawn(async move {
             while let Some(job) = rx.recv().await {
@@ -120,7 +136,7 @@ impl ScamDetectQueue {
             }
         });
 
-        Self { tx: Some(tx), config }
+        Self { tx: Some(tx), text_tx, config }
     }
 
     pub fn enabled(&self) -> bool {
@@ -165,6 +181,85 @@ impl ScamDetectQueue {
             }
         }
     }
+
+    /// Queues a text message for the AI classifier. `Shadow` only logs
+    /// blocking verdicts; `Enforce` sends them down the same quarantine path
+    /// as images.
+    pub fn try_enqueue_text(
+        &self,
+        ctx: Arc<Context>,
+        message: &Message,
+        quarantine_channel_id: u64,
+        mode: ScamTextMode,
+    ) {
+        let Some(tx) = &self.text_tx else {
+            return;
+        };
+        if mode == ScamTextMode::Off || !text_scan_eligible(&ctx, message) {
+            return;
+        }
+        let message_id = message.id.get();
+        let job = ScamTextJob {
+            ctx,
+            message: message.clone(),
+            quarantine_channel_id,
+            mode,
+            enqueued_at: Instant::now(),
+        };
+
+        match tx.try_send(job) {
+            Ok(()) => {
+                metrics::counter!("scam_text_jobs_total", "result" => "enqueued").increment(1);
+            }
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                metrics::counter!("scam_text_jobs_total", "result" => "queue_full").increment(1);
+                tracing::warn!(
+                    message_id,
+                    "scam text queue full; dropping text scan"
+                );
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => {
+                metrics::counter!("scam_text_jobs_total", "result" => "queue_closed").increment(1);
+                tracing::warn!("scam text queue closed; dropping text scan");
+            }
+        }
+    }
+}
+
+/// Text scans share the image path's queue capacity, worker count and job
+/// TTL so a link flood cannot pile up classifier calls.
+fn spawn_text_worker(config: Arc<ScamDetectConfig>) -> mpsc::Sender<ScamTextJob> {
+    let (tx, mut rx) = mpsc::channel::<ScamTextJob>(config.queue_capacity);
+    let permits = Arc::new(Semaphore::new(config.workers));
+
+    tokio::spawn(async move {
+        while let Some(job) = rx.recv().await {
+            let permit = match permits.clone().acquire_owned().await {
+                Ok(permit) => permit,
+                Err(_) => break,
+            };
+            if job.enqueued_at.elapsed() > config.job_ttl {
+                metrics::counter!("scam_text_jobs_total", "result" => "expired").increment(1);
+                tracing::warn!(
+                    message_id = job.message.id.get(),
+                    "scam text job expired before processing"
+                );
+                continue;
+            }
+            tokio::spawn(async move {
+                let _permit = permit;
+                scan_text(
+                    &job.ctx,
+                    &job.message,
+                    job.quarantine_channel_id,
+                    job.mode,
+                )
+                .await;
+            });
+        }
+    });
+
+    tx
 }
 
 async fn process_job(
@@ -347,12 +442,136 @@ fn scan_blocks(scan: &ScanResponse) -> bool {
             .eq_ignore_ascii_case("block")
 }
 
+/// Whether the AI text classifier should look at `message`: it carries a
+/// link and its author joined recently.
+fn text_scan_eligible(ctx: &Context, message: &Message) -> bool {
+    let Some(guild_id) = message.guild_id else {
+        return false;
+    };
+    if !moderation::contains_link(&message.content) {
+        return false;
+    }
+    let joined_at = message
+        .member
+        .as_ref()
+        .and_then(|member| member.joined_at)
+        .or_else(|| {
+            ctx.cache
+                .member(guild_id, message.author.id)
+                .and_then(|member| member.joined_at())
+        });
+    moderation::joined_recently(
+        joined_at.map(|t| t.as_secs()),
+        chrono::Utc::now().timestamp(),
+    )
+}
+
+async fn scan_text(
+    ctx: &Arc<Context>,
+    message: &Message,
+    quarantine_channel_id: u64,
+    mode: ScamTextMode,
+) {
+    let Some(guild_id) = message.guild_id else {
+        return;
+    };
+    let client = match ai::client::client().await {
+        Ok(client) => client,
+        Err(e) => {
+            tracing::warn!(error = %e, "ai client unavailable; skipping scam text scan");
+            return;
+        }
+    };
+    // Billed to the guild: the author did not ask for this call.
+    let usage = UsageScope::new(ctx, Some(guild_id.get()), None);
+    let verdict = match moderation::classify(
+        client,
+        &ctx.ai_scheduler,
+        &usage,
+        &message.content,
+    )
+    .await
+    {
+        Ok(verdict) => verdict,
+        Err(e) => {
+            tracing::warn!(
+                guild_id = guild_id.get(),
+                message_id = message.id.get(),
+                error = %e,
+                "scam text scan failed"
+            );
+            return;
+        }
+    };
+    if !verdict.blocks() {
+        tracing::debug!(
+            guild_id = guild_id.get(),
+            message_id = message.id.get(),
+            is_scam = verdict.is_scam,
+            confidence = verdict.confidence,
+            "scam text scan did not block"
+        );
+        return;
+    }
+
+    match mode {
+        ScamTextMode::Off => {}
+        ScamTextMode::Shadow => {
+            metrics::counter!("scam_text_blocks_total", "mode" => "shadow").increment(1);
+            tracing::info!(
+                guild_id = guild_id.get(),
+                channel_id = message.channel_id.get(),
+                message_id = message.id.get(),
+                user_id = message.author.id.get(),
+                confidence = verdict.confidence,
+                category = verdict.category,
+                reason = verdict.reason,
+                "scam text detected in shadow mode; no action taken"
+            );
+        }
+        ScamTextMode::Enforce => {
+            metrics::counter!("scam_text_blocks_total", "mode" => "enforce").increment(1);
+            tracing::warn!(
+                guild_id = guild_id.get(),
+                channel_id = message.channel_id.get(),
+                message_id = message.id.get(),
+                user_id = message.author.id.get(),
+                confidence = verdict.confidence,
+                category = verdict.category,
+                reason = verdict.reason,
+                "scam text detected; quarantining member"
+            );
+            if spam::SpamService::is_quarantined(ctx, guild_id.get(), message.author.id.get()).await
+            {
+                delete_detected_message(ctx, message).await;
+                return;
+            }
+            quarantine_message(ctx, message, quarantine_channel_id).await;
+        }
+    }
+}
+
 async fn quarantine_detected(ctx: &Arc<Context>, job: &ScamScanJob, scan: &ScanResponse) {
-    let Some(guild_id) = job.message.guild_id else {
+    tracing::warn!(
+        guild_id = job.message.guild_id.map(|id| id.get()),
+        channel_id = job.message.channel_id.get(),
+        message_id = job.message.id.get(),
+        user_id = job.message.author.id.get(),
+        risk = scan.risk,
+        action = scan.action,
+        reasons = ?scan.reasons,
+        "scam image detected; quarantining member"
+    );
+    quarantine_message(ctx, &job.message, job.quarantine_channel_id).await;
+}
+
+/// Deletes `message`, posts the quarantine notice and quarantines its author.
+async fn quarantine_message(ctx: &Arc<Context>, message: &Message, quarantine_channel_id: u64) {
+    let Some(guild_id) = message.guild_id else {
         return;
     };
-    let user_id = job.message.author.id;
-    delete_detected_message(ctx, &job.message).await;
+    let user_id = message.author.id;
+    delete_detected_message(ctx, message).await;
 
     let token = format!("{:06}", fastrand::u32(0..1_000_000));
     let token =
@@ -362,14 +581,10 @@ async fn quarantine_detected(ctx: &Arc<Context>, job: &ScamScanJob, scan: &ScanR
         };
 
     if let Some(guild_ref) = ctx.cache.guild(guild_id)
-        && let Ok(embed) = spam::embed::quarantine_embed(
-            &guild_ref,
-            &job.message,
-            job.quarantine_channel_id,
-            &token,
-        )
+        && let Ok(embed) =
+            spam::embed::quarantine_embed(&guild_ref, message, quarantine_channel_id, &token)
     {
-        let channel_id = Id::new(job.quarantine_channel_id);
+        let channel_id = Id::new(quarantine_channel_id);
         if let Err(e) = ctx
             .http
             .create_message(channel_id)
@@ -381,21 +596,11 @@ async fn quarantine_detected(ctx: &Arc<Context>, job: &ScamScanJob, scan: &ScanR
                 channel_id = channel_id.get(),
                 user_id = user_id.get(),
                 error = %e,
-                "failed to send scam image quarantine notice"
+                "failed to send scam quarantine notice"
             );
         }
     }
 
-    tracing::warn!(
-        guild_id = guild_id.get(),
-        channel_id = job.message.channel_id.get(),
-        message_id = job.message.id.get(),
-        user_id = user_id.get(),
-        risk = scan.risk,
-        action = scan.action,
-        reasons = ?scan.reasons,
-        "scam image detected; quarantining member"
-    );
     spam::quarantine::quarantine_member(ctx, guild_id, user_id, &token).await;
 }
 
@@ -411,7 +616,7 @@ async fn delete_detected_message(ctx: &Arc<Context>, message: &Message) {
             channel_id = message.channel_id.get(),
             message_id = message.id.get(),
             error = %e,
-            "failed to delete scam image message"
+            "failed to delete scam message"
         );
     }
 }
@@ -0,0 +1,189 @@
+use super::filter::AlertSource;
+use crate::warframe::{
+    api::{ArchonHunt, Cycle, Fissure, Invasion, Sortie, VoidTrader},
+    utils::format_time,
+};
+
+/// One worldstate entry that alerts can match. `key` is stable for as long
+/// as the entry is live, so a new key means a new entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorldEvent {
+    pub source: AlertSource,
+    pub key: String,
+    pub summary: String,
+    /// Lowercased values by attribute; a key may repeat, e.g. one `item`
+    /// per Baro offering.
+    pub attrs: Vec<(&'static str, String)>,
+}
+
+impl WorldEvent {
+    fn new(source: AlertSource, key: String, summary: String) -> Self {
+        Self { source, key, summary, attrs: Vec::new() }
+    }
+
+    fn attr(mut self, key: &'static str, value: &str) -> Self {
+        self.attrs
+            .push((key, value.to_lowercase()));
+        self
+    }
+}
+
+/// One `mode` per flag, so a Steel Path Void Storm matches both `mode:hard`
+/// and `mode:storm`.
+fn fissure_modes(fissure: &Fissure) -> Vec<&'static str> {
+    let mut modes = Vec::new();
+    if fissure.is_hard {
+        modes.push("hard");
+    }
+    if fissure.is_storm {
+        modes.push("storm");
+    }
+    if modes.is_empty() {
+        modes.push("normal");
+    }
+    modes
+}
+
+pub fn fissure_events(fissures: &[Fissure]) -> Vec<WorldEvent> {
+    fissures
+        .iter()
+        .map(|f| {
+            let label = match (f.is_hard, f.is_storm) {
+                (true, true) => " (Steel Path Void Storm)",
+                (false, true) => " (Void Storm)",
+                (true, false) => " (Steel Path)",
+                (false, false) => "",
+            };
+            let event = WorldEvent::new(
+                AlertSource::Fissure,
+                format!("fissure:{}", f.id),
+                format!(
+                    "**{} {}** fissure{label} — {} ends {}",
+                    f.tier,
+                    f.mission_type,
+                    f.node,
+                    format_time(&f.expiry)
+                ),
+            )
+            .attr("tier", &f.tier)
+            .attr("mission", &f.mission_type)
+            .attr("node", &f.node)
+            .attr("enemy", &f.enemy);
+            fissure_modes(f)
+                .into_iter()
+                .fold(event, |event, mode| event.attr("mode", mode))
+        })
+        .collect()
+}
+
+pub fn invasion_events(invasions: &[Invasion]) -> Vec<WorldEvent> {
+    invasions
+        .iter()
+        .filter(|i| !i.completed)
+        .map(|i| {
+            let rewards: Vec<&str> = [&i.attacker, &i.defender]
+                .into_iter()
+                .filter_map(|side| side.reward.as_ref())
+                .map(|r| r.as_string.as_str())
+                .filter(|r| !r.is_empty())
+                .collect();
+            let mut event = WorldEvent::new(
+                AlertSource::Invasion,
+                format!("invasion:{}", i.id),
+                format!(
+                    "**Invasion** {} — {} ({})",
+                    i.node,
+                    i.desc,
+                    rewards.join(" / ")
+                ),
+            )
+            .attr("node", &i.node)
+            .attr("faction", &i.attacker.faction)
+            .attr("faction", &i.defender.faction);
+            for reward in rewards {
+                event = event.attr("reward", reward);
+            }
+            event
+        })
+        .collect()
+}
+
+pub fn cycle_event(place: &str, cycle: &Cycle) -> WorldEvent {
+    let mut name = place.to_string();
+    if let Some(first) = name.get_mut(0..1) {
+        first.make_ascii_uppercase();
+    }
+    WorldEvent::new(
+        AlertSource::Cycle,
+        format!("cycle:{place}:{}:{}", cycle.state, cycle.expiry),
+        format!(
+            "**{name}** is now **{}**, ends {}",
+            cycle.state,
+            format_time(&cycle.expiry)
+        ),
+    )
+    .attr("place", place)
+    .attr("state", &cycle.state)
+}
+
+pub fn sortie_event(sortie: &Sortie) -> WorldEvent {
+    let mut event = WorldEvent::new(
+        AlertSource::Sortie,
+        format!("sortie:{}", sortie.id),
+        format!(
+            "**Sortie** — {} ({}), ends {}",
+            sortie.boss,
+            sortie.faction,
+            format_time(&sortie.expiry)
+        ),
+    )
+    .attr("boss", &sortie.boss);
+    for variant in &sortie.variants {
+        event = event
+            .attr("mission", &variant.mission_type)
+            .attr("modifier", &variant.modifier);
+    }
+    event
+}
+
+pub fn archon_event(hunt: &ArchonHunt) -> WorldEvent {
+    let mut event = WorldEvent::new(
+        AlertSource::Archon,
+        format!("archon:{}", hunt.id),
+        format!(
+            "**Archon Hunt** — {}, ends {}",
+            hunt.boss,
+            format_time(&hunt.expiry)
+        ),
+    )
+    .attr("boss", &hunt.boss);
+    for mission in &hunt.missions {
+        event = event.attr("mission", &mission.mission_type);
+    }
+    event
+}
+
+/// Baro only counts once he has arrived; his inventory is unknown before.
+pub fn baro_event(trader: &VoidTrader) -> Option<WorldEvent> {
+    if !trader.active {
+        return None;
+    }
+    let mut event = WorldEvent::new(
+        AlertSource::Baro,
+        format!("baro:{}", trader.id),
+        format!(
+            "**Baro Ki'Teer** arrived at {}, leaves {}",
+            trader.location,
+            format_time(&trader.expiry)
+        ),
+    )
+    .attr("location", &trader.location);
+    for item in &trader.inventory {
+        event = event.attr("item", &item.item);
+    }
+    Some(event)
+}
+
+#[cfg(test)]
+#[path = "tests/events.rs"]
+mod tests;
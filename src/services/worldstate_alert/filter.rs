@@ -0,0 +1,174 @@
+use std::fmt;
+
+use super::events::WorldEvent;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AlertSource {
+    Fissure,
+    Invasion,
+    Cycle,
+    Sortie,
+    Archon,
+    Baro,
+}
+
+impl AlertSource {
+    pub const ALL: [Self; 6] =
+        [Self::Fissure, Self::Invasion, Self::Cycle, Self::Sortie, Self::Archon, Self::Baro];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Fissure => "fissure",
+            Self::Invasion => "invasion",
+            Self::Cycle => "cycle",
+            Self::Sortie => "sortie",
+            Self::Archon => "archon",
+            Self::Baro => "baro",
+        }
+    }
+
+    /// Attributes a filter on this source may test.
+    pub fn keys(self) -> &'static [&'static str] {
+        match self {
+            Self::Fissure => &["tier", "mission", "node", "enemy", "mode"],
+            Self::Invasion => &["reward", "node", "faction"],
+            Self::Cycle => &["place", "state"],
+            Self::Sortie => &["boss", "mission", "modifier"],
+            Self::Archon => &["boss", "mission"],
+            Self::Baro => &["item", "location"],
+        }
+    }
+
+    fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|source| source.as_str() == name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterError {
+    Empty,
+    UnknownSource(String),
+    UnknownKey { source: AlertSource, key: String },
+    MissingValue(String),
+    UnclosedQuote,
+}
+
+impl fmt::Display for FilterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(
+                f,
+                "ต้องระบุตัวกรอง เช่น `fissure tier:axi mode:storm`"
+            ),
+            Self::UnknownSource(name) => {
+                let names: Vec<&str> = AlertSource::ALL
+                    .iter()
+                    .map(|s| s.as_str())
+                    .collect();
+                write!(f, "ไม่รู้จัก `{name}` ใช้ได้: {}", names.join(", "))
+            }
+            Self::UnknownKey { source, key } => write!(
+                f,
+                "`{}` ไม่มี `{key}` ใช้ได้: {}",
+                source.as_str(),
+                source.keys().join(", ")
+            ),
+            Self::MissingValue(term) => write!(f, "`{term}` ต้องอยู่ในรูป key:value"),
+            Self::UnclosedQuote => write!(f, "เครื่องหมาย \" ไม่ครบคู่"),
+        }
+    }
+}
+
+impl std::error::Error for FilterError {}
+
+/// `source key:value ...`; every clause must match, case-insensitively,
+/// as a substring of one of the entry's values for that key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlertFilter {
+    pub source: AlertSource,
+    pub clauses: Vec<(String, String)>,
+}
+
+/// Splits on whitespace, keeping `"quoted words"` together.
+fn tokenize(input: &str) -> Result<Vec<String>, FilterError> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in input.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if quoted {
+        return Err(FilterError::UnclosedQuote);
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    Ok(tokens)
+}
+
+impl AlertFilter {
+    pub fn parse(input: &str) -> Result<Self, FilterError> {
+        let tokens = tokenize(&input.to_lowercase())?;
+        let (first, rest) = tokens
+            .split_first()
+            .ok_or(FilterError::Empty)?;
+        let source =
+            AlertSource::parse(first).ok_or_else(|| FilterError::UnknownSource(first.clone()))?;
+        let mut clauses = Vec::with_capacity(rest.len());
+        for token in rest {
+            let Some((key, value)) = token.split_once(':') else {
+                return Err(FilterError::MissingValue(token.clone()));
+            };
+            let value = value
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ");
+            if value.is_empty() {
+                return Err(FilterError::MissingValue(token.clone()));
+            }
+            if !source.keys().contains(&key) {
+                return Err(FilterError::UnknownKey { source, key: key.to_string() });
+            }
+            clauses.push((key.to_string(), value));
+        }
+        Ok(Self { source, clauses })
+    }
+
+    pub fn matches(&self, event: &WorldEvent) -> bool {
+        event.source == self.source
+            && self.clauses.iter().all(|(key, value)| {
+                event
+                    .attrs
+                    .iter()
+                    .any(|(k, v)| k == key && v.contains(value.as_str()))
+            })
+    }
+}
+
+impl fmt::Display for AlertFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.source.as_str())?;
+        for (key, value) in &self.clauses {
+            if value.contains(' ') {
+                write!(f, " {key}:\"{value}\"")?;
+            } else {
+                write!(f, " {key}:{value}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/filter.rs"]
+mod tests;
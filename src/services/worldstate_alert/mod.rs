@@ -0,0 +1,369 @@
+pub mod events;
+pub mod filter;
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
+
+use chrono::Utc;
+use futures::StreamExt;
+use mongodb::bson::doc;
+use tokio::task::JoinHandle;
+use twilight_model::{
+    channel::message::Embed,
+    id::{
+        Id,
+        marker::{ChannelMarker, UserMarker},
+    },
+};
+use twilight_util::builder::embed::EmbedBuilder;
+
+use crate::{
+    configs::CACHE_PREFIX,
+    context::Context,
+    dbs::{
+        mongo::models::{channel::ChannelEnum, worldstate_alert::WorldstateAlert},
+        redis::{redis_delete, redis_set_nx_ex},
+    },
+    open_dm,
+    services::{channel::ChannelService, shutdown},
+    warframe::worldstate,
+};
+
+use events::WorldEvent;
+use filter::{AlertFilter, AlertSource};
+
+pub const MAX_ALERTS_PER_USER: usize = 10;
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+/// Longer than any entry stays live, so one entry never notifies twice.
+const DEDUPE_TTL: usize = 7 * 24 * 60 * 60;
+const COLOR: u32 = 0xF1C40F;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddOutcome {
+    Added,
+    Duplicate,
+    LimitReached,
+}
+
+/// Entry keys seen by the previous poll, per source.
+pub(crate) type Seen = HashMap<AlertSource, HashSet<String>>;
+
+pub struct WorldstateAlertService;
+
+impl WorldstateAlertService {
+    pub async fn list(ctx: &Context, guild_id: u64, user_id: u64) -> Vec<WorldstateAlert> {
+        let mut alerts = Vec::new();
+        match ctx
+            .mongo
+            .worldstate_alerts
+            .find(doc! {"guild_id": guild_id as i64, "user_id": user_id as i64})
+            .await
+        {
+            Ok(mut cursor) => {
+                while let Some(Ok(alert)) = cursor.next().await {
+                    alerts.push(alert);
+                }
+            }
+            Err(e) => {
+                tracing::warn!(guild_id, user_id, error = %e, "failed to load worldstate alerts");
+            }
+        }
+        alerts.sort_by_key(|alert| alert.created_at);
+        alerts
+    }
+
+    pub async fn add(
+        ctx: &Context,
+        guild_id: u64,
+        user_id: u64,
+        filter: &AlertFilter,
+        role_id: Option<u64>,
+    ) -> anyhow::Result<AddOutcome> {
+        let filter = filter.to_string();
+        let existing = Self::list(ctx, guild_id, user_id).await;
+        if existing
+            .iter()
+            .any(|alert| alert.filter == filter)
+        {
+            return Ok(AddOutcome::Duplicate);
+        }
+        if existing.len() >= MAX_ALERTS_PER_USER {
+            return Ok(AddOutcome::LimitReached);
+        }
+        ctx.mongo
+            .worldstate_alerts
+            .insert_one(WorldstateAlert {
+                id: None,
+                guild_id,
+                user_id,
+                filter,
+                role_id,
+                created_at: Utc::now().timestamp(),
+            })
+            .await?;
+        Ok(AddOutcome::Added)
+    }
+
+    /// Removes the `index`th (1-based) alert as shown by `list`.
+    pub async fn remove(
+        ctx: &Context,
+        guild_id: u64,
+        user_id: u64,
+        index: usize,
+    ) -> anyhow::Result<Option<WorldstateAlert>> {
+        let alerts = Self::list(ctx, guild_id, user_id).await;
+        let Some(alert) = index
+            .checked_sub(1)
+            .and_then(|i| alerts.get(i))
+        else {
+            return Ok(None);
+        };
+        ctx.mongo
+            .worldstate_alerts
+            .delete_one(doc! {
+                "guild_id": guild_id as i64,
+                "user_id": user_id as i64,
+                "filter": &alert.filter,
+            })
+            .await?;
+        Ok(Some(alert.clone()))
+    }
+
+    pub fn reply_embed(description: &str) -> anyhow::Result<Embed> {
+        let embed = EmbedBuilder::new()
+            .color(COLOR)
+            .title("🔔 Worldstate alerts")
+            .description(description)
+            .validate()?
+            .build();
+        Ok(embed)
+    }
+
+    /// Current entries per source; a source whose fetch failed is left out
+    /// so its entries are not mistaken for gone.
+    async fn snapshot(ctx: &Arc<Context>) -> HashMap<AlertSource, Vec<WorldEvent>> {
+        let (fissures, invasions, sortie, archon, baro) = tokio::join!(
+            worldstate::fissures(ctx),
+            worldstate::invasions(ctx),
+            worldstate::sortie(ctx),
+            worldstate::archon_hunt(ctx),
+            worldstate::void_trader(ctx),
+        );
+        let mut cycles = Vec::with_capacity(worldstate::CYCLES.len());
+        let mut cycles_ok = true;
+        for (endpoint, place) in worldstate::CYCLES {
+            match worldstate::cycle(ctx, endpoint).await {
+                Ok(cycle) => cycles.push(events::cycle_event(place, &cycle)),
+                Err(e) => {
+                    tracing::warn!(endpoint, error = %e, "failed to fetch cycle for alerts");
+                    cycles_ok = false;
+                }
+            }
+        }
+
+        let mut snapshot = HashMap::new();
+        let mut insert = |source: AlertSource, result: anyhow::Result<Vec<WorldEvent>>| match result
+        {
+            Ok(events) => {
+                snapshot.insert(source, events);
+            }
+            Err(e) => {
+                tracing::warn!(source = source.as_str(), error = %e, "failed to fetch worldstate for alerts");
+            }
+        };
+        insert(
+            AlertSource::Fissure,
+            fissures.map(|f| events::fissure_events(&f)),
+        );
+        insert(
+            AlertSource::Invasion,
+            invasions.map(|i| events::invasion_events(&i)),
+        );
+        insert(
+            AlertSource::Sortie,
+            sortie.map(|s| vec![events::sortie_event(&s)]),
+        );
+        insert(
+            AlertSource::Archon,
+            archon.map(|a| vec![events::archon_event(&a)]),
+        );
+        insert(
+            AlertSource::Baro,
+            baro.map(|b| {
+                events::baro_event(&b)
+                    .into_iter()
+                    .collect()
+            }),
+        );
+        if cycles_ok {
+            snapshot.insert(AlertSource::Cycle, cycles);
+        }
+        snapshot
+    }
+
+    /// Entries absent from the previous poll; updates `seen` for every
+    /// source present in `snapshot`.
+    pub(crate) fn diff(
+        seen: &mut Seen,
+        snapshot: HashMap<AlertSource, Vec<WorldEvent>>,
+    ) -> Vec<WorldEvent> {
+        let mut fresh = Vec::new();
+        for (source, events) in snapshot {
+            let previous = seen.remove(&source).unwrap_or_default();
+            let keys = events
+                .iter()
+                .map(|event| event.key.clone())
+                .collect();
+            fresh.extend(
+                events
+                    .into_iter()
+                    .filter(|event| !previous.contains(&event.key)),
+            );
+            seen.insert(source, keys);
+        }
+        fresh
+    }
+
+    async fn load_all(ctx: &Context) -> Vec<(WorldstateAlert, AlertFilter)> {
+        let mut alerts = Vec::new();
+        match ctx
+            .mongo
+            .worldstate_alerts
+            .find(doc! {})
+            .await
+        {
+            Ok(mut cursor) => {
+                while let Some(Ok(alert)) = cursor.next().await {
+                    match AlertFilter::parse(&alert.filter) {
+                        Ok(filter) => alerts.push((alert, filter)),
+                        Err(e) => {
+                            tracing::warn!(filter = %alert.filter, error = %e, "skipping unparsable worldstate alert");
+                        }
+                    }
+                }
+            }
+            Err(e) => tracing::warn!(error = %e, "failed to load worldstate alerts"),
+        }
+        alerts
+    }
+
+    /// Redis key claiming `event` for one recipient; overlapping alerts that
+    /// reach the same DM or role share it.
+    pub(crate) fn dedupe_key(alert: &WorldstateAlert, event: &WorldEvent) -> String {
+        match alert.role_id {
+            Some(role_id) => format!(
+                "{CACHE_PREFIX}:wf:alert:role:{}:{role_id}:{}",
+                alert.guild_id, event.key
+            ),
+            None => format!(
+                "{CACHE_PREFIX}:wf:alert:dm:{}:{}",
+                alert.user_id, event.key
+            ),
+        }
+    }
+
+    /// Sends `event` for `alert` once; a failed send releases the claim so
+    /// the next poll tries again.
+    async fn deliver(ctx: &Context, alert: &WorldstateAlert, event: &WorldEvent) {
+        let key = Self::dedupe_key(alert, event);
+        if !redis_set_nx_ex(&ctx.redis, &key, &1, DEDUPE_TTL).await {
+            return;
+        }
+        if !Self::send(ctx, alert, event).await {
+            redis_delete(&ctx.redis, &key).await;
+        }
+    }
+
+    async fn send(ctx: &Context, alert: &WorldstateAlert, event: &WorldEvent) -> bool {
+        let (channel_id, content, delivery) = match alert.role_id {
+            Some(role_id) => {
+                let Some(channel) =
+                    ChannelService::get_by_type(ctx, alert.guild_id, &ChannelEnum::Notification)
+                        .await
+                else {
+                    return false;
+                };
+                let channel_id: Id<ChannelMarker> = Id::new(channel.channel_id);
+                (
+                    channel_id,
+                    format!("🔔 {} <@&{role_id}>", event.summary),
+                    "role",
+                )
+            }
+            None => {
+                let user_id: Id<UserMarker> = Id::new(alert.user_id);
+                let dm = match open_dm!(ctx.http, user_id).await {
+                    Ok(dm) => dm,
+                    Err(e) => {
+                        tracing::warn!(user_id = alert.user_id, error = %e, "failed to open dm for worldstate alert");
+                        return false;
+                    }
+                };
+                (
+                    dm.id,
+                    format!("🔔 {}\n-# `{}`", event.summary, alert.filter),
+                    "dm",
+                )
+            }
+        };
+        match ctx
+            .http
+            .create_message(channel_id)
+            .content(&content)
+            .await
+        {
+            Ok(_) => {
+                metrics::counter!("worldstate_alerts_sent_total", "delivery" => delivery)
+                    .increment(1);
+                true
+            }
+            Err(e) => {
+                tracing::warn!(
+                    channel_id = channel_id.get(),
+                    error = %e,
+                    "failed to send worldstate alert",
+                );
+                false
+            }
+        }
+    }
+
+    pub(crate) async fn poll(ctx: &Arc<Context>, seen: &mut Seen) {
+        let fresh = Self::diff(seen, Self::snapshot(ctx).await);
+        if fresh.is_empty() {
+            return;
+        }
+        for (alert, filter) in Self::load_all(ctx).await {
+            for event in fresh
+                .iter()
+                .filter(|event| filter.matches(event))
+            {
+                Self::deliver(ctx, &alert, event).await;
+            }
+        }
+    }
+
+    pub fn spawn(ctx: &Arc<Context>) -> JoinHandle<()> {
+        let ctx = ctx.clone();
+        tokio::spawn(async move {
+            let token = shutdown::get_token();
+            // The first poll treats everything as new; the Redis claims keep
+            // a restart from repeating alerts already sent.
+            let mut seen = Seen::new();
+            let mut interval = tokio::time::interval(POLL_INTERVAL);
+            loop {
+                tokio::select! {
+                    _ = token.cancelled() => break,
+                    _ = interval.tick() => Self::poll(&ctx, &mut seen).await,
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/mod.rs"]
+mod tests;
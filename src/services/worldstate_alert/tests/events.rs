@@ -0,0 +1,111 @@
+use super::*;
+use crate::warframe::api::{InvasionReward, InvasionSide, VoidTraderItem};
+
+#[test]
+fn fissure_events_record_tier_and_mode() {
+    let fissure = Fissure {
+        id: "f1".to_string(),
+        node: "Ukko (Void)".to_string(),
+        mission_type: "Capture".to_string(),
+        enemy: "Corrupted".to_string(),
+        tier: "Axi".to_string(),
+        tier_num: 4,
+        expiry: "2030-01-01T00:00:00Z".to_string(),
+        is_storm: false,
+        is_hard: true,
+    };
+    let storm = Fissure { id: "f2".to_string(), is_storm: true, ..fissure.clone() };
+    let events = fissure_events(&[fissure, storm]);
+    assert_eq!(events[0].key, "fissure:f1");
+    assert!(
+        events[0]
+            .summary
+            .contains("(Steel Path)")
+    );
+    assert!(
+        events[0]
+            .attrs
+            .contains(&("tier", "axi".to_string()))
+    );
+    assert!(
+        events[0]
+            .attrs
+            .contains(&("mode", "hard".to_string()))
+    );
+    assert!(
+        events[1]
+            .summary
+            .contains("(Steel Path Void Storm)")
+    );
+    let modes: Vec<&str> = events[1]
+        .attrs
+        .iter()
+        .filter(|(k, _)| *k == "mode")
+        .map(|(_, v)| v.as_str())
+        .collect();
+    assert_eq!(modes, ["hard", "storm"]);
+}
+
+#[test]
+fn invasion_events_skip_completed_and_list_each_reward() {
+    let side = |faction: &str, reward: Option<&str>| InvasionSide {
+        faction: faction.to_string(),
+        reward: reward.map(|r| InvasionReward { as_string: r.to_string() }),
+    };
+    let invasion = |id: &str, completed: bool| Invasion {
+        id: id.to_string(),
+        node: "Kiliken (Venus)".to_string(),
+        desc: "Infested Outbreak".to_string(),
+        attacker: side("Infested", None),
+        defender: side("Corpus", Some("Orokin Catalyst Blueprint")),
+        vs_infestation: true,
+        completion: 10.0,
+        completed,
+    };
+    let events = invasion_events(&[invasion("a", false), invasion("b", true)]);
+    assert_eq!(events.len(), 1);
+    let rewards: Vec<&str> = events[0]
+        .attrs
+        .iter()
+        .filter(|(k, _)| *k == "reward")
+        .map(|(_, v)| v.as_str())
+        .collect();
+    assert_eq!(rewards, ["orokin catalyst blueprint"]);
+}
+
+#[test]
+fn cycle_event_key_changes_with_each_phase() {
+    let night = Cycle { state: "night".to_string(), expiry: "2030-01-01T00:00:00Z".to_string() };
+    let day = Cycle { state: "day".to_string(), expiry: "2030-01-01T01:40:00Z".to_string() };
+    let event = cycle_event("cetus", &night);
+    assert!(
+        event
+            .summary
+            .starts_with("**Cetus** is now **night**")
+    );
+    assert_ne!(event.key, cycle_event("cetus", &day).key);
+}
+
+#[test]
+fn baro_event_only_once_he_arrives() {
+    let mut trader = VoidTrader {
+        id: "v1".to_string(),
+        location: "Strata Relay (Earth)".to_string(),
+        activation: "2030-01-01T00:00:00Z".to_string(),
+        expiry: "2030-01-03T00:00:00Z".to_string(),
+        active: false,
+        inventory: vec![VoidTraderItem {
+            item: "Primed Flow".to_string(),
+            ducats: 350,
+            credits: 250_000,
+        }],
+    };
+    assert_eq!(baro_event(&trader), None);
+    trader.active = true;
+    let event = baro_event(&trader).unwrap();
+    assert!(
+        event
+            .attrs
+            .contains(&("item", "primed flow".to_string()))
+    );
+}
@@ -0,0 +1,115 @@
+use super::*;
+use crate::services::worldstate_alert::events::WorldEvent;
+
+fn event(source: AlertSource, attrs: &[(&'static str, &str)]) -> WorldEvent {
+    WorldEvent {
+        source,
+        key: "k".to_string(),
+        summary: String::new(),
+        attrs: attrs
+            .iter()
+            .map(|(k, v)| (*k, v.to_string()))
+            .collect(),
+    }
+}
+
+#[test]
+fn parse_lowercases_and_keeps_quoted_values() {
+    let filter = AlertFilter::parse("Invasion  reward:\"Orokin   Catalyst\" node:venus").unwrap();
+    assert_eq!(filter.source, AlertSource::Invasion);
+    assert_eq!(
+        filter.clauses,
+        vec![
+            (
+                "reward".to_string(),
+                "orokin catalyst".to_string()
+            ),
+            ("node".to_string(), "venus".to_string()),
+        ]
+    );
+    assert_eq!(
+        filter.to_string(),
+        "invasion reward:\"orokin catalyst\" node:venus"
+    );
+    assert_eq!(
+        AlertFilter::parse(&filter.to_string()).unwrap(),
+        filter
+    );
+}
+
+#[test]
+fn parse_rejects_unknown_sources_keys_and_bare_words() {
+    assert_eq!(AlertFilter::parse("  "), Err(FilterError::Empty));
+    assert_eq!(
+        AlertFilter::parse("nightwave"),
+        Err(FilterError::UnknownSource(
+            "nightwave".to_string()
+        ))
+    );
+    assert_eq!(
+        AlertFilter::parse("cycle reward:x"),
+        Err(FilterError::UnknownKey { source: AlertSource::Cycle, key: "reward".to_string() })
+    );
+    assert_eq!(
+        AlertFilter::parse("fissure axi"),
+        Err(FilterError::MissingValue("axi".to_string()))
+    );
+    assert_eq!(
+        AlertFilter::parse("fissure tier:\"axi"),
+        Err(FilterError::UnclosedQuote)
+    );
+}
+
+#[test]
+fn matches_requires_every_clause_and_the_source() {
+    let fissure = event(
+        AlertSource::Fissure,
+        &[("tier", "axi"), ("mode", "storm"), ("node", "ukko (void)")],
+    );
+    assert!(
+        AlertFilter::parse("fissure tier:axi mode:storm")
+            .unwrap()
+            .matches(&fissure)
+    );
+    assert!(
+        !AlertFilter::parse("fissure tier:axi mode:hard")
+            .unwrap()
+            .matches(&fissure)
+    );
+    assert!(
+        AlertFilter::parse("fissure")
+            .unwrap()
+            .matches(&fissure)
+    );
+    assert!(
+        !AlertFilter::parse("invasion")
+            .unwrap()
+            .matches(&fissure)
+    );
+
+    // A Steel Path Void Storm fissure for Axi carries both modes.
+    let both = event(
+        AlertSource::Fissure,
+        &[("tier", "axi"), ("mode", "hard"), ("mode", "storm")],
+    );
+    assert!(
+        AlertFilter::parse("fissure tier:axi mode:hard mode:storm")
+            .unwrap()
+            .matches(&both)
+    );
+    assert!(
+        !AlertFilter::parse("fissure tier:axi mode:hard mode:storm")
+            .unwrap()
+            .matches(&fissure)
+    );
+
+    let invasion = event(
+        AlertSource::Invasion,
+        &[("reward", "3x fieldron"), ("reward", "orokin catalyst blueprint")],
+    );
+    assert!(
+        AlertFilter::parse("invasion reward:catalyst")
+            .unwrap()
+            .matches(&invasion)
+    );
+}
@@ -0,0 +1,174 @@
+use super::*;
+use crate::context::ContextBuilder;
+use crate::context::mock_http::MessageOp;
+use crate::dbs::redis::redis_set_ex;
+use crate::warframe::api::{Invasion, InvasionReward, InvasionSide};
+
+async fn build_context() -> Arc<Context> {
+    Arc::new(
+        ContextBuilder::new()
+            .watchers(false)
+            .build()
+            .await
+            .expect("failed to build Context"),
+    )
+}
+
+fn event(source: AlertSource, key: &str) -> WorldEvent {
+    WorldEvent { source, key: key.to_string(), summary: key.to_string(), attrs: Vec::new() }
+}
+
+#[test]
+fn diff_reports_new_keys_and_keeps_sources_that_failed() {
+    let mut seen = Seen::new();
+    let first = HashMap::from([
+        (
+            AlertSource::Fissure,
+            vec![event(AlertSource::Fissure, "a")],
+        ),
+        (
+            AlertSource::Sortie,
+            vec![event(AlertSource::Sortie, "s")],
+        ),
+    ]);
+    assert_eq!(
+        WorldstateAlertService::diff(&mut seen, first).len(),
+        2
+    );
+
+    // The sortie fetch failed this time, so it must not come back as new.
+    let second = HashMap::from([(
+        AlertSource::Fissure,
+        vec![event(AlertSource::Fissure, "a"), event(AlertSource::Fissure, "b")],
+    )]);
+    let fresh = WorldstateAlertService::diff(&mut seen, second);
+    assert_eq!(fresh, vec![event(AlertSource::Fissure, "b")]);
+    let third = HashMap::from([(
+        AlertSource::Sortie,
+        vec![event(AlertSource::Sortie, "s")],
+    )]);
+    assert!(WorldstateAlertService::diff(&mut seen, third).is_empty());
+}
+
+#[tokio::test]
+async fn add_list_and_remove_round_trip() {
+    let ctx = build_context().await;
+    let filter = AlertFilter::parse("cycle place:cetus state:night").unwrap();
+
+    assert_eq!(
+        WorldstateAlertService::add(&ctx, 1, 2, &filter, None)
+            .await
+            .unwrap(),
+        AddOutcome::Added
+    );
+    assert_eq!(
+        WorldstateAlertService::add(&ctx, 1, 2, &filter, None)
+            .await
+            .unwrap(),
+        AddOutcome::Duplicate
+    );
+    let alerts = WorldstateAlertService::list(&ctx, 1, 2).await;
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].filter, "cycle place:cetus state:night");
+
+    assert!(
+        WorldstateAlertService::remove(&ctx, 1, 2, 2)
+            .await
+            .unwrap()
+            .is_none()
+    );
+    let removed = WorldstateAlertService::remove(&ctx, 1, 2, 1)
+        .await
+        .unwrap();
+    assert_eq!(
+        removed.map(|a| a.filter),
+        Some(filter.to_string())
+    );
+    assert!(
+        WorldstateAlertService::list(&ctx, 1, 2)
+            .await
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn poll_sends_one_dm_per_matching_invasion() {
+    let ctx = build_context().await;
+    let user_id = 4242;
+    let filter = AlertFilter::parse("invasion reward:catalyst").unwrap();
+    WorldstateAlertService::add(&ctx, 1, user_id, &filter, None)
+        .await
+        .unwrap();
+
+    let invasions = vec![Invasion {
+        id: "poll-test".to_string(),
+        node: "Kiliken (Venus)".to_string(),
+        desc: "Grineer Offensive".to_string(),
+        attacker: InvasionSide {
+            faction: "Grineer".to_string(),
+            reward: Some(InvasionReward { as_string: "Orokin Catalyst Blueprint".to_string() }),
+        },
+        defender: InvasionSide { faction: "Corpus".to_string(), reward: None },
+        vs_infestation: false,
+        completion: 5.0,
+        completed: false,
+    }];
+    redis_set_ex(
+        &ctx.redis,
+        &format!("{CACHE_PREFIX}:wf:invasions"),
+        &invasions,
+        60,
+    )
+    .await;
+
+    let dms = |ctx: &Context| {
+        ctx.http
+            .messages
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|m| matches!(m.kind, MessageOp::Create) && m.channel_id.get() == user_id)
+            .count()
+    };
+    let mut seen = Seen::new();
+    WorldstateAlertService::poll(&ctx, &mut seen).await;
+    assert_eq!(dms(&ctx), 1);
+
+    // A restart forgets `seen`, but the Redis claim still holds.
+    let mut seen = Seen::new();
+    WorldstateAlertService::poll(&ctx, &mut seen).await;
+    assert_eq!(dms(&ctx), 1);
+}
+
+#[tokio::test]
+async fn deliver_releases_the_claim_when_sending_fails() {
+    let ctx = build_context().await;
+    let user_id = 4343;
+    let alert = WorldstateAlert {
+        id: None,
+        guild_id: 1,
+        user_id,
+        filter: "fissure tier:axi".to_string(),
+        role_id: None,
+        created_at: 0,
+    };
+    let event = event(AlertSource::Fissure, "fissure:deliver-test");
+    let dms = |ctx: &Context| {
+        ctx.http
+            .messages
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|m| matches!(m.kind, MessageOp::Create) && m.channel_id.get() == user_id)
+            .count()
+    };
+
+    ctx.http.fail_next_create_message();
+    WorldstateAlertService::deliver(&ctx, &alert, &event).await;
+    assert_eq!(dms(&ctx), 0);
+
+    WorldstateAlertService::deliver(&ctx, &alert, &event).await;
+    assert_eq!(dms(&ctx), 1);
+    WorldstateAlertService::deliver(&ctx, &alert, &event).await;
+    assert_eq!(dms(&ctx), 1);
+}
@@ -4,10 +4,15 @@ use async_trait::async_trait;
 use twilight_interactions::command::CreateCommand;
 use twilight_model::application::{
     command::Command,
-    interaction::{Interaction, application_command::CommandData},
+    interaction::{Interaction, application_command::CommandData, modal::ModalInteractionData},
 };
 
-use crate::{commands::ai::AiCommand, context::Context, slices::registry::FeatureSlice};
+use crate::{
+    commands::ai::{AiCommand, KB_MODAL_ID, context_menu},
+    context::Context,
+    services::ai::message_action::{ASK_COMMAND, TRANSLATE_COMMAND},
+    slices::registry::FeatureSlice,
+};
 
 pub struct AiAssistantSlice;
 
@@ -15,13 +20,46 @@ pub struct AiAssistantSlice;
 impl FeatureSlice for AiAssistantSlice {
     fn register_commands(&self, commands: &mut Vec<Command>) {
         commands.push(AiCommand::create_command().into());
+        commands.extend(context_menu::commands());
     }
 
     fn command_names(&self) -> &'static [&'static str] {
+        &["ai", ASK_COMMAND, TRANSLATE_COMMAND]
+    }
+
+    fn autocomplete_names(&self) -> &'static [&'static str] {
         &["ai"]
     }
 
+    fn modal_ids(&self) -> &'static [&'static str] {
+        &[KB_MODAL_ID]
+    }
+
     async fn handle_command(&self, ctx: Arc<Context>, interaction: Interaction, data: CommandData) {
-        AiCommand::handle(ctx, interaction, data).await;
+        if data.name == "ai" {
+            AiCommand::handle(ctx, interaction, data).await;
+        } else {
+            context_menu::handle(ctx, interaction, data).await;
+        }
+    }
+
+    async fn handle_autocomplete(
+        &self,
+        ctx: Arc<Context>,
+        interaction: Interaction,
+        data: CommandData,
+    ) {
+        AiCommand::autocomplete(ctx, interaction, data).await;
+    }
+
+    async fn handle_modal(
+        &self,
+        ctx: Arc<Context>,
+        interaction: Interaction,
+        data: ModalInteractionData,
+    ) {
+        if data.custom_id == KB_MODAL_ID {
+            AiCommand::handle_kb_modal(ctx, interaction, data).await;
+        }
     }
 }
@@ -31,7 +31,7 @@ impl FeatureSlice for WarframeSlice {
     }
 
     fn component_prefixes(&self) -> &'static [&'static str] {
-        &["market_"]
+        &["market_", "riven_"]
     }
 
     async fn handle_command(&self, ctx: Arc<Context>, interaction: Interaction, data: CommandData) {
@@ -5,6 +5,31 @@ use twilight_util::builder::embed::{EmbedBuilder, EmbedFooterBuilder, ImageSourc
 
 use super::{COLOR, COLOR_INVALID};
 
+/// Discord's limit on an embed field value.
+pub(crate) const FIELD_LIMIT: usize = 1024;
+
+/// Joins `lines` within Discord's field limit, counting the lines that
+/// did not fit.
+pub(crate) fn join_lines(lines: &[String]) -> String {
+    if lines.is_empty() {
+        return "-".to_string();
+    }
+    // Room for the trailing "… +N" marker.
+    const RESERVE: usize = 16;
+    let mut out = String::new();
+    for (i, line) in lines.iter().enumerate() {
+        if out.len() + line.len() + 1 > FIELD_LIMIT - RESERVE {
+            out.push_str(&format!("\n… +{}", lines.len() - i));
+            break;
+        }
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(line);
+    }
+    out
+}
+
 pub fn footer_with_icon(
     guild: &Reference<'_, Id<GuildMarker>, CachedGuild>,
 ) -> anyhow::Result<EmbedFooter> {
@@ -39,6 +64,16 @@ pub fn guild_unavailable_embed() -> anyhow::Result<Embed> {
     Ok(embed)
 }
 
+pub fn invalid_option_embed(description: &str) -> anyhow::Result<Embed> {
+    let embed = EmbedBuilder::new()
+        .color(COLOR_INVALID)
+        .title("ค่าที่ระบุไม่ถูกต้อง")
+        .description(description)
+        .validate()?
+        .build();
+    Ok(embed)
+}
+
 pub fn pong_embed(latency_ms: Option<u64>) -> anyhow::Result<Embed> {
     let desc = match latency_ms {
         Some(ms) => format!("Latency: {ms}ms"),
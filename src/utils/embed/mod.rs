@@ -8,12 +8,38 @@ use twilight_model::{
 };
 use twilight_util::builder::embed::{EmbedBuilder, EmbedFieldBuilder, ImageSource};
 
+use crate::dbs::mongo::models::{
+    ai_persona::AiPersona,
+    guild_settings::{
+        AiAccessSettings, AiChannelMode, AiQuotaSettings, AiSafetySettings, AiSafetyThreshold,
+        ScamTextMode,
+    },
+};
+
 pub(super) const COLOR: u32 = 0xF1C40F;
 pub(super) const COLOR_INVALID: u32 = 0xE74C3C;
 
 pub mod general;
 
-pub use general::{footer_with_icon, guild_only_embed, guild_unavailable_embed, pong_embed};
+pub(crate) use general::{FIELD_LIMIT, join_lines};
+pub use general::{
+    footer_with_icon, guild_only_embed, guild_unavailable_embed, invalid_option_embed, pong_embed,
+};
+
+/// Embed field values are capped at 1024 characters.
+const FIELD_PREVIEW_CHARS: usize = 1000;
+
+fn preview(text: &str) -> String {
+    if text.chars().count() <= FIELD_PREVIEW_CHARS {
+        return text.to_string();
+    }
+    let mut preview: String = text
+        .chars()
+        .take(FIELD_PREVIEW_CHARS)
+        .collect();
+    preview.push('…');
+    preview
+}
 
 pub fn welcome_embed(
     guild: &Reference<'_, Id<GuildMarker>, CachedGuild>,
@@ -139,6 +165,275 @@ pub fn set_scam_detect_embed(
     Ok(embed.build())
 }
 
+pub fn set_scam_text_embed(
+    guild: &Reference<'_, Id<GuildMarker>, CachedGuild>,
+    mode: ScamTextMode,
+    setter: &str,
+) -> anyhow::Result<Embed> {
+    let now = Utc::now().timestamp();
+    let mut footer = footer_with_icon(guild)?;
+    footer.text = guild.name().to_string();
+    let (state, effect) = match mode {
+        ScamTextMode::Off => ("off", "Messages are not checked."),
+        ScamTextMode::Shadow => (
+            "shadow",
+            "Links from recently joined members are classified and logged only.",
+        ),
+        ScamTextMode::Enforce => (
+            "enforce",
+            "Links from recently joined members that the AI flags are deleted and their authors quarantined.",
+        ),
+    };
+
+    let embed = EmbedBuilder::new()
+        .color(COLOR)
+        .title("Scam text detection")
+        .description(format!(
+            "Scam text detection is now **{state}** for this server.\n{effect}"
+        ))
+        .field(EmbedFieldBuilder::new("ผู้ตั้งค่า", setter).inline())
+        .field(EmbedFieldBuilder::new("เวลา", format!("<t:{now}:R>")).inline())
+        .footer(footer)
+        .validate()?;
+
+    Ok(embed.build())
+}
+
+fn quota_limit_text(limit: Option<u64>, default: &str) -> String {
+    match limit {
+        None => default.to_string(),
+        Some(0) => "ไม่จำกัด".to_string(),
+        Some(limit) => limit.to_string(),
+    }
+}
+
+pub fn set_ai_quota_embed(
+    guild: &Reference<'_, Id<GuildMarker>, CachedGuild>,
+    quota: &AiQuotaSettings,
+    setter: &str,
+) -> anyhow::Result<Embed> {
+    let now = Utc::now().timestamp();
+    let mut footer = footer_with_icon(guild)?;
+    footer.text = guild.name().to_string();
+
+    let embed = EmbedBuilder::new()
+        .color(COLOR)
+        .title("โควตา AI รายวัน")
+        .description("การตั้งค่าสำเร็จ 🎉")
+        .field(
+            EmbedFieldBuilder::new(
+                "คำขอต่อผู้ใช้",
+                quota_limit_text(quota.user_requests, "ค่าเริ่มต้น"),
+            )
+            .inline(),
+        )
+        .field(
+            EmbedFieldBuilder::new(
+                "โทเคนต่อผู้ใช้",
+                quota_limit_text(quota.user_tokens, "ค่าเริ่มต้น"),
+            )
+            .inline(),
+        )
+        .field(
+            EmbedFieldBuilder::new(
+                "คำขอทั้งเซิร์ฟเวอร์",
+                quota_limit_text(quota.guild_requests, "ไม่จำกัด"),
+            )
+            .inline(),
+        )
+        .field(
+            EmbedFieldBuilder::new(
+                "โทเคนทั้งเซิร์ฟเวอร์",
+                quota_limit_text(quota.guild_tokens, "ไม่จำกัด"),
+            )
+            .inline(),
+        )
+        .field(EmbedFieldBuilder::new("ผู้ตั้งค่า", setter).inline())
+        .field(EmbedFieldBuilder::new("เวลา", format!("<t:{now}:R>")).inline())
+        .footer(footer)
+        .validate()?;
+
+    Ok(embed.build())
+}
+
+pub fn set_ai_safety_embed(
+    guild: &Reference<'_, Id<GuildMarker>, CachedGuild>,
+    safety: &AiSafetySettings,
+    setter: &str,
+) -> anyhow::Result<Embed> {
+    let now = Utc::now().timestamp();
+    let mut footer = footer_with_icon(guild)?;
+    footer.text = guild.name().to_string();
+
+    let mut builder = EmbedBuilder::new()
+        .color(COLOR)
+        .title("ตัวกรองความปลอดภัย AI")
+        .description("การตั้งค่าสำเร็จ 🎉");
+    for (label, threshold) in [
+        ("การคุกคาม", safety.harassment),
+        ("ถ้อยคำแสดงความเกลียดชัง", safety.hate_speech),
+        ("เนื้อหาทางเพศ", safety.sexually_explicit),
+        ("เนื้อหาอันตราย", safety.dangerous_content),
+    ] {
+        builder =
+            builder.field(EmbedFieldBuilder::new(label, safety_threshold_text(threshold)).inline());
+    }
+
+    let embed = builder
+        .field(EmbedFieldBuilder::new("ผู้ตั้งค่า", setter).inline())
+        .field(EmbedFieldBuilder::new("เวลา", format!("<t:{now}:R>")).inline())
+        .footer(footer)
+        .validate()?;
+
+    Ok(embed.build())
+}
+
+pub fn set_ai_persona_embed(
+    guild: &Reference<'_, Id<GuildMarker>, CachedGuild>,
+    persona: &AiPersona,
+    created: bool,
+    setter: &str,
+) -> anyhow::Result<Embed> {
+    let now = Utc::now().timestamp();
+    let mut footer = footer_with_icon(guild)?;
+    footer.text = guild.name().to_string();
+
+    let models = if persona.models.is_empty() {
+        "ทุกโมเดล".to_string()
+    } else {
+        persona.models.join(", ")
+    };
+    let temperature = persona
+        .temperature
+        .map_or_else(|| "ค่าเริ่มต้น".to_string(), |t| format!("{t:.2}"));
+
+    let embed = EmbedBuilder::new()
+        .color(COLOR)
+        .title(format!("เพอร์โซนา AI: {}", persona.name))
+        .description(if created {
+            "สร้างเพอร์โซนาสำเร็จ 🎉"
+        } else {
+            "แก้ไขเพอร์โซนาสำเร็จ 🎉"
+        })
+        .field(EmbedFieldBuilder::new(
+            "พรอมพ์",
+            preview(&persona.system_prompt),
+        ))
+        .field(EmbedFieldBuilder::new("โมเดลที่อนุญาต", models).inline())
+        .field(EmbedFieldBuilder::new("Temperature", temperature).inline())
+        .field(EmbedFieldBuilder::new("ผู้ตั้งค่า", setter).inline())
+        .field(EmbedFieldBuilder::new("เวลา", format!("<t:{now}:R>")).inline())
+        .footer(footer)
+        .validate()?;
+
+    Ok(embed.build())
+}
+
+pub fn remove_ai_persona_embed(
+    guild: &Reference<'_, Id<GuildMarker>, CachedGuild>,
+    name: &str,
+    setter: &str,
+) -> anyhow::Result<Embed> {
+    let now = Utc::now().timestamp();
+    let mut footer = footer_with_icon(guild)?;
+    footer.text = guild.name().to_string();
+
+    let embed = EmbedBuilder::new()
+        .color(COLOR)
+        .title(format!("เพอร์โซนา AI: {name}"))
+        .description("ลบเพอร์โซนาสำเร็จ")
+        .field(EmbedFieldBuilder::new("ผู้ตั้งค่า", setter).inline())
+        .field(EmbedFieldBuilder::new("เวลา", format!("<t:{now}:R>")).inline())
+        .footer(footer)
+        .validate()?;
+
+    Ok(embed.build())
+}
+
+pub fn set_ai_base_prompt_embed(
+    guild: &Reference<'_, Id<GuildMarker>, CachedGuild>,
+    prompt: Option<&str>,
+    setter: &str,
+) -> anyhow::Result<Embed> {
+    let now = Utc::now().timestamp();
+    let mut footer = footer_with_icon(guild)?;
+    footer.text = guild.name().to_string();
+
+    let value = prompt.map_or_else(|| "ใช้พรอมพ์หลักของบอท".to_string(), preview);
+
+    let embed = EmbedBuilder::new()
+        .color(COLOR)
+        .title("พรอมพ์หลักของ AI")
+        .description("การตั้งค่าสำเร็จ 🎉")
+        .field(EmbedFieldBuilder::new("พรอมพ์", value))
+        .field(EmbedFieldBuilder::new("ผู้ตั้งค่า", setter).inline())
+        .field(EmbedFieldBuilder::new("เวลา", format!("<t:{now}:R>")).inline())
+        .footer(footer)
+        .validate()?;
+
+    Ok(embed.build())
+}
+
+pub fn set_ai_access_embed(
+    guild: &Reference<'_, Id<GuildMarker>, CachedGuild>,
+    access: &AiAccessSettings,
+    setter: &str,
+) -> anyhow::Result<Embed> {
+    let now = Utc::now().timestamp();
+    let mut footer = footer_with_icon(guild)?;
+    footer.text = guild.name().to_string();
+
+    let on_off = |value: bool| if value { "เปิด" } else { "ปิด" };
+    let mode = match access.channel_mode {
+        AiChannelMode::All => "ทุกช่อง",
+        AiChannelMode::Allow => "เฉพาะช่องในรายการ",
+        AiChannelMode::Deny => "ทุกช่องยกเว้นในรายการ",
+    };
+    let channels = if access.channels.is_empty() {
+        "ไม่มี".to_string()
+    } else {
+        access
+            .channels
+            .iter()
+            .map(|id| format!("<#{id}>"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    let embed = EmbedBuilder::new()
+        .color(COLOR)
+        .title("การเข้าถึง AI")
+        .description("การตั้งค่าสำเร็จ 🎉")
+        .field(EmbedFieldBuilder::new("สถานะ", on_off(access.enabled)).inline())
+        .field(EmbedFieldBuilder::new("ช่องที่ใช้ได้", mode).inline())
+        .field(
+            EmbedFieldBuilder::new(
+                "ตอบกลับบอทนับเป็นการเรียก",
+                on_off(access.reply_mentions),
+            )
+            .inline(),
+        )
+        .field(EmbedFieldBuilder::new("ข้อความส่วนตัว", on_off(access.allow_dms)).inline())
+        .field(EmbedFieldBuilder::new("รายการช่อง", channels))
+        .field(EmbedFieldBuilder::new("ผู้ตั้งค่า", setter).inline())
+        .field(EmbedFieldBuilder::new("เวลา", format!("<t:{now}:R>")).inline())
+        .footer(footer)
+        .validate()?;
+
+    Ok(embed.build())
+}
+
+fn safety_threshold_text(threshold: Option<AiSafetyThreshold>) -> &'static str {
+    match threshold {
+        None => "ค่าเริ่มต้น",
+        Some(AiSafetyThreshold::Off) => "ปิด",
+        Some(AiSafetyThreshold::BlockNone) => "ไม่บล็อก",
+        Some(AiSafetyThreshold::BlockOnlyHigh) => "บล็อกเฉพาะระดับสูง",
+        Some(AiSafetyThreshold::BlockMediumAndAbove) => "บล็อกระดับกลางขึ้นไป",
+        Some(AiSafetyThreshold::BlockLowAndAbove) => "บล็อกระดับต่ำขึ้นไป",
+    }
+}
+
 pub fn role_message_embed(
     guild: &Reference<'_, Id<GuildMarker>, CachedGuild>,
     roles: &[(impl Display, impl Display)],
@@ -169,10 +464,23 @@ pub fn help_embed(guild: &Reference<'_, Id<GuildMarker>, CachedGuild>) -> anyhow
 **/ping** - ดูความหน่วงของบอท\n\
 **/intro** - แนะนำตัวคุณ\n\
 **/warframe market <item>** - ตรวจสอบราคาตลาด\n\
+**/warframe price <item>** - ดูราคาซื้อขายจริงย้อนหลังและแนวโน้ม\n\
+**/warframe watch add <item> <buy|sell> <price>** - รับ DM เมื่อมีออเดอร์ถึงราคาที่ต้องการ\n\
+**/warframe riven <weapon>** - ค้นหา riven ที่ประมูลตามค่าบวก ค่าลบ และราคา\n\
 **/warframe build <item>** - ค้นหา build\n\
+**/warframe item <name>** - ดูค่าพื้นฐาน แหล่งดรอป และส่วนประกอบของไอเทม\n\
+**/warframe farm <item>** - ค้นหา relic และภารกิจที่ดรอปไอเทม พร้อมรอยแยกที่เปิดอยู่\n\
+**/warframe worldstate <section>** - ดูรอยแยก Sortie Archon Hunt Invasion และ Baro\n\
+**/warframe alert add|list|remove** - รับแจ้งเตือนเมื่อมีเหตุการณ์ที่ตรงกับตัวกรอง\n\
 **/ai prompt <text>** - ตั้งค่า prompt ส่วนตัว\n\
 **/ai talk <message>** - สนทนากับ AI\n\
-**/ai clear** - ล้างประวัติการคุยกับ AI";
+**/ai clear** - ล้างประวัติการคุยกับ AI\n\
+**/ai quota** - ดูโควตา AI ที่เหลือของวันนี้\n\
+**/ai summarize [messages] [since]** - สรุปข้อความล่าสุดในช่อง\n\
+**/ai persona [name]** - เลือกเพอร์โซนา AI ของเซิร์ฟเวอร์\n\
+**/ai kb add|remove|list** - จัดการคลังความรู้ที่ AI ใช้ตอบ (ผู้จัดการเซิร์ฟเวอร์)\n\
+**/ai memory show|forget** - ดูหรือลบสิ่งที่ AI จำเกี่ยวกับคุณ\n\
+**คลิกขวาที่ข้อความ > Apps > ถาม AI / แปลภาษา** - ให้ AI อธิบายหรือแปลข้อความนั้น";
     let embed = EmbedBuilder::new()
         .color(COLOR)
         .title("คำสั่งบอท")
@@ -116,3 +116,14 @@ fn test_pong_embed_latency_na() {
     assert_eq!(embed.description.as_deref(), Some("Latency: N/A"));
     assert_eq!(embed.color, Some(COLOR));
 }
+
+#[test]
+fn join_lines_stays_within_the_field_limit() {
+    let lines: Vec<String> = (0..200)
+        .map(|i| format!("line number {i}"))
+        .collect();
+    let joined = join_lines(&lines);
+    assert!(joined.len() <= FIELD_LIMIT);
+    assert!(joined.ends_with(&format!("+{}", 200 - joined.lines().count() + 1)));
+    assert_eq!(join_lines(&[]), "-");
+}
@@ -0,0 +1,34 @@
+use twilight_model::application::interaction::Interaction;
+
+/// The languages replies are written in. Thai stays the default; any other
+/// client locale gets English.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Lang {
+    #[default]
+    Th,
+    En,
+}
+
+impl Lang {
+    pub fn from_locale(locale: Option<&str>) -> Self {
+        match locale {
+            Some(locale) if !locale.starts_with("th") => Self::En,
+            _ => Self::Th,
+        }
+    }
+
+    pub fn of(interaction: &Interaction) -> Self {
+        Self::from_locale(interaction.locale.as_deref())
+    }
+
+    pub fn pick(self, th: &'static str, en: &'static str) -> &'static str {
+        match self {
+            Self::Th => th,
+            Self::En => en,
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "tests/locale.rs"]
+mod tests;
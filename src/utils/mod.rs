@@ -3,5 +3,7 @@ pub mod embed;
 pub mod env;
 pub mod http;
 pub mod interaction;
+pub mod locale;
 pub mod modal;
 pub mod reaction;
+pub mod session;
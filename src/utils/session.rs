@@ -0,0 +1,61 @@
+use std::future::Future;
+
+use deadpool_redis::Pool;
+use serde::{Serialize, de::DeserializeOwned};
+use twilight_model::id::{Id, marker::MessageMarker};
+
+use crate::{
+    configs::CACHE_PREFIX,
+    dbs::redis::{redis_get, redis_set_ex},
+};
+
+/// The state behind a message's buttons, kept in Redis for `ttl` seconds
+/// after the last interaction and rebuilt from the button's custom id once
+/// it has expired.
+pub struct MessageSessions {
+    name: &'static str,
+    ttl: usize,
+}
+
+impl MessageSessions {
+    pub const fn new(name: &'static str, ttl: usize) -> Self {
+        Self { name, ttl }
+    }
+
+    fn key(&self, message_id: Id<MessageMarker>) -> String {
+        format!(
+            "{CACHE_PREFIX}:{}:session:{message_id}",
+            self.name
+        )
+    }
+
+    /// Saves `session` for another `ttl`, counted from now.
+    pub async fn insert<T>(&self, pool: &Pool, message_id: Id<MessageMarker>, session: &T)
+    where
+        T: Serialize + Sync,
+    {
+        redis_set_ex(pool, &self.key(message_id), session, self.ttl).await;
+    }
+
+    /// The stored session, or the one `restore` rebuilds from the button's
+    /// `state` when it has expired; `true` marks a rebuilt session.
+    pub async fn load_or_restore<T, S, F, Fut>(
+        &self,
+        pool: &Pool,
+        message_id: Id<MessageMarker>,
+        state: Option<S>,
+        restore: F,
+    ) -> Option<(T, bool)>
+    where
+        T: DeserializeOwned + Send + Sync,
+        F: FnOnce(S) -> Fut,
+        Fut: Future<Output = Option<T>>,
+    {
+        if let Some(session) = redis_get::<T>(pool, &self.key(message_id)).await {
+            return Some((session, false));
+        }
+        restore(state?)
+            .await
+            .map(|session| (session, true))
+    }
+}
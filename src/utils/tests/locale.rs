@@ -0,0 +1,10 @@
+use super::*;
+
+#[test]
+fn thai_is_the_default_and_other_locales_get_english() {
+    assert_eq!(Lang::from_locale(None), Lang::Th);
+    assert_eq!(Lang::from_locale(Some("th")), Lang::Th);
+    assert_eq!(Lang::from_locale(Some("en-US")), Lang::En);
+    assert_eq!(Lang::from_locale(Some("ja")), Lang::En);
+    assert_eq!(Lang::En.pick("ไทย", "English"), "English");
+}
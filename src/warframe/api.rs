@@ -29,6 +29,106 @@ pub struct SteelPathData {
     pub activation: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Fissure {
+    pub id: String,
+    pub node: String,
+    #[serde(rename = "missionType")]
+    pub mission_type: String,
+    #[serde(default)]
+    pub enemy: String,
+    pub tier: String,
+    #[serde(rename = "tierNum", default)]
+    pub tier_num: u8,
+    pub expiry: String,
+    #[serde(rename = "isStorm", default)]
+    pub is_storm: bool,
+    #[serde(rename = "isHard", default)]
+    pub is_hard: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SortieVariant {
+    #[serde(rename = "missionType")]
+    pub mission_type: String,
+    pub modifier: String,
+    pub node: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Sortie {
+    pub id: String,
+    pub boss: String,
+    pub faction: String,
+    pub expiry: String,
+    #[serde(default)]
+    pub variants: Vec<SortieVariant>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchonMission {
+    pub node: String,
+    #[serde(rename = "type")]
+    pub mission_type: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchonHunt {
+    pub id: String,
+    pub boss: String,
+    pub expiry: String,
+    #[serde(default)]
+    pub missions: Vec<ArchonMission>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvasionReward {
+    #[serde(rename = "asString", default)]
+    pub as_string: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvasionSide {
+    pub faction: String,
+    pub reward: Option<InvasionReward>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Invasion {
+    pub id: String,
+    pub node: String,
+    pub desc: String,
+    pub attacker: InvasionSide,
+    pub defender: InvasionSide,
+    #[serde(rename = "vsInfestation", default)]
+    pub vs_infestation: bool,
+    #[serde(default)]
+    pub completion: f64,
+    #[serde(default)]
+    pub completed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoidTraderItem {
+    pub item: String,
+    #[serde(default)]
+    pub ducats: u32,
+    #[serde(default)]
+    pub credits: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoidTrader {
+    pub id: String,
+    pub location: String,
+    pub activation: String,
+    pub expiry: String,
+    #[serde(default)]
+    pub active: bool,
+    #[serde(default)]
+    pub inventory: Vec<VoidTraderItem>,
+}
+
 async fn fetch_json<H, T>(client: &H, path: &str) -> anyhow::Result<T>
 where
     H: HttpProvider + Sync,
@@ -60,6 +160,41 @@ where
     fetch_json(client, "steelPath").await
 }
 
+pub async fn fissures<H>(client: &H) -> anyhow::Result<Vec<Fissure>>
+where
+    H: HttpProvider + Sync,
+{
+    fetch_json(client, "fissures").await
+}
+
+pub async fn sortie<H>(client: &H) -> anyhow::Result<Sortie>
+where
+    H: HttpProvider + Sync,
+{
+    fetch_json(client, "sortie").await
+}
+
+pub async fn archon_hunt<H>(client: &H) -> anyhow::Result<ArchonHunt>
+where
+    H: HttpProvider + Sync,
+{
+    fetch_json(client, "archonHunt").await
+}
+
+pub async fn invasions<H>(client: &H) -> anyhow::Result<Vec<Invasion>>
+where
+    H: HttpProvider + Sync,
+{
+    fetch_json(client, "invasions").await
+}
+
+pub async fn void_trader<H>(client: &H) -> anyhow::Result<VoidTrader>
+where
+    H: HttpProvider + Sync,
+{
+    fetch_json(client, "voidTrader").await
+}
+
 #[cfg(test)]
 #[path = "tests/api.rs"]
 mod tests;
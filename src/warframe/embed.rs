@@ -9,17 +9,18 @@ use std::{future::Future, sync::Arc};
 
 use super::api;
 use super::utils::{format_time, title_case};
+use super::worldstate;
 use crate::configs::{CACHE_PREFIX, Reaction};
 use crate::context::Context;
 use crate::dbs::redis::{redis_get, redis_set_ex};
 use crate::utils::embed::footer_with_icon;
 use serde::{Serialize, de::DeserializeOwned};
 
-const COLOR: u32 = 0xF1C40F;
-const URL: &str = "https://github.com/kengzzzz/discord-rs";
-const MIN_CACHE_TTL: usize = 60;
+pub(super) const COLOR: u32 = 0xF1C40F;
+pub(super) const URL: &str = "https://github.com/kengzzzz/discord-rs";
+pub(super) const MIN_CACHE_TTL: usize = 60;
 
-fn ttl_from_expiry(expiry: &str) -> usize {
+pub(super) fn ttl_from_expiry(expiry: &str) -> usize {
     if let Ok(t) = chrono::DateTime::parse_from_rfc3339(expiry) {
         let secs = t.with_timezone(&Utc).timestamp() - Utc::now().timestamp();
         std::cmp::max(secs.max(0) as usize, MIN_CACHE_TTL)
@@ -28,7 +29,7 @@ fn ttl_from_expiry(expiry: &str) -> usize {
     }
 }
 
-async fn cached_or_request<T, F, Fut, G>(
+pub(super) async fn cached_or_request<T, F, Fut, G>(
     pool: &Pool,
     key: &str,
     fetcher: F,
@@ -71,15 +72,7 @@ async fn image_link(ctx: &Arc<Context>) -> anyhow::Result<Option<String>> {
 }
 
 async fn cycle_field(ctx: &Arc<Context>, endpoint: &str, name: &str) -> anyhow::Result<EmbedField> {
-    let key = format!("{CACHE_PREFIX}:wf:cycle:{endpoint}");
-    let client = ctx.reqwest.clone();
-    let data = cached_or_request(
-        &ctx.redis,
-        &key,
-        move || async move { api::cycle(&client, endpoint).await },
-        |d| ttl_from_expiry(&d.expiry),
-    )
-    .await?;
+    let data = worldstate::cycle(ctx, endpoint).await?;
     let field = EmbedFieldBuilder::new(
         format!(
             "{}{}{}",
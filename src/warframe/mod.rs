@@ -1,5 +1,6 @@
 pub mod api;
 pub mod embed;
 pub mod utils;
+pub mod worldstate;
 
 pub use embed::status_embed;
@@ -74,3 +74,56 @@ async fn test_steel_path_fetch_json() {
     assert_eq!(sp.expiry, "2030-01-01T00:00:00Z");
     assert_eq!(sp.activation.unwrap(), "2029-12-01T00:00:00Z");
 }
+
+#[tokio::test]
+async fn test_fissures_fetch_json() {
+    let url = format!("{BASE_URL}/fissures");
+    let mut map = HashMap::new();
+    map.insert(url, "[{\"id\":\"f1\",\"node\":\"Ukko (Void)\",\"missionType\":\"Capture\",\"enemy\":\"Corrupted\",\"tier\":\"Axi\",\"tierNum\":4,\"expiry\":\"2030-01-01T00:00:00Z\",\"isStorm\":true,\"isHard\":false,\"expired\":false}]".to_string());
+    let client = MockHttp::new(map);
+
+    let fissures = fissures(&client)
+        .await
+        .expect("fissures");
+    assert_eq!(fissures.len(), 1);
+    assert_eq!(fissures[0].tier, "Axi");
+    assert!(fissures[0].is_storm);
+    assert!(!fissures[0].is_hard);
+}
+
+#[tokio::test]
+async fn test_invasions_fetch_json() {
+    let url = format!("{BASE_URL}/invasions");
+    let mut map = HashMap::new();
+    map.insert(url, "[{\"id\":\"i1\",\"node\":\"Kiliken (Venus)\",\"desc\":\"Grineer Offensive\",\"attacker\":{\"faction\":\"Grineer\",\"reward\":{\"asString\":\"Orokin Catalyst Blueprint\"}},\"defender\":{\"faction\":\"Corpus\",\"reward\":{\"asString\":\"3x Fieldron\"}},\"vsInfestation\":false,\"completion\":42.5,\"completed\":false}]".to_string());
+    let client = MockHttp::new(map);
+
+    let invasions = invasions(&client)
+        .await
+        .expect("invasions");
+    assert_eq!(
+        invasions[0]
+            .attacker
+            .reward
+            .as_ref()
+            .unwrap()
+            .as_string,
+        "Orokin Catalyst Blueprint"
+    );
+    assert_eq!(invasions[0].completion, 42.5);
+}
+
+#[tokio::test]
+async fn test_void_trader_fetch_json() {
+    let url = format!("{BASE_URL}/voidTrader");
+    let mut map = HashMap::new();
+    map.insert(url, "{\"id\":\"v1\",\"location\":\"Strata Relay (Earth)\",\"activation\":\"2030-01-01T00:00:00Z\",\"expiry\":\"2030-01-03T00:00:00Z\",\"active\":true,\"inventory\":[{\"item\":\"Primed Flow\",\"ducats\":350,\"credits\":250000}]}".to_string());
+    let client = MockHttp::new(map);
+
+    let trader = void_trader(&client)
+        .await
+        .expect("void trader");
+    assert!(trader.active);
+    assert_eq!(trader.inventory[0].item, "Primed Flow");
+    assert_eq!(trader.inventory[0].ducats, 350);
+}
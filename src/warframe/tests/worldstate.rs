@@ -0,0 +1,184 @@
+use super::*;
+use crate::warframe::api::{InvasionReward, InvasionSide, VoidTraderItem};
+
+fn fissure(tier: &str, tier_num: u8, hard: bool, storm: bool) -> Fissure {
+    Fissure {
+        id: format!("{tier}-{hard}-{storm}"),
+        node: "Ukko (Void)".to_string(),
+        mission_type: "Capture".to_string(),
+        enemy: "Corrupted".to_string(),
+        tier: tier.to_string(),
+        tier_num,
+        expiry: "2030-01-01T00:00:00Z".to_string(),
+        is_storm: storm,
+        is_hard: hard,
+    }
+}
+
+fn side(faction: &str, reward: Option<&str>) -> InvasionSide {
+    InvasionSide {
+        faction: faction.to_string(),
+        reward: reward.map(|r| InvasionReward { as_string: r.to_string() }),
+    }
+}
+
+#[test]
+fn fissures_embed_groups_by_mode_and_orders_by_tier() {
+    let fissures = vec![
+        fissure("Axi", 4, false, false),
+        fissure("Lith", 1, false, false),
+        fissure("Neo", 3, true, false),
+        fissure("Meso", 2, false, true),
+        fissure("Requiem", 5, true, true),
+    ];
+    let embed = fissures_embed(&fissures).build();
+    let names: Vec<&str> = embed
+        .fields
+        .iter()
+        .map(|f| f.name.as_str())
+        .collect();
+    assert_eq!(
+        names,
+        ["Normal", "Steel Path", "Void Storm", "Steel Path Void Storm"]
+    );
+    assert!(
+        embed.fields[3]
+            .value
+            .starts_with("**Requiem**")
+    );
+    assert!(
+        embed.fields[0]
+            .value
+            .starts_with("**Lith**")
+    );
+    assert!(
+        embed.fields[0]
+            .value
+            .contains("\n**Axi** Capture — Ukko (Void)")
+    );
+}
+
+#[test]
+fn fissures_embed_says_when_nothing_is_open() {
+    let embed = fissures_embed(&[]).build();
+    assert!(embed.fields.is_empty());
+    assert_eq!(
+        embed.description.as_deref(),
+        Some("No active fissures")
+    );
+}
+
+#[test]
+fn invasions_embed_skips_completed_and_missing_rewards() {
+    let invasions = vec![
+        Invasion {
+            id: "a".to_string(),
+            node: "Kiliken (Venus)".to_string(),
+            desc: "Infested Outbreak".to_string(),
+            attacker: side("Infested", None),
+            defender: side("Corpus", Some("Orokin Catalyst Blueprint")),
+            vs_infestation: true,
+            completion: 12.34,
+            completed: false,
+        },
+        Invasion {
+            id: "b".to_string(),
+            node: "Done (Earth)".to_string(),
+            desc: "Grineer Offensive".to_string(),
+            attacker: side("Grineer", Some("Detonite Injector")),
+            defender: side("Corpus", Some("Fieldron")),
+            vs_infestation: false,
+            completion: 100.0,
+            completed: true,
+        },
+    ];
+    let embed = invasions_embed(&invasions).build();
+    assert_eq!(embed.fields.len(), 1);
+    assert_eq!(
+        embed.fields[0].value,
+        "Infested vs Corpus\n- / Orokin Catalyst Blueprint\n12.3%"
+    );
+}
+
+#[test]
+fn void_trader_embed_shows_arrival_until_active() {
+    let mut trader = VoidTrader {
+        id: "v".to_string(),
+        location: "Strata Relay (Earth)".to_string(),
+        activation: "2030-01-01T00:00:00Z".to_string(),
+        expiry: "2030-01-03T00:00:00Z".to_string(),
+        active: false,
+        inventory: Vec::new(),
+    };
+    let embed = void_trader_embed(&trader).build();
+    assert_eq!(
+        embed.description.as_deref(),
+        Some(format!("arrives {}", format_time(&trader.activation)).as_str())
+    );
+    assert!(embed.fields.is_empty());
+
+    trader.active = true;
+    trader.inventory.push(VoidTraderItem {
+        item: "Primed Flow".to_string(),
+        ducats: 350,
+        credits: 250_000,
+    });
+    let embed = void_trader_embed(&trader).build();
+    assert_eq!(
+        embed.fields[0].value,
+        "**Primed Flow** — 350 ducats, 250000 credits"
+    );
+}
+
+#[test]
+fn void_trader_arrival_messages_split_long_inventories() {
+    let trader = VoidTrader {
+        id: "v".to_string(),
+        location: "Strata Relay (Earth)".to_string(),
+        activation: "2030-01-01T00:00:00Z".to_string(),
+        expiry: "2030-01-03T00:00:00Z".to_string(),
+        active: true,
+        inventory: (0..400)
+            .map(|i| VoidTraderItem {
+                item: format!("Primed Mod Number {i}"),
+                ducats: 300,
+                credits: 200_000,
+            })
+            .collect(),
+    };
+    let messages = void_trader_arrival_messages(&trader).unwrap();
+    assert!(messages.len() > 1);
+    for embeds in &messages {
+        assert!(embeds.len() <= MAX_EMBEDS);
+        assert!(
+            embeds
+                .iter()
+                .map(embed_chars)
+                .sum::<usize>()
+                <= MESSAGE_CHARS
+        );
+    }
+    let embeds: Vec<&Embed> = messages.iter().flatten().collect();
+    assert_eq!(
+        embeds[0].title.as_deref(),
+        Some("Baro Ki'Teer has arrived at Strata Relay (Earth)")
+    );
+    let fields: Vec<&str> = embeds
+        .iter()
+        .flat_map(|e| e.fields.iter())
+        .map(|f| f.value.as_str())
+        .collect();
+    assert!(
+        fields
+            .iter()
+            .all(|v| v.len() <= FIELD_LIMIT)
+    );
+    assert_eq!(
+        fields
+            .iter()
+            .map(|v| v.lines().count())
+            .sum::<usize>(),
+        400
+    );
+    assert!(embeds[0].fields.len() <= FIELDS_PER_EMBED);
+}
@@ -0,0 +1,380 @@
+use chrono::Utc;
+use twilight_cache_inmemory::{Reference, model::CachedGuild};
+use twilight_model::channel::message::Embed;
+use twilight_model::id::{Id, marker::GuildMarker};
+use twilight_util::builder::embed::{EmbedBuilder, EmbedFieldBuilder};
+
+use std::sync::Arc;
+
+use super::api::{self, ArchonHunt, Cycle, Fissure, Invasion, Sortie, VoidTrader};
+use super::embed::{COLOR, MIN_CACHE_TTL, URL, cached_or_request, ttl_from_expiry};
+use super::utils::format_time;
+use crate::configs::CACHE_PREFIX;
+use crate::context::Context;
+use crate::utils::embed::{FIELD_LIMIT, footer_with_icon, join_lines};
+
+const MAX_FIELDS: usize = 25;
+/// Five full fields stay under Discord's 6000 character embed total.
+const FIELDS_PER_EMBED: usize = 5;
+/// Discord's limit on embeds per message.
+const MAX_EMBEDS: usize = 10;
+/// Discord's character total across every embed of one message.
+const MESSAGE_CHARS: usize = 6000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorldstateSection {
+    Fissures,
+    Sortie,
+    ArchonHunt,
+    Invasions,
+    VoidTrader,
+}
+
+/// Open-world cycles as `(endpoint, place)`.
+pub const CYCLES: [(&str, &str); 4] = [
+    ("cetusCycle", "cetus"),
+    ("vallisCycle", "vallis"),
+    ("cambionCycle", "cambion"),
+    ("zarimanCycle", "zariman"),
+];
+
+pub async fn cycle(ctx: &Arc<Context>, endpoint: &str) -> anyhow::Result<Cycle> {
+    let key = format!("{CACHE_PREFIX}:wf:cycle:{endpoint}");
+    let client = ctx.reqwest.clone();
+    cached_or_request(
+        &ctx.redis,
+        &key,
+        move || async move { api::cycle(&client, endpoint).await },
+        |d| ttl_from_expiry(&d.expiry),
+    )
+    .await
+}
+
+pub async fn fissures(ctx: &Arc<Context>) -> anyhow::Result<Vec<Fissure>> {
+    let key = format!("{CACHE_PREFIX}:wf:fissures");
+    let client = ctx.reqwest.clone();
+    cached_or_request(
+        &ctx.redis,
+        &key,
+        move || async move { api::fissures(&client).await },
+        |d| {
+            d.iter()
+                .map(|f| ttl_from_expiry(&f.expiry))
+                .min()
+                .unwrap_or(MIN_CACHE_TTL)
+        },
+    )
+    .await
+}
+
+pub async fn sortie(ctx: &Arc<Context>) -> anyhow::Result<Sortie> {
+    let key = format!("{CACHE_PREFIX}:wf:sortie");
+    let client = ctx.reqwest.clone();
+    cached_or_request(
+        &ctx.redis,
+        &key,
+        move || async move { api::sortie(&client).await },
+        |d| ttl_from_expiry(&d.expiry),
+    )
+    .await
+}
+
+pub async fn archon_hunt(ctx: &Arc<Context>) -> anyhow::Result<ArchonHunt> {
+    let key = format!("{CACHE_PREFIX}:wf:archon-hunt");
+    let client = ctx.reqwest.clone();
+    cached_or_request(
+        &ctx.redis,
+        &key,
+        move || async move { api::archon_hunt(&client).await },
+        |d| ttl_from_expiry(&d.expiry),
+    )
+    .await
+}
+
+pub async fn invasions(ctx: &Arc<Context>) -> anyhow::Result<Vec<Invasion>> {
+    let key = format!("{CACHE_PREFIX}:wf:invasions");
+    let client = ctx.reqwest.clone();
+    // Invasions end on progress rather than on a timer.
+    cached_or_request(
+        &ctx.redis,
+        &key,
+        move || async move { api::invasions(&client).await },
+        |_| MIN_CACHE_TTL,
+    )
+    .await
+}
+
+pub async fn void_trader(ctx: &Arc<Context>) -> anyhow::Result<VoidTrader> {
+    let key = format!("{CACHE_PREFIX}:wf:void-trader");
+    let client = ctx.reqwest.clone();
+    cached_or_request(
+        &ctx.redis,
+        &key,
+        move || async move { api::void_trader(&client).await },
+        |d| {
+            if d.active { ttl_from_expiry(&d.expiry) } else { ttl_from_expiry(&d.activation) }
+        },
+    )
+    .await
+}
+
+pub fn fissures_embed(fissures: &[Fissure]) -> EmbedBuilder {
+    let mut sorted: Vec<&Fissure> = fissures.iter().collect();
+    sorted.sort_by(|a, b| {
+        a.tier_num
+            .cmp(&b.tier_num)
+            .then(a.expiry.cmp(&b.expiry))
+    });
+    let mut builder = EmbedBuilder::new().title("[PC] Void Fissures");
+    let groups = [
+        ("Normal", false, false),
+        ("Steel Path", true, false),
+        ("Void Storm", false, true),
+        ("Steel Path Void Storm", true, true),
+    ];
+    let mut empty = true;
+    for (name, hard, storm) in groups {
+        let lines: Vec<String> = sorted
+            .iter()
+            .filter(|f| f.is_hard == hard && f.is_storm == storm)
+            .map(|f| {
+                format!(
+                    "**{}** {} — {} {}",
+                    f.tier,
+                    f.mission_type,
+                    f.node,
+                    format_time(&f.expiry)
+                )
+            })
+            .collect();
+        if !lines.is_empty() {
+            empty = false;
+            builder = builder.field(EmbedFieldBuilder::new(name, join_lines(&lines)));
+        }
+    }
+    if empty {
+        builder = builder.description("No active fissures");
+    }
+    builder
+}
+
+pub fn sortie_embed(sortie: &Sortie) -> EmbedBuilder {
+    let mut builder = EmbedBuilder::new()
+        .title(format!(
+            "[PC] Sortie — {} ({})",
+            sortie.boss, sortie.faction
+        ))
+        .description(format!("ends {}", format_time(&sortie.expiry)));
+    for (i, variant) in sortie.variants.iter().enumerate() {
+        builder = builder.field(EmbedFieldBuilder::new(
+            format!(
+                "{}. {} — {}",
+                i + 1,
+                variant.mission_type,
+                variant.node
+            ),
+            variant.modifier.clone(),
+        ));
+    }
+    builder
+}
+
+pub fn archon_hunt_embed(hunt: &ArchonHunt) -> EmbedBuilder {
+    let mut builder = EmbedBuilder::new()
+        .title(format!("[PC] Archon Hunt — {}", hunt.boss))
+        .description(format!("ends {}", format_time(&hunt.expiry)));
+    for (i, mission) in hunt.missions.iter().enumerate() {
+        builder = builder.field(EmbedFieldBuilder::new(
+            format!("{}. {}", i + 1, mission.mission_type),
+            mission.node.clone(),
+        ));
+    }
+    builder
+}
+
+pub fn invasions_embed(invasions: &[Invasion]) -> EmbedBuilder {
+    let mut builder = EmbedBuilder::new().title("[PC] Invasions");
+    let active: Vec<&Invasion> = invasions
+        .iter()
+        .filter(|i| !i.completed)
+        .collect();
+    for invasion in active.iter().take(MAX_FIELDS) {
+        let reward = |side: &api::InvasionSide| {
+            side.reward
+                .as_ref()
+                .map(|r| r.as_string.as_str())
+                .filter(|r| !r.is_empty())
+                .unwrap_or("-")
+                .to_string()
+        };
+        let value = format!(
+            "{} vs {}\n{} / {}\n{:.1}%",
+            invasion.attacker.faction,
+            invasion.defender.faction,
+            reward(&invasion.attacker),
+            reward(&invasion.defender),
+            invasion.completion
+        );
+        builder = builder.field(
+            EmbedFieldBuilder::new(
+                format!("{} — {}", invasion.node, invasion.desc),
+                value,
+            )
+            .inline(),
+        );
+    }
+    if active.is_empty() {
+        builder = builder.description("No active invasions");
+    }
+    builder
+}
+
+fn inventory_lines(trader: &VoidTrader) -> Vec<String> {
+    trader
+        .inventory
+        .iter()
+        .map(|item| {
+            format!(
+                "**{}** — {} ducats, {} credits",
+                item.item, item.ducats, item.credits
+            )
+        })
+        .collect()
+}
+
+/// Groups whole lines into field values of at most `FIELD_LIMIT` bytes.
+fn split_lines(lines: &[String]) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    for line in lines {
+        if !current.is_empty() && current.len() + line.len() + 1 > FIELD_LIMIT {
+            chunks.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push('\n');
+        }
+        current.push_str(line);
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+pub fn void_trader_embed(trader: &VoidTrader) -> EmbedBuilder {
+    let builder = EmbedBuilder::new().title(format!("[PC] Baro Ki'Teer — {}", trader.location));
+    if !trader.active {
+        return builder.description(format!(
+            "arrives {}",
+            format_time(&trader.activation)
+        ));
+    }
+    builder
+        .description(format!("leaves {}", format_time(&trader.expiry)))
+        .field(EmbedFieldBuilder::new(
+            "Inventory",
+            join_lines(&inventory_lines(trader)),
+        ))
+}
+
+/// Characters of `embed` that count towards `MESSAGE_CHARS`.
+fn embed_chars(embed: &Embed) -> usize {
+    let len = |s: &Option<String>| {
+        s.as_deref()
+            .map_or(0, |s| s.chars().count())
+    };
+    len(&embed.title)
+        + len(&embed.description)
+        + embed
+            .fields
+            .iter()
+            .map(|f| f.name.chars().count() + f.value.chars().count())
+            .sum::<usize>()
+        + embed
+            .footer
+            .as_ref()
+            .map_or(0, |f| f.text.chars().count())
+        + embed
+            .author
+            .as_ref()
+            .map_or(0, |a| a.name.chars().count())
+}
+
+/// The full inventory for an arrival announcement, one entry per message:
+/// each stays within `MAX_EMBEDS` embeds and `MESSAGE_CHARS` characters.
+pub fn void_trader_arrival_messages(trader: &VoidTrader) -> anyhow::Result<Vec<Vec<Embed>>> {
+    let mut messages: Vec<Vec<Embed>> = Vec::new();
+    let mut chars = 0;
+    for embed in void_trader_arrival_embeds(trader)? {
+        let len = embed_chars(&embed);
+        match messages.last_mut() {
+            Some(group) if group.len() < MAX_EMBEDS && chars + len <= MESSAGE_CHARS => {
+                group.push(embed);
+                chars += len;
+            }
+            _ => {
+                messages.push(vec![embed]);
+                chars = len;
+            }
+        }
+    }
+    Ok(messages)
+}
+
+fn void_trader_arrival_embeds(trader: &VoidTrader) -> anyhow::Result<Vec<Embed>> {
+    let chunks = split_lines(&inventory_lines(trader));
+    let total = chunks.len();
+    let mut embeds = Vec::new();
+    let mut builder = EmbedBuilder::new()
+        .title(format!(
+            "Baro Ki'Teer has arrived at {}",
+            trader.location
+        ))
+        .description(format!("leaves {}", format_time(&trader.expiry)))
+        .url(URL)
+        .color(COLOR);
+    for (i, chunk) in chunks.into_iter().enumerate() {
+        if i > 0 && i % FIELDS_PER_EMBED == 0 {
+            embeds.push(builder.validate()?.build());
+            builder = EmbedBuilder::new().color(COLOR);
+        }
+        builder = builder.field(EmbedFieldBuilder::new(
+            format!("Inventory ({}/{total})", i + 1),
+            chunk,
+        ));
+    }
+    embeds.push(builder.validate()?.build());
+    Ok(embeds)
+}
+
+pub async fn worldstate_embed(
+    ctx: &Arc<Context>,
+    guild: &Reference<'_, Id<GuildMarker>, CachedGuild>,
+    section: WorldstateSection,
+) -> anyhow::Result<Embed> {
+    let builder = match section {
+        WorldstateSection::Fissures => fissures_embed(&fissures(ctx).await?),
+        WorldstateSection::Sortie => sortie_embed(&sortie(ctx).await?),
+        WorldstateSection::ArchonHunt => archon_hunt_embed(&archon_hunt(ctx).await?),
+        WorldstateSection::Invasions => invasions_embed(&invasions(ctx).await?),
+        WorldstateSection::VoidTrader => void_trader_embed(&void_trader(ctx).await?),
+    };
+
+    let mut footer = footer_with_icon(guild)?;
+    footer.text = guild.name().to_string();
+
+    let embed = builder
+        .url(URL)
+        .color(COLOR)
+        .footer(footer)
+        .timestamp(twilight_model::util::Timestamp::from_micros(
+            Utc::now().timestamp_micros(),
+        )?)
+        .validate()?
+        .build();
+    Ok(embed)
+}
+
+#[cfg(test)]
+#[path = "tests/worldstate.rs"]
+mod tests;
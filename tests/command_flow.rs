@@ -12,7 +12,7 @@ use discord_bot::{
     services::guild_settings::GuildSettingsService,
     utils::embed,
 };
-use twilight_model::application::command::CommandOptionType;
+use twilight_model::application::command::{CommandOptionType, CommandType};
 use twilight_model::application::interaction::application_command::{
     CommandDataOption, CommandOptionValue,
 };
@@ -283,6 +283,113 @@ async fn warframe_build_command_falls_back_to_general_results() {
     );
 }
 
+#[tokio::test]
+async fn warframe_item_command_shows_stored_record() {
+    let ctx = build_context().await;
+    let guild = make_guild(Id::new(1), "guild");
+    cache_guild(&ctx.cache, guild.clone());
+    ctx.redis_set(
+        "discord-bot:build-items",
+        &serde_json::json!({
+            "names": ["Rubico Prime"],
+            "records": [{
+                "name": "Rubico Prime",
+                "category": "Primary",
+                "kind": "Sniper Rifle",
+                "mastery": 13,
+                "stats": [["criticalChance", 0.38]],
+                "drops": [],
+                "components": [{"name": "Barrel", "count": 1, "drops": [{"location": "Meso R2 Relic", "chance": 0.11}]}],
+                "wiki": "https://warframe.fandom.com/wiki/Rubico_Prime",
+                "vaulted": true
+            }],
+            "etag": null
+        }),
+    )
+    .await;
+    discord_bot::services::build::BuildService::init(ctx.clone()).await;
+    let options = vec![CommandDataOption {
+        name: "item".into(),
+        value: CommandOptionValue::SubCommand(vec![CommandDataOption {
+            name: "name".into(),
+            value: CommandOptionValue::String("rubico prime".into()),
+        }]),
+    }];
+    let (interaction, data) = command_interaction_with_options("warframe", Some(1), options);
+
+    WarframeCommand::handle(ctx.clone(), interaction, data).await;
+
+    let record = last_message(&ctx.http).expect("message record");
+    assert!(matches!(record.kind, MessageOp::Update));
+    let embed = &record.embeds[0];
+    assert_eq!(embed.title.as_deref(), Some("Rubico Prime"));
+    assert_eq!(
+        embed.url.as_deref(),
+        Some("https://warframe.fandom.com/wiki/Rubico_Prime")
+    );
+    let fields: Vec<(&str, &str)> = embed
+        .fields
+        .iter()
+        .map(|f| (f.name.as_str(), f.value.as_str()))
+        .collect();
+    assert_eq!(
+        fields,
+        [
+            ("ประเภท", "Sniper Rifle"),
+            ("Mastery", "MR 13"),
+            ("ค่าพื้นฐาน", "Crit Chance: **38.0%**"),
+            ("ส่วนประกอบ", "1× Barrel · Meso R2 Relic — 11.00%"),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn warframe_farm_command_lists_vaulted_relics() {
+    let ctx = build_context().await;
+    let guild = make_guild(Id::new(1), "guild");
+    cache_guild(&ctx.cache, guild.clone());
+    ctx.redis_set(
+        "discord-bot:farm-drops",
+        &serde_json::json!({
+            "items": [{
+                "name": "Akstiletto Prime Barrel",
+                "relics": [{"relic": "Axi A1", "rarity": "Uncommon", "chance": 11.0, "vaulted": true}]
+            }],
+            "etag": null,
+            "updated_at": chrono::Utc::now().timestamp()
+        }),
+    )
+    .await;
+    discord_bot::services::farm::FarmService::init(ctx.clone()).await;
+    let options = vec![CommandDataOption {
+        name: "farm".into(),
+        value: CommandOptionValue::SubCommand(vec![CommandDataOption {
+            name: "item".into(),
+            value: CommandOptionValue::String("Akstiletto Prime Barrel".into()),
+        }]),
+    }];
+    let (interaction, data) = command_interaction_with_options("warframe", Some(1), options);
+
+    WarframeCommand::handle(ctx.clone(), interaction, data).await;
+
+    let record = last_message(&ctx.http).expect("message record");
+    assert!(matches!(record.kind, MessageOp::Update));
+    let embed = &record.embeds[0];
+    assert_eq!(
+        embed.title.as_deref(),
+        Some("แหล่งฟาร์ม Akstiletto Prime Barrel")
+    );
+    assert_eq!(embed.fields[0].name, "Relic ที่ดรอป");
+    assert_eq!(
+        embed.fields[0].value,
+        "**Axi A1** · Uncommon 11.00% — 🔒 Vault แล้ว"
+    );
+}
+
+/// Shared by every market test: `MarketService::init` keeps whichever list
+/// reaches the process-wide mock Redis first.
+const MARKET_ITEMS: &str = "{\"data\":[{\"id\":\"test-id\",\"slug\":\"test_item\",\"i18n\":{\"en\":{\"name\":\"Test Item\"}}},{\"id\":\"price-id\",\"slug\":\"price_item\",\"i18n\":{\"en\":{\"name\":\"Price Item\"}}}]}";
+
 #[tokio::test]
 async fn warframe_market_command_embed() {
     let ctx = build_context().await;
@@ -290,7 +397,7 @@ async fn warframe_market_command_embed() {
     cache_guild(&ctx.cache, guild.clone());
     ctx.reqwest.add_json_response(
         "https://api.warframe.market/v2/items",
-        "{\"data\":[{\"id\":\"test-id\",\"slug\":\"test_item\",\"i18n\":{\"en\":{\"name\":\"Test Item\"}}}]}",
+        MARKET_ITEMS,
     );
     ctx.reqwest.add_json_response(
         "https://api.warframe.market/v2/orders/itemId/test-id",
@@ -336,6 +443,164 @@ async fn warframe_market_command_embed() {
     );
 }
 
+#[tokio::test]
+async fn warframe_riven_command_lists_auctions() {
+    let ctx = build_context().await;
+    let guild = make_guild(Id::new(1), "guild");
+    cache_guild(&ctx.cache, guild.clone());
+    ctx.reqwest.add_json_response(
+        "https://api.warframe.market/v1/riven/items",
+        "{\"payload\":{\"items\":[{\"url_name\":\"rubico\",\"item_name\":\"Rubico\"}]}}",
+    );
+    ctx.reqwest.add_json_response(
+        "https://api.warframe.market/v1/riven/attributes",
+        "{\"payload\":{\"attributes\":[{\"url_name\":\"critical_chance\",\"effect\":\"Critical Chance\"},{\"url_name\":\"multishot\",\"effect\":\"Multishot\"}]}}",
+    );
+    ctx.reqwest.add_json_response(
+        "https://api.warframe.market/v1/auctions/search?type=riven&weapon_url_name=rubico&sort_by=price_asc&positive_stats=critical_chance%2Cmultishot",
+        "{\"payload\":{\"auctions\":[{\"id\":\"auc1\",\"buyout_price\":350,\"starting_price\":350,\"is_direct_sell\":true,\"owner\":{\"ingame_name\":\"Trader\",\"status\":\"ingame\"},\"item\":{\"name\":\"crita-satilis\",\"attributes\":[]}}]}}",
+    );
+    discord_bot::services::riven::RivenService::init(ctx.clone()).await;
+    let options = vec![CommandDataOption {
+        name: "riven".into(),
+        value: CommandOptionValue::SubCommand(vec![
+            CommandDataOption {
+                name: "weapon".into(),
+                value: CommandOptionValue::String("Rubico".into()),
+            },
+            CommandDataOption {
+                name: "positive".into(),
+                value: CommandOptionValue::String("Critical Chance, multishot".into()),
+            },
+        ]),
+    }];
+    let (interaction, data) = command_interaction_with_options("warframe", Some(1), options);
+
+    WarframeCommand::handle(ctx.clone(), interaction, data).await;
+
+    let record = last_message(&ctx.http).expect("message record");
+    assert!(matches!(record.kind, MessageOp::Update));
+    let embed = &record.embeds[0];
+    assert_eq!(embed.title.as_deref(), Some("Riven Rubico"));
+    assert_eq!(
+        embed.fields[0].name,
+        "Rubico crita-satilis — 350p (ซื้อทันที)"
+    );
+}
+
+#[tokio::test]
+async fn warframe_price_command_renders_statistics() {
+    let ctx = build_context().await;
+    let guild = make_guild(Id::new(1), "guild");
+    cache_guild(&ctx.cache, guild.clone());
+    ctx.reqwest.add_json_response(
+        "https://api.warframe.market/v2/items",
+        MARKET_ITEMS,
+    );
+    ctx.reqwest.add_json_response(
+        "https://api.warframe.market/v1/items/price_item/statistics",
+        "{\"payload\":{\"statistics_closed\":{\"48hours\":[{\"datetime\":\"2030-01-02T00:00:00Z\",\"volume\":4,\"min_price\":10,\"max_price\":14,\"median\":12}],\"90days\":[{\"datetime\":\"2030-01-01T00:00:00Z\",\"volume\":6,\"min_price\":8,\"max_price\":16,\"median\":11},{\"datetime\":\"2030-01-02T00:00:00Z\",\"volume\":4,\"min_price\":10,\"max_price\":14,\"median\":12}]}}}",
+    );
+    discord_bot::services::market::MarketService::init(ctx.clone()).await;
+    let options = vec![CommandDataOption {
+        name: "price".into(),
+        value: CommandOptionValue::SubCommand(vec![CommandDataOption {
+            name: "item".into(),
+            value: CommandOptionValue::String("Price Item".into()),
+        }]),
+    }];
+    let (interaction, data) = command_interaction_with_options("warframe", Some(1), options);
+
+    WarframeCommand::handle(ctx.clone(), interaction, data).await;
+
+    let record = last_message(&ctx.http).expect("message record");
+    assert!(matches!(record.kind, MessageOp::Update));
+    let embed = &record.embeds[0];
+    assert_eq!(embed.title.as_deref(), Some("ราคา Price Item"));
+    assert_eq!(
+        embed.url.as_deref(),
+        Some("https://warframe.market/items/price_item")
+    );
+    assert_eq!(embed.fields[0].name, "ราคากลาง");
+    assert_eq!(
+        embed.fields[0].value,
+        "48 ชม.: **12p** · 4 ชิ้น · 10p–14p\n90 วัน: **11p** · 10 ชิ้น · 8p–16p\n`▁█`"
+    );
+}
+
+#[tokio::test]
+async fn warframe_watch_add_confirms_watch() {
+    let ctx = build_context().await;
+    let guild = make_guild(Id::new(1), "guild");
+    cache_guild(&ctx.cache, guild.clone());
+    ctx.reqwest.add_json_response(
+        "https://api.warframe.market/v2/items",
+        MARKET_ITEMS,
+    );
+    discord_bot::services::market::MarketService::init(ctx.clone()).await;
+    let options = vec![CommandDataOption {
+        name: "watch".into(),
+        value: CommandOptionValue::SubCommandGroup(vec![CommandDataOption {
+            name: "add".into(),
+            value: CommandOptionValue::SubCommand(vec![
+                CommandDataOption {
+                    name: "item".into(),
+                    value: CommandOptionValue::String("Test Item".into()),
+                },
+                CommandDataOption {
+                    name: "kind".into(),
+                    value: CommandOptionValue::String("buy".into()),
+                },
+                CommandDataOption { name: "price".into(), value: CommandOptionValue::Integer(25) },
+            ]),
+        }]),
+    }];
+    let (interaction, data) = command_interaction_with_options("warframe", Some(1), options);
+
+    WarframeCommand::handle(ctx.clone(), interaction, data).await;
+
+    let record = last_message(&ctx.http).expect("message record");
+    assert!(matches!(record.kind, MessageOp::Update));
+    assert_eq!(
+        record.embeds[0].description.as_deref(),
+        Some("จะส่ง DM เมื่อพบ `buy Test Item ≤ 25p`")
+    );
+}
+
+#[tokio::test]
+async fn warframe_worldstate_command_renders_sortie() {
+    let ctx = build_context().await;
+    let guild = make_guild(Id::new(1), "guild");
+    cache_guild(&ctx.cache, guild.clone());
+    ctx.reqwest.add_json_response(
+        "https://api.warframestat.us/pc/sortie",
+        "{\"id\":\"s1\",\"boss\":\"Kela De Thaym\",\"faction\":\"Grineer\",\"expiry\":\"2030-01-01T00:00:00Z\",\"variants\":[{\"missionType\":\"Survival\",\"modifier\":\"Enemy Physical Enhancement: Impact\",\"node\":\"Kappa (Sedna)\"}]}",
+    );
+    let options = vec![CommandDataOption {
+        name: "worldstate".into(),
+        value: CommandOptionValue::SubCommand(vec![CommandDataOption {
+            name: "section".into(),
+            value: CommandOptionValue::String("sortie".into()),
+        }]),
+    }];
+    let (interaction, data) = command_interaction_with_options("warframe", Some(1), options);
+
+    WarframeCommand::handle(ctx.clone(), interaction, data).await;
+
+    let record = last_message(&ctx.http).expect("message record");
+    assert!(matches!(record.kind, MessageOp::Update));
+    let embed = &record.embeds[0];
+    assert_eq!(
+        embed.title.as_deref(),
+        Some("[PC] Sortie — Kela De Thaym (Grineer)")
+    );
+    assert_eq!(
+        embed.fields[0].name,
+        "1. Survival — Kappa (Sedna)"
+    );
+    assert_eq!(embed.footer.as_ref().unwrap().text, "guild");
+}
+
 #[test]
 fn warframe_market_command_is_registered_with_item_autocomplete() {
     let commands = registry().collect_commands();
@@ -375,6 +640,20 @@ fn warframe_market_command_is_registered_with_item_autocomplete() {
     );
 }
 
+#[test]
+fn ai_message_commands_are_registered() {
+    let commands = registry().collect_commands();
+    for name in ["Ask AI", "Translate"] {
+        let command = commands
+            .iter()
+            .find(|command| command.name == name)
+            .unwrap_or_else(|| panic!("{name} is registered"));
+        assert_eq!(command.kind, CommandType::Message);
+        assert!(command.description.is_empty());
+        assert!(command.options.is_empty());
+    }
+}
+
 #[test]
 fn admin_command_registers_scam_detect_controls() {
     let commands = registry().collect_commands();